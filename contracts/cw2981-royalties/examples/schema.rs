@@ -1,12 +1,12 @@
 use cosmwasm_schema::write_api;
 
 use cosmwasm_std::Empty;
-use cw2981_royalties::{msg::QueryMsg, ExecuteMsg};
+use cw2981_royalties::{msg::QueryMsg, DefaultOptionMetadataExtensionWithRoyaltyMsg, ExecuteMsg};
 use cw721::msg::Cw721InstantiateMsg;
 
 fn main() {
     write_api! {
-        instantiate: Cw721InstantiateMsg<Empty>,
+        instantiate: Cw721InstantiateMsg<DefaultOptionMetadataExtensionWithRoyaltyMsg, Empty>,
         execute: ExecuteMsg,
         query: QueryMsg,
     }
@@ -50,7 +50,11 @@ pub struct MetadataWithRoyalty {
     pub royalty_payment_address: Option<String>,
 }
 
-impl Cw721State for MetadataWithRoyalty {}
+impl Cw721State for MetadataWithRoyalty {
+    fn traits(&self) -> Vec<Trait> {
+        self.attributes.clone().unwrap_or_default()
+    }
+}
 impl Cw721CustomMsg for MetadataWithRoyalty {}
 
 #[cfg(not(feature = "library"))]
@@ -60,8 +64,9 @@ pub mod entry {
     use super::*;
 
     use cosmwasm_std::entry_point;
-    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response};
+    use cosmwasm_std::{Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response};
     use cw721::msg::Cw721InstantiateMsg;
+    use cw721::state::Cw721Config;
     use cw721::traits::{Cw721Execute, Cw721Query};
     use state::Cw2981Contract;
 
@@ -70,7 +75,7 @@ pub mod entry {
         mut deps: DepsMut,
         env: Env,
         info: MessageInfo,
-        msg: Cw721InstantiateMsg<Empty>,
+        msg: Cw721InstantiateMsg<DefaultOptionMetadataExtensionWithRoyaltyMsg, Empty>,
     ) -> Result<Response, ContractError> {
         Ok(Cw2981Contract::default().instantiate_with_version(
             deps.branch(),
@@ -89,20 +94,27 @@ pub mod entry {
         info: MessageInfo,
         msg: ExecuteMsg,
     ) -> Result<Response, ContractError> {
-        if let ExecuteMsg::Mint {
-            extension:
-                Some(MetadataWithRoyalty {
-                    royalty_percentage: Some(royalty_percentage),
-                    ..
-                }),
-            ..
-        } = &msg
+        // royalty of minted and updated tokens
+        let extensions = match &msg {
+            ExecuteMsg::Mint { extension, .. } | ExecuteMsg::UpdateNftInfo { extension, .. } => {
+                vec![extension]
+            }
+            ExecuteMsg::BatchUpdateNftInfo { updates } => {
+                updates.iter().map(|update| &update.extension).collect()
+            }
+            _ => vec![],
+        };
+        for royalty_percentage in extensions
+            .into_iter()
+            .filter_map(|extension| extension.as_ref()?.royalty_percentage)
         {
             // validate royalty_percentage to be between 0 and 100
             // no need to check < 0 because royalty_percentage is u64
-            if *royalty_percentage > 100 {
+            if royalty_percentage > 100 {
                 return Err(ContractError::InvalidRoyaltyPercentage);
             }
+            Cw721Config::<DefaultOptionMetadataExtensionWithRoyalty>::default()
+                .assert_royalty_cap(deps.storage, Decimal::percent(royalty_percentage))?;
         }
 
         Cw2981Contract::default()
@@ -143,6 +155,7 @@ mod tests {
     use cosmwasm_std::{from_json, Uint128};
 
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cw721::error::Cw721ContractError;
     use cw721::msg::Cw721InstantiateMsg;
     use cw721::traits::Cw721Query;
     use state::Cw2981Contract;
@@ -162,6 +175,19 @@ mod tests {
             minter: None,
             creator: None,
             withdraw_address: None,
+            burn_policy: None,
+            ipfs_gateway: None,
+            mint_price: None,
+            public_minting: false,
+            max_royalty_bps: None,
+            commit_hash: None,
+            initial_mints: None,
+            track_burned_ids: false,
+            base_token_uri: None,
+            allow_force_transfer: false,
+            collection_metadata_uri: None,
+            allow_mint_to_contract: false,
+            unique_token_uris: false,
         };
         entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
 
@@ -177,12 +203,13 @@ mod tests {
             owner: "john".to_string(),
             token_uri: token_uri.clone(),
             extension: extension.clone(),
+            ibc_data: None,
         };
         let env = mock_env();
         entry::execute(deps.as_mut(), env.clone(), info, exec_msg).unwrap();
 
         let res = contract
-            .query_nft_info(deps.as_ref().storage, token_id.into())
+            .query_nft_info(deps.as_ref(), &mock_env(), token_id.into())
             .unwrap();
         assert_eq!(res.token_uri, token_uri);
         assert_eq!(res.extension, extension);
@@ -201,6 +228,19 @@ mod tests {
             minter: None,
             creator: None,
             withdraw_address: None,
+            burn_policy: None,
+            ipfs_gateway: None,
+            mint_price: None,
+            public_minting: false,
+            max_royalty_bps: None,
+            commit_hash: None,
+            initial_mints: None,
+            track_burned_ids: false,
+            base_token_uri: None,
+            allow_force_transfer: false,
+            collection_metadata_uri: None,
+            allow_mint_to_contract: false,
+            unique_token_uris: false,
         };
         entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
 
@@ -215,12 +255,75 @@ mod tests {
                 royalty_percentage: Some(101),
                 ..MetadataWithRoyalty::default()
             }),
+            ibc_data: None,
         };
         // mint will return StdError
         let err = entry::execute(deps.as_mut(), mock_env(), info, exec_msg).unwrap_err();
         assert_eq!(err, ContractError::InvalidRoyaltyPercentage);
     }
 
+    #[test]
+    fn validate_royalty_cap() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info(CREATOR, &[]);
+        let init_msg = Cw721InstantiateMsg {
+            name: "SpaceShips".to_string(),
+            symbol: "SPACE".to_string(),
+            collection_info_extension: Empty {},
+            minter: None,
+            creator: None,
+            withdraw_address: None,
+            burn_policy: None,
+            ipfs_gateway: None,
+            mint_price: None,
+            public_minting: false,
+            max_royalty_bps: Some(1000),
+            commit_hash: None,
+            initial_mints: None,
+            track_burned_ids: false,
+            base_token_uri: None,
+            allow_force_transfer: false,
+            collection_metadata_uri: None,
+            allow_mint_to_contract: false,
+            unique_token_uris: false,
+        };
+        entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
+
+        let mint_msg = |token_id: &str, royalty_percentage: u64| ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: "john".to_string(),
+            token_uri: None,
+            extension: Some(MetadataWithRoyalty {
+                royalty_percentage: Some(royalty_percentage),
+                ..MetadataWithRoyalty::default()
+            }),
+            ibc_data: None,
+        };
+        let err =
+            entry::execute(deps.as_mut(), mock_env(), info.clone(), mint_msg("1", 11)).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Base(Cw721ContractError::RoyaltyExceedsCap {})
+        );
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), mint_msg("1", 10)).unwrap();
+
+        // cap applies to updates too
+        let update_msg = ExecuteMsg::UpdateNftInfo {
+            token_id: "1".to_string(),
+            token_uri: None,
+            extension: Some(MetadataWithRoyalty {
+                royalty_percentage: Some(11),
+                ..MetadataWithRoyalty::default()
+            }),
+        };
+        let err = entry::execute(deps.as_mut(), mock_env(), info, update_msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Base(Cw721ContractError::RoyaltyExceedsCap {})
+        );
+    }
+
     #[test]
     fn check_royalties_response() {
         let mut deps = mock_dependencies();
@@ -234,6 +337,19 @@ mod tests {
             minter: None,
             creator: None,
             withdraw_address: None,
+            burn_policy: None,
+            ipfs_gateway: None,
+            mint_price: None,
+            public_minting: false,
+            max_royalty_bps: None,
+            commit_hash: None,
+            initial_mints: None,
+            track_burned_ids: false,
+            base_token_uri: None,
+            allow_force_transfer: false,
+            collection_metadata_uri: None,
+            allow_mint_to_contract: false,
+            unique_token_uris: false,
         };
         entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
 
@@ -247,6 +363,7 @@ mod tests {
                 name: Some("Starship USS Enterprise".to_string()),
                 ..MetadataWithRoyalty::default()
             }),
+            ibc_data: None,
         };
         entry::execute(deps.as_mut(), mock_env(), info, exec_msg).unwrap();
 
@@ -275,6 +392,19 @@ mod tests {
             minter: None,
             creator: None,
             withdraw_address: None,
+            burn_policy: None,
+            ipfs_gateway: None,
+            mint_price: None,
+            public_minting: false,
+            max_royalty_bps: None,
+            commit_hash: None,
+            initial_mints: None,
+            track_burned_ids: false,
+            base_token_uri: None,
+            allow_force_transfer: false,
+            collection_metadata_uri: None,
+            allow_mint_to_contract: false,
+            unique_token_uris: false,
         };
         let env = mock_env();
         entry::instantiate(deps.as_mut(), env.clone(), info.clone(), init_msg).unwrap();
@@ -292,6 +422,7 @@ mod tests {
                 royalty_percentage: Some(10),
                 ..MetadataWithRoyalty::default()
             }),
+            ibc_data: None,
         };
         entry::execute(deps.as_mut(), mock_env(), info.clone(), exec_msg).unwrap();
 
@@ -327,8 +458,9 @@ mod tests {
                 royalty_percentage: Some(4),
                 ..MetadataWithRoyalty::default()
             }),
+            ibc_data: None,
         };
-        entry::execute(deps.as_mut(), mock_env(), info, voyager_exec_msg).unwrap();
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), voyager_exec_msg).unwrap();
 
         // 43 x 0.04 (i.e., 4%) should be 1.72
         // we expect this to be rounded down to 1
@@ -344,5 +476,32 @@ mod tests {
         )
         .unwrap();
         assert_eq!(res, voyager_expected);
+
+        // without payment address, royalties go to the collection's royalty recipient
+        let defiant_token_id = "Defiant";
+        let defiant_exec_msg = ExecuteMsg::Mint {
+            token_id: defiant_token_id.to_string(),
+            owner: "sisko".into(),
+            token_uri: None,
+            extension: Some(MetadataWithRoyalty {
+                royalty_percentage: Some(5),
+                ..MetadataWithRoyalty::default()
+            }),
+            ibc_data: None,
+        };
+        entry::execute(deps.as_mut(), mock_env(), info, defiant_exec_msg).unwrap();
+        let res = query_royalties_info(
+            deps.as_ref(),
+            defiant_token_id.to_string(),
+            Uint128::new(100),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            RoyaltiesInfoResponse {
+                address: CREATOR.into(),
+                royalty_amount: Uint128::new(5),
+            }
+        );
     }
 }
@@ -24,6 +24,7 @@ pub enum QueryMsg {
     /// by the marketplace selling the NFT, if CheckRoyalties
     /// returns true
     /// See https://eips.ethereum.org/EIPS/eip-2981
+    /// Without token's `royalty_payment_address`, address is resolved like cw721's `RoyaltyRecipient` query.
     #[returns(RoyaltiesInfoResponse)]
     RoyaltyInfo {
         token_id: String,
@@ -149,10 +150,22 @@ pub enum QueryMsg {
     GetCollectionInfoExtension { msg: Empty },
 }
 
-impl From<QueryMsg> for Cw721QueryMsg<DefaultOptionMetadataExtensionWithRoyalty, Empty, Empty> {
+impl From<QueryMsg>
+    for Cw721QueryMsg<
+        DefaultOptionMetadataExtensionWithRoyalty,
+        DefaultOptionMetadataExtensionWithRoyaltyMsg,
+        Empty,
+        Empty,
+    >
+{
     fn from(
         msg: QueryMsg,
-    ) -> Cw721QueryMsg<DefaultOptionMetadataExtensionWithRoyalty, Empty, Empty> {
+    ) -> Cw721QueryMsg<
+        DefaultOptionMetadataExtensionWithRoyalty,
+        DefaultOptionMetadataExtensionWithRoyaltyMsg,
+        Empty,
+        Empty,
+    > {
         match msg {
             QueryMsg::OwnerOf {
                 token_id,
@@ -183,10 +196,13 @@ impl From<QueryMsg> for Cw721QueryMsg<DefaultOptionMetadataExtensionWithRoyalty,
                 owner,
                 start_after,
                 limit,
+                cursor: None,
+            },
+            QueryMsg::AllTokens { start_after, limit } => Cw721QueryMsg::AllTokens {
+                start_after,
+                limit,
+                cursor: None,
             },
-            QueryMsg::AllTokens { start_after, limit } => {
-                Cw721QueryMsg::AllTokens { start_after, limit }
-            }
             #[allow(deprecated)]
             QueryMsg::Minter {} => Cw721QueryMsg::Minter {},
             QueryMsg::GetMinterOwnership {} => Cw721QueryMsg::GetMinterOwnership {},
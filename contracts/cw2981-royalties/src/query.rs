@@ -1,12 +1,16 @@
 use crate::msg::{CheckRoyaltiesResponse, RoyaltiesInfoResponse};
 use crate::state::Cw2981Contract;
-use crate::DefaultOptionMetadataExtensionWithRoyalty;
+use crate::{
+    DefaultOptionMetadataExtensionWithRoyalty, DefaultOptionMetadataExtensionWithRoyaltyMsg,
+};
 use cosmwasm_std::{Decimal, Deps, Empty, StdResult, Uint128};
+use cw721::state::Cw721Config;
 use cw721::traits::Cw721Query;
 
 impl
     Cw721Query<
         DefaultOptionMetadataExtensionWithRoyalty,
+        DefaultOptionMetadataExtensionWithRoyaltyMsg,
         Empty, // no collection extension
         Empty, // no extension query
     > for Cw2981Contract<'_>
@@ -21,7 +25,9 @@ pub fn query_royalties_info(
     sale_price: Uint128,
 ) -> StdResult<RoyaltiesInfoResponse> {
     let contract = Cw2981Contract::default();
-    let token_info = contract.query_nft_info(deps.storage, token_id)?;
+    let token_info = Cw721Config::<DefaultOptionMetadataExtensionWithRoyalty>::default()
+        .nft_info
+        .load(deps.storage, &token_id)?;
 
     let royalty_percentage = match token_info.extension {
         Some(ref ext) => match ext.royalty_percentage {
@@ -32,9 +38,13 @@ pub fn query_royalties_info(
     };
     let royalty_from_sale_price = sale_price * royalty_percentage;
 
-    let royalty_address = match token_info.extension {
-        Some(ext) => ext.royalty_payment_address.unwrap_or_default(),
-        None => String::from(""),
+    // token's payment address takes precedence over collection's royalty recipient
+    let royalty_address = match token_info
+        .extension
+        .and_then(|ext| ext.royalty_payment_address)
+    {
+        Some(address) => address,
+        None => contract.query_royalty_recipient(deps)?.unwrap_or_default(),
     };
 
     Ok(RoyaltiesInfoResponse {
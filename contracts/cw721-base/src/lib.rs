@@ -97,6 +97,19 @@ mod tests {
             minter: None,
             creator: None,
             withdraw_address: None,
+            burn_policy: None,
+            ipfs_gateway: None,
+            mint_price: None,
+            public_minting: false,
+            max_royalty_bps: None,
+            commit_hash: None,
+            initial_mints: None,
+            track_burned_ids: false,
+            base_token_uri: None,
+            allow_force_transfer: false,
+            collection_metadata_uri: None,
+            allow_mint_to_contract: false,
+            unique_token_uris: false,
         };
         contract
             .instantiate(deps.as_mut(), &mock_env(), &info.clone(), init_msg)
@@ -110,13 +123,14 @@ mod tests {
             owner: "john".to_string(),
             token_uri: token_uri.clone(),
             extension: extension.clone(),
+            ibc_data: None,
         };
         contract
             .execute(deps.as_mut(), &mock_env(), &info, exec_msg)
             .unwrap();
 
         let res = contract
-            .query_nft_info(deps.as_ref().storage, token_id.into())
+            .query_nft_info(deps.as_ref(), &mock_env(), token_id.into())
             .unwrap();
         assert_eq!(res.token_uri, token_uri);
         assert_eq!(res.extension, Some(Empty {}));
@@ -8,7 +8,12 @@ use cw721::{
 
 pub type ExecuteMsg =
     Cw721ExecuteMsg<EmptyOptionalNftExtensionMsg, DefaultOptionalCollectionExtensionMsg, Empty>;
-pub type InstantiateMsg = Cw721InstantiateMsg<DefaultOptionalCollectionExtensionMsg>;
+pub type InstantiateMsg =
+    Cw721InstantiateMsg<EmptyOptionalNftExtensionMsg, DefaultOptionalCollectionExtensionMsg>;
 pub type MigrateMsg = Cw721MigrateMsg;
-pub type QueryMsg =
-    Cw721QueryMsg<EmptyOptionalNftExtension, DefaultOptionalCollectionExtension, Empty>;
+pub type QueryMsg = Cw721QueryMsg<
+    EmptyOptionalNftExtension,
+    EmptyOptionalNftExtensionMsg,
+    DefaultOptionalCollectionExtension,
+    Empty,
+>;
@@ -84,6 +84,7 @@ fn proper_instantiation() {
             symbol: SYMBOL.to_string(),
             extension: None,
             updated_at: env.block.time,
+            collection_metadata_uri: None,
         }
     );
 
@@ -147,6 +148,7 @@ fn proper_instantiation_with_collection_info() {
             symbol: SYMBOL.to_string(),
             extension: None,
             updated_at: env.block.time,
+            collection_metadata_uri: None,
         }
     );
 
@@ -184,6 +186,7 @@ fn test_mint() {
         owner: String::from("medusa"),
         token_uri: Some(token_uri.clone()),
         extension: None,
+        ibc_data: None,
     };
 
     // random cannot mint
@@ -221,6 +224,8 @@ fn test_mint() {
         NftInfoResponse::<DefaultOptionalNftExtension> {
             token_uri: Some(token_uri),
             extension: None,
+            ibc_data: None,
+            minter: Some(MINTER_ADDR.to_string()),
         }
     );
 
@@ -239,6 +244,7 @@ fn test_mint() {
         OwnerOfResponse {
             owner: String::from("medusa"),
             approvals: vec![],
+            expired: false,
         }
     );
 
@@ -255,6 +261,7 @@ fn test_mint() {
         owner: String::from("hercules"),
         token_uri: None,
         extension: None,
+        ibc_data: None,
     };
 
     let allowed = mock_info(MINTER_ADDR, &[]);
@@ -284,6 +291,7 @@ fn test_update_minter() {
         owner: String::from("medusa"),
         token_uri: Some(token_uri.clone()),
         extension: None,
+        ibc_data: None,
     };
 
     // Minter can mint
@@ -349,6 +357,7 @@ fn test_update_minter() {
         owner: String::from("medusa"),
         token_uri: Some(token_uri),
         extension: None,
+        ibc_data: None,
     };
 
     // Old owner can not mint.
@@ -376,6 +385,7 @@ fn test_burn() {
         owner: MINTER_ADDR.to_string(),
         token_uri: Some(token_uri),
         extension: None,
+        ibc_data: None,
     };
 
     let burn_msg = Cw721ExecuteMsg::Burn {
@@ -464,6 +474,7 @@ fn test_transfer_nft() {
         owner: String::from(owner),
         token_uri: Some(token_uri),
         extension: None,
+        ibc_data: None,
     };
 
     let mut env = mock_env();
@@ -477,6 +488,7 @@ fn test_transfer_nft() {
     let transfer_msg = Cw721ExecuteMsg::TransferNft {
         recipient: String::from("random"),
         token_id: token_id.clone(),
+        expected_owner: None,
     };
 
     let err = contract
@@ -493,6 +505,7 @@ fn test_transfer_nft() {
     let transfer_msg = Cw721ExecuteMsg::TransferNft {
         recipient: String::from(new_owner),
         token_id: token_id.clone(),
+        expected_owner: None,
     };
 
     let res = contract
@@ -544,6 +557,7 @@ fn test_send_nft() {
         owner: String::from("venus"),
         token_uri: Some(token_uri),
         extension: None,
+        ibc_data: None,
     };
 
     let mut env = mock_env();
@@ -558,6 +572,7 @@ fn test_send_nft() {
         contract: target.clone(),
         token_id: token_id.clone(),
         msg: msg.clone(),
+        expected_owner: None,
     };
 
     let random = mock_info("random", &[]);
@@ -630,6 +645,7 @@ fn test_approve_revoke() {
         owner: String::from("demeter"),
         token_uri: Some(token_uri),
         extension: None,
+        ibc_data: None,
     };
 
     let mut env = mock_env();
@@ -704,6 +720,7 @@ fn test_approve_revoke() {
     let transfer_msg = Cw721ExecuteMsg::TransferNft {
         recipient: String::from("person"),
         token_id: token_id.clone(),
+        expected_owner: None,
     };
     contract
         .execute(deps.as_mut(), env.clone(), random, transfer_msg)
@@ -726,6 +743,7 @@ fn test_approve_revoke() {
         OwnerOfResponse {
             owner: String::from("person"),
             approvals: vec![],
+            expired: false,
         }
     );
 
@@ -770,6 +788,7 @@ fn test_approve_revoke() {
         OwnerOfResponse {
             owner: String::from("person"),
             approvals: vec![],
+            expired: false,
         }
     );
 
@@ -820,6 +839,7 @@ fn test_approve_all_revoke_all() {
         owner: String::from("demeter"),
         token_uri: Some(token_uri1),
         extension: None,
+        ibc_data: None,
     };
 
     let minter = mock_info(MINTER_ADDR, &[]);
@@ -832,6 +852,7 @@ fn test_approve_all_revoke_all() {
         owner: String::from("demeter"),
         token_uri: Some(token_uri2),
         extension: None,
+        ibc_data: None,
     };
 
     contract
@@ -871,6 +892,7 @@ fn test_approve_all_revoke_all() {
             .add_attribute("action", "approve_all")
             .add_attribute("sender", "demeter")
             .add_attribute("operator", "random")
+            .add_attribute("expires", "expiration: never")
     );
 
     // random can now transfer
@@ -878,6 +900,7 @@ fn test_approve_all_revoke_all() {
     let transfer_msg = Cw721ExecuteMsg::TransferNft {
         recipient: String::from("person"),
         token_id: token_id1,
+        expected_owner: None,
     };
     contract
         .execute(deps.as_mut(), mock_env(), random.clone(), transfer_msg)
@@ -895,6 +918,7 @@ fn test_approve_all_revoke_all() {
         contract: String::from("another_contract"),
         token_id: token_id2,
         msg: to_json_binary(&msg).unwrap(),
+        expected_owner: None,
     };
     contract
         .execute(deps.as_mut(), mock_env(), random, send_msg)
@@ -1110,6 +1134,7 @@ fn test_tokens_by_owner() {
         owner: demeter.clone(),
         token_uri: None,
         extension: None,
+        ibc_data: None,
     };
     contract
         .execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg)
@@ -1120,6 +1145,7 @@ fn test_tokens_by_owner() {
         owner: ceres.clone(),
         token_uri: None,
         extension: None,
+        ibc_data: None,
     };
     contract
         .execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg)
@@ -1130,6 +1156,7 @@ fn test_tokens_by_owner() {
         owner: demeter.clone(),
         token_uri: None,
         extension: None,
+        ibc_data: None,
     };
     contract
         .execute(deps.as_mut(), mock_env(), minter, mint_msg)
@@ -1217,6 +1244,7 @@ fn test_nft_info() {
         owner,
         token_uri: None,
         extension: None,
+        ibc_data: None,
     };
     contract
         .execute(deps.as_mut(), env.clone(), minter, mint_msg)
@@ -1259,6 +1287,7 @@ fn test_all_nft_info() {
         owner,
         token_uri: None,
         extension: None,
+        ibc_data: None,
     };
     contract
         .execute(deps.as_mut(), env.clone(), minter, mint_msg)
@@ -1307,6 +1336,7 @@ fn test_owner_of() {
         owner,
         token_uri: None,
         extension: None,
+        ibc_data: None,
     };
     contract
         .execute(deps.as_mut(), env.clone(), minter, mint_msg)
@@ -1355,6 +1385,7 @@ fn test_approval() {
         owner: owner.clone(),
         token_uri: None,
         extension: None,
+        ibc_data: None,
     };
     contract
         .execute(deps.as_mut(), env.clone(), minter, mint_msg)
@@ -1411,6 +1442,7 @@ fn test_approvals() {
         owner,
         token_uri: None,
         extension: None,
+        ibc_data: None,
     };
     contract
         .execute(deps.as_mut(), env.clone(), minter, mint_msg)
@@ -1459,6 +1491,7 @@ fn test_tokens() {
         owner: owner.clone(),
         token_uri: None,
         extension: None,
+        ibc_data: None,
     };
     contract
         .execute(deps.as_mut(), env.clone(), minter, mint_msg)
@@ -1489,7 +1522,13 @@ fn test_tokens() {
             false,
         )
         .unwrap();
-    assert_eq!(tokens, TokensResponse { tokens: vec![] });
+    assert_eq!(
+        tokens,
+        TokensResponse {
+            tokens: vec![],
+            next_cursor: None,
+        }
+    );
 
     // assert invalid nft is returned
     let tokens = contract
@@ -1498,7 +1537,8 @@ fn test_tokens() {
     assert_eq!(
         tokens,
         TokensResponse {
-            tokens: [token_id].to_vec()
+            tokens: [token_id].to_vec(),
+            next_cursor: None,
         }
     );
 }
@@ -1518,6 +1558,7 @@ fn test_all_tokens() {
         owner: owner.clone(),
         token_uri: None,
         extension: None,
+        ibc_data: None,
     };
     contract
         .execute(deps.as_mut(), env.clone(), minter, mint_msg)
@@ -1534,7 +1575,13 @@ fn test_all_tokens() {
     let tokens = contract
         .query_tokens_include_expired_nft(deps.as_ref(), env.clone(), owner, None, None, false)
         .unwrap();
-    assert_eq!(tokens, TokensResponse { tokens: vec![] });
+    assert_eq!(
+        tokens,
+        TokensResponse {
+            tokens: vec![],
+            next_cursor: None,
+        }
+    );
 
     // assert invalid nft is returned
     let tokens = contract
@@ -1543,7 +1590,8 @@ fn test_all_tokens() {
     assert_eq!(
         tokens,
         TokensResponse {
-            tokens: [token_id].to_vec()
+            tokens: [token_id].to_vec(),
+            next_cursor: None,
         }
     );
 }
@@ -2,10 +2,10 @@ use crate::{
     error::ContractError, msg::InstantiateMsg, state::DefaultCw721ExpirationContract,
     CONTRACT_NAME, CONTRACT_VERSION,
 };
-use cosmwasm_std::{Binary, DepsMut, Empty, Env, MessageInfo, Response};
+use cosmwasm_std::{Binary, DepsMut, Empty, Env, MessageInfo, Response, Timestamp};
 use cw721::{
-    msg::{Cw721ExecuteMsg, Cw721InstantiateMsg, Cw721MigrateMsg},
-    traits::Cw721Execute,
+    msg::{Cw721ExecuteMsg, Cw721InstantiateMsg, Cw721MigrateMsg, MintVoucher, NftTransfer},
+    traits::{Cw721Execute, Cw721Query},
     Expiration,
 };
 use cw721::{DefaultOptionalCollectionExtensionMsg, DefaultOptionalNftExtensionMsg};
@@ -37,6 +37,19 @@ impl DefaultCw721ExpirationContract<'static> {
                 minter: msg.minter,
                 creator: msg.creator,
                 withdraw_address: msg.withdraw_address,
+                burn_policy: None,
+                ipfs_gateway: None,
+                mint_price: None,
+                public_minting: false,
+                max_royalty_bps: None,
+                commit_hash: None,
+                initial_mints: None,
+                track_burned_ids: false,
+                base_token_uri: None,
+                allow_force_transfer: false,
+                collection_metadata_uri: None,
+                allow_mint_to_contract: false,
+                unique_token_uris: false,
             },
             CONTRACT_NAME,
             CONTRACT_VERSION,
@@ -62,8 +75,33 @@ impl DefaultCw721ExpirationContract<'static> {
                 owner,
                 token_uri,
                 extension,
-            } => {
-                contract.mint_with_timestamp(deps, env, info, token_id, owner, token_uri, extension)
+                ibc_data,
+            } => contract.mint_with_timestamp(
+                deps, env, info, token_id, owner, token_uri, extension, ibc_data,
+            ),
+            Cw721ExecuteMsg::MintAuto {
+                owner,
+                token_uri,
+                extension,
+            } => contract.mint_auto_with_timestamp(deps, env, info, owner, token_uri, extension),
+            Cw721ExecuteMsg::MintTimelocked {
+                token_id,
+                owner,
+                token_uri,
+                extension,
+                transferable_after,
+            } => contract.mint_timelocked_with_timestamp(
+                deps,
+                env,
+                info,
+                token_id,
+                owner,
+                token_uri,
+                extension,
+                transferable_after,
+            ),
+            Cw721ExecuteMsg::RedeemVoucher { voucher, signature } => {
+                contract.redeem_voucher_with_timestamp(deps, env, info, voucher, signature)
             }
             Cw721ExecuteMsg::Approve {
                 spender,
@@ -76,12 +114,69 @@ impl DefaultCw721ExpirationContract<'static> {
             Cw721ExecuteMsg::TransferNft {
                 recipient,
                 token_id,
-            } => contract.transfer_nft_include_nft_expired(deps, env, info, recipient, token_id),
+                expected_owner,
+            } => contract.transfer_nft_include_nft_expired(
+                deps,
+                env,
+                info,
+                recipient,
+                token_id,
+                expected_owner,
+            ),
+            Cw721ExecuteMsg::SafeTransferNft {
+                recipient,
+                token_id,
+                expected_owner,
+            } => contract.safe_transfer_nft_include_nft_expired(
+                deps,
+                env,
+                info,
+                recipient,
+                token_id,
+                expected_owner,
+            ),
             Cw721ExecuteMsg::SendNft {
                 contract: recipient,
                 token_id,
                 msg,
-            } => contract.send_nft_include_nft_expired(deps, env, info, recipient, token_id, msg),
+                expected_owner,
+            } => contract.send_nft_include_nft_expired(
+                deps,
+                env,
+                info,
+                recipient,
+                token_id,
+                msg,
+                expected_owner,
+            ),
+            Cw721ExecuteMsg::DistributeNfts { transfers } => {
+                contract.distribute_nfts_include_nft_expired(deps, env, info, transfers)
+            }
+            Cw721ExecuteMsg::TransferAndApprove {
+                recipient,
+                token_id,
+                spender,
+                expires,
+            } => contract.transfer_and_approve_include_nft_expired(
+                deps, env, info, recipient, token_id, spender, expires,
+            ),
+            Cw721ExecuteMsg::ProposeSwap {
+                my_token,
+                want_token,
+                counterparty,
+                expires,
+            } => contract.propose_swap_include_nft_expired(
+                deps,
+                env,
+                info,
+                my_token,
+                want_token,
+                counterparty,
+                expires,
+            ),
+            Cw721ExecuteMsg::AcceptSwap { offer_id } => {
+                contract.accept_swap_include_nft_expired(deps, env, info, offer_id)
+            }
             Cw721ExecuteMsg::Burn { token_id } => {
                 contract.burn_nft_include_nft_expired(deps, env, info, token_id)
             }
@@ -102,13 +197,95 @@ impl DefaultCw721ExpirationContract<'static> {
         owner: String,
         token_uri: Option<String>,
         extension: DefaultOptionalNftExtensionMsg,
+        ibc_data: Option<Binary>,
     ) -> Result<Response<Empty>, ContractError> {
         let mint_timstamp = env.block.time;
         self.mint_timestamps
             .save(deps.storage, &token_id, &mint_timstamp)?;
         let res = self
             .base_contract
-            .mint(deps, &env, &info, token_id, owner, token_uri, extension)?
+            .mint_with_ibc_data(
+                deps, &env, &info, token_id, owner, token_uri, extension, ibc_data,
+            )?
+            .add_attribute("mint_timestamp", mint_timstamp.to_string());
+        Ok(res)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_timelocked_with_timestamp(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        owner: String,
+        token_uri: Option<String>,
+        extension: DefaultOptionalNftExtensionMsg,
+        transferable_after: Timestamp,
+    ) -> Result<Response<Empty>, ContractError> {
+        let mint_timstamp = env.block.time;
+        self.mint_timestamps
+            .save(deps.storage, &token_id, &mint_timstamp)?;
+        let res = self
+            .base_contract
+            .mint_timelocked(
+                deps,
+                &env,
+                &info,
+                token_id,
+                owner,
+                token_uri,
+                extension,
+                transferable_after,
+            )?
+            .add_attribute("mint_timestamp", mint_timstamp.to_string());
+        Ok(res)
+    }
+
+    pub fn mint_auto_with_timestamp(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        owner: String,
+        token_uri: Option<String>,
+        extension: DefaultOptionalNftExtensionMsg,
+    ) -> Result<Response<Empty>, ContractError> {
+        let mint_timstamp = env.block.time;
+        let res = self.base_contract.mint_auto(
+            deps.branch(),
+            &env,
+            &info,
+            owner,
+            token_uri,
+            extension,
+        )?;
+        // token id is only known after minting
+        let token_id = res
+            .attributes
+            .iter()
+            .find(|attr| attr.key == "token_id")
+            .map(|attr| attr.value.clone())
+            .unwrap_or_default();
+        self.mint_timestamps
+            .save(deps.storage, &token_id, &mint_timstamp)?;
+        Ok(res.add_attribute("mint_timestamp", mint_timstamp.to_string()))
+    }
+
+    pub fn redeem_voucher_with_timestamp(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        voucher: MintVoucher<DefaultOptionalNftExtensionMsg>,
+        signature: Binary,
+    ) -> Result<Response<Empty>, ContractError> {
+        let mint_timstamp = env.block.time;
+        self.mint_timestamps
+            .save(deps.storage, &voucher.token_id, &mint_timstamp)?;
+        let res = self
+            .base_contract
+            .redeem_voucher(deps, &env, &info, voucher, signature)?
             .add_attribute("mint_timestamp", mint_timstamp.to_string());
         Ok(res)
     }
@@ -149,13 +326,40 @@ impl DefaultCw721ExpirationContract<'static> {
         info: MessageInfo,
         recipient: String,
         token_id: String,
+        expected_owner: Option<String>,
     ) -> Result<Response<Empty>, ContractError> {
         self.assert_nft_expired(deps.as_ref(), &env, token_id.as_str())?;
-        Ok(self
-            .base_contract
-            .transfer_nft(deps, &env, &info, recipient, token_id)?)
+        Ok(self.base_contract.transfer_nft(
+            deps,
+            &env,
+            &info,
+            recipient,
+            token_id,
+            expected_owner,
+        )?)
     }
 
+    pub fn safe_transfer_nft_include_nft_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+        token_id: String,
+        expected_owner: Option<String>,
+    ) -> Result<Response<Empty>, ContractError> {
+        self.assert_nft_expired(deps.as_ref(), &env, token_id.as_str())?;
+        Ok(self.base_contract.safe_transfer_nft(
+            deps,
+            &env,
+            &info,
+            recipient,
+            token_id,
+            expected_owner,
+        )?)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn send_nft_include_nft_expired(
         &self,
         deps: DepsMut,
@@ -164,11 +368,91 @@ impl DefaultCw721ExpirationContract<'static> {
         contract: String,
         token_id: String,
         msg: Binary,
+        expected_owner: Option<String>,
+    ) -> Result<Response<Empty>, ContractError> {
+        self.assert_nft_expired(deps.as_ref(), &env, token_id.as_str())?;
+        Ok(self.base_contract.send_nft(
+            deps,
+            &env,
+            &info,
+            contract,
+            token_id,
+            msg,
+            expected_owner,
+        )?)
+    }
+
+    pub fn distribute_nfts_include_nft_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        transfers: Vec<NftTransfer>,
+    ) -> Result<Response<Empty>, ContractError> {
+        for transfer in &transfers {
+            self.assert_nft_expired(deps.as_ref(), &env, transfer.token_id.as_str())?;
+        }
+        Ok(self
+            .base_contract
+            .distribute_nfts(deps, &env, &info, transfers)?)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn transfer_and_approve_include_nft_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+        token_id: String,
+        spender: String,
+        expires: Option<Expiration>,
     ) -> Result<Response<Empty>, ContractError> {
         self.assert_nft_expired(deps.as_ref(), &env, token_id.as_str())?;
         Ok(self
             .base_contract
-            .send_nft(deps, &env, &info, contract, token_id, msg)?)
+            .transfer_and_approve(deps, &env, &info, recipient, token_id, spender, expires)?)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn propose_swap_include_nft_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        my_token: String,
+        want_token: String,
+        counterparty: String,
+        expires: Option<Expiration>,
+    ) -> Result<Response<Empty>, ContractError> {
+        self.assert_nft_expired(deps.as_ref(), &env, my_token.as_str())?;
+        self.assert_nft_expired(deps.as_ref(), &env, want_token.as_str())?;
+        Ok(self.base_contract.propose_swap(
+            deps,
+            &env,
+            &info,
+            my_token,
+            want_token,
+            counterparty,
+            expires,
+        )?)
+    }
+
+    pub fn accept_swap_include_nft_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        offer_id: u64,
+    ) -> Result<Response<Empty>, ContractError> {
+        let offer = self
+            .base_contract
+            .query_swap_offer(deps.as_ref(), offer_id)?;
+        self.assert_nft_expired(deps.as_ref(), &env, offer.my_token.as_str())?;
+        self.assert_nft_expired(deps.as_ref(), &env, offer.want_token.as_str())?;
+        Ok(self
+            .base_contract
+            .accept_swap(deps, &env, &info, offer_id)?)
     }
 
     pub fn burn_nft_include_nft_expired(
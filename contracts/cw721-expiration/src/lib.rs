@@ -100,7 +100,7 @@ mod tests {
             InstantiateMsg {
                 expiration_days: 0,
                 name: "collection_name".into(),
-                symbol: "collection_symbol".into(),
+                symbol: "SYMBOL".into(),
                 collection_info_extension: None,
                 minter: Some("minter".into()),
                 creator: Some("creator".into()),
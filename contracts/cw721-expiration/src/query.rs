@@ -211,7 +211,7 @@ impl DefaultCw721ExpirationContract<'static> {
         if !include_expired_nft {
             self.assert_nft_expired(deps, &env, token_id.as_str())?;
         }
-        Ok(self.base_contract.query_nft_info(deps.storage, token_id)?)
+        Ok(self.base_contract.query_nft_info(deps, &env, token_id)?)
     }
 
     pub fn query_nft_by_extension_include_expired_nft(
@@ -225,7 +225,7 @@ impl DefaultCw721ExpirationContract<'static> {
         if !include_expired_nft {
             self.assert_nft_expired(deps, &env, token_id.as_str())?;
         }
-        Ok(self.base_contract.query_nft_info(deps.storage, token_id)?)
+        Ok(self.base_contract.query_nft_info(deps, &env, token_id)?)
     }
 
     pub fn query_owner_of_include_expired_nft(
@@ -291,9 +291,9 @@ impl DefaultCw721ExpirationContract<'static> {
         limit: Option<u32>,
         include_expired_nft: bool,
     ) -> StdResult<TokensResponse> {
-        let tokens = self
-            .base_contract
-            .query_tokens(deps, &env, owner, start_after, limit)?;
+        let tokens =
+            self.base_contract
+                .query_tokens(deps, &env, owner, start_after, limit, None)?;
         if include_expired_nft {
             return Ok(tokens);
         }
@@ -305,7 +305,10 @@ impl DefaultCw721ExpirationContract<'static> {
             })
             .map(|token_id| token_id.to_string())
             .collect();
-        Ok(TokensResponse { tokens: filtered })
+        Ok(TokensResponse {
+            tokens: filtered,
+            next_cursor: tokens.next_cursor,
+        })
     }
 
     pub fn query_all_tokens_include_expired_nft(
@@ -318,7 +321,7 @@ impl DefaultCw721ExpirationContract<'static> {
     ) -> Result<TokensResponse, ContractError> {
         let tokens = self
             .base_contract
-            .query_all_tokens(deps, &env, start_after, limit)?;
+            .query_all_tokens(deps, &env, start_after, limit, None)?;
         if include_expired_nft {
             return Ok(tokens);
         }
@@ -330,7 +333,10 @@ impl DefaultCw721ExpirationContract<'static> {
             })
             .map(|token_id| token_id.to_string())
             .collect();
-        Ok(TokensResponse { tokens: filtered })
+        Ok(TokensResponse {
+            tokens: filtered,
+            next_cursor: tokens.next_cursor,
+        })
     }
 
     pub fn query_all_nft_info_include_expired_nft(
@@ -1,22 +1,26 @@
 use crate::error::ContractError;
-use crate::msg::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::state::{Config, CONFIG};
+use crate::msg::{BuyMsg, ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg, TraitPool};
+use crate::state::{
+    Config, CONFIG, DECIMAL_UNIT_PRICE, PREMINT, RESERVED_TOKEN_IDS, TOKEN_METADATA, TRAIT_POOL,
+};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Reply, ReplyOn, Response,
-    StdResult, SubMsg, Uint128, WasmMsg,
+    from_json, to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Reply,
+    ReplyOn, Response, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
 use cw20::Cw20ReceiveMsg;
 use cw721::helpers::DefaultCw721Helper;
 use cw721::msg::{Cw721ExecuteMsg, Cw721InstantiateMsg, NftExtensionMsg};
+use cw721::state::Trait;
 use cw721::traits::Cw721Calls;
 use cw721::{
     DefaultOptionalCollectionExtension, DefaultOptionalCollectionExtensionMsg,
-    DefaultOptionalNftExtensionMsg,
+    DefaultOptionalNftExtension, DefaultOptionalNftExtensionMsg, NftExtension,
 };
 use cw_utils::parse_reply_instantiate_data;
+use sha2::{Digest, Sha256};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:cw721-fixed-price";
@@ -33,8 +37,19 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-    if msg.unit_price == Uint128::new(0) {
-        return Err(ContractError::InvalidUnitPrice {});
+    match msg.decimal_unit_price {
+        Some(decimal_unit_price) => {
+            if decimal_unit_price.is_zero() {
+                return Err(ContractError::InvalidUnitPrice {});
+            }
+            DECIMAL_UNIT_PRICE.save(deps.storage, &decimal_unit_price)?;
+        }
+        // unit price is unused in decimal mode
+        None => {
+            if msg.unit_price == Uint128::new(0) {
+                return Err(ContractError::InvalidUnitPrice {});
+            }
+        }
     }
 
     if msg.max_tokens == 0 {
@@ -56,16 +71,75 @@ pub fn instantiate(
 
     CONFIG.save(deps.storage, &config)?;
 
+    // reserve premint token ids, so public sale skips them
+    let premint = msg.premint.unwrap_or_default();
+    for spec in &premint {
+        // reserved tokens are part of the sale's token id range
+        if !spec
+            .token_id
+            .parse::<u32>()
+            .is_ok_and(|token_id| token_id < msg.max_tokens)
+        {
+            return Err(ContractError::InvalidReservedTokenId {
+                token_id: spec.token_id.clone(),
+            });
+        }
+        if RESERVED_TOKEN_IDS.has(deps.storage, &spec.token_id) {
+            return Err(ContractError::DuplicateReservedTokenId {
+                token_id: spec.token_id.clone(),
+            });
+        }
+        RESERVED_TOKEN_IDS.save(deps.storage, &spec.token_id, &Empty {})?;
+    }
+    PREMINT.save(deps.storage, &premint)?;
+
+    if let Some(trait_pool) = msg.trait_pool {
+        for pool in &trait_pool {
+            let total_weight: u64 = pool.values.iter().map(|v| u64::from(v.weight)).sum();
+            if total_weight == 0 {
+                return Err(ContractError::InvalidTraitPool {
+                    trait_type: pool.trait_type.clone(),
+                });
+            }
+        }
+        TRAIT_POOL.save(deps.storage, &trait_pool)?;
+    }
+
+    if let Some(token_metadata) = msg.token_metadata {
+        if token_metadata.len() > msg.max_tokens as usize {
+            return Err(ContractError::InvalidTokenMetadata {});
+        }
+        for (token_id, metadata) in token_metadata.iter().enumerate() {
+            TOKEN_METADATA.save(deps.storage, &token_id.to_string(), metadata)?;
+        }
+    }
+
     let sub_msg: Vec<SubMsg> = vec![SubMsg {
         msg: WasmMsg::Instantiate {
             code_id: msg.token_code_id,
-            msg: to_json_binary(&Cw721InstantiateMsg {
+            msg: to_json_binary(&Cw721InstantiateMsg::<
+                DefaultOptionalNftExtensionMsg,
+                DefaultOptionalCollectionExtensionMsg,
+            > {
                 name: msg.name.clone(),
                 symbol: msg.symbol,
                 collection_info_extension: msg.collection_info_extension,
                 minter: None,
                 creator: None,
                 withdraw_address: msg.withdraw_address,
+                burn_policy: None,
+                ipfs_gateway: None,
+                mint_price: None,
+                public_minting: false,
+                max_royalty_bps: None,
+                commit_hash: None,
+                initial_mints: None,
+                track_burned_ids: false,
+                base_token_uri: None,
+                allow_force_transfer: false,
+                collection_metadata_uri: None,
+                allow_mint_to_contract: false,
+                unique_token_uris: false,
             })?,
             funds: vec![],
             admin: None,
@@ -94,19 +168,67 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
     }
 
     let reply = parse_reply_instantiate_data(msg).unwrap();
-    config.cw721_address = Addr::unchecked(reply.contract_address).into();
+    let cw721 = Addr::unchecked(reply.contract_address);
+    config.cw721_address = Some(cw721.clone());
     CONFIG.save(deps.storage, &config)?;
 
-    Ok(Response::new())
+    // mint reserved tokens to owner, before public sale starts
+    let premint = PREMINT.may_load(deps.storage)?.unwrap_or_default();
+    PREMINT.remove(deps.storage);
+    let mut mint_msgs = vec![];
+    for spec in premint {
+        let extension = spec.extension.or(config.extension.clone());
+        let mint_msg = Cw721ExecuteMsg::<
+            DefaultOptionalNftExtensionMsg,
+            DefaultOptionalCollectionExtensionMsg,
+            Empty,
+        >::Mint {
+            token_id: spec.token_id,
+            owner: config.owner.to_string(),
+            token_uri: spec.token_uri.or(Some(config.token_uri.clone())),
+            extension: extension.map(|e| e.into()),
+            ibc_data: None,
+        };
+        mint_msgs.push(DefaultCw721Helper::new(cw721.clone()).call(mint_msg)?);
+    }
+
+    Ok(Response::new().add_messages(mint_msgs))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::GetConfig {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::NextTokenId {} => to_json_binary(&query_next_token_id(deps)?),
+        QueryMsg::PreviewGenerated { token_id } => {
+            to_json_binary(&query_preview_generated(deps, token_id)?)
+        }
     }
 }
 
+fn query_next_token_id(deps: Deps) -> StdResult<Option<String>> {
+    let config = CONFIG.load(deps.storage)?;
+    let token_id = next_token_id(deps.storage, &config);
+    if token_id >= config.max_tokens {
+        return Ok(None);
+    }
+    Ok(Some(token_id.to_string()))
+}
+
+fn query_preview_generated(deps: Deps, token_id: String) -> StdResult<DefaultOptionalNftExtension> {
+    let config = CONFIG.load(deps.storage)?;
+    generate_extension(deps.storage, &config, &token_id)
+}
+
+/// First unused token id not reserved by premint
+fn next_token_id(storage: &dyn Storage, config: &Config) -> u32 {
+    let mut token_id = config.unused_token_id;
+    while RESERVED_TOKEN_IDS.has(storage, &token_id.to_string()) {
+        token_id += 1;
+    }
+    token_id
+}
+
 fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let config = CONFIG.load(deps.storage)?;
     Ok(ConfigResponse {
@@ -115,6 +237,7 @@ fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
         cw721_address: config.cw721_address,
         max_tokens: config.max_tokens,
         unit_price: config.unit_price,
+        decimal_unit_price: DECIMAL_UNIT_PRICE.may_load(deps.storage)?,
         name: config.name,
         symbol: config.symbol,
         token_uri: config.token_uri,
@@ -126,7 +249,7 @@ fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
@@ -135,16 +258,17 @@ pub fn execute(
             sender,
             amount,
             msg,
-        }) => execute_receive(deps, info, sender, amount, msg),
+        }) => execute_receive(deps, env, info, sender, amount, msg),
     }
 }
 
 pub fn execute_receive(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     sender: String,
     amount: Uint128,
-    _msg: Binary,
+    msg: Binary,
 ) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
     if config.cw20_address != info.sender {
@@ -155,43 +279,145 @@ pub fn execute_receive(
         return Err(ContractError::Uninitialized {});
     }
 
+    // skip token ids reserved by premint
+    config.unused_token_id = next_token_id(deps.storage, &config);
+
     if config.unused_token_id >= config.max_tokens {
         return Err(ContractError::SoldOut {});
     }
 
-    if amount != config.unit_price {
+    // empty payload is a plain buy for the sender
+    let buy: BuyMsg = if msg.is_empty() {
+        BuyMsg::default()
+    } else {
+        from_json(&msg)?
+    };
+
+    // decimal price is rounded up, so seller never receives less than the price
+    let price = match DECIMAL_UNIT_PRICE.may_load(deps.storage)? {
+        Some(decimal_unit_price) => decimal_unit_price.to_uint_ceil(),
+        None => config.unit_price,
+    };
+    if buy.expected_price.is_some_and(|expected| expected != price) {
+        return Err(ContractError::PriceChanged { actual: price });
+    }
+    if amount != price {
         return Err(ContractError::WrongPaymentAmount {});
     }
+    let token_id = config.unused_token_id.to_string();
+    // forwarded token is minted to this contract first, so it can send it
+    let (owner, forward_msg) = match (buy.recipient, buy.forward) {
+        (Some(_), Some(_)) => return Err(ContractError::RecipientAndForward {}),
+        (Some(recipient), None) => (deps.api.addr_validate(&recipient)?.into_string(), None),
+        (None, Some(forward)) => (
+            env.contract.address.into_string(),
+            Some(Cw721ExecuteMsg::<
+                DefaultOptionalNftExtensionMsg,
+                DefaultOptionalCollectionExtensionMsg,
+                Empty,
+            >::SendNft {
+                contract: deps.api.addr_validate(&forward.contract)?.into_string(),
+                token_id: token_id.clone(),
+                msg: forward.msg,
+                expected_owner: None,
+            }),
+        ),
+        (None, None) => (sender, None),
+    };
 
-    let extension: Option<NftExtensionMsg> = config.extension.clone().map(|e| e.into());
+    let token_uri = TOKEN_METADATA
+        .may_load(deps.storage, &token_id)?
+        .and_then(|metadata| metadata.token_uri)
+        .unwrap_or(config.token_uri.clone());
+    let extension = generate_extension(deps.storage, &config, &token_id)?;
+    let extension: Option<NftExtensionMsg> = extension.map(|e| e.into());
     let mint_msg = Cw721ExecuteMsg::<
         DefaultOptionalNftExtensionMsg,
         DefaultOptionalCollectionExtensionMsg,
         Empty,
     >::Mint {
-        token_id: config.unused_token_id.to_string(),
-        owner: sender,
-        token_uri: config.token_uri.clone().into(),
+        token_id,
+        owner,
+        token_uri: Some(token_uri),
         extension,
+        ibc_data: None,
     };
 
     match config.cw721_address.clone() {
         Some(cw721) => {
-            let msg = DefaultCw721Helper::new(cw721).call(mint_msg)?;
+            let helper = DefaultCw721Helper::new(cw721);
+            let mut msgs = vec![helper.call(mint_msg)?];
+            if let Some(forward_msg) = forward_msg {
+                msgs.push(helper.call(forward_msg)?);
+            }
             config.unused_token_id += 1;
             CONFIG.save(deps.storage, &config)?;
 
-            Ok(Response::new().add_message(msg))
+            Ok(Response::new().add_messages(msgs))
         }
         None => Err(ContractError::Cw721NotLinked {}),
     }
 }
 
+/// Per-token or else configured extension, with attributes generated from trait pool (if set)
+fn generate_extension(
+    storage: &dyn Storage,
+    config: &Config,
+    token_id: &str,
+) -> StdResult<DefaultOptionalNftExtension> {
+    let extension = TOKEN_METADATA
+        .may_load(storage, token_id)?
+        .and_then(|metadata| metadata.extension)
+        .or(config.extension.clone());
+    let trait_pool = match TRAIT_POOL.may_load(storage)? {
+        Some(trait_pool) => trait_pool,
+        None => return Ok(extension),
+    };
+    let attributes = trait_pool
+        .iter()
+        .map(|pool| generate_trait(pool, token_id))
+        .collect();
+    Ok(Some(NftExtension {
+        attributes: Some(attributes),
+        ..extension.unwrap_or_default()
+    }))
+}
+
+/// Picks a value from pool by weight, seeded by hash of token id and trait type
+fn generate_trait(pool: &TraitPool, token_id: &str) -> Trait {
+    let hash = Sha256::new()
+        .chain_update(token_id.as_bytes())
+        .chain_update(pool.trait_type.as_bytes())
+        .finalize();
+    let total_weight: u64 = pool.values.iter().map(|v| u64::from(v.weight)).sum();
+    let mut pick = u64::from_be_bytes(hash[..8].try_into().unwrap()) % total_weight;
+    let value = pool
+        .values
+        .iter()
+        .find(|v| {
+            if pick < u64::from(v.weight) {
+                return true;
+            }
+            pick -= u64::from(v.weight);
+            false
+        })
+        // total weight is validated on instantiate to be non-zero
+        .unwrap();
+    Trait {
+        display_type: None,
+        trait_type: pool.trait_type.clone(),
+        value: value.value.clone(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::msg::{ForwardMsg, MintSpec, TokenMetadata, WeightedTraitValue};
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MOCK_CONTRACT_ADDR};
-    use cosmwasm_std::{from_json, to_json_binary, CosmosMsg, SubMsgResponse, SubMsgResult};
+    use cosmwasm_std::{
+        from_json, to_json_binary, CosmosMsg, Decimal, SubMsgResponse, SubMsgResult,
+    };
     use cw721::DefaultOptionalNftExtensionMsg;
     use prost::Message;
 
@@ -213,6 +439,7 @@ mod tests {
             owner: Addr::unchecked("owner"),
             max_tokens: 1,
             unit_price: Uint128::new(1),
+            decimal_unit_price: None,
             name: String::from("SYNTH"),
             symbol: String::from("SYNTH"),
             collection_info_extension: None,
@@ -221,6 +448,9 @@ mod tests {
             token_uri: String::from("https://ipfs.io/ipfs/Q"),
             extension: None,
             withdraw_address: None,
+            premint: None,
+            trait_pool: None,
+            token_metadata: None,
         };
 
         let info = mock_info("owner", &[]);
@@ -233,13 +463,29 @@ mod tests {
             vec![SubMsg {
                 msg: WasmMsg::Instantiate {
                     code_id: msg.token_code_id,
-                    msg: to_json_binary(&Cw721InstantiateMsg {
+                    msg: to_json_binary(&Cw721InstantiateMsg::<
+                        DefaultOptionalNftExtensionMsg,
+                        DefaultOptionalCollectionExtensionMsg,
+                    > {
                         name: msg.name.clone(),
                         symbol: msg.symbol.clone(),
                         collection_info_extension: msg.collection_info_extension,
                         minter: None,
                         creator: None,
                         withdraw_address: None,
+                        burn_policy: None,
+                        ipfs_gateway: None,
+                        mint_price: None,
+                        public_minting: false,
+                        max_royalty_bps: None,
+                        commit_hash: None,
+                        initial_mints: None,
+                        track_burned_ids: false,
+                        base_token_uri: None,
+                        allow_force_transfer: false,
+                        collection_metadata_uri: None,
+                        allow_mint_to_contract: false,
+                        unique_token_uris: false,
                     })
                     .unwrap(),
                     funds: vec![],
@@ -274,15 +520,16 @@ mod tests {
 
         let query_msg = QueryMsg::GetConfig {};
         let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
-        let config: Config = from_json(res).unwrap();
+        let config: ConfigResponse = from_json(res).unwrap();
         assert_eq!(
             config,
-            Config {
+            ConfigResponse {
                 owner: Addr::unchecked("owner"),
                 cw20_address: msg.cw20_address,
                 cw721_address: Some(Addr::unchecked(NFT_CONTRACT_ADDR)),
                 max_tokens: msg.max_tokens,
                 unit_price: msg.unit_price,
+                decimal_unit_price: None,
                 name: msg.name,
                 symbol: msg.symbol,
                 token_uri: msg.token_uri,
@@ -299,6 +546,7 @@ mod tests {
             owner: Addr::unchecked("owner"),
             max_tokens: 1,
             unit_price: Uint128::new(0),
+            decimal_unit_price: None,
             name: String::from("SYNTH"),
             symbol: String::from("SYNTH"),
             collection_info_extension: None,
@@ -307,6 +555,9 @@ mod tests {
             token_uri: String::from("https://ipfs.io/ipfs/Q"),
             extension: None,
             withdraw_address: None,
+            premint: None,
+            trait_pool: None,
+            token_metadata: None,
         };
 
         let info = mock_info("owner", &[]);
@@ -325,6 +576,7 @@ mod tests {
             owner: Addr::unchecked("owner"),
             max_tokens: 0,
             unit_price: Uint128::new(1),
+            decimal_unit_price: None,
             name: String::from("SYNTH"),
             symbol: String::from("SYNTH"),
             collection_info_extension: None,
@@ -333,6 +585,9 @@ mod tests {
             token_uri: String::from("https://ipfs.io/ipfs/Q"),
             extension: None,
             withdraw_address: None,
+            premint: None,
+            trait_pool: None,
+            token_metadata: None,
         };
 
         let info = mock_info("owner", &[]);
@@ -351,6 +606,7 @@ mod tests {
             owner: Addr::unchecked("owner"),
             max_tokens: 1,
             unit_price: Uint128::new(1),
+            decimal_unit_price: None,
             name: String::from("SYNTH"),
             symbol: String::from("SYNTH"),
             collection_info_extension: None,
@@ -359,6 +615,9 @@ mod tests {
             token_uri: String::from("https://ipfs.io/ipfs/Q"),
             extension: None,
             withdraw_address: None,
+            premint: None,
+            trait_pool: None,
+            token_metadata: None,
         };
 
         let info = mock_info("owner", &[]);
@@ -400,6 +659,7 @@ mod tests {
             owner: String::from("minter"),
             token_uri: Some(String::from("https://ipfs.io/ipfs/Q")),
             extension: None,
+            ibc_data: None,
         };
 
         assert_eq!(
@@ -417,6 +677,616 @@ mod tests {
         );
     }
 
+    /// Instantiates by owner and links the cw721 contract, returns the response minting reserved
+    /// tokens
+    fn premint_setup(
+        mut deps: DepsMut,
+        msg: InstantiateMsg<DefaultOptionalCollectionExtension>,
+    ) -> Response {
+        instantiate(deps.branch(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+        let instantiate_reply = MsgInstantiateContractResponse {
+            contract_address: NFT_CONTRACT_ADDR.to_string(),
+            data: vec![2u8; 32769],
+        };
+        let mut encoded_instantiate_reply =
+            Vec::<u8>::with_capacity(instantiate_reply.encoded_len());
+        instantiate_reply
+            .encode(&mut encoded_instantiate_reply)
+            .unwrap();
+        let reply_msg = Reply {
+            id: INSTANTIATE_TOKEN_REPLY_ID,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(encoded_instantiate_reply.into()),
+            }),
+        };
+        reply(deps, mock_env(), reply_msg).unwrap()
+    }
+
+    #[test]
+    fn premint() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            owner: Addr::unchecked("owner"),
+            max_tokens: 3,
+            unit_price: Uint128::new(1),
+            decimal_unit_price: None,
+            name: String::from("SYNTH"),
+            symbol: String::from("SYNTH"),
+            collection_info_extension: None,
+            token_code_id: 10u64,
+            cw20_address: Addr::unchecked(MOCK_CONTRACT_ADDR),
+            token_uri: String::from("https://ipfs.io/ipfs/Q"),
+            extension: None,
+            withdraw_address: None,
+            premint: Some(vec![
+                MintSpec {
+                    token_id: String::from("0"),
+                    token_uri: None,
+                    extension: None,
+                },
+                MintSpec {
+                    token_id: String::from("1"),
+                    token_uri: Some(String::from("https://ipfs.io/ipfs/R")),
+                    extension: None,
+                },
+            ]),
+            trait_pool: None,
+            token_metadata: None,
+        };
+
+        // duplicate reserved token ids are rejected
+        let mut duplicate_msg = msg.clone();
+        duplicate_msg.premint = Some(vec![
+            MintSpec {
+                token_id: String::from("0"),
+                token_uri: None,
+                extension: None,
+            },
+            MintSpec {
+                token_id: String::from("0"),
+                token_uri: None,
+                extension: None,
+            },
+        ]);
+        let err = instantiate(
+            mock_dependencies().as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            duplicate_msg,
+        )
+        .unwrap_err();
+        match err {
+            ContractError::DuplicateReservedTokenId { token_id } => assert_eq!(token_id, "0"),
+            e => panic!("unexpected error: {e}"),
+        }
+
+        // reserved token ids must be within max tokens
+        for token_id in ["3", "a"] {
+            let mut invalid_msg = msg.clone();
+            invalid_msg.premint = Some(vec![MintSpec {
+                token_id: String::from(token_id),
+                token_uri: None,
+                extension: None,
+            }]);
+            let err = instantiate(
+                mock_dependencies().as_mut(),
+                mock_env(),
+                mock_info("owner", &[]),
+                invalid_msg,
+            )
+            .unwrap_err();
+            match err {
+                ContractError::InvalidReservedTokenId { token_id: id } => assert_eq!(id, token_id),
+                e => panic!("unexpected error: {e}"),
+            }
+        }
+
+        let res = premint_setup(deps.as_mut(), msg);
+
+        // reserved tokens are minted to owner
+        let expected_mints = [
+            ("0", "https://ipfs.io/ipfs/Q"),
+            ("1", "https://ipfs.io/ipfs/R"),
+        ];
+        assert_eq!(res.messages.len(), expected_mints.len());
+        for (sub_msg, (token_id, token_uri)) in res.messages.iter().zip(expected_mints) {
+            let mint_msg = Cw721ExecuteMsg::<
+                DefaultOptionalNftExtensionMsg,
+                DefaultOptionalCollectionExtensionMsg,
+                Empty,
+            >::Mint {
+                token_id: String::from(token_id),
+                owner: String::from("owner"),
+                token_uri: Some(String::from(token_uri)),
+                extension: None,
+                ibc_data: None,
+            };
+            assert_eq!(
+                sub_msg.msg,
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: NFT_CONTRACT_ADDR.to_string(),
+                    msg: to_json_binary(&mint_msg).unwrap(),
+                    funds: vec![],
+                })
+            );
+        }
+
+        // public sale skips reserved token ids
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("minter"),
+            amount: Uint128::new(1),
+            msg: [].into(),
+        });
+        let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+
+        let mint_msg = Cw721ExecuteMsg::<
+            DefaultOptionalNftExtensionMsg,
+            DefaultOptionalCollectionExtensionMsg,
+            Empty,
+        >::Mint {
+            token_id: String::from("2"),
+            owner: String::from("minter"),
+            token_uri: Some(String::from("https://ipfs.io/ipfs/Q")),
+            extension: None,
+            ibc_data: None,
+        };
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: NFT_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&mint_msg).unwrap(),
+                funds: vec![],
+            })
+        );
+
+        // reserved tokens count towards max tokens
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        match err {
+            ContractError::SoldOut {} => {}
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn next_token_id_tracks_mints() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            owner: Addr::unchecked("owner"),
+            max_tokens: 4,
+            unit_price: Uint128::new(1),
+            decimal_unit_price: None,
+            name: String::from("SYNTH"),
+            symbol: String::from("SYNTH"),
+            collection_info_extension: None,
+            token_code_id: 10u64,
+            cw20_address: Addr::unchecked(MOCK_CONTRACT_ADDR),
+            token_uri: String::from("https://ipfs.io/ipfs/Q"),
+            extension: None,
+            withdraw_address: None,
+            premint: Some(vec![MintSpec {
+                token_id: String::from("1"),
+                token_uri: None,
+                extension: None,
+            }]),
+            trait_pool: None,
+            token_metadata: None,
+        };
+        premint_setup(deps.as_mut(), msg);
+
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("minter"),
+            amount: Uint128::new(1),
+            msg: [].into(),
+        });
+        let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+
+        // reserved token id 1 is skipped
+        for expected in ["0", "2", "3"] {
+            let res = query(deps.as_ref(), mock_env(), QueryMsg::NextTokenId {}).unwrap();
+            let next: Option<String> = from_json(res).unwrap();
+            assert_eq!(next, Some(String::from(expected)));
+
+            let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+            let mint_msg = Cw721ExecuteMsg::<
+                DefaultOptionalNftExtensionMsg,
+                DefaultOptionalCollectionExtensionMsg,
+                Empty,
+            >::Mint {
+                token_id: String::from(expected),
+                owner: String::from("minter"),
+                token_uri: Some(String::from("https://ipfs.io/ipfs/Q")),
+                extension: None,
+                ibc_data: None,
+            };
+            assert_eq!(
+                res.messages[0].msg,
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: NFT_CONTRACT_ADDR.to_string(),
+                    msg: to_json_binary(&mint_msg).unwrap(),
+                    funds: vec![],
+                })
+            );
+        }
+
+        // sold out
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::NextTokenId {}).unwrap();
+        let next: Option<String> = from_json(res).unwrap();
+        assert_eq!(next, None);
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        match err {
+            ContractError::SoldOut {} => {}
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn trait_pool() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            owner: Addr::unchecked("owner"),
+            max_tokens: 2,
+            unit_price: Uint128::new(1),
+            decimal_unit_price: None,
+            name: String::from("SYNTH"),
+            symbol: String::from("SYNTH"),
+            collection_info_extension: None,
+            token_code_id: 10u64,
+            cw20_address: Addr::unchecked(MOCK_CONTRACT_ADDR),
+            token_uri: String::from("https://ipfs.io/ipfs/Q"),
+            extension: Some(NftExtension {
+                name: Some(String::from("Synth")),
+                ..NftExtension::default()
+            }),
+            withdraw_address: None,
+            premint: None,
+            trait_pool: Some(vec![
+                TraitPool {
+                    trait_type: String::from("background"),
+                    values: vec![
+                        WeightedTraitValue {
+                            value: String::from("red"),
+                            weight: 1,
+                        },
+                        WeightedTraitValue {
+                            value: String::from("blue"),
+                            weight: 1,
+                        },
+                        WeightedTraitValue {
+                            value: String::from("green"),
+                            weight: 2,
+                        },
+                    ],
+                },
+                TraitPool {
+                    trait_type: String::from("eyes"),
+                    values: vec![
+                        WeightedTraitValue {
+                            value: String::from("laser"),
+                            weight: 1,
+                        },
+                        WeightedTraitValue {
+                            value: String::from("normal"),
+                            weight: 9,
+                        },
+                    ],
+                },
+            ]),
+            token_metadata: None,
+        };
+
+        // pool without any weight is rejected
+        let mut invalid_msg = msg.clone();
+        invalid_msg.trait_pool = Some(vec![TraitPool {
+            trait_type: String::from("background"),
+            values: vec![WeightedTraitValue {
+                value: String::from("red"),
+                weight: 0,
+            }],
+        }]);
+        let err = instantiate(
+            mock_dependencies().as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            invalid_msg,
+        )
+        .unwrap_err();
+        match err {
+            ContractError::InvalidTraitPool { trait_type } => assert_eq!(trait_type, "background"),
+            e => panic!("unexpected error: {e}"),
+        }
+
+        let info = mock_info("owner", &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let instantiate_reply = MsgInstantiateContractResponse {
+            contract_address: NFT_CONTRACT_ADDR.to_string(),
+            data: vec![2u8; 32769],
+        };
+        let mut encoded_instantiate_reply =
+            Vec::<u8>::with_capacity(instantiate_reply.encoded_len());
+        instantiate_reply
+            .encode(&mut encoded_instantiate_reply)
+            .unwrap();
+        let reply_msg = Reply {
+            id: INSTANTIATE_TOKEN_REPLY_ID,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(encoded_instantiate_reply.into()),
+            }),
+        };
+        reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+
+        // same id always yields same traits
+        let config = CONFIG.load(deps.as_ref().storage).unwrap();
+        assert_eq!(
+            generate_extension(deps.as_ref().storage, &config, "0").unwrap(),
+            generate_extension(deps.as_ref().storage, &config, "0").unwrap()
+        );
+
+        // different ids yield different traits
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("minter"),
+            amount: Uint128::new(1),
+            msg: [].into(),
+        });
+        let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+        for (token_id, background) in [("0", "red"), ("1", "green")] {
+            let preview = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::PreviewGenerated {
+                    token_id: String::from(token_id),
+                },
+            )
+            .unwrap();
+            let preview: DefaultOptionalNftExtension = from_json(preview).unwrap();
+            let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+            let extension = NftExtension {
+                name: Some(String::from("Synth")),
+                attributes: Some(vec![
+                    Trait {
+                        display_type: None,
+                        trait_type: String::from("background"),
+                        value: String::from(background),
+                    },
+                    Trait {
+                        display_type: None,
+                        trait_type: String::from("eyes"),
+                        value: String::from("normal"),
+                    },
+                ]),
+                ..NftExtension::default()
+            };
+            // preview matches minted extension
+            assert_eq!(preview, Some(extension.clone()));
+            let mint_msg = Cw721ExecuteMsg::<
+                DefaultOptionalNftExtensionMsg,
+                DefaultOptionalCollectionExtensionMsg,
+                Empty,
+            >::Mint {
+                token_id: String::from(token_id),
+                owner: String::from("minter"),
+                token_uri: Some(String::from("https://ipfs.io/ipfs/Q")),
+                extension: Some(extension.into()),
+                ibc_data: None,
+            };
+            assert_eq!(
+                res.messages[0].msg,
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: NFT_CONTRACT_ADDR.to_string(),
+                    msg: to_json_binary(&mint_msg).unwrap(),
+                    funds: vec![],
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn token_metadata() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            owner: Addr::unchecked("owner"),
+            max_tokens: 3,
+            unit_price: Uint128::new(1),
+            decimal_unit_price: None,
+            name: String::from("SYNTH"),
+            symbol: String::from("SYNTH"),
+            collection_info_extension: None,
+            token_code_id: 10u64,
+            cw20_address: Addr::unchecked(MOCK_CONTRACT_ADDR),
+            token_uri: String::from("https://ipfs.io/ipfs/Q"),
+            extension: None,
+            withdraw_address: None,
+            premint: None,
+            trait_pool: None,
+            token_metadata: Some(vec![
+                TokenMetadata {
+                    token_uri: Some(String::from("https://ipfs.io/ipfs/Q/0.json")),
+                    extension: Some(NftExtension {
+                        name: Some(String::from("zero")),
+                        ..NftExtension::default()
+                    }),
+                },
+                TokenMetadata {
+                    token_uri: None,
+                    extension: Some(NftExtension {
+                        name: Some(String::from("one")),
+                        ..NftExtension::default()
+                    }),
+                },
+            ]),
+        };
+
+        // more entries than tokens are rejected
+        let mut invalid_msg = msg.clone();
+        invalid_msg.max_tokens = 1;
+        let err = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            invalid_msg,
+        )
+        .unwrap_err();
+        match err {
+            ContractError::InvalidTokenMetadata {} => {}
+            e => panic!("unexpected error: {e}"),
+        }
+
+        let info = mock_info("owner", &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let instantiate_reply = MsgInstantiateContractResponse {
+            contract_address: NFT_CONTRACT_ADDR.to_string(),
+            data: vec![2u8; 32769],
+        };
+        let mut encoded_instantiate_reply =
+            Vec::<u8>::with_capacity(instantiate_reply.encoded_len());
+        instantiate_reply
+            .encode(&mut encoded_instantiate_reply)
+            .unwrap();
+
+        let reply_msg = Reply {
+            id: INSTANTIATE_TOKEN_REPLY_ID,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(encoded_instantiate_reply.into()),
+            }),
+        };
+        reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("minter"),
+            amount: Uint128::new(1),
+            msg: [].into(),
+        });
+        let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+
+        // i-th mint gets i-th metadata, falling back to shared values
+        let expected = [
+            ("https://ipfs.io/ipfs/Q/0.json", Some("zero")),
+            ("https://ipfs.io/ipfs/Q", Some("one")),
+            ("https://ipfs.io/ipfs/Q", None),
+        ];
+        for (token_id, (token_uri, name)) in expected.into_iter().enumerate() {
+            let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+            let mint_msg = Cw721ExecuteMsg::<
+                DefaultOptionalNftExtensionMsg,
+                DefaultOptionalCollectionExtensionMsg,
+                Empty,
+            >::Mint {
+                token_id: token_id.to_string(),
+                owner: String::from("minter"),
+                token_uri: Some(String::from(token_uri)),
+                extension: name.map(|name| {
+                    NftExtension {
+                        name: Some(String::from(name)),
+                        ..NftExtension::default()
+                    }
+                    .into()
+                }),
+                ibc_data: None,
+            };
+            assert_eq!(
+                res.messages[0].msg,
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: NFT_CONTRACT_ADDR.to_string(),
+                    msg: to_json_binary(&mint_msg).unwrap(),
+                    funds: vec![],
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn decimal_unit_price() {
+        let msg = InstantiateMsg {
+            owner: Addr::unchecked("owner"),
+            max_tokens: 1,
+            unit_price: Uint128::new(1),
+            decimal_unit_price: None,
+            name: String::from("SYNTH"),
+            symbol: String::from("SYNTH"),
+            collection_info_extension: None,
+            token_code_id: 10u64,
+            cw20_address: Addr::unchecked(MOCK_CONTRACT_ADDR),
+            token_uri: String::from("https://ipfs.io/ipfs/Q"),
+            extension: None,
+            withdraw_address: None,
+            premint: None,
+            trait_pool: None,
+            token_metadata: None,
+        };
+
+        // zero price is rejected
+        let mut zero_msg = msg.clone();
+        zero_msg.decimal_unit_price = Some(Decimal::zero());
+        let err = instantiate(
+            mock_dependencies().as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            zero_msg,
+        )
+        .unwrap_err();
+        match err {
+            ContractError::InvalidUnitPrice {} => {}
+            e => panic!("unexpected error: {e}"),
+        }
+
+        // (price, rejected amount, required amount), fractional prices are rounded up
+        let cases = [
+            ("0.1", 0, 1),
+            ("1.5", 1, 2),
+            ("2", 3, 2),
+            ("2.000000000000000001", 2, 3),
+        ];
+        for (price, rejected, required) in cases {
+            let mut deps = mock_dependencies();
+            let mut msg = msg.clone();
+            msg.decimal_unit_price = Some(price.parse().unwrap());
+            // unused in decimal mode, so zero is fine
+            msg.unit_price = Uint128::zero();
+            instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+            let instantiate_reply = MsgInstantiateContractResponse {
+                contract_address: NFT_CONTRACT_ADDR.to_string(),
+                data: vec![2u8; 32769],
+            };
+            let mut encoded_instantiate_reply =
+                Vec::<u8>::with_capacity(instantiate_reply.encoded_len());
+            instantiate_reply
+                .encode(&mut encoded_instantiate_reply)
+                .unwrap();
+            let reply_msg = Reply {
+                id: INSTANTIATE_TOKEN_REPLY_ID,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: Some(encoded_instantiate_reply.into()),
+                }),
+            };
+            reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+
+            let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+            let receive_msg = |amount: u128| {
+                ExecuteMsg::Receive(Cw20ReceiveMsg {
+                    sender: String::from("minter"),
+                    amount: Uint128::new(amount),
+                    msg: [].into(),
+                })
+            };
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                receive_msg(rejected),
+            )
+            .unwrap_err();
+            match err {
+                ContractError::WrongPaymentAmount {} => {}
+                e => panic!("unexpected error: {e}"),
+            }
+            execute(deps.as_mut(), mock_env(), info, receive_msg(required)).unwrap();
+        }
+    }
+
     #[test]
     fn invalid_reply_id() {
         let mut deps = mock_dependencies();
@@ -424,6 +1294,7 @@ mod tests {
             owner: Addr::unchecked("owner"),
             max_tokens: 1,
             unit_price: Uint128::new(1),
+            decimal_unit_price: None,
             name: String::from("SYNTH"),
             symbol: String::from("SYNTH"),
             collection_info_extension: None,
@@ -432,6 +1303,9 @@ mod tests {
             token_uri: String::from("https://ipfs.io/ipfs/Q"),
             extension: None,
             withdraw_address: None,
+            premint: None,
+            trait_pool: None,
+            token_metadata: None,
         };
 
         let info = mock_info("owner", &[]);
@@ -467,6 +1341,7 @@ mod tests {
             owner: Addr::unchecked("owner"),
             max_tokens: 1,
             unit_price: Uint128::new(1),
+            decimal_unit_price: None,
             name: String::from("SYNTH"),
             symbol: String::from("SYNTH"),
             collection_info_extension: None,
@@ -475,6 +1350,9 @@ mod tests {
             token_uri: String::from("https://ipfs.io/ipfs/Q"),
             extension: None,
             withdraw_address: None,
+            premint: None,
+            trait_pool: None,
+            token_metadata: None,
         };
 
         let info = mock_info("owner", &[]);
@@ -512,6 +1390,7 @@ mod tests {
             owner: Addr::unchecked("owner"),
             max_tokens: 1,
             unit_price: Uint128::new(1),
+            decimal_unit_price: None,
             name: String::from("SYNTH"),
             symbol: String::from("SYNTH"),
             collection_info_extension: None,
@@ -520,6 +1399,9 @@ mod tests {
             token_uri: String::from("https://ipfs.io/ipfs/Q"),
             extension: None,
             withdraw_address: None,
+            premint: None,
+            trait_pool: None,
+            token_metadata: None,
         };
 
         let info = mock_info("owner", &[]);
@@ -568,6 +1450,7 @@ mod tests {
             owner: Addr::unchecked("owner"),
             max_tokens: 1,
             unit_price: Uint128::new(1),
+            decimal_unit_price: None,
             name: String::from("SYNTH"),
             symbol: String::from("SYNTH"),
             collection_info_extension: None,
@@ -576,6 +1459,9 @@ mod tests {
             token_uri: String::from("https://ipfs.io/ipfs/Q"),
             extension: None,
             withdraw_address: None,
+            premint: None,
+            trait_pool: None,
+            token_metadata: None,
         };
 
         let info = mock_info("owner", &[]);
@@ -604,6 +1490,7 @@ mod tests {
             owner: Addr::unchecked("owner"),
             max_tokens: 1,
             unit_price: Uint128::new(1),
+            decimal_unit_price: None,
             name: String::from("SYNTH"),
             symbol: String::from("SYNTH"),
             collection_info_extension: None,
@@ -612,6 +1499,9 @@ mod tests {
             token_uri: String::from("https://ipfs.io/ipfs/Q"),
             extension: None,
             withdraw_address: None,
+            premint: None,
+            trait_pool: None,
+            token_metadata: None,
         };
 
         let info = mock_info("owner", &[]);
@@ -660,6 +1550,7 @@ mod tests {
             owner: Addr::unchecked("owner"),
             max_tokens: 1,
             unit_price: Uint128::new(1),
+            decimal_unit_price: None,
             name: String::from("SYNTH"),
             symbol: String::from("SYNTH"),
             collection_info_extension: None,
@@ -668,6 +1559,9 @@ mod tests {
             token_uri: String::from("https://ipfs.io/ipfs/Q"),
             extension: None,
             withdraw_address: None,
+            premint: None,
+            trait_pool: None,
+            token_metadata: None,
         };
 
         let info = mock_info("owner", &[]);
@@ -708,4 +1602,300 @@ mod tests {
             e => panic!("unexpected error: {e}"),
         }
     }
+
+    #[test]
+    fn buy_for_recipient() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            owner: Addr::unchecked("owner"),
+            max_tokens: 2,
+            unit_price: Uint128::new(1),
+            decimal_unit_price: None,
+            name: String::from("SYNTH"),
+            symbol: String::from("SYNTH"),
+            collection_info_extension: None,
+            token_code_id: 10u64,
+            cw20_address: Addr::unchecked(MOCK_CONTRACT_ADDR),
+            token_uri: String::from("https://ipfs.io/ipfs/Q"),
+            extension: None,
+            withdraw_address: None,
+            premint: None,
+            trait_pool: None,
+            token_metadata: None,
+        };
+
+        let info = mock_info("owner", &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let instantiate_reply = MsgInstantiateContractResponse {
+            contract_address: NFT_CONTRACT_ADDR.to_string(),
+            data: vec![2u8; 32769],
+        };
+        let mut encoded_instantiate_reply =
+            Vec::<u8>::with_capacity(instantiate_reply.encoded_len());
+        instantiate_reply
+            .encode(&mut encoded_instantiate_reply)
+            .unwrap();
+
+        let reply_msg = Reply {
+            id: INSTANTIATE_TOKEN_REPLY_ID,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(encoded_instantiate_reply.into()),
+            }),
+        };
+        reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+
+        let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+        let buy = |recipient: &str| {
+            ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: String::from("minter"),
+                amount: Uint128::new(1),
+                msg: to_json_binary(&BuyMsg {
+                    recipient: Some(String::from(recipient)),
+                    forward: None,
+                    expected_price: None,
+                })
+                .unwrap(),
+            })
+        };
+
+        // invalid recipient is rejected
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), buy("")).unwrap_err();
+        match err {
+            ContractError::Std(_) => {}
+            e => panic!("unexpected error: {e}"),
+        }
+
+        // token is minted to recipient instead of sender
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), buy("friend")).unwrap();
+        let mint_msg = Cw721ExecuteMsg::<
+            DefaultOptionalNftExtensionMsg,
+            DefaultOptionalCollectionExtensionMsg,
+            Empty,
+        >::Mint {
+            token_id: String::from("0"),
+            owner: String::from("friend"),
+            token_uri: Some(String::from("https://ipfs.io/ipfs/Q")),
+            extension: None,
+            ibc_data: None,
+        };
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: NFT_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&mint_msg).unwrap(),
+                funds: vec![],
+            })
+        );
+
+        // payload without recipient mints to sender
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: String::from("minter"),
+            amount: Uint128::new(1),
+            msg: to_json_binary(&BuyMsg::default()).unwrap(),
+        });
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let mint_msg = Cw721ExecuteMsg::<
+            DefaultOptionalNftExtensionMsg,
+            DefaultOptionalCollectionExtensionMsg,
+            Empty,
+        >::Mint {
+            token_id: String::from("1"),
+            owner: String::from("minter"),
+            token_uri: Some(String::from("https://ipfs.io/ipfs/Q")),
+            extension: None,
+            ibc_data: None,
+        };
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: NFT_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&mint_msg).unwrap(),
+                funds: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn buy_and_forward() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            owner: Addr::unchecked("owner"),
+            max_tokens: 1,
+            unit_price: Uint128::new(1),
+            decimal_unit_price: None,
+            name: String::from("SYNTH"),
+            symbol: String::from("SYNTH"),
+            collection_info_extension: None,
+            token_code_id: 10u64,
+            cw20_address: Addr::unchecked(MOCK_CONTRACT_ADDR),
+            token_uri: String::from("https://ipfs.io/ipfs/Q"),
+            extension: None,
+            withdraw_address: None,
+            premint: None,
+            trait_pool: None,
+            token_metadata: None,
+        };
+
+        let info = mock_info("owner", &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let instantiate_reply = MsgInstantiateContractResponse {
+            contract_address: NFT_CONTRACT_ADDR.to_string(),
+            data: vec![2u8; 32769],
+        };
+        let mut encoded_instantiate_reply =
+            Vec::<u8>::with_capacity(instantiate_reply.encoded_len());
+        instantiate_reply
+            .encode(&mut encoded_instantiate_reply)
+            .unwrap();
+
+        let reply_msg = Reply {
+            id: INSTANTIATE_TOKEN_REPLY_ID,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(encoded_instantiate_reply.into()),
+            }),
+        };
+        reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+
+        let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+        let forward = ForwardMsg {
+            contract: String::from("staking"),
+            msg: to_json_binary("stake for minter").unwrap(),
+        };
+        let buy = |recipient: Option<String>| {
+            ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: String::from("minter"),
+                amount: Uint128::new(1),
+                msg: to_json_binary(&BuyMsg {
+                    recipient,
+                    forward: Some(forward.clone()),
+                    expected_price: None,
+                })
+                .unwrap(),
+            })
+        };
+
+        // recipient and forward are exclusive
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            buy(Some(String::from("friend"))),
+        )
+        .unwrap_err();
+        match err {
+            ContractError::RecipientAndForward {} => {}
+            e => panic!("unexpected error: {e}"),
+        }
+
+        // token is minted to this contract, then sent to the target contract
+        let res = execute(deps.as_mut(), mock_env(), info, buy(None)).unwrap();
+        let mint_msg = Cw721ExecuteMsg::<
+            DefaultOptionalNftExtensionMsg,
+            DefaultOptionalCollectionExtensionMsg,
+            Empty,
+        >::Mint {
+            token_id: String::from("0"),
+            owner: MOCK_CONTRACT_ADDR.to_string(),
+            token_uri: Some(String::from("https://ipfs.io/ipfs/Q")),
+            extension: None,
+            ibc_data: None,
+        };
+        let send_msg = Cw721ExecuteMsg::<
+            DefaultOptionalNftExtensionMsg,
+            DefaultOptionalCollectionExtensionMsg,
+            Empty,
+        >::SendNft {
+            contract: String::from("staking"),
+            token_id: String::from("0"),
+            msg: forward.msg,
+            expected_owner: None,
+        };
+        assert_eq!(res.messages.len(), 2);
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: NFT_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&mint_msg).unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(
+            res.messages[1].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: NFT_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&send_msg).unwrap(),
+                funds: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn expected_price() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            owner: Addr::unchecked("owner"),
+            max_tokens: 1,
+            unit_price: Uint128::new(1),
+            decimal_unit_price: Some(Decimal::percent(150)),
+            name: String::from("SYNTH"),
+            symbol: String::from("SYNTH"),
+            collection_info_extension: None,
+            token_code_id: 10u64,
+            cw20_address: Addr::unchecked(MOCK_CONTRACT_ADDR),
+            token_uri: String::from("https://ipfs.io/ipfs/Q"),
+            extension: None,
+            withdraw_address: None,
+            premint: None,
+            trait_pool: None,
+            token_metadata: None,
+        };
+
+        let info = mock_info("owner", &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let instantiate_reply = MsgInstantiateContractResponse {
+            contract_address: NFT_CONTRACT_ADDR.to_string(),
+            data: vec![2u8; 32769],
+        };
+        let mut encoded_instantiate_reply =
+            Vec::<u8>::with_capacity(instantiate_reply.encoded_len());
+        instantiate_reply
+            .encode(&mut encoded_instantiate_reply)
+            .unwrap();
+
+        let reply_msg = Reply {
+            id: INSTANTIATE_TOKEN_REPLY_ID,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(encoded_instantiate_reply.into()),
+            }),
+        };
+        reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+
+        let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+        let buy = |amount: u128, expected_price: u128| {
+            ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: String::from("minter"),
+                amount: Uint128::new(amount),
+                msg: to_json_binary(&BuyMsg {
+                    recipient: None,
+                    forward: None,
+                    expected_price: Some(Uint128::new(expected_price)),
+                })
+                .unwrap(),
+            })
+        };
+
+        // buyer expected the old price, actual price is rounded up 1.5
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), buy(1, 1)).unwrap_err();
+        match err {
+            ContractError::PriceChanged { actual } => assert_eq!(actual, Uint128::new(2)),
+            e => panic!("unexpected error: {e}"),
+        }
+
+        // matching expectation mints
+        let res = execute(deps.as_mut(), mock_env(), info, buy(2, 2)).unwrap();
+        assert_eq!(res.messages.len(), 1);
+    }
 }
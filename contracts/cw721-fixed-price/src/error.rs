@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -35,4 +35,22 @@ pub enum ContractError {
 
     #[error("Cw721AlreadyLinked")]
     Cw721AlreadyLinked {},
+
+    #[error("DuplicateReservedTokenId: {token_id}")]
+    DuplicateReservedTokenId { token_id: String },
+
+    #[error("InvalidReservedTokenId: {token_id}")]
+    InvalidReservedTokenId { token_id: String },
+
+    #[error("InvalidTraitPool: {trait_type}")]
+    InvalidTraitPool { trait_type: String },
+
+    #[error("InvalidTokenMetadata")]
+    InvalidTokenMetadata {},
+
+    #[error("RecipientAndForward")]
+    RecipientAndForward {},
+
+    #[error("PriceChanged: {actual}")]
+    PriceChanged { actual: Uint128 },
 }
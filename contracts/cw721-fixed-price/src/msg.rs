@@ -1,5 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Binary, Decimal, Uint128};
 use cw20::Cw20ReceiveMsg;
 use cw721::DefaultOptionalNftExtension;
 
@@ -8,6 +8,9 @@ pub struct InstantiateMsg<TCollectionExtensionMsg> {
     pub owner: Addr,
     pub max_tokens: u32,
     pub unit_price: Uint128,
+    /// Price as decimal, e.g. derived from an oracle feed. If set, it replaces `unit_price`
+    /// and the required payment is rounded up to the next integer amount to protect the seller.
+    pub decimal_unit_price: Option<Decimal>,
     /// Name of the NFT contract
     pub name: String,
     /// Symbol of the NFT contract
@@ -19,18 +22,87 @@ pub struct InstantiateMsg<TCollectionExtensionMsg> {
     pub token_uri: String,
     pub extension: DefaultOptionalNftExtension,
     pub withdraw_address: Option<String>,
+    /// Reserved tokens minted to the owner once the cw721 contract is linked, before the public sale starts.
+    /// Public sale skips any token id reserved here.
+    pub premint: Option<Vec<MintSpec>>,
+    /// Traits generated on-chain for each token minted by the public sale, replacing `extension.attributes`.
+    /// For each pool one value is picked by weight, seeded by the SHA-256 hash of token id and trait type.
+    pub trait_pool: Option<Vec<TraitPool>>,
+    /// Metadata for tokens minted by the public sale, where the i-th entry is used for token id `i`.
+    /// Must not exceed `max_tokens`. Tokens without an entry use `token_uri` and `extension`.
+    pub token_metadata: Option<Vec<TokenMetadata>>,
+}
+
+#[cw_serde]
+pub struct TraitPool {
+    pub trait_type: String,
+    /// Possible values, each picked with probability `weight / sum of weights`
+    pub values: Vec<WeightedTraitValue>,
+}
+
+#[cw_serde]
+pub struct WeightedTraitValue {
+    pub value: String,
+    pub weight: u32,
+}
+
+#[cw_serde]
+pub struct MintSpec {
+    /// Unique ID of the reserved NFT
+    pub token_id: String,
+    /// Falls back to `InstantiateMsg.token_uri` if not set
+    pub token_uri: Option<String>,
+    /// Falls back to `InstantiateMsg.extension` if not set
+    pub extension: DefaultOptionalNftExtension,
+}
+
+#[cw_serde]
+pub struct TokenMetadata {
+    /// Falls back to `InstantiateMsg.token_uri` if not set
+    pub token_uri: Option<String>,
+    /// Falls back to `InstantiateMsg.extension` if not set
+    pub extension: DefaultOptionalNftExtension,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
+    /// Buys a token, optional `BuyMsg` is passed in `Cw20ReceiveMsg.msg`
     Receive(Cw20ReceiveMsg),
 }
 
+/// Payload of a cw20 send to this contract. An empty payload buys a token for the sender.
+#[cw_serde]
+#[derive(Default)]
+pub struct BuyMsg {
+    /// Receives the minted token, e.g. for gifting. Defaults to the cw20 sender.
+    pub recipient: Option<String>,
+    /// Sends the minted token to a contract right away, e.g. to buy and stake in one step.
+    /// Can't be combined with `recipient`.
+    pub forward: Option<ForwardMsg>,
+    /// Price the buyer agreed to. If set, mint is rejected when the current price differs.
+    pub expected_price: Option<Uint128>,
+}
+
+/// `SendNft` of the minted token, issued by this contract as its intermediate owner. The
+/// receiving contract gets this contract as `sender`, so `msg` should identify the buyer.
+#[cw_serde]
+pub struct ForwardMsg {
+    pub contract: String,
+    pub msg: Binary,
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
     #[returns(ConfigResponse)]
     GetConfig {},
+    /// Token id minted by the next public sale, skipping reserved ids. `None` if sold out.
+    #[returns(Option<String>)]
+    NextTokenId {},
+    /// Extension the public sale would mint for the given token id, including generated traits.
+    /// Nothing is minted, this is for previews only.
+    #[returns(DefaultOptionalNftExtension)]
+    PreviewGenerated { token_id: String },
 }
 
 #[cw_serde]
@@ -40,6 +112,7 @@ pub struct ConfigResponse {
     pub cw721_address: Option<Addr>,
     pub max_tokens: u32,
     pub unit_price: Uint128,
+    pub decimal_unit_price: Option<Decimal>,
     pub name: String,
     pub symbol: String,
     pub token_uri: String,
@@ -1,7 +1,9 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Decimal, Empty, Uint128};
 use cw721::DefaultOptionalNftExtension;
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
+
+use crate::msg::{MintSpec, TokenMetadata, TraitPool};
 
 #[cw_serde]
 pub struct Config {
@@ -18,3 +20,13 @@ pub struct Config {
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
+/// Tokens minted to the owner once the cw721 contract is linked.
+pub const PREMINT: Item<Vec<MintSpec>> = Item::new("premint");
+/// Token ids reserved by premint, skipped by the public sale.
+pub const RESERVED_TOKEN_IDS: Map<&str, Empty> = Map::new("reserved_token_ids");
+/// Trait pool for generating token metadata on public sale.
+pub const TRAIT_POOL: Item<Vec<TraitPool>> = Item::new("trait_pool");
+/// Per-token metadata for public sale, stored as token id -> metadata.
+pub const TOKEN_METADATA: Map<&str, TokenMetadata> = Map::new("token_metadata");
+/// Decimal price replacing `Config.unit_price`, rounded up on payment.
+pub const DECIMAL_UNIT_PRICE: Item<Decimal> = Item::new("decimal_unit_price");
@@ -24,6 +24,19 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 ///     minter: None,
 ///     creator: None,
 ///     withdraw_address: None,
+///     burn_policy: None,
+///     ipfs_gateway: None,
+///     mint_price: None,
+///     public_minting: false,
+///     max_royalty_bps: None,
+///     commit_hash: None,
+///     initial_mints: None,
+///     track_burned_ids: false,
+///     base_token_uri: None,
+///     allow_force_transfer: false,
+///     collection_metadata_uri: None,
+///     allow_mint_to_contract: false,
+///     unique_token_uris: false,
 /// };
 /// // ...
 /// // mint:
@@ -44,6 +57,7 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 ///     owner: "john".to_string(),
 ///     token_uri: token_uri.clone(),
 ///     extension: extension.clone(),
+///     ibc_data: None,
 /// };
 /// // ...
 /// ```
@@ -124,11 +138,24 @@ mod tests {
             mock_info("sender", &[]),
             InstantiateMsg {
                 name: "collection_name".into(),
-                symbol: "collection_symbol".into(),
+                symbol: "SYMBOL".into(),
                 collection_info_extension: None,
                 minter: None,
                 creator: None,
                 withdraw_address: None,
+                burn_policy: None,
+                ipfs_gateway: None,
+                mint_price: None,
+                public_minting: false,
+                max_royalty_bps: None,
+                commit_hash: None,
+                initial_mints: None,
+                track_burned_ids: false,
+                base_token_uri: None,
+                allow_force_transfer: false,
+                collection_metadata_uri: None,
+                allow_mint_to_contract: false,
+                unique_token_uris: false,
             },
         )
         .unwrap();
@@ -150,6 +177,19 @@ mod tests {
             minter: None,
             creator: None,
             withdraw_address: None,
+            burn_policy: None,
+            ipfs_gateway: None,
+            mint_price: None,
+            public_minting: false,
+            max_royalty_bps: None,
+            commit_hash: None,
+            initial_mints: None,
+            track_burned_ids: false,
+            base_token_uri: None,
+            allow_force_transfer: false,
+            collection_metadata_uri: None,
+            allow_mint_to_contract: false,
+            unique_token_uris: false,
         };
         contract
             .instantiate(deps.as_mut(), &mock_env(), &info.clone(), init_msg)
@@ -172,13 +212,14 @@ mod tests {
             owner: "john".to_string(),
             token_uri: token_uri.clone(),
             extension: extension.clone(),
+            ibc_data: None,
         };
         contract
             .execute(deps.as_mut(), &mock_env(), &info, exec_msg)
             .unwrap();
 
         let res = contract
-            .query_nft_info(deps.as_ref().storage, token_id.into())
+            .query_nft_info(deps.as_ref(), &mock_env(), token_id.into())
             .unwrap();
         assert_eq!(res.token_uri, token_uri);
         assert_eq!(
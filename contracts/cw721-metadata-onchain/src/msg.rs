@@ -6,7 +6,10 @@ use cw721::{
     DefaultOptionalNftExtensionMsg,
 };
 
-pub type InstantiateMsg = cw721::msg::Cw721InstantiateMsg<DefaultOptionalCollectionExtensionMsg>;
+pub type InstantiateMsg = cw721::msg::Cw721InstantiateMsg<
+    DefaultOptionalNftExtensionMsg,
+    DefaultOptionalCollectionExtensionMsg,
+>;
 pub type ExecuteMsg = cw721::msg::Cw721ExecuteMsg<
     DefaultOptionalNftExtensionMsg,
     DefaultOptionalCollectionExtensionMsg,
@@ -14,6 +17,7 @@ pub type ExecuteMsg = cw721::msg::Cw721ExecuteMsg<
 >;
 pub type QueryMsg = cw721::msg::Cw721QueryMsg<
     DefaultOptionalNftExtension,
+    DefaultOptionalNftExtensionMsg,
     DefaultOptionalCollectionExtension,
     Empty,
 >;
@@ -22,7 +22,7 @@ pub mod entry {
         entry_point, to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response,
     };
     use cw721::error::Cw721ContractError;
-    use cw721::msg::{Cw721ExecuteMsg, Cw721InstantiateMsg};
+    use cw721::msg::{CanTransferResponse, Cw721ExecuteMsg, Cw721InstantiateMsg};
     use cw721::traits::{Cw721Execute, Cw721Query};
     use cw721::{EmptyOptionalCollectionExtensionMsg, EmptyOptionalNftExtensionMsg};
 
@@ -50,6 +50,19 @@ pub mod entry {
             minter: msg.minter,
             creator: msg.creator,
             withdraw_address: msg.withdraw_address,
+            burn_policy: None,
+            ipfs_gateway: None,
+            mint_price: None,
+            public_minting: false,
+            max_royalty_bps: None,
+            commit_hash: None,
+            initial_mints: None,
+            track_burned_ids: false,
+            base_token_uri: None,
+            allow_force_transfer: false,
+            collection_metadata_uri: None,
+            allow_mint_to_contract: false,
+            unique_token_uris: false,
         };
 
         Cw721NonTransferableContract::default().instantiate_with_version(
@@ -96,8 +109,10 @@ pub mod entry {
                     owner,
                     token_uri,
                     extension,
-                } => Cw721NonTransferableContract::default()
-                    .mint(deps, &env, &info, token_id, owner, token_uri, extension),
+                    ibc_data,
+                } => Cw721NonTransferableContract::default().mint_with_ibc_data(
+                    deps, &env, &info, token_id, owner, token_uri, extension, ibc_data,
+                ),
                 _ => Err(Cw721ContractError::Ownership(
                     cw721::OwnershipError::NotOwner,
                 )),
@@ -109,6 +124,18 @@ pub mod entry {
     pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, Cw721ContractError> {
         match msg {
             QueryMsg::Admin {} => Ok(to_json_binary(&admin(deps)?)?),
+            QueryMsg::CanTransfer { token_id, sender } => {
+                // without admin, no one can transfer
+                let can_transfer = match CONFIG.load(deps.storage)?.admin {
+                    Some(admin) if admin == sender => Cw721NonTransferableContract::default()
+                        .query_can_transfer(deps, &env, token_id, sender)?,
+                    _ => CanTransferResponse {
+                        allowed: false,
+                        reason: Some("Only admin can transfer".to_string()),
+                    },
+                };
+                Ok(to_json_binary(&can_transfer)?)
+            }
             _ => Cw721EmptyExtensions::default().query(deps, &env, msg.into()),
         }
     }
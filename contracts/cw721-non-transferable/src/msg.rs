@@ -1,7 +1,10 @@
 use cosmwasm_schema::cw_serde;
 
 use cosmwasm_std::Empty;
-use cw721::{msg::Cw721QueryMsg, EmptyOptionalCollectionExtension, EmptyOptionalNftExtension};
+use cw721::{
+    msg::Cw721QueryMsg, EmptyOptionalCollectionExtension, EmptyOptionalNftExtension,
+    EmptyOptionalNftExtensionMsg,
+};
 
 #[cw_serde]
 pub struct InstantiateMsg<TCollectionExtension> {
@@ -35,6 +38,11 @@ pub enum QueryMsg {
         token_id: String,
         include_expired: Option<bool>,
     },
+    /// Only admin (if set) is allowed to transfer tokens.
+    CanTransfer {
+        token_id: String,
+        sender: String,
+    },
     AllOperators {
         owner: String,
         include_expired: Option<bool>,
@@ -80,11 +88,21 @@ pub enum QueryMsg {
 }
 
 impl From<QueryMsg>
-    for Cw721QueryMsg<EmptyOptionalNftExtension, EmptyOptionalCollectionExtension, Empty>
+    for Cw721QueryMsg<
+        EmptyOptionalNftExtension,
+        EmptyOptionalNftExtensionMsg,
+        EmptyOptionalCollectionExtension,
+        Empty,
+    >
 {
     fn from(
         msg: QueryMsg,
-    ) -> Cw721QueryMsg<EmptyOptionalNftExtension, EmptyOptionalCollectionExtension, Empty> {
+    ) -> Cw721QueryMsg<
+        EmptyOptionalNftExtension,
+        EmptyOptionalNftExtensionMsg,
+        EmptyOptionalCollectionExtension,
+        Empty,
+    > {
         match msg {
             QueryMsg::OwnerOf {
                 token_id,
@@ -115,10 +133,13 @@ impl From<QueryMsg>
                 owner,
                 start_after,
                 limit,
+                cursor: None,
+            },
+            QueryMsg::AllTokens { start_after, limit } => Cw721QueryMsg::AllTokens {
+                start_after,
+                limit,
+                cursor: None,
             },
-            QueryMsg::AllTokens { start_after, limit } => {
-                Cw721QueryMsg::AllTokens { start_after, limit }
-            }
             #[allow(deprecated)]
             QueryMsg::Minter {} => Cw721QueryMsg::Minter {},
             QueryMsg::GetMinterOwnership {} => Cw721QueryMsg::GetMinterOwnership {},
@@ -127,6 +148,7 @@ impl From<QueryMsg>
             QueryMsg::AllOperators { .. } => unreachable!("AllOperators is not supported!"),
             QueryMsg::Approval { .. } => unreachable!("Approval is not supported!"),
             QueryMsg::Approvals { .. } => unreachable!("Approvals is not supported!"),
+            QueryMsg::CanTransfer { .. } => unreachable!("CanTransfer is handled by contract!"),
             QueryMsg::Admin { .. } => unreachable!("Approvals is not supported!"),
         }
     }
@@ -23,6 +23,7 @@ fn test_cw721_base_receive_succeed() {
                 contract: receiver_contract.to_string(),
                 token_id: "test".to_string(),
                 msg: to_json_binary(&InnerMsg::Succeed).unwrap(),
+                expected_owner: None,
             },
             &[],
         )
@@ -60,7 +61,9 @@ fn test_cw721_base_receive_succeed() {
 
 #[test]
 fn test_cw721_base_receive_fail() {
+    use cw721_base::helpers::EmptyCw721Helper;
     use cw721_base::msg::*;
+    use cw721_base::traits::Cw721Calls;
     use cw721_receiver_tester::msg::InnerMsg;
 
     let mut app = App::default();
@@ -79,6 +82,7 @@ fn test_cw721_base_receive_fail() {
             contract: receiver_contract.to_string(),
             token_id: "test".to_string(),
             msg: to_json_binary(&InnerMsg::Fail).unwrap(),
+            expected_owner: None,
         },
         &[],
     );
@@ -86,16 +90,23 @@ fn test_cw721_base_receive_fail() {
 
     // send incorrect message
     let result = app.execute_contract(
-        admin,
-        nft_contract,
+        admin.clone(),
+        nft_contract.clone(),
         &ExecuteMsg::SendNft {
             contract: receiver_contract.to_string(),
             token_id: "test".to_string(),
             msg: Binary::from(br#"{"invalid": "fields"}"#),
+            expected_owner: None,
         },
         &[],
     );
     assert!(result.is_err());
+
+    // failing receiver reverts the transfer, so sender still owns the token
+    let owner = EmptyCw721Helper::new(nft_contract)
+        .owner_of(&app.wrap(), "test", false)
+        .unwrap();
+    assert_eq!(owner.owner, admin.to_string());
 }
 
 struct Contracts {
@@ -129,6 +140,19 @@ fn setup_contracts(app: &mut App, admin: Addr) -> Contracts {
                 minter: Some(admin.to_string()),
                 creator: Some(admin.to_string()),
                 withdraw_address: None,
+                burn_policy: None,
+                ipfs_gateway: None,
+                mint_price: None,
+                public_minting: false,
+                max_royalty_bps: None,
+                commit_hash: None,
+                initial_mints: None,
+                track_burned_ids: false,
+                base_token_uri: None,
+                allow_force_transfer: false,
+                collection_metadata_uri: None,
+                allow_mint_to_contract: false,
+                unique_token_uris: false,
             },
             &[],
             "nft".to_string(),
@@ -156,6 +180,7 @@ fn setup_contracts(app: &mut App, admin: Addr) -> Contracts {
             owner: admin.to_string(),
             token_uri: Some("https://example.com".to_string()),
             extension: None,
+            ibc_data: None,
         },
         &[],
     )
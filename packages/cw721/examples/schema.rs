@@ -1,19 +1,20 @@
 use std::env::current_dir;
 use std::fs::create_dir_all;
 
-use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, schema_for};
+use cosmwasm_schema::{
+    export_schema, export_schema_with_title, remove_schemas, schema_for, write_api,
+};
 
-use cosmwasm_std::Empty;
 use cw721::{
     msg::{
         AllNftInfoResponse, ApprovalResponse, ApprovalsResponse,
-        CollectionInfoAndExtensionResponse, ConfigResponse, Cw721ExecuteMsg, Cw721InstantiateMsg,
-        Cw721MigrateMsg, Cw721QueryMsg, MinterResponse, NftInfoResponse, NumTokensResponse,
-        OperatorResponse, OperatorsResponse, OwnerOfResponse, TokensResponse,
+        CollectionInfoAndExtensionResponse, ConfigResponse, Cw721MigrateMsg, MinterResponse,
+        NftInfoResponse, NumTokensResponse, OperatorResponse, OperatorsResponse, OwnerOfResponse,
+        TokensResponse,
     },
     receiver::Cw721ReceiveMsg,
+    DefaultCw721ExecuteMsg, DefaultCw721InstantiateMsg, DefaultCw721QueryMsg,
     DefaultOptionalCollectionExtension, DefaultOptionalNftExtension,
-    DefaultOptionalNftExtensionMsg,
 };
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -23,23 +24,17 @@ fn main() {
 
     // entry points - generate always with title for avoiding name suffixes like "..._empty_for_..." due to generics
     export_schema_with_title(
-        &schema_for!(Cw721InstantiateMsg<DefaultOptionalCollectionExtension>),
+        &schema_for!(DefaultCw721InstantiateMsg),
         &out_dir,
         "Cw721InstantiateMsg",
     );
     export_schema_with_title(
-        &schema_for!(
-            Cw721ExecuteMsg::<
-                DefaultOptionalNftExtensionMsg,
-                DefaultOptionalCollectionExtension,
-                Empty,
-            >
-        ),
+        &schema_for!(DefaultCw721ExecuteMsg),
         &out_dir,
         "Cw721ExecuteMsg",
     );
     export_schema_with_title(
-        &schema_for!(Cw721QueryMsg<DefaultOptionalNftExtension, DefaultOptionalCollectionExtension, Empty>),
+        &schema_for!(DefaultCw721QueryMsg),
         &out_dir,
         "Cw721QueryMsg",
     );
@@ -77,4 +72,12 @@ fn main() {
     export_schema(&schema_for!(MinterResponse), &out_dir);
     export_schema(&schema_for!(NumTokensResponse), &out_dir);
     export_schema(&schema_for!(TokensResponse), &out_dir);
+
+    // full api incl. query responses, with default extensions resolved (e.g. for ts-codegen)
+    write_api! {
+        instantiate: DefaultCw721InstantiateMsg,
+        execute: DefaultCw721ExecuteMsg,
+        query: DefaultCw721QueryMsg,
+        migrate: Cw721MigrateMsg,
+    }
 }
@@ -1,6 +1,6 @@
 use std::{num::ParseIntError, str::ParseBoolError};
 
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Coin, StdError, Timestamp};
 use cw_ownable::OwnershipError;
 use thiserror::Error;
 use url::ParseError;
@@ -34,6 +34,30 @@ pub enum Cw721ContractError {
     #[error("token_id already claimed")]
     Claimed {},
 
+    #[error("token_id was burned and cannot be minted again")]
+    TokenIdWasBurned {},
+
+    #[error("Force transfer is not enabled for this collection")]
+    ForceTransferDisabled {},
+
+    #[error("Cannot mint to the contract itself")]
+    CannotMintToContract {},
+
+    #[error("Recipient is not a valid address or the contract itself")]
+    InvalidRecipient {},
+
+    #[error("Token has expired")]
+    TokenExpired {},
+
+    #[error("Token has not expired yet")]
+    TokenNotExpired {},
+
+    #[error("Token uri is already used by another token")]
+    DuplicateTokenUri {},
+
+    #[error("Duplicate token id: {token_id}")]
+    DuplicateTokenId { token_id: String },
+
     #[error("Caller is not collection creator")]
     NotCreator {},
 
@@ -43,27 +67,108 @@ pub enum Cw721ContractError {
     #[error("Caller is neither minter nor collection creator")]
     NotMinterOrCreator {},
 
+    #[error("Caller is not authorized")]
+    Unauthorized {},
+
     #[error("Cannot set approval that is already expired")]
     Expired {},
 
+    #[error("Cannot approve the token owner itself")]
+    CannotApproveSelf {},
+
+    #[error("Cannot approve this contract")]
+    CannotApproveContract {},
+
     #[error("Approval not found for: {spender}")]
     ApprovalNotFound { spender: String },
 
+    #[error("Current owner of token does not match expected owner")]
+    OwnerMismatch {},
+
+    #[error("Token {token_id} is not owned by this contract")]
+    NotContractOwned { token_id: String },
+
+    #[error("Token {token_id} is locked by a pending swap offer")]
+    TokenLocked { token_id: String },
+
+    #[error("Token can't be transferred, sent or burned before its timelock ends")]
+    TokenTimelocked {},
+
+    #[error("Token can't be transferred before {ready_at}")]
+    TransferCooldown { ready_at: Timestamp },
+
+    #[error("Payment of {expected} required")]
+    MissingPayment { expected: Coin },
+
+    #[error("Payment must be exactly {expected}")]
+    WrongPayment { expected: Coin },
+
+    #[error("Public minting requires a mint price")]
+    MissingMintPrice {},
+
+    #[error("Transfer fee required")]
+    TransferFeeRequired {},
+
+    #[error("Swap offer has expired")]
+    SwapOfferExpired {},
+
     #[error("No withdraw address set")]
     NoWithdrawAddress {},
 
+    #[error("No voucher signer set")]
+    NoVoucherSigner {},
+
+    #[error("Invalid secp256k1 public key, must be 33 or 65 bytes")]
+    InvalidPublicKey {},
+
+    #[error("Invalid voucher signature")]
+    InvalidVoucherSignature {},
+
+    #[error("Voucher has already been redeemed")]
+    VoucherRedeemed {},
+
+    #[error("No royalty info set")]
+    NoRoyaltyInfo {},
+
+    #[error("No funds sent")]
+    NoFunds {},
+
+    #[error("Unsupported denom: {denom}")]
+    UnsupportedDenom { denom: String },
+
+    #[error("Invalid metadata schema hash: {hash}. Must be a hex-encoded SHA-256 hash.")]
+    InvalidMetadataSchemaHash { hash: String },
+
     #[error("Collection name must not be empty")]
     CollectionNameEmpty {},
 
     #[error("Collection symbol must not be empty")]
     CollectionSymbolEmpty {},
 
+    #[error("Collection name too long. Max length is {max_length} characters.")]
+    InvalidName { max_length: u32 },
+
+    #[error("Invalid collection symbol {symbol}. Must be alphanumeric with max length of {max_length} characters.")]
+    InvalidSymbol { symbol: String, max_length: u32 },
+
     #[error("Collection description must not be empty")]
     CollectionDescriptionEmpty {},
 
     #[error("Collection description too long. Max length is {max_length} characters.")]
     CollectionDescriptionTooLong { max_length: u32 },
 
+    #[error("Commit hash must be a 32 bytes SHA-256 hash")]
+    InvalidCommitHash {},
+
+    #[error("No commit to reveal")]
+    NoCommit {},
+
+    #[error("Revealed data does not match the commit")]
+    CommitMismatch {},
+
+    #[error("Royalty share exceeds the collection's royalty cap")]
+    RoyaltyExceedsCap {},
+
     #[error("InvalidRoyalties: {0}")]
     InvalidRoyalties(String),
 
@@ -1,21 +1,31 @@
+use std::collections::HashSet;
+
 use cosmwasm_std::{
-    Addr, Api, BankMsg, Binary, Coin, CustomMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response,
-    StdResult, Storage,
+    Addr, Api, BankMsg, Binary, Coin, CustomMsg, Decimal, Deps, DepsMut, Empty, Env, Event,
+    MessageInfo, Order, Response, StdError, StdResult, Storage, Timestamp, Uint128,
 };
 use cw_ownable::{none_or, Action, Ownership, OwnershipError};
-use cw_storage_plus::Item;
-use cw_utils::Expiration;
+use cw_storage_plus::{Bound, Item};
+use cw_utils::{must_pay, Duration, Expiration, PaymentError};
+use url::Url;
 
 use crate::{
     error::Cw721ContractError,
     extension::{
         Cw721BaseExtensions, Cw721EmptyExtensions, Cw721Extensions, Cw721OnchainExtensions,
     },
-    helpers::value_or_empty,
-    msg::{CollectionInfoMsg, Cw721InstantiateMsg, Cw721MigrateMsg, NftInfoMsg},
-    query::query_collection_info_and_extension,
+    helpers::{commit_hash, value_or_empty, voucher_hash},
+    msg::{
+        empty_as_none, Clearable, CollectionInfoMsg, Cw721InstantiateMsg, Cw721MigrateMsg, MintMsg,
+        MintVoucher, NftInfoMsg, NftInfoUpdate, NftTransfer, RevealAssignment, RoyaltyInfoResponse,
+        RoyaltySplitMsg,
+    },
+    query::{query_collection_info_and_extension, DEFAULT_LIMIT, MAX_LIMIT},
     receiver::Cw721ReceiveMsg,
-    state::{CollectionInfo, Cw721Config, NftInfo, CREATOR, MINTER},
+    state::{
+        BurnPolicy, CollectionInfo, Cw721Config, NftInfo, RoyaltySplit, SwapOffer,
+        TokenIndexBackfill, CREATOR, MAX_APPROVAL_SWEEP, METADATA_SCHEMA_HASH_LENGTH, MINTER,
+    },
     traits::{
         Cw721CustomMsg, Cw721Execute, Cw721State, FromAttributesState, StateFactory,
         ToAttributesState,
@@ -26,42 +36,88 @@ use crate::{
 };
 
 // ------- instantiate -------
-pub fn instantiate_with_version<TCollectionExtension, TCollectionExtensionMsg, TCustomResponseMsg>(
+pub fn instantiate_with_version<
+    TNftExtension,
+    TNftExtensionMsg,
+    TCollectionExtension,
+    TCollectionExtensionMsg,
+    TCustomResponseMsg,
+>(
     deps: DepsMut,
     env: &Env,
     info: &MessageInfo,
-    msg: Cw721InstantiateMsg<TCollectionExtensionMsg>,
+    msg: Cw721InstantiateMsg<TNftExtensionMsg, TCollectionExtensionMsg>,
     contract_name: &str,
     contract_version: &str,
 ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
 where
+    TNftExtension: Cw721State,
+    TNftExtensionMsg: Cw721CustomMsg + StateFactory<TNftExtension>,
     TCollectionExtension: Cw721State + ToAttributesState + FromAttributesState,
     TCollectionExtensionMsg: Cw721CustomMsg + StateFactory<TCollectionExtension>,
+    TCustomResponseMsg: CustomMsg,
 {
     cw2::set_contract_version(deps.storage, contract_name, contract_version)?;
-    instantiate(deps, env, info, msg)
+    instantiate::<
+        TNftExtension,
+        TNftExtensionMsg,
+        TCollectionExtension,
+        TCollectionExtensionMsg,
+        TCustomResponseMsg,
+    >(deps, env, info, msg)
 }
 
-pub fn instantiate<TCollectionExtension, TCollectionExtensionMsg, TCustomResponseMsg>(
-    deps: DepsMut,
+/// Initial mints (if any) are minted on behalf of the minter.
+pub fn instantiate<
+    TNftExtension,
+    TNftExtensionMsg,
+    TCollectionExtension,
+    TCollectionExtensionMsg,
+    TCustomResponseMsg,
+>(
+    mut deps: DepsMut,
     env: &Env,
     info: &MessageInfo,
-    msg: Cw721InstantiateMsg<TCollectionExtensionMsg>,
+    msg: Cw721InstantiateMsg<TNftExtensionMsg, TCollectionExtensionMsg>,
 ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
 where
+    TNftExtension: Cw721State,
+    TNftExtensionMsg: Cw721CustomMsg + StateFactory<TNftExtension>,
     TCollectionExtension: Cw721State + ToAttributesState + FromAttributesState,
     TCollectionExtensionMsg: Cw721CustomMsg + StateFactory<TCollectionExtension>,
+    TCustomResponseMsg: CustomMsg,
 {
     let config = Cw721Config::<Option<Empty>>::default();
 
+    // ---- royalty cap before(!) collection info, so its royalty info is checked against it ----
+    if let Some(max_royalty_bps) = msg.max_royalty_bps {
+        if max_royalty_bps > 10_000 {
+            return Err(Cw721ContractError::InvalidRoyalties(
+                "Royalty cap cannot be greater than 10000 bps".to_string(),
+            ));
+        }
+        config
+            .max_royalty_bps
+            .save(deps.storage, &max_royalty_bps)?;
+    }
+
     // ---- update collection info before(!) creator and minter is set ----
     let collection_metadata_msg = CollectionInfoMsg {
         name: Some(msg.name),
         symbol: Some(msg.symbol),
         extension: msg.collection_info_extension,
+        collection_metadata_uri: msg
+            .collection_metadata_uri
+            .map_or(Clearable::NoChange, Clearable::Set),
+        transfer_fee: Clearable::NoChange,
     };
     let collection_info = collection_metadata_msg.create(deps.as_ref(), env, info.into(), None)?;
     let extension_attributes = collection_info.extension.to_attributes_state()?;
+    if let Some(collection_metadata_uri) = &collection_info.collection_metadata_uri {
+        config
+            .collection_metadata_uri
+            .save(deps.storage, collection_metadata_uri)?;
+    }
     let collection_info = collection_info.into();
     config
         .collection_info
@@ -89,12 +145,82 @@ where
 
     if let Some(withdraw_address) = msg.withdraw_address.clone() {
         let creator = deps.api.addr_validate(creator)?;
-        set_withdraw_address::<TCustomResponseMsg>(deps, &creator, withdraw_address)?;
+        set_withdraw_address::<TCustomResponseMsg>(deps.branch(), &creator, withdraw_address)?;
+    }
+
+    if let Some(burn_policy) = msg.burn_policy {
+        config.burn_policy.save(deps.storage, &burn_policy)?;
+    }
+
+    if let Some(ipfs_gateway) = msg.ipfs_gateway {
+        Url::parse(&ipfs_gateway)?;
+        config.ipfs_gateway.save(deps.storage, &ipfs_gateway)?;
+    }
+
+    // public minting without price would make minting free for anyone
+    if msg.public_minting && msg.mint_price.is_none() {
+        return Err(Cw721ContractError::MissingMintPrice {});
+    }
+    if let Some(mint_price) = msg.mint_price {
+        config.mint_price.save(deps.storage, &mint_price)?;
+    }
+    if msg.public_minting {
+        config.public_minting.save(deps.storage, &true)?;
+    }
+    if let Some(commit_hash) = msg.commit_hash {
+        if commit_hash.len() != 32 {
+            return Err(Cw721ContractError::InvalidCommitHash {});
+        }
+        config.commit_hash.save(deps.storage, &commit_hash)?;
+    }
+    if msg.track_burned_ids {
+        config.track_burned_ids.save(deps.storage, &true)?;
+    }
+    if msg.allow_force_transfer {
+        config.allow_force_transfer.save(deps.storage, &true)?;
+    }
+    if msg.allow_mint_to_contract {
+        config.allow_mint_to_contract.save(deps.storage, &true)?;
+    }
+    if msg.unique_token_uris {
+        config.unique_token_uris.save(deps.storage, &true)?;
+    }
+    if let Some(base_token_uri) = msg.base_token_uri {
+        Url::parse(&base_token_uri)?;
+        config.base_token_uri.save(deps.storage, &base_token_uri)?;
+    }
+
+    // ---- mint initial tokens after(!) setup, so all mint checks apply ----
+    let initial_mints = msg.initial_mints.unwrap_or_default();
+    let num_initial_mints = initial_mints.len();
+    if num_initial_mints > 0 {
+        let minter_info = MessageInfo {
+            sender: deps.api.addr_validate(minter)?,
+            funds: vec![],
+        };
+        for MintMsg {
+            token_id,
+            owner,
+            token_uri,
+            extension,
+        } in initial_mints
+        {
+            mint::<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+                deps.branch(),
+                env,
+                &minter_info,
+                token_id,
+                owner,
+                token_uri,
+                extension,
+            )?;
+        }
     }
 
     Ok(Response::default()
         .add_attribute("minter", minter)
-        .add_attribute("creator", creator))
+        .add_attribute("creator", creator)
+        .add_attribute("initial_mints", num_initial_mints.to_string()))
 }
 
 // ------- helper cw721 functions -------
@@ -114,27 +240,314 @@ pub fn initialize_minter(
     MINTER.initialize_owner(storage, api, minter)
 }
 
-pub fn transfer_nft<TNftExtension>(
+pub fn transfer_nft<TNftExtension, TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    recipient: String,
+    token_id: String,
+    expected_owner: Option<String>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TCustomResponseMsg: CustomMsg,
+{
+    let fee_msg = check_transfer_fee(deps.storage, info, 1)?;
+    _transfer_nft::<TNftExtension>(
+        deps,
+        env,
+        info,
+        &recipient,
+        &token_id,
+        expected_owner.as_deref(),
+    )?;
+
+    Ok(Response::new()
+        .add_messages(fee_msg)
+        .add_attribute("action", "transfer_nft")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("recipient", recipient)
+        .add_attribute("token_id", token_id))
+}
+
+/// Transfers the token without charging the transfer fee, callers charge it once per message.
+pub fn _transfer_nft<TNftExtension>(
     deps: DepsMut,
     env: &Env,
     info: &MessageInfo,
     recipient: &str,
     token_id: &str,
+    expected_owner: Option<&str>,
 ) -> Result<NftInfo<TNftExtension>, Cw721ContractError>
 where
     TNftExtension: Cw721State,
 {
     let config = Cw721Config::<TNftExtension>::default();
     let mut token = config.nft_info.load(deps.storage, token_id)?;
+    // reject in case token has changed hands in the meantime
+    if let Some(expected_owner) = expected_owner {
+        if token.owner != expected_owner {
+            return Err(Cw721ContractError::OwnerMismatch {});
+        }
+    }
     // ensure we have permissions
-    check_can_send(deps.as_ref(), env, info.sender.as_str(), &token)?;
-    // set owner and remove existing approvals
-    token.owner = deps.api.addr_validate(recipient)?;
-    token.approvals = vec![];
-    config.nft_info.save(deps.storage, token_id, &token)?;
+    check_can_transfer(deps.as_ref(), env, info.sender.as_str(), token_id, &token)?;
+    let recipient = deps.api.addr_validate(recipient)?;
+    let previous_owner = token.owner.clone();
+    change_owner(deps.storage, env, token_id, &mut token, recipient)?;
+    sweep_expired_approvals::<TNftExtension>(deps.storage, env, &previous_owner, token_id)?;
     Ok(token)
 }
 
+/// Recipient must be a valid address and not the contract itself, see `SafeTransferNft`.
+pub fn check_recipient(deps: Deps, env: &Env, recipient: &str) -> Result<Addr, Cw721ContractError> {
+    let recipient = deps
+        .api
+        .addr_validate(recipient)
+        .map_err(|_| Cw721ContractError::InvalidRecipient {})?;
+    if recipient == env.contract.address {
+        return Err(Cw721ContractError::InvalidRecipient {});
+    }
+    Ok(recipient)
+}
+
+pub fn recover_nft<TNftExtension, TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    token_id: String,
+    recipient: String,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TCustomResponseMsg: CustomMsg,
+{
+    CREATOR.assert_owner(deps.storage, &info.sender)?;
+    let config = Cw721Config::<TNftExtension>::default();
+    let mut token = config.nft_info.load(deps.storage, &token_id)?;
+    // only tokens stranded on the contract itself, creator has no say on any other token
+    if token.owner != env.contract.address {
+        return Err(Cw721ContractError::NotContractOwned { token_id });
+    }
+    let recipient_addr = deps.api.addr_validate(&recipient)?;
+    change_owner(deps.storage, env, &token_id, &mut token, recipient_addr)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "recover_nft")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("recipient", recipient)
+        .add_attribute("token_id", token_id))
+}
+
+pub fn force_transfer_all<TNftExtension, TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    from: String,
+    to: String,
+    limit: Option<u32>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TCustomResponseMsg: CustomMsg,
+{
+    CREATOR.assert_owner(deps.storage, &info.sender)?;
+    let config = Cw721Config::<TNftExtension>::default();
+    if !config
+        .allow_force_transfer
+        .may_load(deps.storage)?
+        .unwrap_or(false)
+    {
+        return Err(Cw721ContractError::ForceTransferDisabled {});
+    }
+    let from_addr = deps.api.addr_validate(&from)?;
+    let to_addr = deps.api.addr_validate(&to)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    // transferred tokens leave the owner index, so next call continues with remaining ones
+    let token_ids = config
+        .nft_info
+        .idx
+        .owner
+        .prefix(from_addr)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "force_transfer_all")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("from", from)
+        .add_attribute("to", to)
+        .add_attribute("num_tokens", token_ids.len().to_string());
+    for token_id in token_ids {
+        let mut token = config.nft_info.load(deps.storage, &token_id)?;
+        change_owner(deps.storage, env, &token_id, &mut token, to_addr.clone())?;
+        // pending offer of previous owner would keep the token locked
+        let mut event = Event::new("force_transfer").add_attribute("token_id", &token_id);
+        if let Some(offer_id) = config.swap_locks.may_load(deps.storage, &token_id)? {
+            match config.swap_offers.may_load(deps.storage, offer_id)? {
+                Some(offer) => remove_swap_offer(deps.storage, offer_id, &offer)?,
+                None => config.swap_locks.remove(deps.storage, &token_id),
+            }
+            event = event.add_attribute("cancelled_offer_id", offer_id.to_string());
+        }
+        response = response.add_event(event);
+    }
+    Ok(response)
+}
+
+/// sets new owner and removes existing approvals
+fn change_owner<TNftExtension>(
+    storage: &mut dyn Storage,
+    env: &Env,
+    token_id: &str,
+    token: &mut NftInfo<TNftExtension>,
+    owner: Addr,
+) -> StdResult<()>
+where
+    TNftExtension: Cw721State,
+{
+    let config = Cw721Config::<TNftExtension>::default();
+    token.owner = owner;
+    token.approvals = vec![];
+    config.nft_info.save(storage, token_id, token)?;
+    if config.transfer_cooldown.may_load(storage)?.is_some() {
+        config
+            .last_transfer_at
+            .save(storage, token_id, &env.block.time)?;
+    }
+    config
+        .owner_history
+        .save(storage, token_id, &token.owner, env.block.height)
+}
+
+/// Drops expired approvals from up to `MAX_APPROVAL_SWEEP` tokens of `owner` following `token_id`,
+/// so stale approvals don't pile up on tokens that are never approved or transferred again.
+fn sweep_expired_approvals<TNftExtension>(
+    storage: &mut dyn Storage,
+    env: &Env,
+    owner: &Addr,
+    token_id: &str,
+) -> StdResult<()>
+where
+    TNftExtension: Cw721State,
+{
+    let config = Cw721Config::<TNftExtension>::default();
+    let tokens = config
+        .nft_info
+        .idx
+        .owner
+        .prefix(owner.clone())
+        .range(
+            storage,
+            Some(Bound::ExclusiveRaw(token_id.as_bytes().to_vec())),
+            None,
+            Order::Ascending,
+        )
+        .take(MAX_APPROVAL_SWEEP)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (id, mut token) in tokens {
+        let count = token.approvals.len();
+        token.approvals.retain(|apr| !apr.is_expired(&env.block));
+        if token.approvals.len() != count {
+            config.nft_info.save(storage, &id, &token)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn distribute_nfts<TNftExtension, TCustomResponseMsg>(
+    mut deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    transfers: Vec<NftTransfer>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TCustomResponseMsg: CustomMsg,
+{
+    let config = Cw721Config::<TNftExtension>::default();
+    // verify all transfers upfront, so either all or none are applied
+    let mut recipients = HashSet::new();
+    let mut token_ids = HashSet::new();
+    for NftTransfer {
+        recipient,
+        token_id,
+    } in &transfers
+    {
+        if !token_ids.insert(token_id.as_str()) {
+            return Err(Cw721ContractError::DuplicateTokenId {
+                token_id: token_id.clone(),
+            });
+        }
+        let token = config.nft_info.load(deps.storage, token_id)?;
+        check_can_transfer(deps.as_ref(), env, info.sender.as_str(), token_id, &token)?;
+        deps.api.addr_validate(recipient)?;
+        recipients.insert(recipient.as_str());
+    }
+    let fee_msg = check_transfer_fee(deps.storage, info, transfers.len() as u128)?;
+    let mut response = Response::new()
+        .add_messages(fee_msg)
+        .add_attribute("action", "distribute_nfts")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("num_tokens", transfers.len().to_string())
+        .add_attribute("num_recipients", recipients.len().to_string());
+    for NftTransfer {
+        recipient,
+        token_id,
+    } in &transfers
+    {
+        _transfer_nft::<TNftExtension>(deps.branch(), env, info, recipient, token_id, None)?;
+        response = response
+            .add_attribute("token_id", token_id)
+            .add_attribute("recipient", recipient);
+    }
+
+    Ok(response)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_and_approve<TNftExtension, TCustomResponseMsg>(
+    mut deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    recipient: String,
+    token_id: String,
+    spender: String,
+    expires: Option<Expiration>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TCustomResponseMsg: CustomMsg,
+{
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    // reject expired data as invalid
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(Cw721ContractError::Expired {});
+    }
+    let fee_msg = check_transfer_fee(deps.storage, info, 1)?;
+    // authorization is checked against the current owner, sender is no longer eligible afterwards
+    let mut token =
+        _transfer_nft::<TNftExtension>(deps.branch(), env, info, &recipient, &token_id, None)?;
+    assert_valid_spender(env, &token.owner, &spender_addr)?;
+    token.approvals.push(Approval {
+        spender: spender_addr,
+        expires,
+    });
+    Cw721Config::<TNftExtension>::default()
+        .nft_info
+        .save(deps.storage, &token_id, &token)?;
+
+    Ok(Response::new()
+        .add_messages(fee_msg)
+        .add_attribute("action", "transfer_and_approve")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("recipient", recipient)
+        .add_attribute("token_id", token_id)
+        .add_attribute("spender", spender))
+}
+
 pub fn send_nft<TNftExtension, TCustomResponseMsg>(
     deps: DepsMut,
     env: &Env,
@@ -142,13 +555,22 @@ pub fn send_nft<TNftExtension, TCustomResponseMsg>(
     contract: String,
     token_id: String,
     msg: Binary,
+    expected_owner: Option<String>,
 ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
 where
     TNftExtension: Cw721State,
     TCustomResponseMsg: CustomMsg,
 {
+    let fee_msg = check_transfer_fee(deps.storage, info, 1)?;
     // Transfer token
-    transfer_nft::<TNftExtension>(deps, env, info, &contract, &token_id)?;
+    _transfer_nft::<TNftExtension>(
+        deps,
+        env,
+        info,
+        &contract,
+        &token_id,
+        expected_owner.as_deref(),
+    )?;
 
     let send = Cw721ReceiveMsg {
         sender: info.sender.to_string(),
@@ -156,8 +578,10 @@ where
         msg,
     };
 
-    // Send message
+    // Send message. Not a submessage with reply: if receiver fails, the whole tx including
+    // above transfer is reverted.
     Ok(Response::new()
+        .add_messages(fee_msg)
         .add_message(send.into_cosmos_msg(contract.clone())?)
         .add_attribute("action", "send_nft")
         .add_attribute("sender", info.sender.to_string())
@@ -165,6 +589,205 @@ where
         .add_attribute("token_id", token_id))
 }
 
+pub fn reveal_commit<TNftExtension, TCustomResponseMsg>(
+    deps: DepsMut,
+    info: &MessageInfo,
+    seed: Binary,
+    assignments: Vec<RevealAssignment>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+{
+    CREATOR.assert_owner(deps.storage, &info.sender)?;
+    let config = Cw721Config::<TNftExtension>::default();
+    let commit = config
+        .commit_hash
+        .may_load(deps.storage)?
+        .ok_or(Cw721ContractError::NoCommit {})?;
+    if commit_hash(&seed, &assignments)? != commit.as_slice() {
+        return Err(Cw721ContractError::CommitMismatch {});
+    }
+    for RevealAssignment {
+        token_id,
+        token_uri,
+    } in &assignments
+    {
+        Url::parse(token_uri)?;
+        let mut token = config.nft_info.load(deps.storage, token_id)?;
+        config.unindex_token_uri(deps.storage, token.token_uri.as_deref());
+        config.index_token_uri(deps.storage, token_id, Some(token_uri))?;
+        token.token_uri = Some(token_uri.clone());
+        config.nft_info.save(deps.storage, token_id, &token)?;
+    }
+    config.commit_hash.remove(deps.storage);
+    Ok(Response::new()
+        .add_attribute("action", "reveal_commit")
+        .add_attribute("num_tokens", assignments.len().to_string()))
+}
+
+pub fn propose_swap<TNftExtension, TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    my_token: String,
+    want_token: String,
+    counterparty: String,
+    expires: Option<Expiration>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TCustomResponseMsg: CustomMsg,
+{
+    let config = Cw721Config::<TNftExtension>::default();
+    // only owner can offer its token
+    let token = config.nft_info.load(deps.storage, &my_token)?;
+    if token.owner != info.sender {
+        return Err(Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    }
+    check_can_transfer(deps.as_ref(), env, info.sender.as_str(), &my_token, &token)?;
+    // wanted token must be transferable by counterparty
+    let counterparty = deps.api.addr_validate(&counterparty)?;
+    let token = config.nft_info.load(deps.storage, &want_token)?;
+    check_can_transfer(
+        deps.as_ref(),
+        env,
+        counterparty.as_str(),
+        &want_token,
+        &token,
+    )?;
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(Cw721ContractError::Expired {});
+    }
+
+    let offer_id = config
+        .swap_offer_count
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        + 1;
+    config.swap_offer_count.save(deps.storage, &offer_id)?;
+    let offer = SwapOffer {
+        proposer: info.sender.clone(),
+        my_token,
+        counterparty,
+        want_token,
+        expires,
+    };
+    config.swap_offers.save(deps.storage, offer_id, &offer)?;
+    config
+        .swap_locks
+        .save(deps.storage, &offer.my_token, &offer_id)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "propose_swap")
+        .add_attribute("offer_id", offer_id.to_string())
+        .add_attribute("proposer", offer.proposer)
+        .add_attribute("my_token", offer.my_token)
+        .add_attribute("counterparty", offer.counterparty)
+        .add_attribute("want_token", offer.want_token))
+}
+
+pub fn accept_swap<TNftExtension, TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    offer_id: u64,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TCustomResponseMsg: CustomMsg,
+{
+    let config = Cw721Config::<TNftExtension>::default();
+    let offer = config.swap_offers.load(deps.storage, offer_id)?;
+    if offer.counterparty != info.sender {
+        return Err(Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    }
+    if offer.is_expired(&env.block) {
+        return Err(Cw721ContractError::SwapOfferExpired {});
+    }
+    // counterparty must still own wanted token
+    let mut want_token = config.nft_info.load(deps.storage, &offer.want_token)?;
+    if want_token.owner != info.sender {
+        return Err(Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    }
+    // offered token is locked, so proposer still owns it
+    let mut my_token = config.nft_info.load(deps.storage, &offer.my_token)?;
+    if my_token.owner != offer.proposer {
+        return Err(Cw721ContractError::OwnerMismatch {});
+    }
+
+    // unlock offered token before(!) checking both tokens can be transferred
+    remove_swap_offer(deps.storage, offer_id, &offer)?;
+    check_can_transfer(
+        deps.as_ref(),
+        env,
+        offer.proposer.as_str(),
+        &offer.my_token,
+        &my_token,
+    )?;
+    check_can_transfer(
+        deps.as_ref(),
+        env,
+        info.sender.as_str(),
+        &offer.want_token,
+        &want_token,
+    )?;
+    let fee_msg = check_transfer_fee(deps.storage, info, 2)?;
+    change_owner(
+        deps.storage,
+        env,
+        &offer.my_token,
+        &mut my_token,
+        offer.counterparty.clone(),
+    )?;
+    change_owner(
+        deps.storage,
+        env,
+        &offer.want_token,
+        &mut want_token,
+        offer.proposer.clone(),
+    )?;
+
+    Ok(Response::new()
+        .add_messages(fee_msg)
+        .add_attribute("action", "accept_swap")
+        .add_attribute("offer_id", offer_id.to_string())
+        .add_attribute("proposer", offer.proposer)
+        .add_attribute("my_token", offer.my_token)
+        .add_attribute("counterparty", offer.counterparty)
+        .add_attribute("want_token", offer.want_token))
+}
+
+/// Proposer can cancel its offer, anyone can clean up an expired offer.
+pub fn cancel_swap<TCustomResponseMsg>(
+    storage: &mut dyn Storage,
+    env: &Env,
+    info: &MessageInfo,
+    offer_id: u64,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    let offer = config.swap_offers.load(storage, offer_id)?;
+    if offer.proposer != info.sender && !offer.is_expired(&env.block) {
+        return Err(Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    }
+    remove_swap_offer(storage, offer_id, &offer)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_swap")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("offer_id", offer_id.to_string()))
+}
+
+/// removes offer and unlocks offered token, unless it is locked by a newer offer
+fn remove_swap_offer(storage: &mut dyn Storage, offer_id: u64, offer: &SwapOffer) -> StdResult<()> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    config.swap_offers.remove(storage, offer_id);
+    if config.swap_locks.may_load(storage, &offer.my_token)? == Some(offer_id) {
+        config.swap_locks.remove(storage, &offer.my_token);
+    }
+    Ok(())
+}
+
 pub fn approve<TNftExtension, TCustomResponseMsg>(
     deps: DepsMut,
     env: &Env,
@@ -211,6 +834,7 @@ where
 
     // only difference between approve and revoke
     if add {
+        assert_valid_spender(env, &token.owner, &spender_addr)?;
         // reject expired data as invalid
         let expires = expires.unwrap_or_default();
         if expires.is_expired(&env.block) {
@@ -254,15 +878,24 @@ pub fn approve_all<TCustomResponseMsg>(
     operator: String,
     expires: Option<Expiration>,
 ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    // without explicit expiration, collection default applies (if set), otherwise never
+    let expires = match expires {
+        Some(expires) => expires,
+        None => config
+            .default_approval_expiry
+            .may_load(deps.storage)?
+            .map(|duration| duration.after(&env.block))
+            .unwrap_or_default(),
+    };
     // reject expired data as invalid
-    let expires = expires.unwrap_or_default();
     if expires.is_expired(&env.block) {
         return Err(Cw721ContractError::Expired {});
     }
 
     // set the operator for us
     let operator_addr = deps.api.addr_validate(&operator)?;
-    let config = Cw721Config::<Option<Empty>>::default();
+    assert_valid_spender(env, &info.sender, &operator_addr)?;
     config
         .operators
         // stores info.sender as key (=granter, NFT owner) and operator as value (operator only(!) has control over NFTs of granter)
@@ -272,7 +905,8 @@ pub fn approve_all<TCustomResponseMsg>(
     Ok(Response::new()
         .add_attribute("action", "approve_all")
         .add_attribute("sender", info.sender.to_string())
-        .add_attribute("operator", operator))
+        .add_attribute("operator", operator)
+        .add_attribute("expires", expires.to_string()))
 }
 
 pub fn revoke_all<TCustomResponseMsg>(
@@ -293,31 +927,104 @@ pub fn revoke_all<TCustomResponseMsg>(
         .add_attribute("operator", operator))
 }
 
-pub fn burn_nft<TCustomResponseMsg>(
+pub fn burn_nft<TNftExtension, TCustomResponseMsg>(
     deps: DepsMut,
     env: &Env,
     info: &MessageInfo,
     token_id: String,
-) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
-    let config = Cw721Config::<Option<Empty>>::default();
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+{
+    let config = Cw721Config::<TNftExtension>::default();
     let token = config.nft_info.load(deps.storage, &token_id)?;
-    check_can_send(deps.as_ref(), env, info.sender.as_str(), &token)?;
+    check_can_burn(deps.as_ref(), env, &info.sender, &token)?;
+    assert_not_swap_locked(deps.storage, env, &token_id)?;
+    assert_not_timelocked(env, &token)?;
 
-    config.nft_info.remove(deps.storage, &token_id)?;
-    config.decrement_tokens(deps.storage)?;
+    let mut response = Response::new();
+    if let Some(fee) = config.burn_fee.may_load(deps.storage)? {
+        // fee may have been set before its denom got delisted
+        assert_accepted_denom(deps.storage, &fee.denom)?;
+        assert_exact_payment(info, &fee)?;
+        // without withdraw address, fee stays in contract
+        if let Some(address) = config.withdraw_address.may_load(deps.storage)? {
+            response = response.add_message(BankMsg::Send {
+                to_address: address,
+                amount: vec![fee],
+            });
+        }
+    }
+    remove_token(deps, env, &token_id, &token)?;
 
-    Ok(Response::new()
+    Ok(response
         .add_attribute("action", "burn")
         .add_attribute("sender", info.sender.to_string())
         .add_attribute("token_id", token_id))
 }
 
-pub fn update_collection_info<TCollectionExtension, TCollectionExtensionMsg, TCustomResponseMsg>(
+/// Burns an expired token, see `mint_with_expiration`. Anyone may clean up expired tokens,
+/// neither burn policy nor burn fee apply.
+pub fn burn_expired<TNftExtension, TCustomResponseMsg>(
     deps: DepsMut,
-    info: Option<&MessageInfo>,
     env: &Env,
-    msg: CollectionInfoMsg<TCollectionExtensionMsg>,
-) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+    info: &MessageInfo,
+    token_id: String,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+{
+    let token = Cw721Config::<TNftExtension>::default()
+        .nft_info
+        .load(deps.storage, &token_id)?;
+    if !token.is_expired(&env.block) {
+        return Err(Cw721ContractError::TokenNotExpired {});
+    }
+    remove_token(deps, env, &token_id, &token)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "burn_expired")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("token_id", token_id))
+}
+
+/// Removes a burned token and all its per-token state.
+fn remove_token<TNftExtension>(
+    deps: DepsMut,
+    env: &Env,
+    token_id: &str,
+    token: &NftInfo<TNftExtension>,
+) -> Result<(), Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+{
+    let config = Cw721Config::<TNftExtension>::default();
+    config.nft_info.remove(deps.storage, token_id)?;
+    config.unindex_token_uri(deps.storage, token.token_uri.as_deref());
+    config.last_transfer_at.remove(deps.storage, token_id);
+    config.token_royalties.remove(deps.storage, token_id);
+    if config
+        .track_burned_ids
+        .may_load(deps.storage)?
+        .unwrap_or(false)
+    {
+        config.burned_ids.save(deps.storage, token_id, &Empty {})?;
+    }
+    config.decrement_trait_counts(deps.storage, &token.extension.traits())?;
+    config
+        .owner_history
+        .remove(deps.storage, token_id, env.block.height)?;
+    config.remove_token_index(deps.storage, token_id)?;
+    config.decrement_tokens(deps.storage)?;
+    Ok(())
+}
+
+pub fn update_collection_info<TCollectionExtension, TCollectionExtensionMsg, TCustomResponseMsg>(
+    deps: DepsMut,
+    info: Option<&MessageInfo>,
+    env: &Env,
+    msg: CollectionInfoMsg<TCollectionExtensionMsg>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
 where
     TCollectionExtension: Cw721State + ToAttributesState + FromAttributesState,
     TCollectionExtensionMsg: Cw721CustomMsg + StateFactory<TCollectionExtension>,
@@ -327,6 +1034,20 @@ where
     let current = query_collection_info_and_extension::<TCollectionExtension>(deps.as_ref())?;
     let collection_info = msg.create(deps.as_ref(), env, info, Some(&current))?;
     let extension_attributes = collection_info.extension.to_attributes_state()?;
+    match &collection_info.collection_metadata_uri {
+        Some(collection_metadata_uri) => config
+            .collection_metadata_uri
+            .save(deps.storage, collection_metadata_uri)?,
+        None => config.collection_metadata_uri.remove(deps.storage),
+    }
+    match &msg.transfer_fee {
+        Clearable::Set(fee) => {
+            assert_accepted_denom(deps.storage, &fee.denom)?;
+            config.transfer_fee.save(deps.storage, fee)?;
+        }
+        Clearable::Clear => config.transfer_fee.remove(deps.storage),
+        Clearable::NoChange => {}
+    }
     config
         .collection_info
         .save(deps.storage, &collection_info.into())?;
@@ -344,7 +1065,106 @@ where
     }
 }
 
-#[allow(clippy::too_many_arguments)]
+/// Validation of mints, shared with `ValidateMint` query: sender may mint (paying the mint price
+/// on public minting), token id is neither taken nor burned, and token is valid.
+/// Returns the token to be stored and the mint price payment, if any, or all failing checks.
+pub fn check_mint<TNftExtension, TNftExtensionMsg>(
+    deps: Deps,
+    env: &Env,
+    info: &MessageInfo,
+    token_id: &str,
+    owner: String,
+    token_uri: Option<String>,
+    extension: TNftExtensionMsg,
+) -> Result<(NftInfo<TNftExtension>, Option<BankMsg>), Vec<Cw721ContractError>>
+where
+    TNftExtension: Cw721State,
+    TNftExtensionMsg: Cw721CustomMsg + StateFactory<TNftExtension>,
+{
+    let config = Cw721Config::<TNftExtension>::default();
+    let std_err = |err: StdError| vec![Cw721ContractError::from(err)];
+    let mut errors = vec![];
+    // on public minting, anyone but the minter pays and mints on behalf of the minter
+    let mut price_msg = None;
+    let mut minter_info = info.clone();
+    if config
+        .public_minting
+        .may_load(deps.storage)
+        .map_err(std_err)?
+        .unwrap_or(false)
+        && assert_minter(deps.storage, &info.sender).is_err()
+    {
+        match check_mint_price(deps.storage, info) {
+            Ok(msg) => price_msg = msg,
+            Err(err) => errors.push(err),
+        }
+        minter_info.sender = MINTER
+            .get_ownership(deps.storage)
+            .map_err(std_err)?
+            .owner
+            .ok_or_else(|| vec![Cw721ContractError::NotMinter {}])?;
+    }
+
+    let is_minter = match assert_minter(deps.storage, &minter_info.sender) {
+        Ok(()) => true,
+        Err(err) => {
+            errors.push(err);
+            false
+        }
+    };
+    if config.burned_ids.has(deps.storage, token_id) {
+        errors.push(Cw721ContractError::TokenIdWasBurned {});
+    }
+    let token_uri = empty_as_none(token_uri);
+    if let Some(token_uri) = &token_uri {
+        if let Err(err) = Url::parse(token_uri) {
+            errors.push(err.into());
+        }
+    }
+    let owner_addr = match deps.api.addr_validate(&owner) {
+        Ok(owner_addr) => Some(owner_addr),
+        Err(err) => {
+            errors.push(err.into());
+            None
+        }
+    };
+    // extension's own permission check is redundant, so it is only validated for the minter
+    if is_minter {
+        if let Err(err) = extension.validate(deps, env, Some(&minter_info), None) {
+            errors.push(err);
+        }
+    }
+    if config.nft_info.has(deps.storage, token_id) {
+        errors.push(Cw721ContractError::Claimed {});
+    }
+    if owner_addr.as_ref() == Some(&env.contract.address)
+        && !config
+            .allow_mint_to_contract
+            .may_load(deps.storage)
+            .map_err(std_err)?
+            .unwrap_or(false)
+    {
+        errors.push(Cw721ContractError::CannotMintToContract {});
+    }
+    if let Err(err) = config.assert_token_uri_unused(deps.storage, token_uri.as_deref()) {
+        errors.push(err);
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let token_msg = NftInfoMsg {
+        owner,
+        approvals: vec![],
+        token_uri,
+        extension,
+    };
+    let token = token_msg
+        .create(deps, env, Some(&minter_info), None)
+        .map_err(|err| vec![err])?;
+    Ok((token, price_msg))
+}
+
 pub fn mint<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
     deps: DepsMut,
     env: &Env,
@@ -359,25 +1179,30 @@ where
     TNftExtensionMsg: Cw721CustomMsg + StateFactory<TNftExtension>,
     TCustomResponseMsg: CustomMsg,
 {
-    // create the token
-    let token_msg = NftInfoMsg {
-        owner: owner.clone(),
-        approvals: vec![],
-        token_uri: token_uri.clone(),
-        extension,
-    };
-    let token = token_msg.create(deps.as_ref(), env, info.into(), None)?;
     let config = Cw721Config::<TNftExtension>::default();
+    let (mut token, price_msg) = check_mint(
+        deps.as_ref(),
+        env,
+        info,
+        &token_id,
+        owner.clone(),
+        token_uri.clone(),
+        extension,
+    )
+    .map_err(|mut errors| errors.remove(0))?;
+    token.minter = Some(info.sender.clone());
+    config.nft_info.save(deps.storage, &token_id, &token)?;
+    config.index_token_uri(deps.storage, &token_id, token.token_uri.as_deref())?;
     config
-        .nft_info
-        .update(deps.storage, &token_id, |old| match old {
-            Some(_) => Err(Cw721ContractError::Claimed {}),
-            None => Ok(token),
-        })?;
+        .owner_history
+        .save(deps.storage, &token_id, &token.owner, env.block.height)?;
+    config.increment_trait_counts(deps.storage, &token.extension.traits())?;
 
-    config.increment_tokens(deps.storage)?;
+    let num_tokens = config.increment_tokens(deps.storage)?;
+    config.push_token_index(deps.storage, &token_id)?;
 
     let mut res = Response::new()
+        .add_messages(price_msg)
         .add_attribute("action", "mint")
         .add_attribute("minter", info.sender.to_string())
         .add_attribute("owner", owner)
@@ -385,9 +1210,240 @@ where
     if let Some(token_uri) = token_uri {
         res = res.add_attribute("token_uri", value_or_empty(&token_uri));
     }
+    Ok(res.add_attribute("num_tokens", num_tokens.to_string()))
+}
+
+/// Mints a token that is timelocked until `transferable_after`, see `assert_not_timelocked`.
+#[allow(clippy::too_many_arguments)]
+pub fn mint_timelocked<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+    mut deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    token_id: String,
+    owner: String,
+    token_uri: Option<String>,
+    extension: TNftExtensionMsg,
+    transferable_after: Timestamp,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TNftExtensionMsg: Cw721CustomMsg + StateFactory<TNftExtension>,
+    TCustomResponseMsg: CustomMsg,
+{
+    assert_minter(deps.storage, &info.sender)?;
+    let res = mint::<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+        deps.branch(),
+        env,
+        info,
+        token_id.clone(),
+        owner,
+        token_uri,
+        extension,
+    )?;
+    let config = Cw721Config::<TNftExtension>::default();
+    let mut token = config.nft_info.load(deps.storage, &token_id)?;
+    token.transferable_after = Some(transferable_after);
+    config.nft_info.save(deps.storage, &token_id, &token)?;
+    Ok(res.add_attribute("transferable_after", transferable_after.to_string()))
+}
+
+/// Mints a token carrying ics721 `tokenData`, which only the minter may set.
+#[allow(clippy::too_many_arguments)]
+pub fn mint_with_ibc_data<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+    mut deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    token_id: String,
+    owner: String,
+    token_uri: Option<String>,
+    extension: TNftExtensionMsg,
+    ibc_data: Option<Binary>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TNftExtensionMsg: Cw721CustomMsg + StateFactory<TNftExtension>,
+    TCustomResponseMsg: CustomMsg,
+{
+    let ibc_data = match ibc_data {
+        Some(ibc_data) => ibc_data,
+        None => {
+            return mint::<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+                deps, env, info, token_id, owner, token_uri, extension,
+            )
+        }
+    };
+    assert_minter(deps.storage, &info.sender)?;
+    let res = mint::<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+        deps.branch(),
+        env,
+        info,
+        token_id.clone(),
+        owner,
+        token_uri,
+        extension,
+    )?;
+    let config = Cw721Config::<TNftExtension>::default();
+    let mut token = config.nft_info.load(deps.storage, &token_id)?;
+    token.ibc_data = Some(ibc_data);
+    config.nft_info.save(deps.storage, &token_id, &token)?;
     Ok(res)
 }
 
+/// Mints a token that becomes invalid at `expires`, see `burn_expired`.
+#[allow(clippy::too_many_arguments)]
+pub fn mint_with_expiration<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+    mut deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    token_id: String,
+    owner: String,
+    token_uri: Option<String>,
+    extension: TNftExtensionMsg,
+    expires: Expiration,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TNftExtensionMsg: Cw721CustomMsg + StateFactory<TNftExtension>,
+    TCustomResponseMsg: CustomMsg,
+{
+    assert_minter(deps.storage, &info.sender)?;
+    if expires.is_expired(&env.block) {
+        return Err(Cw721ContractError::Expired {});
+    }
+    let res = mint::<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+        deps.branch(),
+        env,
+        info,
+        token_id.clone(),
+        owner,
+        token_uri,
+        extension,
+    )?;
+    let config = Cw721Config::<TNftExtension>::default();
+    let mut token = config.nft_info.load(deps.storage, &token_id)?;
+    token.expires = Some(expires);
+    config.nft_info.save(deps.storage, &token_id, &token)?;
+    Ok(res.add_attribute("expires", expires.to_string()))
+}
+
+/// Mints a token with the next sequential id not taken yet.
+pub fn mint_auto<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    owner: String,
+    token_uri: Option<String>,
+    extension: TNftExtensionMsg,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TNftExtensionMsg: Cw721CustomMsg + StateFactory<TNftExtension>,
+    TCustomResponseMsg: CustomMsg,
+{
+    let config = Cw721Config::<TNftExtension>::default();
+    let mut id = config
+        .auto_token_id
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        + 1;
+    // skip ids already minted manually or burned
+    while config.nft_info.has(deps.storage, &id.to_string())
+        || config.burned_ids.has(deps.storage, &id.to_string())
+    {
+        id += 1;
+    }
+    config.auto_token_id.save(deps.storage, &id)?;
+    mint::<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+        deps,
+        env,
+        info,
+        id.to_string(),
+        owner,
+        token_uri,
+        extension,
+    )
+}
+
+pub fn redeem_voucher<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+    mut deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    voucher: MintVoucher<TNftExtensionMsg>,
+    signature: Binary,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TNftExtensionMsg: Cw721CustomMsg + StateFactory<TNftExtension>,
+    TCustomResponseMsg: CustomMsg,
+{
+    let config = Cw721Config::<TNftExtension>::default();
+    let pubkey = config
+        .voucher_signer
+        .may_load(deps.storage)?
+        .ok_or(Cw721ContractError::NoVoucherSigner {})?;
+    // voucher is bound to its owner, so it can't be redeemed by anyone else
+    if voucher.owner != info.sender {
+        return Err(Cw721ContractError::Unauthorized {});
+    }
+    let hash = voucher_hash(&env.contract.address, &voucher)?;
+    if !matches!(
+        deps.api.secp256k1_verify(&hash, &signature, &pubkey),
+        Ok(true)
+    ) {
+        return Err(Cw721ContractError::InvalidVoucherSignature {});
+    }
+    if config.redeemed_vouchers.has(deps.storage, &hash) {
+        return Err(Cw721ContractError::VoucherRedeemed {});
+    }
+    config
+        .redeemed_vouchers
+        .save(deps.storage, &hash, &Empty {})?;
+
+    // voucher is signed on behalf of the minter, so it is minted as such
+    let minter = MINTER
+        .get_ownership(deps.storage)?
+        .owner
+        .ok_or(Cw721ContractError::NotMinter {})?;
+    let minter_info = MessageInfo {
+        sender: minter,
+        funds: vec![],
+    };
+    let res = mint::<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+        deps.branch(),
+        env,
+        &minter_info,
+        voucher.token_id,
+        voucher.owner,
+        voucher.token_uri,
+        voucher.extension,
+    )?;
+    Ok(res.add_attribute("redeemer", info.sender.to_string()))
+}
+
+pub fn set_voucher_signer<TCustomResponseMsg>(
+    storage: &mut dyn Storage,
+    sender: &Addr,
+    pubkey: Option<Binary>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    assert_minter(storage, sender)?;
+    let config = Cw721Config::<Option<Empty>>::default();
+    match pubkey {
+        Some(pubkey) => {
+            if pubkey.len() != 33 && pubkey.len() != 65 {
+                return Err(Cw721ContractError::InvalidPublicKey {});
+            }
+            config.voucher_signer.save(storage, &pubkey)?;
+            Ok(Response::new()
+                .add_attribute("action", "set_voucher_signer")
+                .add_attribute("voucher_signer", pubkey.to_base64()))
+        }
+        None => {
+            config.voucher_signer.remove(storage);
+            Ok(Response::new().add_attribute("action", "remove_voucher_signer"))
+        }
+    }
+}
+
 pub fn update_minter_ownership<TCustomResponseMsg>(
     api: &dyn Api,
     storage: &mut dyn Storage,
@@ -439,11 +1495,49 @@ where
     };
     let updated = nft_info_msg.create(deps.as_ref(), env, info, Some(&current_nft_info))?;
     contract.nft_info.save(deps.storage, &token_id, &updated)?;
+    contract.unindex_token_uri(deps.storage, current_nft_info.token_uri.as_deref());
+    contract.index_token_uri(deps.storage, &token_id, updated.token_uri.as_deref())?;
+    contract.decrement_trait_counts(deps.storage, &current_nft_info.extension.traits())?;
+    contract.increment_trait_counts(deps.storage, &updated.extension.traits())?;
     Ok(Response::new()
         .add_attribute("action", "update_nft_info")
         .add_attribute("token_id", token_id))
 }
 
+pub fn batch_update_nft_info<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+    mut deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    updates: Vec<NftInfoUpdate<TNftExtensionMsg>>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+    TNftExtensionMsg: Cw721CustomMsg + StateFactory<TNftExtension>,
+    TCustomResponseMsg: CustomMsg,
+{
+    assert_creator(deps.storage, &info.sender)?;
+    let num_tokens = updates.len();
+    // any failing update reverts the whole batch
+    for NftInfoUpdate {
+        token_id,
+        token_uri,
+        extension,
+    } in updates
+    {
+        update_nft_info::<TNftExtension, TNftExtensionMsg, Empty>(
+            deps.branch(),
+            env,
+            Some(info),
+            token_id,
+            token_uri,
+            extension,
+        )?;
+    }
+    Ok(Response::new()
+        .add_attribute("action", "batch_update_nft_info")
+        .add_attribute("num_tokens", num_tokens.to_string()))
+}
+
 pub fn set_withdraw_address<TCustomResponseMsg>(
     deps: DepsMut,
     sender: &Addr,
@@ -476,6 +1570,408 @@ pub fn remove_withdraw_address<TCustomResponseMsg>(
     }
 }
 
+pub fn set_metadata_schema_hash<TCustomResponseMsg>(
+    storage: &mut dyn Storage,
+    sender: &Addr,
+    metadata_schema_hash: Option<String>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    CREATOR.assert_owner(storage, sender)?;
+    let config = Cw721Config::<Option<Empty>>::default();
+    match metadata_schema_hash {
+        Some(hash) => {
+            if hash.len() != METADATA_SCHEMA_HASH_LENGTH
+                || !hash.chars().all(|c| c.is_ascii_hexdigit())
+            {
+                return Err(Cw721ContractError::InvalidMetadataSchemaHash { hash });
+            }
+            let hash = hash.to_ascii_lowercase();
+            config.metadata_schema_hash.save(storage, &hash)?;
+            Ok(Response::new()
+                .add_attribute("action", "set_metadata_schema_hash")
+                .add_attribute("metadata_schema_hash", hash))
+        }
+        None => {
+            config.metadata_schema_hash.remove(storage);
+            Ok(Response::new().add_attribute("action", "remove_metadata_schema_hash"))
+        }
+    }
+}
+
+pub fn set_burn_fee<TCustomResponseMsg>(
+    storage: &mut dyn Storage,
+    sender: &Addr,
+    burn_fee: Option<Coin>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    CREATOR.assert_owner(storage, sender)?;
+    let config = Cw721Config::<Option<Empty>>::default();
+    match burn_fee {
+        Some(fee) => {
+            assert_accepted_denom(storage, &fee.denom)?;
+            config.burn_fee.save(storage, &fee)?;
+            Ok(Response::new()
+                .add_attribute("action", "set_burn_fee")
+                .add_attribute("burn_fee", fee.to_string()))
+        }
+        None => {
+            config.burn_fee.remove(storage);
+            Ok(Response::new().add_attribute("action", "remove_burn_fee"))
+        }
+    }
+}
+
+pub fn set_max_royalty_bps<TCustomResponseMsg>(
+    storage: &mut dyn Storage,
+    sender: &Addr,
+    max_royalty_bps: u16,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    CREATOR.assert_owner(storage, sender)?;
+    let config = Cw721Config::<Option<Empty>>::default();
+    // cap protects buyers, so it can't be raised
+    let current = config.max_royalty_bps.may_load(storage)?.unwrap_or(10_000);
+    if max_royalty_bps > current {
+        return Err(Cw721ContractError::InvalidRoyalties(format!(
+            "Royalty cap cannot be raised above {current} bps"
+        )));
+    }
+    config.max_royalty_bps.save(storage, &max_royalty_bps)?;
+    // current royalty must comply with new cap
+    if let Some(royalty_info) = config.royalty_info(storage)? {
+        config.assert_royalty_cap(storage, royalty_info.share)?;
+    }
+    Ok(Response::new()
+        .add_attribute("action", "set_max_royalty_bps")
+        .add_attribute("max_royalty_bps", max_royalty_bps.to_string()))
+}
+
+/// Changes the collection royalty's `payment_address`, keeping its share.
+pub fn update_royalty_recipient<TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    address: String,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    CREATOR.assert_owner(deps.storage, sender)?;
+    let config = Cw721Config::<Option<Empty>>::default();
+    let mut royalty_info = config
+        .royalty_info(deps.storage)?
+        .ok_or(Cw721ContractError::NoRoyaltyInfo {})?;
+    royalty_info.payment_address = deps.api.addr_validate(&address)?;
+    for attr in royalty_info.to_attributes_state()? {
+        config
+            .collection_extension
+            .save(deps.storage, attr.key.clone(), &attr)?;
+    }
+    config
+        .collection_info
+        .update(deps.storage, |mut collection_info| -> StdResult<_> {
+            collection_info.updated_at = env.block.time;
+            Ok(collection_info)
+        })?;
+    Ok(Response::new()
+        .add_attribute("action", "update_royalty_recipient")
+        .add_attribute("payment_address", address))
+}
+
+pub fn set_token_royalty<TCustomResponseMsg>(
+    deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    token_id: String,
+    royalty: Option<RoyaltyInfoResponse>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    CREATOR.assert_owner(deps.storage, sender)?;
+    let config = Cw721Config::<Option<Empty>>::default();
+    // make sure token exists
+    config.nft_info.load(deps.storage, &token_id)?;
+    match royalty {
+        Some(royalty) => {
+            // share increase is limited relative to the royalty currently applying to the token
+            let current = config.token_royalty_info(deps.storage, &token_id)?;
+            let royalty_info = royalty.create(deps.as_ref(), env, None, current.as_ref())?;
+            config
+                .token_royalties
+                .save(deps.storage, &token_id, &royalty_info)?;
+            Ok(Response::new()
+                .add_attribute("action", "set_token_royalty")
+                .add_attribute("token_id", token_id)
+                .add_attribute("payment_address", royalty_info.payment_address.to_string())
+                .add_attribute("share", royalty_info.share.to_string()))
+        }
+        None => {
+            config.token_royalties.remove(deps.storage, &token_id);
+            Ok(Response::new()
+                .add_attribute("action", "remove_token_royalty")
+                .add_attribute("token_id", token_id))
+        }
+    }
+}
+
+pub fn set_default_approval_expiry<TCustomResponseMsg>(
+    storage: &mut dyn Storage,
+    sender: &Addr,
+    default_approval_expiry: Option<Duration>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    CREATOR.assert_owner(storage, sender)?;
+    let config = Cw721Config::<Option<Empty>>::default();
+    match default_approval_expiry {
+        Some(default_approval_expiry) => {
+            config
+                .default_approval_expiry
+                .save(storage, &default_approval_expiry)?;
+            Ok(Response::new()
+                .add_attribute("action", "set_default_approval_expiry")
+                .add_attribute(
+                    "default_approval_expiry",
+                    default_approval_expiry.to_string(),
+                ))
+        }
+        None => {
+            config.default_approval_expiry.remove(storage);
+            Ok(Response::new().add_attribute("action", "remove_default_approval_expiry"))
+        }
+    }
+}
+
+pub fn set_royalty_split<TCustomResponseMsg>(
+    deps: DepsMut,
+    sender: &Addr,
+    splits: Option<Vec<RoyaltySplitMsg>>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    CREATOR.assert_owner(deps.storage, sender)?;
+    let config = Cw721Config::<Option<Empty>>::default();
+    let splits = match splits {
+        Some(splits) => splits,
+        None => {
+            config.royalty_splits.remove(deps.storage);
+            return Ok(Response::new().add_attribute("action", "remove_royalty_split"));
+        }
+    };
+    if splits.is_empty() {
+        return Err(Cw721ContractError::InvalidRoyalties(
+            "Split needs at least one recipient".to_string(),
+        ));
+    }
+    let mut total_share = Decimal::zero();
+    let mut royalty_splits = vec![];
+    for RoyaltySplitMsg { address, share } in splits {
+        total_share += share;
+        royalty_splits.push(RoyaltySplit {
+            address: deps.api.addr_validate(&address)?,
+            share,
+        });
+    }
+    if total_share > Decimal::one() {
+        return Err(Cw721ContractError::InvalidRoyalties(
+            "Split shares cannot sum up to more than 100%".to_string(),
+        ));
+    }
+    config.royalty_splits.save(deps.storage, &royalty_splits)?;
+    let mut response = Response::new().add_attribute("action", "set_royalty_split");
+    for RoyaltySplit { address, share } in royalty_splits {
+        response = response
+            .add_attribute("address", address.to_string())
+            .add_attribute("share", share.to_string());
+    }
+    Ok(response)
+}
+
+pub fn set_transfer_cooldown<TCustomResponseMsg>(
+    storage: &mut dyn Storage,
+    sender: &Addr,
+    transfer_cooldown: Option<u64>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    CREATOR.assert_owner(storage, sender)?;
+    let config = Cw721Config::<Option<Empty>>::default();
+    match transfer_cooldown {
+        Some(transfer_cooldown) => {
+            config.transfer_cooldown.save(storage, &transfer_cooldown)?;
+            Ok(Response::new()
+                .add_attribute("action", "set_transfer_cooldown")
+                .add_attribute("transfer_cooldown", transfer_cooldown.to_string()))
+        }
+        None => {
+            config.transfer_cooldown.remove(storage);
+            Ok(Response::new().add_attribute("action", "remove_transfer_cooldown"))
+        }
+    }
+}
+
+/// Checks the transfer fee (if set) is paid for the given number of transfers.
+/// Returns the msg forwarding the fee to the withdraw address (if set), otherwise fee stays in contract.
+pub fn check_transfer_fee(
+    storage: &dyn Storage,
+    info: &MessageInfo,
+    num_transfers: u128,
+) -> Result<Option<BankMsg>, Cw721ContractError> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    let fee = match config.transfer_fee.may_load(storage)? {
+        Some(fee) => fee,
+        None => return Ok(None),
+    };
+    // fee may have been set before its denom got delisted
+    assert_accepted_denom(storage, &fee.denom)?;
+    let fee = Coin {
+        amount: fee
+            .amount
+            .checked_mul(num_transfers.into())
+            .map_err(StdError::from)?,
+        denom: fee.denom,
+    };
+    match assert_exact_payment(info, &fee) {
+        Err(Cw721ContractError::MissingPayment { .. }) => {
+            return Err(Cw721ContractError::TransferFeeRequired {})
+        }
+        res => res?,
+    }
+    Ok(config
+        .withdraw_address
+        .may_load(storage)?
+        .map(|address| BankMsg::Send {
+            to_address: address,
+            amount: vec![fee],
+        }))
+}
+
+/// Checks `mint_price` is paid, returning the message forwarding it to withdraw address (if set).
+pub fn check_mint_price(
+    storage: &dyn Storage,
+    info: &MessageInfo,
+) -> Result<Option<BankMsg>, Cw721ContractError> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    let price = match config.mint_price.may_load(storage)? {
+        Some(price) => price,
+        None => return Ok(None),
+    };
+    assert_accepted_denom(storage, &price.denom)?;
+    assert_exact_payment(info, &price)?;
+    Ok(config
+        .withdraw_address
+        .may_load(storage)?
+        .map(|address| BankMsg::Send {
+            to_address: address,
+            amount: vec![price],
+        }))
+}
+
+/// Fails unless funds are exactly the expected coin, so neither overpayment nor other denoms
+/// end up unnoticed in the contract.
+pub fn assert_exact_payment(info: &MessageInfo, expected: &Coin) -> Result<(), Cw721ContractError> {
+    match must_pay(info, &expected.denom) {
+        Ok(amount) if amount == expected.amount => Ok(()),
+        Err(PaymentError::NoFunds {}) => Err(Cw721ContractError::MissingPayment {
+            expected: expected.clone(),
+        }),
+        _ => Err(Cw721ContractError::WrongPayment {
+            expected: expected.clone(),
+        }),
+    }
+}
+
+pub fn set_accepted_denoms<TCustomResponseMsg>(
+    storage: &mut dyn Storage,
+    sender: &Addr,
+    accepted_denoms: Vec<String>,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    CREATOR.assert_owner(storage, sender)?;
+    let config = Cw721Config::<Option<Empty>>::default();
+    if accepted_denoms.is_empty() {
+        config.accepted_denoms.remove(storage);
+        return Ok(Response::new().add_attribute("action", "remove_accepted_denoms"));
+    }
+    config.accepted_denoms.save(storage, &accepted_denoms)?;
+    Ok(Response::new()
+        .add_attribute("action", "set_accepted_denoms")
+        .add_attribute("accepted_denoms", accepted_denoms.join(",")))
+}
+
+/// returns error if denom is not accepted for fee payments
+pub fn assert_accepted_denom(storage: &dyn Storage, denom: &str) -> Result<(), Cw721ContractError> {
+    let accepted_denoms = Cw721Config::<Option<Empty>>::default()
+        .accepted_denoms
+        .may_load(storage)?;
+    match accepted_denoms {
+        Some(accepted_denoms) if !accepted_denoms.iter().any(|d| d == denom) => {
+            Err(Cw721ContractError::UnsupportedDenom {
+                denom: denom.to_string(),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+pub fn pay_royalty<TCustomResponseMsg>(
+    deps: Deps,
+    info: &MessageInfo,
+    token_id: String,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    // make sure token exists
+    config.nft_info.load(deps.storage, &token_id)?;
+    config
+        .token_royalty_info(deps.storage, &token_id)?
+        .ok_or(Cw721ContractError::NoRoyaltyInfo {})?;
+    if info.funds.is_empty() {
+        return Err(Cw721ContractError::NoFunds {});
+    }
+
+    // royalties per recipient, in order of `royalty_payments`
+    let mut royalties: Vec<(Addr, Vec<Coin>)> = vec![];
+    let mut remainders = vec![];
+    for coin in &info.funds {
+        assert_accepted_denom(deps.storage, &coin.denom)?;
+        let mut royalty_total = Uint128::zero();
+        for (address, royalty) in config.royalty_payments(deps.storage, &token_id, coin.amount)? {
+            royalty_total += royalty;
+            if royalty.is_zero() {
+                continue;
+            }
+            let royalty = Coin::new(royalty.u128(), &coin.denom);
+            match royalties
+                .iter_mut()
+                .find(|(recipient, _)| *recipient == address)
+            {
+                Some((_, coins)) => coins.push(royalty),
+                None => royalties.push((address, vec![royalty])),
+            }
+        }
+        let remainder = coin.amount - royalty_total;
+        if !remainder.is_zero() {
+            remainders.push(Coin::new(remainder.u128(), &coin.denom));
+        }
+    }
+
+    let mut response = Response::new()
+        .add_attribute("action", "pay_royalty")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("token_id", token_id);
+    for (recipient, coins) in royalties {
+        response = response
+            .add_attribute("royalty_recipient", recipient.to_string())
+            .add_attribute("royalty_amount", coins_to_string(&coins))
+            .add_message(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: coins,
+            });
+    }
+    if !remainders.is_empty() {
+        response = response
+            .add_attribute("remainder", coins_to_string(&remainders))
+            .add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: remainders,
+            });
+    }
+    Ok(response)
+}
+
+fn coins_to_string(coins: &[Coin]) -> String {
+    coins
+        .iter()
+        .map(|coin| coin.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 pub fn withdraw_funds<TCustomResponseMsg>(
     storage: &mut dyn Storage,
     amount: &Coin,
@@ -499,6 +1995,33 @@ pub fn withdraw_funds<TCustomResponseMsg>(
     }
 }
 
+/// Sweeps all native balances to the withdraw address. No-op if the contract has no balance.
+pub fn withdraw_all<TCustomResponseMsg>(
+    deps: Deps,
+    env: &Env,
+) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+    let address = Cw721Config::<Option<Empty>>::default()
+        .withdraw_address
+        .may_load(deps.storage)?
+        .ok_or(Cw721ContractError::NoWithdrawAddress {})?;
+    let balances = deps.querier.query_all_balances(&env.contract.address)?;
+    let amount = balances
+        .iter()
+        .map(Coin::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    let mut response = Response::new()
+        .add_attribute("action", "withdraw_all")
+        .add_attribute("amount", value_or_empty(&amount));
+    if !balances.is_empty() {
+        response = response.add_message(BankMsg::Send {
+            to_address: address,
+            amount: balances,
+        });
+    }
+    Ok(response)
+}
+
 /// returns true if the sender can execute approve or reject on the contract
 pub fn check_can_approve<TNftExtension>(
     deps: Deps,
@@ -572,6 +2095,135 @@ pub fn check_can_send<TNftExtension>(
     }
 }
 
+/// Authorization of transfers and sends, shared with `CanTransfer` query:
+/// sender may send token, and token is neither expired, locked by a swap offer, timelocked, nor
+/// cooling down.
+pub fn check_can_transfer<TNftExtension>(
+    deps: Deps,
+    env: &Env,
+    sender: &str,
+    token_id: &str,
+    token: &NftInfo<TNftExtension>,
+) -> Result<(), Cw721ContractError> {
+    if token.is_expired(&env.block) {
+        return Err(Cw721ContractError::TokenExpired {});
+    }
+    check_can_send(deps, env, sender, token)?;
+    assert_not_swap_locked(deps.storage, env, token_id)?;
+    assert_not_timelocked(env, token)?;
+    assert_transfer_cooldown(deps, env, sender, token_id)
+}
+
+/// returns error if sender may neither send token nor burn it according to the burn policy
+pub fn check_can_burn<TNftExtension>(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
+    token: &NftInfo<TNftExtension>,
+) -> Result<(), Cw721ContractError> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    let burn_policy = config
+        .burn_policy
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    match burn_policy {
+        // keep error of owner check for backward compatibility
+        BurnPolicy::OwnerOnly => check_can_send(deps, env, sender.as_str(), token),
+        BurnPolicy::OwnerOrMinter => {
+            if check_can_send(deps, env, sender.as_str(), token).is_ok()
+                || MINTER.assert_owner(deps.storage, sender).is_ok()
+            {
+                Ok(())
+            } else {
+                Err(Cw721ContractError::Unauthorized {})
+            }
+        }
+        BurnPolicy::OwnerOrCreator => {
+            if check_can_send(deps, env, sender.as_str(), token).is_ok()
+                || CREATOR.assert_owner(deps.storage, sender).is_ok()
+            {
+                Ok(())
+            } else {
+                Err(Cw721ContractError::Unauthorized {})
+            }
+        }
+    }
+}
+
+/// Fails if token is timelocked, see `mint_timelocked`.
+pub fn assert_not_timelocked<TNftExtension>(
+    env: &Env,
+    token: &NftInfo<TNftExtension>,
+) -> Result<(), Cw721ContractError> {
+    if token.is_timelocked(&env.block) {
+        return Err(Cw721ContractError::TokenTimelocked {});
+    }
+    Ok(())
+}
+
+/// Fails if token was transferred less than `transfer_cooldown` seconds ago.
+/// Minter and creator are exempt, so initial distribution isn't throttled.
+pub fn assert_transfer_cooldown(
+    deps: Deps,
+    env: &Env,
+    sender: &str,
+    token_id: &str,
+) -> Result<(), Cw721ContractError> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    let transfer_cooldown = match config.transfer_cooldown.may_load(deps.storage)? {
+        Some(transfer_cooldown) => transfer_cooldown,
+        None => return Ok(()),
+    };
+    let sender = deps.api.addr_validate(sender)?;
+    if MINTER.assert_owner(deps.storage, &sender).is_ok()
+        || CREATOR.assert_owner(deps.storage, &sender).is_ok()
+    {
+        return Ok(());
+    }
+    if let Some(last_transfer_at) = config.last_transfer_at.may_load(deps.storage, token_id)? {
+        let ready_at = last_transfer_at.plus_seconds(transfer_cooldown);
+        if env.block.time < ready_at {
+            return Err(Cw721ContractError::TransferCooldown { ready_at });
+        }
+    }
+    Ok(())
+}
+
+/// Fails if `spender` is the granting `owner` itself or this contract, as such grants are meaningless.
+pub fn assert_valid_spender(
+    env: &Env,
+    owner: &Addr,
+    spender: &Addr,
+) -> Result<(), Cw721ContractError> {
+    if spender == owner {
+        return Err(Cw721ContractError::CannotApproveSelf {});
+    }
+    if spender == env.contract.address {
+        return Err(Cw721ContractError::CannotApproveContract {});
+    }
+    Ok(())
+}
+
+/// returns error if token is locked by a pending swap offer
+pub fn assert_not_swap_locked(
+    storage: &dyn Storage,
+    env: &Env,
+    token_id: &str,
+) -> Result<(), Cw721ContractError> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    if let Some(offer_id) = config.swap_locks.may_load(storage, token_id)? {
+        // lock is released once offer expires
+        if let Some(offer) = config.swap_offers.may_load(storage, offer_id)? {
+            if !offer.is_expired(&env.block) {
+                return Err(Cw721ContractError::TokenLocked {
+                    token_id: token_id.to_string(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
 pub fn assert_minter(storage: &dyn Storage, sender: &Addr) -> Result<(), Cw721ContractError> {
     if MINTER.assert_owner(storage, sender).is_err() {
         return Err(Cw721ContractError::NotMinter {});
@@ -587,17 +2239,23 @@ pub fn assert_creator(storage: &dyn Storage, sender: &Addr) -> Result<(), Cw721C
 }
 
 // ------- migrate -------
-pub fn migrate(
+pub fn migrate<TNftExtension>(
     deps: DepsMut,
     env: Env,
     msg: Cw721MigrateMsg,
     contract_name: &str,
     contract_version: &str,
-) -> Result<Response, Cw721ContractError> {
+) -> Result<Response, Cw721ContractError>
+where
+    TNftExtension: Cw721State,
+{
     let response = Response::<Empty>::default();
     // first migrate legacy data ...
     let response = migrate_legacy_minter_and_creator(deps.storage, deps.api, &env, &msg, response)?;
     let response = migrate_legacy_collection_info(deps.storage, &env, &msg, response)?;
+    let response = migrate_num_tokens::<TNftExtension>(deps.storage, &msg, response)?;
+    let response = migrate_supply_stats(deps.storage, response)?;
+    let response = migrate_token_index::<TNftExtension>(deps.storage, &msg, response)?;
     // ... then migrate
     let response = migrate_version(deps.storage, contract_name, contract_version, response)?;
     // ... and update creator and minter AFTER legacy migration
@@ -606,6 +2264,121 @@ pub fn migrate(
     Ok(response)
 }
 
+/// Overwrites `num_tokens` with the number of stored tokens, only if explicitly requested.
+/// `total_minted` is resynced accordingly, and the token index is rebuilt via `BackfillTokenIndex`.
+pub fn migrate_num_tokens<TNftExtension>(
+    storage: &mut dyn Storage,
+    msg: &Cw721MigrateMsg,
+    response: Response,
+) -> StdResult<Response>
+where
+    TNftExtension: Cw721State,
+{
+    if !matches!(msg, Cw721MigrateMsg::RecomputeNumTokens {}) {
+        return Ok(response);
+    }
+    let config = Cw721Config::<TNftExtension>::default();
+    let num_tokens = config
+        .nft_info
+        .keys_raw(storage, None, None, Order::Ascending)
+        .count() as u64;
+    config.num_tokens.save(storage, &num_tokens)?;
+    let total_minted = num_tokens + config.total_burned(storage)?;
+    config.total_minted.save(storage, &total_minted)?;
+    // index positions are derived from `num_tokens`, so the index is rebuilt from scratch
+    config.token_index_backfill.save(
+        storage,
+        &TokenIndexBackfill {
+            start_after: None,
+            num_indexed: 0,
+        },
+    )?;
+    Ok(response
+        .add_attribute("recomputed_num_tokens", num_tokens.to_string())
+        .add_attribute("recomputed_total_minted", total_minted.to_string()))
+}
+
+/// Persists `total_minted` and `total_burned` in case they are not set yet.
+/// Tokens burned before supply stats were introduced are unknown, so they count as never minted.
+pub fn migrate_supply_stats(storage: &mut dyn Storage, response: Response) -> StdResult<Response> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    if config.total_minted.may_load(storage)?.is_some() {
+        return Ok(response);
+    }
+    let total_minted = config.total_minted(storage)?;
+    let total_burned = config.total_burned(storage)?;
+    config.total_minted.save(storage, &total_minted)?;
+    config.total_burned.save(storage, &total_burned)?;
+    Ok(response.add_attribute("migrated total minted", total_minted.to_string()))
+}
+
+/// Starts indexing existing tokens in case the token index is empty (i.e. contract was migrated
+/// from a version without it), and indexes the next page of tokens on `BackfillTokenIndex`.
+pub fn migrate_token_index<TNftExtension>(
+    storage: &mut dyn Storage,
+    msg: &Cw721MigrateMsg,
+    response: Response,
+) -> StdResult<Response>
+where
+    TNftExtension: Cw721State,
+{
+    let config = Cw721Config::<TNftExtension>::default();
+    let mut backfill = match config.token_index_backfill.may_load(storage)? {
+        Some(backfill) => backfill,
+        None if config.token_count(storage)? > 0
+            && config.token_by_index.may_load(storage, 0)?.is_none() =>
+        {
+            TokenIndexBackfill {
+                start_after: None,
+                num_indexed: 0,
+            }
+        }
+        None => return Ok(response),
+    };
+    let limit = match msg {
+        Cw721MigrateMsg::BackfillTokenIndex { limit } => *limit as usize,
+        _ => {
+            config.token_index_backfill.save(storage, &backfill)?;
+            return Ok(response.add_attribute("token_index_backfill", "pending"));
+        }
+    };
+    let token_ids = config
+        .nft_info
+        .keys(
+            storage,
+            backfill.start_after.as_deref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    for token_id in &token_ids {
+        // tokens minted after migration are indexed already
+        if config
+            .token_index_position(storage, token_id, backfill.num_indexed)?
+            .is_none()
+        {
+            config
+                .token_by_index
+                .save(storage, backfill.num_indexed, token_id)?;
+            config
+                .token_indexes
+                .save(storage, token_id, &backfill.num_indexed)?;
+            backfill.num_indexed += 1;
+        }
+    }
+    let response = response.add_attribute("num_indexed_tokens", backfill.num_indexed.to_string());
+    if token_ids.len() < limit {
+        config.token_index_backfill.remove(storage);
+        return Ok(response.add_attribute("token_index_backfill", "complete"));
+    }
+    if let Some(last) = token_ids.last() {
+        backfill.start_after = Some(last.clone());
+    }
+    config.token_index_backfill.save(storage, &backfill)?;
+    Ok(response.add_attribute("token_index_backfill", "pending"))
+}
+
 pub fn migrate_version(
     storage: &mut dyn Storage,
     contradct_name: &str,
@@ -635,6 +2408,7 @@ pub fn migrate_creator(
                 return Ok(response.add_attribute("creator", creator));
             }
         }
+        Cw721MigrateMsg::RecomputeNumTokens {} | Cw721MigrateMsg::BackfillTokenIndex { .. } => {}
     }
     Ok(response)
 }
@@ -653,6 +2427,7 @@ pub fn migrate_minter(
                 return Ok(response.add_attribute("minter", minter));
             }
         }
+        Cw721MigrateMsg::RecomputeNumTokens {} | Cw721MigrateMsg::BackfillTokenIndex { .. } => {}
     }
     Ok(response)
 }
@@ -144,6 +144,19 @@ impl Default for Cw721EmptyExtensions<'static> {
 ///     minter: None,
 ///     creator: None,
 ///     withdraw_address: None,
+///     burn_policy: None,
+///     ipfs_gateway: None,
+///     mint_price: None,
+///     public_minting: false,
+///     max_royalty_bps: None,
+///     commit_hash: None,
+///     initial_mints: None,
+///     track_burned_ids: false,
+///     base_token_uri: None,
+///     allow_force_transfer: false,
+///     collection_metadata_uri: None,
+///     allow_mint_to_contract: false,
+///     unique_token_uris: false,
 /// };
 /// //...
 /// // mint:
@@ -168,6 +181,7 @@ impl Default for Cw721EmptyExtensions<'static> {
 ///     owner: "john".to_string(),
 ///     token_uri: token_uri.clone(),
 ///     extension: extension.clone(), // use `extension: None` for no metadata
+///     ibc_data: None,
 /// };
 /// //...
 /// ```
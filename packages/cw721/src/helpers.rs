@@ -1,12 +1,47 @@
 use std::marker::PhantomData;
 
+use crate::error::Cw721ContractError;
+use crate::msg::{
+    CollectionExtensionMsg, Cw721QueryMsg, MintVoucher, OwnerOfResponse, RevealAssignment,
+};
+use crate::state::MAX_COLLECTION_DESCRIPTION_LENGTH;
 use crate::traits::{Cw721Calls, Cw721CustomMsg, Cw721State};
 use crate::{
     DefaultOptionalCollectionExtension, DefaultOptionalCollectionExtensionMsg,
     DefaultOptionalNftExtension, DefaultOptionalNftExtensionMsg,
 };
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Empty};
+use cosmwasm_std::{to_json_vec, Addr, Empty, QuerierWrapper, StdResult};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use url::Url;
+
+/// Checks the description length and that `image` and `external_link` are URLs.
+/// Used on instantiation and on `UpdateCollectionInfo`.
+pub fn validate_collection_extension<TRoyaltyInfoResponse>(
+    msg: &CollectionExtensionMsg<TRoyaltyInfoResponse>,
+) -> Result<(), Cw721ContractError> {
+    // check description length, must not be empty and max 512 chars
+    if let Some(description) = &msg.description {
+        if description.is_empty() {
+            return Err(Cw721ContractError::CollectionDescriptionEmpty {});
+        }
+        if description.len() > MAX_COLLECTION_DESCRIPTION_LENGTH as usize {
+            return Err(Cw721ContractError::CollectionDescriptionTooLong {
+                max_length: MAX_COLLECTION_DESCRIPTION_LENGTH,
+            });
+        }
+    }
+
+    // check images are URLs
+    if let Some(image) = &msg.image {
+        Url::parse(image)?;
+    }
+    if let Some(external_link) = msg.external_link.as_set() {
+        Url::parse(external_link)?;
+    }
+    Ok(())
+}
 
 /// Returns "empty" if the string is empty, otherwise the string itself
 pub fn value_or_empty(value: &str) -> String {
@@ -17,6 +52,74 @@ pub fn value_or_empty(value: &str) -> String {
     }
 }
 
+/// Queries the owner of `token_id` in the cw721 contract `collection`.
+pub fn query_owner(
+    querier: &QuerierWrapper,
+    collection: impl Into<String>,
+    token_id: impl Into<String>,
+) -> StdResult<Addr> {
+    let res: OwnerOfResponse = querier.query_wasm_smart(
+        collection,
+        &Cw721QueryMsg::<Empty, Empty, Empty, Empty>::OwnerOf {
+            token_id: token_id.into(),
+            include_expired: None,
+        },
+    )?;
+    Ok(Addr::unchecked(res.owner))
+}
+
+/// Returns whether `addr` owns `token_id` in the cw721 contract `collection`.
+pub fn is_owner(
+    querier: &QuerierWrapper,
+    collection: impl Into<String>,
+    token_id: impl Into<String>,
+    addr: &Addr,
+) -> StdResult<bool> {
+    Ok(query_owner(querier, collection, token_id)? == *addr)
+}
+
+/// Hash a voucher signer signs: SHA-256 of the collection address followed by the JSON-encoded
+/// voucher. Including the address prevents replaying a voucher on another collection.
+pub fn voucher_hash<TNftExtensionMsg>(
+    collection: &Addr,
+    voucher: &MintVoucher<TNftExtensionMsg>,
+) -> StdResult<Vec<u8>>
+where
+    TNftExtensionMsg: Serialize,
+{
+    let mut hasher = Sha256::new();
+    hasher.update(collection.as_bytes());
+    hasher.update(to_json_vec(voucher)?);
+    Ok(hasher.finalize().to_vec())
+}
+
+/// Commitment of a metadata assignment: SHA-256 of the seed followed by the JSON-encoded
+/// assignments. The seed keeps the assignment from being guessed before the reveal.
+pub fn commit_hash(seed: &[u8], assignments: &[RevealAssignment]) -> StdResult<Vec<u8>> {
+    let mut hasher = Sha256::new();
+    hasher.update(seed);
+    hasher.update(to_json_vec(assignments)?);
+    Ok(hasher.finalize().to_vec())
+}
+
+/// Rewrites `ipfs://{cid}` to `{gateway}/{cid}`, any other URL is returned as is.
+pub fn resolve_ipfs_url(url: &str, gateway: &str) -> String {
+    match url.strip_prefix("ipfs://") {
+        Some(cid) => format!("{}/{}", gateway.trim_end_matches('/'), cid),
+        None => url.to_string(),
+    }
+}
+
+/// Token URI as returned by queries: the stored `token_uri`, or else `{base_token_uri}{token_id}`
+/// if a base is set. The computed URI is never stored.
+pub fn resolve_token_uri(
+    token_uri: Option<String>,
+    base_token_uri: Option<&str>,
+    token_id: &str,
+) -> Option<String> {
+    token_uri.or_else(|| base_token_uri.map(|base| format!("{base}{token_id}")))
+}
+
 #[deprecated(
     since = "0.19.0",
     note = "Please use `DefaultCw721Helper`, `EmptyCw721Helper`, or `Cw721Helper` instead"
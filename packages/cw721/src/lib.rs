@@ -12,8 +12,8 @@ pub mod traits;
 use cosmwasm_std::Empty;
 pub use cw_utils::Expiration;
 use msg::{
-    CollectionExtensionMsg, CollectionInfoAndExtensionResponse, NftExtensionMsg,
-    RoyaltyInfoResponse,
+    CollectionExtensionMsg, CollectionInfoAndExtensionResponse, Cw721ExecuteMsg,
+    Cw721InstantiateMsg, Cw721QueryMsg, NftExtensionMsg, RoyaltyInfoResponse,
 };
 pub use state::{Approval, Attribute, CollectionExtension, NftExtension, RoyaltyInfo};
 
@@ -45,6 +45,21 @@ pub type DefaultOptionalNftExtensionMsg = Option<NftExtensionMsg>;
 /// Type for `Option<Empty>`
 pub type EmptyOptionalNftExtensionMsg = Option<Empty>;
 
+// Messages with default extensions resolved, e.g. for schema export and generated client types.
+/// Type for `Cw721InstantiateMsg<DefaultOptionalNftExtensionMsg, DefaultOptionalCollectionExtensionMsg>`
+pub type DefaultCw721InstantiateMsg =
+    Cw721InstantiateMsg<DefaultOptionalNftExtensionMsg, DefaultOptionalCollectionExtensionMsg>;
+/// Type for `Cw721ExecuteMsg<DefaultOptionalNftExtensionMsg, DefaultOptionalCollectionExtensionMsg, Empty>`
+pub type DefaultCw721ExecuteMsg =
+    Cw721ExecuteMsg<DefaultOptionalNftExtensionMsg, DefaultOptionalCollectionExtensionMsg, Empty>;
+/// Type for `Cw721QueryMsg<DefaultOptionalNftExtension, DefaultOptionalNftExtensionMsg, DefaultOptionalCollectionExtension, Empty>`
+pub type DefaultCw721QueryMsg = Cw721QueryMsg<
+    DefaultOptionalNftExtension,
+    DefaultOptionalNftExtensionMsg,
+    DefaultOptionalCollectionExtension,
+    Empty,
+>;
+
 // explicit type for better distinction.
 #[deprecated(since = "0.19.0", note = "Please use `NftExtension` instead")]
 pub type MetaData = NftExtension;
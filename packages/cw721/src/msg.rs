@@ -2,21 +2,23 @@ use std::collections::HashMap;
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, Coin, ContractInfoResponse, Decimal, Deps, Env, MessageInfo,
-    Timestamp,
+    to_json_binary, Addr, Binary, Coin, ContractInfoResponse, Decimal, Deps, Empty, Env,
+    MessageInfo, Timestamp, Uint128,
 };
 use cw_ownable::{Action, Ownership};
-use cw_utils::Expiration;
+use cw_utils::{Duration, Expiration};
 use serde::Serialize;
 use url::Url;
 
 use crate::error::Cw721ContractError;
 use crate::execute::{assert_creator, assert_minter};
+use crate::helpers::validate_collection_extension;
 use crate::state::{
-    Attribute, CollectionExtension, CollectionExtensionAttributes, CollectionInfo, NftInfo, Trait,
-    ATTRIBUTE_DESCRIPTION, ATTRIBUTE_EXPLICIT_CONTENT, ATTRIBUTE_EXTERNAL_LINK, ATTRIBUTE_IMAGE,
-    ATTRIBUTE_ROYALTY_INFO, ATTRIBUTE_START_TRADING_TIME, CREATOR,
-    MAX_COLLECTION_DESCRIPTION_LENGTH, MAX_ROYALTY_SHARE_DELTA_PCT, MAX_ROYALTY_SHARE_PCT, MINTER,
+    Attribute, BurnPolicy, CollectionExtension, CollectionExtensionAttributes, CollectionInfo,
+    Cw721Config, NftInfo, SwapOffer, Trait, ATTRIBUTE_DESCRIPTION, ATTRIBUTE_EXPLICIT_CONTENT,
+    ATTRIBUTE_EXTERNAL_LINK, ATTRIBUTE_IMAGE, ATTRIBUTE_ROYALTY_INFO, ATTRIBUTE_START_TRADING_TIME,
+    CREATOR, MAX_COLLECTION_NAME_LENGTH, MAX_COLLECTION_SYMBOL_LENGTH, MAX_ROYALTY_SHARE_DELTA_PCT,
+    MAX_ROYALTY_SHARE_PCT, MINTER,
 };
 use crate::traits::{Cw721CustomMsg, Cw721State, FromAttributesState, ToAttributesState};
 use crate::NftExtension;
@@ -45,6 +47,16 @@ pub enum Cw721ExecuteMsg<
     TransferNft {
         recipient: String,
         token_id: String,
+        /// If set, transfer is rejected unless the token is still owned by this address,
+        /// e.g. for marketplaces preventing to act on a token that has already been sold.
+        expected_owner: Option<String>,
+    },
+    /// Same as `TransferNft`, but rejects recipients that are no valid address or the contract
+    /// itself, where the token would be stranded.
+    SafeTransferNft {
+        recipient: String,
+        token_id: String,
+        expected_owner: Option<String>,
     },
     /// Send is a base message to transfer a token to a contract and trigger an action
     /// on the receiving contract.
@@ -52,6 +64,49 @@ pub enum Cw721ExecuteMsg<
         contract: String,
         token_id: String,
         msg: Binary,
+        /// If set, send is rejected unless the token is still owned by this address.
+        expected_owner: Option<String>,
+    },
+    /// Transfers multiple tokens to their recipients in one go.
+    /// Sender must be eligible to transfer each token, otherwise no token is transferred.
+    DistributeNfts {
+        transfers: Vec<NftTransfer>,
+    },
+    /// Transfers a token to `recipient` and approves `spender` on it in one go,
+    /// e.g. for listing a token in an escrow and approving the settlement contract.
+    /// Sender must be eligible to transfer the token. The approval is only recorded if the
+    /// transfer succeeds. If `expires` is not set, the approval never expires.
+    TransferAndApprove {
+        recipient: String,
+        token_id: String,
+        spender: String,
+        expires: Option<Expiration>,
+    },
+    /// Offers to swap sender's `my_token` for `counterparty`'s `want_token`.
+    /// `my_token` is locked (cannot be transferred or burned) until the offer is accepted,
+    /// cancelled or expired. If `expires` is not set, the offer never expires.
+    /// Both tokens must be transferable, see `CanTransfer`.
+    ProposeSwap {
+        my_token: String,
+        want_token: String,
+        counterparty: String,
+        expires: Option<Expiration>,
+    },
+    /// Reveals the metadata assignment committed on instantiation, setting each token's `token_uri`.
+    /// Fails with `CommitMismatch` unless `helpers::commit_hash(seed, assignments)` matches the commit.
+    /// Can only be revealed once. Only creator can call this.
+    RevealCommit {
+        seed: Binary,
+        assignments: Vec<RevealAssignment>,
+    },
+    /// Counterparty accepts the offer, transferring both tokens atomically.
+    /// Same checks as on transfer apply to both tokens, counterparty pays the transfer fee for both.
+    AcceptSwap {
+        offer_id: u64,
+    },
+    /// Proposer cancels the offer and unlocks `my_token`. Anyone can cancel an expired offer.
+    CancelSwap {
+        offer_id: u64,
     },
     /// Allows operator to transfer / send the token from the owner's account.
     /// If expiration is set, then this allowance has a time/height limit
@@ -88,12 +143,68 @@ pub enum Cw721ExecuteMsg<
         token_uri: Option<String>,
         /// Any custom extension used by this contract
         extension: TNftExtensionMsg,
+        /// Opaque ics721 `tokenData` of a token bridged back, preserved across IBC round trips.
+        /// Can only be set by the minter, also on public minting.
+        ibc_data: Option<Binary>,
+    },
+    /// Mint a new NFT with the next free sequential id, can only be called by the contract minter.
+    /// Ids already taken (e.g. by `Mint`) are skipped. Assigned id is returned in `token_id` attribute.
+    MintAuto {
+        /// The owner of the newly minted NFT
+        owner: String,
+        /// Universal resource identifier for this NFT
+        token_uri: Option<String>,
+        /// Any custom extension used by this contract
+        extension: TNftExtensionMsg,
+    },
+    /// Mint a new NFT that can't be transferred, sent or burned before `transferable_after`,
+    /// e.g. for vesting. Can only be called by the contract minter.
+    MintTimelocked {
+        /// Unique ID of the NFT
+        token_id: String,
+        /// The owner of the newly minted NFT
+        owner: String,
+        /// Universal resource identifier for this NFT
+        token_uri: Option<String>,
+        /// Any custom extension used by this contract
+        extension: TNftExtensionMsg,
+        transferable_after: Timestamp,
+    },
+    /// Mint a new NFT that becomes invalid at `expires`, e.g. for event tickets. Expired tokens
+    /// can't be transferred or sent anymore and anyone may burn them via `BurnExpired`.
+    /// Can only be called by the contract minter.
+    MintWithExpiration {
+        /// Unique ID of the NFT
+        token_id: String,
+        /// The owner of the newly minted NFT
+        owner: String,
+        /// Universal resource identifier for this NFT
+        token_uri: Option<String>,
+        /// Any custom extension used by this contract
+        extension: TNftExtensionMsg,
+        expires: Expiration,
+    },
+    /// Mints the token of a voucher signed off-chain by the voucher signer (see `SetVoucherSigner`).
+    /// Sender must be the voucher's `owner`. Each voucher can only be redeemed once.
+    RedeemVoucher {
+        voucher: MintVoucher<TNftExtensionMsg>,
+        /// secp256k1 signature (64 bytes) of the voucher hash, see `helpers::voucher_hash`.
+        signature: Binary,
+    },
+    /// Sets the secp256k1 public key (33 or 65 bytes) vouchers must be signed with.
+    /// `None` disables voucher redemption. Only minter can call this.
+    SetVoucherSigner {
+        pubkey: Option<Binary>,
     },
 
     /// Burn an NFT the sender has access to
     Burn {
         token_id: String,
     },
+    /// Burn an expired NFT, see `MintWithExpiration`. Anyone can call this.
+    BurnExpired {
+        token_id: String,
+    },
 
     /// Custom msg execution. This is a no-op in default implementation.
     UpdateExtension {
@@ -102,12 +213,19 @@ pub enum Cw721ExecuteMsg<
 
     /// The creator is the only one eligible to update NFT's token uri and onchain metadata (`NftInfo.extension`).
     /// NOTE: approvals and owner are not affected by this call, since they belong to the NFT owner.
+    /// For `token_uri` and each extension field, `None` keeps the current value and an empty
+    /// value clears it (see `NftExtensionMsg`).
     UpdateNftInfo {
         token_id: String,
         /// NOTE: Empty string is handled as None
         token_uri: Option<String>,
         extension: TNftExtensionMsg,
     },
+    /// Applies `UpdateNftInfo` to many tokens at once, e.g. for revealing final metadata.
+    /// Fails as a whole if any update fails. Only creator can call this.
+    BatchUpdateNftInfo {
+        updates: Vec<NftInfoUpdate<TNftExtensionMsg>>,
+    },
 
     /// Sets address to send withdrawn fees to. Only owner can call this.
     SetWithdrawAddress {
@@ -120,10 +238,124 @@ pub enum Cw721ExecuteMsg<
     WithdrawFunds {
         amount: Coin,
     },
+    /// Withdraw all native balances of the contract to the withdraw address.
+    /// Anyone can call this, like `WithdrawFunds`.
+    WithdrawAll {},
+    /// Pays royalty for the given token from sent funds, e.g. by a marketplace on sale.
+    /// Royalty share of each coin is forwarded to the royalty payment address (or split among
+    /// recipients, see `SetRoyaltySplit`), the remainder is returned to sender.
+    PayRoyalty {
+        token_id: String,
+    },
+    /// Sets hex-encoded SHA-256 hash of the JSON schema NFT metadata should conform to.
+    /// `None` removes the hash. Only creator can call this.
+    SetMetadataSchemaHash {
+        metadata_schema_hash: Option<String>,
+    },
+    /// Sets fee to be paid for burning a token, forwarded to withdraw address (if set).
+    /// `None` removes the fee. Only creator can call this.
+    SetBurnFee {
+        burn_fee: Option<Coin>,
+    },
+    /// Sets minimum time in seconds between transfers of the same token (`TransferNft`, `SendNft`,
+    /// `DistributeNfts` and `TransferAndApprove`). Minter and creator are exempt.
+    /// `None` removes the cooldown. Only creator can call this.
+    SetTransferCooldown {
+        transfer_cooldown: Option<u64>,
+    },
+    /// Sets expiration applied to `ApproveAll` without `expires`, e.g. 30 days, so operator
+    /// approvals don't last forever unknowingly. `None` removes it, so such approvals never
+    /// expire. Only creator can call this.
+    SetDefaultApprovalExpiry {
+        default_approval_expiry: Option<Duration>,
+    },
+    /// Caps royalty share in basis points, royalties above it are rejected with `RoyaltyExceedsCap`.
+    /// Cap can only be lowered, and not below current royalty share. Only creator can call this.
+    SetMaxRoyaltyBps {
+        max_royalty_bps: u16,
+    },
+    /// Changes only the `payment_address` of the collection royalty, e.g. on treasury rotation.
+    /// Fails with `NoRoyaltyInfo` if no royalty is set. Only creator can call this.
+    UpdateRoyaltyRecipient {
+        address: String,
+    },
+    /// Sets royalty of a single token, taking precedence over the collection royalty.
+    /// Same limits apply as for the collection royalty (max share, share increase and cap).
+    /// `None` reverts to the collection royalty. Only creator can call this.
+    SetTokenRoyalty {
+        token_id: String,
+        royalty: Option<RoyaltyInfoResponse>,
+    },
+    /// Splits the collection royalty among recipients, e.g. 60/40 between two artists, instead of
+    /// paying it to its `payment_address`. Each `share` is a portion of the royalty, summing up to
+    /// at most 100%. Rounding remainder and any portion not covered go to the first recipient.
+    /// Token royalties are not split. `None` removes the split. Only creator can call this.
+    SetRoyaltySplit {
+        splits: Option<Vec<RoyaltySplitMsg>>,
+    },
+    /// Restricts denoms accepted for any fee payment (e.g. burn fee).
+    /// Empty list accepts all denoms. Only creator can call this.
+    SetAcceptedDenoms {
+        accepted_denoms: Vec<String>,
+    },
+    /// Transfers a token mistakenly sent to this contract's own address to `recipient`.
+    /// Only tokens owned by the contract itself can be recovered. Only creator can call this.
+    RecoverNft {
+        token_id: String,
+        recipient: String,
+    },
+    /// Moves up to `limit` tokens owned by `from` to `to`, in token id order, e.g. for recovering
+    /// a lost account. Call repeatedly until `from` owns no tokens. Only creator can call this,
+    /// and only if `allow_force_transfer` was set on instantiation. Cancels pending swap offers of
+    /// moved tokens.
+    ForceTransferAll {
+        from: String,
+        to: String,
+        limit: Option<u32>,
+    },
 }
 
+/// Lazy mint voucher, signed off-chain and redeemed on-chain by its `owner`.
 #[cw_serde]
-pub struct Cw721InstantiateMsg<TCollectionExtensionMsg> {
+pub struct MintVoucher<TNftExtensionMsg> {
+    pub token_id: String,
+    pub owner: String,
+    pub token_uri: Option<String>,
+    pub extension: TNftExtensionMsg,
+}
+
+/// Single update applied by `BatchUpdateNftInfo`, same semantics as `UpdateNftInfo`.
+#[cw_serde]
+pub struct NftInfoUpdate<TNftExtensionMsg> {
+    pub token_id: String,
+    pub token_uri: Option<String>,
+    pub extension: TNftExtensionMsg,
+}
+
+#[cw_serde]
+pub struct NftTransfer {
+    pub recipient: String,
+    pub token_id: String,
+}
+
+/// Token URI assigned to a token on `RevealCommit`.
+#[cw_serde]
+pub struct RevealAssignment {
+    pub token_id: String,
+    pub token_uri: String,
+}
+
+/// Same as `Cw721ExecuteMsg::Mint`, without ics721 `ibc_data`.
+#[cw_serde]
+pub struct MintMsg<TNftExtensionMsg> {
+    pub token_id: String,
+    pub owner: String,
+    pub token_uri: Option<String>,
+    pub extension: TNftExtensionMsg,
+}
+
+#[cw_serde]
+pub struct Cw721InstantiateMsg<TNftExtensionMsg, TCollectionExtensionMsg> {
     /// Name of the NFT contract
     pub name: String,
     /// Symbol of the NFT contract
@@ -133,13 +365,66 @@ pub struct Cw721InstantiateMsg<TCollectionExtensionMsg> {
 
     /// The minter is the only one who can create new NFTs.
     /// This is designed for a base NFT that is controlled by an external program
-    /// or contract. You will likely replace this with custom logic in custom NFTs
+    /// or contract. You will likely replace this with custom logic in custom NFTs.
+    /// Defaults to the sender.
     pub minter: Option<String>,
 
     /// Sets the creator of collection. The creator is the only one eligible to update `CollectionInfo`.
+    /// Defaults to the sender.
     pub creator: Option<String>,
 
     pub withdraw_address: Option<String>,
+
+    /// Defines who may burn tokens besides owner and approved spenders. Defaults to `BurnPolicy::OwnerOnly`.
+    pub burn_policy: Option<BurnPolicy>,
+
+    /// HTTP gateway (e.g. `https://ipfs.io/ipfs`) for resolving `ipfs://` URLs in `ResolvedNftInfo` query.
+    pub ipfs_gateway: Option<String>,
+
+    /// Price to be paid for minting by anyone but the minter, see `public_minting`.
+    /// Forwarded to withdraw address (if set).
+    pub mint_price: Option<Coin>,
+
+    /// Allows anyone to mint by paying `mint_price`. Otherwise only the minter can mint.
+    #[serde(default)]
+    pub public_minting: bool,
+
+    /// Royalty share cap in basis points (e.g. 1000 for 10%), see `SetMaxRoyaltyBps`.
+    pub max_royalty_bps: Option<u16>,
+
+    /// SHA-256 commitment (32 bytes) of the metadata assignment revealed later via `RevealCommit`,
+    /// see `helpers::commit_hash`.
+    pub commit_hash: Option<Binary>,
+
+    /// Tokens minted on behalf of the minter as part of instantiation, same as `Mint`.
+    /// Instantiation fails if any of them cannot be minted.
+    pub initial_mints: Option<Vec<MintMsg<TNftExtensionMsg>>>,
+
+    /// Records burned token ids, so they can never be minted again. Costs storage per burn.
+    #[serde(default)]
+    pub track_burned_ids: bool,
+
+    /// Prefix for token URIs of tokens minted without `token_uri`: `NftInfo` and `AllNftInfo`
+    /// return `{base_token_uri}{token_id}` for them. An explicit `token_uri` on mint takes precedence.
+    pub base_token_uri: Option<String>,
+
+    /// Allows the creator to move all tokens of any owner via `ForceTransferAll`, e.g. for
+    /// recovering lost accounts in custodial setups. Cannot be changed after instantiation.
+    #[serde(default)]
+    pub allow_force_transfer: bool,
+
+    /// Contract-level metadata URI of the collection, see `CollectionInfoMsg`.
+    pub collection_metadata_uri: Option<String>,
+
+    /// Allows minting tokens to the contract itself. Such tokens are stranded until recovered via
+    /// `RecoverNft`, so minting them is rejected with `CannotMintToContract` by default.
+    #[serde(default)]
+    pub allow_mint_to_contract: bool,
+
+    /// Rejects mints and updates with `DuplicateTokenUri` if the token uri is already used by
+    /// another token. Keeps an index of all token uris, so storage grows with collection size.
+    #[serde(default)]
+    pub unique_token_uris: bool,
 }
 
 #[cw_serde]
@@ -147,6 +432,8 @@ pub struct Cw721InstantiateMsg<TCollectionExtensionMsg> {
 pub enum Cw721QueryMsg<
     // Return type of NFT metadata defined in `NftInfo` and `AllNftInfo`.
     TNftExtension,
+    // NftInfo extension msg for onchain metadata, as passed to `ValidateMint`.
+    TNftExtensionMsg,
     // Return type of collection extension defined in `GetCollectionInfo`.
     TCollectionExtension,
     // Custom query msg for custom contract logic. Default implementation returns an empty binary.
@@ -159,6 +446,33 @@ pub enum Cw721QueryMsg<
         /// unset or false will filter out expired approvals, you must set to true to see them
         include_expired: Option<bool>,
     },
+    /// Return owner, lock state and live approvals of the given token in one call,
+    /// error if token does not exist.
+    #[returns(TokenStateResponse)]
+    TokenState { token_id: String },
+    /// Return the owner of the given token at the beginning of the given block height.
+    /// Owner is `None` in case token was not minted yet or already burned at that height.
+    #[returns(OwnerOfAtResponse)]
+    OwnerOfAt { token_id: String, height: u64 },
+    /// Return whether sender is allowed to transfer the given token, and why not. Error if token does not exist.
+    /// Same checks as on transfer (see `execute::check_can_transfer`): token not expired, owner,
+    /// operator or token approval (non-expired), not locked by a swap offer, not timelocked, and
+    /// transfer cooldown of the token elapsed.
+    #[returns(CanTransferResponse)]
+    CanTransfer { token_id: String, sender: String },
+    /// Dry-runs `Mint` by `sender` with attached `funds`, returning all failing checks.
+    /// Same checks as on mint (see `execute::check_mint`): minter or paid public mint, token id
+    /// neither taken nor burned, valid owner, token uri and extension.
+    #[returns(ValidateMintResponse)]
+    ValidateMint {
+        sender: String,
+        msg: MintMsg<TNftExtensionMsg>,
+        #[serde(default)]
+        funds: Vec<Coin>,
+    },
+    /// Return pending swap offer by id.
+    #[returns(SwapOffer)]
+    SwapOffer { offer_id: u64 },
     /// Return operator that can access all of the owner's tokens.
     #[returns(ApprovalResponse)]
     Approval {
@@ -172,6 +486,26 @@ pub enum Cw721QueryMsg<
         token_id: String,
         include_expired: Option<bool>,
     },
+    /// Lists approvals of all tokens, paginated by token id.
+    /// Tokens without approvals are skipped. Unless `include_expired` is set, expired
+    /// approvals are skipped too. `limit` caps the tokens scanned, so a page may hold less
+    /// approvals; continue with `last_token_id` as `start_after`.
+    #[returns(AllApprovalsResponse)]
+    AllApprovals {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        include_expired: Option<bool>,
+    },
+    /// Lists tokens `operator` can move, paginated by token id: tokens of owners having a live
+    /// `ApproveAll` for `operator` and tokens having a live direct approval for `operator`.
+    /// Heavy query: every token is scanned, so `limit` caps the tokens scanned and a page may
+    /// hold less tokens; continue with `last_token_id` as `start_after`.
+    #[returns(OperableTokensResponse)]
+    OperableTokens {
+        operator: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     /// Return approval of a given operator for all tokens of an owner, error if not set
     #[returns(OperatorResponse)]
     Operator {
@@ -191,6 +525,12 @@ pub enum Cw721QueryMsg<
     /// Total number of tokens issued
     #[returns(NumTokensResponse)]
     NumTokens {},
+    /// Number of current, ever minted and ever burned tokens.
+    #[returns(SupplyStatsResponse)]
+    SupplyStats {},
+    /// Number of tokens per value of the given trait type, e.g. how many tokens have a gold background.
+    #[returns(TraitCountsResponse)]
+    TraitCounts { trait_type: String },
 
     #[deprecated(
         since = "0.19.0",
@@ -212,6 +552,10 @@ pub enum Cw721QueryMsg<
     #[returns(AllInfoResponse)]
     GetAllInfo {},
 
+    /// Returns collection metadata shaped for ics721 class data, see `ClassMetadataResponse`.
+    #[returns(ClassMetadataResponse)]
+    ClassMetadata {},
+
     /// Returns `CollectionExtensionAttributes`
     #[returns(CollectionExtensionAttributes)]
     GetCollectionExtensionAttributes {},
@@ -227,9 +571,13 @@ pub enum Cw721QueryMsg<
     /// Deprecated: use GetMinterOwnership instead! Will be removed in next release!
     Minter {},
 
+    /// Returns the minter, plus `pending_owner` and `pending_expiry` of a proposed minter
+    /// transfer awaiting acceptance via `UpdateMinterOwnership(AcceptOwnership)`.
     #[returns(Ownership<Addr>)]
     GetMinterOwnership {},
 
+    /// Returns the creator, plus `pending_owner` and `pending_expiry` of a proposed creator
+    /// transfer awaiting acceptance via `UpdateCreatorOwnership(AcceptOwnership)`.
     #[returns(Ownership<Addr>)]
     GetCreatorOwnership {},
 
@@ -239,6 +587,11 @@ pub enum Cw721QueryMsg<
     #[returns(NftInfoResponse<TNftExtension>)]
     NftInfo { token_id: String },
 
+    /// Same as `NftInfo`, but `ipfs://` URLs in `token_uri` and onchain metadata (`image`, `animation_url`)
+    /// are rewritten to the `ipfs_gateway` set on instantiation. Stored data is not changed.
+    #[returns(NftInfoResponse<TNftExtension>)]
+    ResolvedNftInfo { token_id: String },
+
     #[returns(Option<NftInfoResponse<TNftExtension>>)]
     GetNftByExtension {
         extension: TNftExtension,
@@ -263,6 +616,8 @@ pub enum Cw721QueryMsg<
         owner: String,
         start_after: Option<String>,
         limit: Option<u32>,
+        /// `next_cursor` of the previous page, takes precedence over `start_after`.
+        cursor: Option<Binary>,
     },
     /// With Enumerable extension.
     /// Requires pagination. Lists all token_ids controlled by the contract.
@@ -270,7 +625,15 @@ pub enum Cw721QueryMsg<
     AllTokens {
         start_after: Option<String>,
         limit: Option<u32>,
+        /// `next_cursor` of the previous page, takes precedence over `start_after`.
+        cursor: Option<Binary>,
     },
+    /// Returns the token id at position `index` in `0..NumTokens`, like ERC-721 Enumerable.
+    /// Positions follow mint order, except that burning moves the last token into the burned
+    /// token's position. Error if out of bounds, or while existing tokens are still being indexed
+    /// after migration (see `Cw721MigrateMsg::BackfillTokenIndex`).
+    #[returns(TokenByIndexResponse)]
+    TokenByIndex { index: u64 },
 
     /// Custom msg query. Default implementation returns an empty binary.
     #[returns(())]
@@ -281,6 +644,77 @@ pub enum Cw721QueryMsg<
 
     #[returns(Option<String>)]
     GetWithdrawAddress {},
+
+    /// Whether a token uses the given `token_uri`. Only tracked if `unique_token_uris` is set on
+    /// instantiation, otherwise always false.
+    #[returns(bool)]
+    IsUriUsed { token_uri: String },
+
+    /// Native balances that can be withdrawn to the withdraw address, i.e. what `WithdrawAll`
+    /// would sweep.
+    #[returns(Vec<Coin>)]
+    WithdrawableBalance {},
+
+    /// Returns the address royalties are paid to: the royalty `payment_address` if royalty info
+    /// is set, else the withdraw address, else the creator. `None` if none of them is set.
+    /// Ignores `SetRoyaltySplit`: once a split is set, the returned address is not paid by the
+    /// collection royalty, use `payments` of `RoyaltyInfo` instead.
+    #[returns(Option<String>)]
+    RoyaltyRecipient {},
+
+    /// Returns hex-encoded hash of the JSON schema NFT metadata should conform to, if set.
+    #[returns(Option<String>)]
+    GetMetadataSchemaHash {},
+
+    /// Returns fee to be paid for burning a token, if set.
+    #[returns(Option<Coin>)]
+    GetBurnFee {},
+
+    /// Returns fee to be paid for each token transfer, if set.
+    #[returns(Option<Coin>)]
+    GetTransferFee {},
+
+    /// Returns the address that minted the given token. Falls back to the collection minter for
+    /// tokens minted before it was recorded.
+    #[returns(Option<String>)]
+    TokenMinter { token_id: String },
+
+    /// Returns expiration applied to `ApproveAll` without `expires`, if set.
+    #[returns(Option<Duration>)]
+    GetDefaultApprovalExpiry {},
+
+    /// Returns transfer cooldown in seconds (if set), and when the given token can be transferred
+    /// again (if still cooling down).
+    #[returns(TransferCooldownResponse)]
+    TransferCooldown { token_id: String },
+
+    /// Returns royalty share cap in basis points, if set.
+    #[returns(Option<u16>)]
+    GetMaxRoyaltyBps {},
+
+    /// Returns royalty recipient and amount owed for a sale of the given token at `sale_price`.
+    /// Token royalty (see `SetTokenRoyalty`) takes precedence over the collection royalty.
+    #[returns(RoyaltyAmountResponse)]
+    RoyaltyInfo {
+        token_id: String,
+        sale_price: Uint128,
+    },
+
+    /// Returns the collection royalty and tokens with royalty override (see `SetTokenRoyalty`),
+    /// paginated by token id.
+    #[returns(RoyaltySummaryResponse)]
+    RoyaltySummary {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Returns denoms accepted for fee payments. Empty list means all denoms are accepted.
+    #[returns(Vec<String>)]
+    GetAcceptedDenoms {},
+
+    /// Returns the public key vouchers must be signed with, if set.
+    #[returns(Option<Binary>)]
+    GetVoucherSigner {},
 }
 
 #[cw_serde]
@@ -289,6 +723,14 @@ pub enum Cw721MigrateMsg {
         minter: Option<String>,
         creator: Option<String>,
     },
+    /// Regular migration that also repairs `num_tokens` by counting all stored tokens, and
+    /// `total_minted` accordingly. Iterates all tokens, so gas grows with collection size.
+    /// The token index is rebuilt afterwards, see `BackfillTokenIndex`.
+    RecomputeNumTokens {},
+    /// Regular migration that also adds up to `limit` further existing tokens to the token index.
+    /// Needs to be repeated until the `token_index_backfill` attribute is `complete`, `TokenByIndex`
+    /// is unavailable until then.
+    BackfillTokenIndex { limit: u32 },
 }
 
 #[cw_serde]
@@ -296,6 +738,14 @@ pub struct CollectionInfoMsg<TCollectionExtensionMsg> {
     pub name: Option<String>,
     pub symbol: Option<String>,
     pub extension: TCollectionExtensionMsg,
+    /// Contract-level metadata URI (OpenSea `contractURI`, ics721 `classUri`), stored apart from
+    /// the collection extension. Must be a URL.
+    #[serde(default)]
+    pub collection_metadata_uri: Clearable<String>,
+    /// Fee to be paid for each token transfer (`TransferNft`, `SendNft`, `DistributeNfts` and
+    /// `TransferAndApprove`), forwarded to withdraw address (if set).
+    #[serde(default)]
+    pub transfer_fee: Clearable<Coin>,
 }
 
 #[cw_serde]
@@ -368,15 +818,62 @@ pub enum AttributeType {
     Custom,
 }
 
+/// Update of an optional field: either set a new value, clear it, or leave it unchanged.
+/// On instantiation, `Clear` and `NoChange` both leave the field unset.
+#[cw_serde]
+pub enum Clearable<T> {
+    Set(T),
+    Clear,
+    NoChange,
+}
+
+impl<T> Default for Clearable<T> {
+    fn default() -> Self {
+        Clearable::NoChange
+    }
+}
+
+impl<T> Clearable<T>
+where
+    T: Clone,
+{
+    pub fn is_no_change(&self) -> bool {
+        matches!(self, Clearable::NoChange)
+    }
+
+    /// New value, in case it is set.
+    pub fn as_set(&self) -> Option<&T> {
+        match self {
+            Clearable::Set(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the updated value of the given current one.
+    pub fn apply(&self, current: Option<T>) -> Option<T> {
+        match self {
+            Clearable::Set(value) => Some(value.clone()),
+            Clearable::Clear => None,
+            Clearable::NoChange => current,
+        }
+    }
+}
+
 #[cw_serde]
 /// NOTE: In case `info` is not provided in `create()` or `validate()` (like for migration), creator/minter assertion is skipped.
 pub struct CollectionExtensionMsg<TRoyaltyInfoResponse> {
+    /// Required, so it can only be updated but not cleared. `None` leaves it unchanged.
     pub description: Option<String>,
+    /// Required, so it can only be updated but not cleared. `None` leaves it unchanged.
     pub image: Option<String>,
-    pub external_link: Option<String>,
-    pub explicit_content: Option<bool>,
-    pub start_trading_time: Option<Timestamp>,
-    pub royalty_info: Option<TRoyaltyInfoResponse>,
+    #[serde(default)]
+    pub external_link: Clearable<String>,
+    #[serde(default)]
+    pub explicit_content: Clearable<bool>,
+    #[serde(default)]
+    pub start_trading_time: Clearable<Timestamp>,
+    #[serde(default)]
+    pub royalty_info: Clearable<TRoyaltyInfoResponse>,
 }
 
 impl<TRoyaltyInfoResponse> Cw721CustomMsg for CollectionExtensionMsg<TRoyaltyInfoResponse> where
@@ -406,38 +903,37 @@ impl StateFactory<CollectionExtension<RoyaltyInfo>>
                 if let Some(image) = &self.image {
                     updated.image.clone_from(image)
                 }
-                if let Some(external_link) = &self.external_link {
-                    updated.external_link = Some(external_link.clone());
-                }
-                if let Some(explicit_content) = self.explicit_content {
-                    updated.explicit_content = Some(explicit_content);
-                }
-                if let Some(start_trading_time) = self.start_trading_time {
-                    updated.start_trading_time = Some(start_trading_time);
-                }
-                if let Some(royalty_info_response) = &self.royalty_info {
-                    match current.royalty_info.clone() {
-                        // Some: existing royalty info for update
-                        Some(current_royalty_info) => {
-                            updated.royalty_info = Some(royalty_info_response.create(
-                                deps,
-                                env,
-                                info,
-                                Some(&current_royalty_info),
-                            )?);
-                        }
-                        // None: no royalty info, so create new
-                        None => {
-                            updated.royalty_info =
-                                Some(royalty_info_response.create(deps, env, info, None)?);
+                updated.external_link = self.external_link.apply(updated.external_link);
+                updated.explicit_content = self.explicit_content.apply(updated.explicit_content);
+                updated.start_trading_time =
+                    self.start_trading_time.apply(updated.start_trading_time);
+                match &self.royalty_info {
+                    Clearable::Set(royalty_info_response) => {
+                        match current.royalty_info.clone() {
+                            // Some: existing royalty info for update
+                            Some(current_royalty_info) => {
+                                updated.royalty_info = Some(royalty_info_response.create(
+                                    deps,
+                                    env,
+                                    info,
+                                    Some(&current_royalty_info),
+                                )?);
+                            }
+                            // None: no royalty info, so create new
+                            None => {
+                                updated.royalty_info =
+                                    Some(royalty_info_response.create(deps, env, info, None)?);
+                            }
                         }
                     }
+                    Clearable::Clear => updated.royalty_info = None,
+                    Clearable::NoChange => {}
                 }
                 Ok(updated)
             }
             // None: create new metadata
             None => {
-                let royalty_info = match &self.royalty_info {
+                let royalty_info = match self.royalty_info.as_set() {
                     // new royalty info
                     Some(royalty_info) => Some(royalty_info.create(deps, env, info, None)?),
                     // current royalty is none and new royalty is none
@@ -446,9 +942,9 @@ impl StateFactory<CollectionExtension<RoyaltyInfo>>
                 let new = CollectionExtension {
                     description: self.description.clone().unwrap_or_default(),
                     image: self.image.clone().unwrap_or_default(),
-                    external_link: self.external_link.clone(),
-                    explicit_content: self.explicit_content,
-                    start_trading_time: self.start_trading_time,
+                    external_link: self.external_link.apply(None),
+                    explicit_content: self.explicit_content.apply(None),
+                    start_trading_time: self.start_trading_time.apply(None),
                     royalty_info,
                 };
                 Ok(new)
@@ -467,7 +963,7 @@ impl StateFactory<CollectionExtension<RoyaltyInfo>>
         let sender = info.map(|i| i.sender.clone());
         // start trading time can only be updated by minter
         let minter_initialized = MINTER.item.may_load(deps.storage)?;
-        if self.start_trading_time.is_some()
+        if !self.start_trading_time.is_no_change()
             && minter_initialized.is_some()
             && sender.is_some()
             && MINTER
@@ -480,8 +976,9 @@ impl StateFactory<CollectionExtension<RoyaltyInfo>>
         let creator_initialized = CREATOR.item.may_load(deps.storage)?;
         if (self.description.is_some()
             || self.image.is_some()
-            || self.external_link.is_some()
-            || self.explicit_content.is_some())
+            || !self.external_link.is_no_change()
+            || !self.explicit_content.is_no_change()
+            || !self.royalty_info.is_no_change())
             && sender.is_some()
             && creator_initialized.is_some()
             && CREATOR
@@ -490,27 +987,8 @@ impl StateFactory<CollectionExtension<RoyaltyInfo>>
         {
             return Err(Cw721ContractError::NotCreator {});
         }
-        // check description length, must not be empty and max 512 chars
-        if let Some(description) = &self.description {
-            if description.is_empty() {
-                return Err(Cw721ContractError::CollectionDescriptionEmpty {});
-            }
-            if description.len() > MAX_COLLECTION_DESCRIPTION_LENGTH as usize {
-                return Err(Cw721ContractError::CollectionDescriptionTooLong {
-                    max_length: MAX_COLLECTION_DESCRIPTION_LENGTH,
-                });
-            }
-        }
-
-        // check images are URLs
-        if let Some(image) = &self.image {
-            Url::parse(image)?;
-        }
-        if let Some(external_link) = &self.external_link {
-            Url::parse(external_link)?;
-        }
         // no need to check royalty info, as it is checked during creation of RoyaltyInfo
-        Ok(())
+        validate_collection_extension(self)
     }
 }
 
@@ -576,6 +1054,7 @@ impl StateFactory<RoyaltyInfo> for RoyaltyInfoResponse {
                 "Share cannot be greater than {MAX_ROYALTY_SHARE_PCT}%"
             )));
         }
+        Cw721Config::<Option<Empty>>::default().assert_royalty_cap(deps.storage, self.share)?;
         // validate payment address
         deps.api.addr_validate(self.payment_address.as_str())?;
         Ok(())
@@ -610,6 +1089,19 @@ pub struct CollectionInfoAndExtensionResponse<TCollectionExtension> {
     pub symbol: String,
     pub extension: TCollectionExtension,
     pub updated_at: Timestamp,
+    #[serde(default)]
+    pub collection_metadata_uri: Option<String>,
+}
+
+/// Collection metadata as expected by ics721 for an NFT class:
+/// - `class_id`: this contract's address,
+/// - `class_uri`: `collection_metadata_uri` if set, else collection extension's `external_link`,
+/// - `class_data`: JSON-encoded `CollectionInfoAndExtensionResponse` (name, symbol, extension, updated_at).
+#[cw_serde]
+pub struct ClassMetadataResponse {
+    pub class_id: String,
+    pub class_uri: Option<String>,
+    pub class_data: Option<Binary>,
 }
 
 /// This is a wrapper around CollectionInfo that includes the extension, contract info, and number of tokens (supply).
@@ -664,6 +1156,9 @@ where
                     self.extension
                         .create(deps, env, info, Some(&current_extension))?;
                 updated.extension = updated_extension;
+                updated.collection_metadata_uri = self
+                    .collection_metadata_uri
+                    .apply(current.collection_metadata_uri.clone());
                 Ok(updated)
             }
             // None: create new metadata
@@ -674,6 +1169,7 @@ where
                     symbol: self.symbol.clone().unwrap(),
                     extension,
                     updated_at: env.block.time,
+                    collection_metadata_uri: self.collection_metadata_uri.as_set().cloned(),
                 };
                 Ok(new)
             }
@@ -687,18 +1183,41 @@ where
         info: Option<&MessageInfo>,
         _current: Option<&CollectionInfoAndExtensionResponse<TCollectionExtension>>,
     ) -> Result<(), Cw721ContractError> {
-        // make sure the name and symbol are not empty
-        if self.name.is_some() && self.name.clone().unwrap().is_empty() {
-            return Err(Cw721ContractError::CollectionNameEmpty {});
+        // make sure the name and symbol are not empty, not too long and symbol is alphanumeric
+        if let Some(name) = &self.name {
+            if name.is_empty() {
+                return Err(Cw721ContractError::CollectionNameEmpty {});
+            }
+            if name.chars().count() > MAX_COLLECTION_NAME_LENGTH as usize {
+                return Err(Cw721ContractError::InvalidName {
+                    max_length: MAX_COLLECTION_NAME_LENGTH,
+                });
+            }
+        }
+        if let Some(symbol) = &self.symbol {
+            if symbol.is_empty() {
+                return Err(Cw721ContractError::CollectionSymbolEmpty {});
+            }
+            if symbol.chars().count() > MAX_COLLECTION_SYMBOL_LENGTH as usize
+                || !symbol.chars().all(|c| c.is_ascii_alphanumeric())
+            {
+                return Err(Cw721ContractError::InvalidSymbol {
+                    symbol: symbol.clone(),
+                    max_length: MAX_COLLECTION_SYMBOL_LENGTH,
+                });
+            }
         }
-        if self.symbol.is_some() && self.symbol.clone().unwrap().is_empty() {
-            return Err(Cw721ContractError::CollectionSymbolEmpty {});
+        if let Some(collection_metadata_uri) = self.collection_metadata_uri.as_set() {
+            Url::parse(collection_metadata_uri)?;
         }
         // collection metadata can only be updated by the creator. creator assertion is skipped for these cases:
         // - CREATOR store is empty/not initioized (like in instantiation)
         // - info is none (like in migration)
         let creator_initialized = CREATOR.item.may_load(deps.storage)?;
-        if (self.name.is_some() || self.symbol.is_some())
+        if (self.name.is_some()
+            || self.symbol.is_some()
+            || !self.collection_metadata_uri.is_no_change()
+            || !self.transfer_fee.is_no_change())
             && creator_initialized.is_some()
             && info.is_some()
             && CREATOR
@@ -805,6 +1324,27 @@ pub struct OwnerOfResponse {
     pub owner: String,
     /// If set this address is approved to transfer/send the token as well
     pub approvals: Vec<Approval>,
+    /// Whether token has expired, see `MintWithExpiration`
+    #[serde(default)]
+    pub expired: bool,
+}
+
+#[cw_serde]
+pub struct TokenStateResponse {
+    pub owner: String,
+    /// Whether token is timelocked or locked by a pending swap offer,
+    /// so it can't be transferred, sent or burned.
+    pub locked: bool,
+    /// End of the timelock if timelocked, else expiration of the swap offer locking the token.
+    pub locked_until: Option<Expiration>,
+    /// Live approvals, expired ones are filtered out.
+    pub approvals: Vec<Approval>,
+}
+
+#[cw_serde]
+pub struct OwnerOfAtResponse {
+    /// Owner of the token at the given height, if any
+    pub owner: Option<String>,
 }
 
 #[cw_serde]
@@ -817,6 +1357,82 @@ pub struct ApprovalsResponse {
     pub approvals: Vec<Approval>,
 }
 
+#[cw_serde]
+pub struct TransferCooldownResponse {
+    pub transfer_cooldown: Option<u64>,
+    pub ready_at: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct RoyaltySplitMsg {
+    pub address: String,
+    pub share: Decimal,
+}
+
+#[cw_serde]
+pub struct RoyaltyAmountResponse {
+    /// Royalty payment address, `None` if no royalty applies or the royalty is split among
+    /// several recipients (see `SetRoyaltySplit`).
+    pub address: Option<String>,
+    pub royalty_amount: Uint128,
+    /// Amount owed per recipient, summing up to `royalty_amount`.
+    pub payments: Vec<RoyaltyPayment>,
+}
+
+#[cw_serde]
+pub struct RoyaltyPayment {
+    pub address: String,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct RoyaltySummaryResponse {
+    /// Collection royalty, applying to all tokens without override.
+    pub royalty_info: Option<RoyaltyInfoResponse>,
+    /// Royalty overrides, ordered by token id.
+    pub token_royalties: Vec<TokenRoyalty>,
+}
+
+#[cw_serde]
+pub struct TokenRoyalty {
+    pub token_id: String,
+    pub royalty_info: RoyaltyInfoResponse,
+}
+
+#[cw_serde]
+pub struct ValidateMintResponse {
+    pub valid: bool,
+    /// Errors the mint would fail with, empty if valid.
+    pub errors: Vec<String>,
+}
+
+#[cw_serde]
+pub struct CanTransferResponse {
+    pub allowed: bool,
+    /// Error the transfer would fail with, if not allowed.
+    pub reason: Option<String>,
+}
+
+#[cw_serde]
+pub struct TokenApprovals {
+    pub token_id: String,
+    pub approvals: Vec<Approval>,
+}
+
+#[cw_serde]
+pub struct AllApprovalsResponse {
+    pub approvals: Vec<TokenApprovals>,
+    /// Last token scanned, `None` once all tokens are scanned.
+    pub last_token_id: Option<String>,
+}
+
+#[cw_serde]
+pub struct OperableTokensResponse {
+    pub tokens: Vec<String>,
+    /// Last token scanned, `None` once all tokens are scanned.
+    pub last_token_id: Option<String>,
+}
+
 #[cw_serde]
 pub struct OperatorResponse {
     pub approval: Approval,
@@ -832,6 +1448,25 @@ pub struct NumTokensResponse {
     pub count: u64,
 }
 
+#[cw_serde]
+pub struct TokenByIndexResponse {
+    pub token_id: String,
+}
+
+#[cw_serde]
+pub struct SupplyStatsResponse {
+    /// Same as `NumTokens`, equals `total_minted - total_burned`.
+    pub current: u64,
+    pub total_minted: u64,
+    pub total_burned: u64,
+}
+
+#[cw_serde]
+pub struct TraitCountsResponse {
+    /// Number of tokens per trait value, ordered by value
+    pub counts: Vec<(String, u64)>,
+}
+
 #[cw_serde]
 pub struct NftInfoResponse<TNftExtension> {
     /// Universal resource identifier for this NFT
@@ -840,6 +1475,15 @@ pub struct NftInfoResponse<TNftExtension> {
     pub token_uri: Option<String>,
     /// You can add any custom metadata here when you extend cw721-base
     pub extension: TNftExtension,
+    /// Opaque ics721 `tokenData` set on mint, if any
+    #[serde(default)]
+    pub ibc_data: Option<Binary>,
+    /// Address that minted the token, if known
+    #[serde(default)]
+    pub minter: Option<String>,
+    /// Whether token has expired, see `MintWithExpiration`
+    #[serde(default)]
+    pub expired: bool,
 }
 
 #[cw_serde]
@@ -856,6 +1500,10 @@ pub struct TokensResponse {
     /// If there are more than `limit`, use `start_after` in future queries
     /// to achieve pagination.
     pub tokens: Vec<String>,
+    /// Opaque cursor to pass as `cursor` for the next page, `None` on the last page.
+    /// Unlike `start_after`, it does not depend on the token id format.
+    #[serde(default)]
+    pub next_cursor: Option<Binary>,
 }
 
 /// Deprecated: use Cw721QueryMsg::GetMinterOwnership instead!
@@ -918,6 +1566,10 @@ where
                     approvals: vec![],
                     token_uri,
                     extension,
+                    ibc_data: None,
+                    minter: None,
+                    expires: None,
+                    transferable_after: None,
                 })
             }
         }
@@ -949,21 +1601,23 @@ where
     }
 }
 
+/// NFT metadata as passed on mint and on update.
+///
+/// Every field follows the same rule:
+/// - `None` keeps the current value (or leaves it unset on mint),
+/// - `Some("")` (or `Some(vec![])` for `attributes`) clears it,
+/// - any other `Some(value)` sets it.
 #[cw_serde]
 #[derive(Default)]
 pub struct NftExtensionMsg {
-    /// NOTE: Empty string is handled as None
     pub image: Option<String>,
     pub image_data: Option<String>,
-    /// NOTE: Empty string is handled as None
     pub external_url: Option<String>,
     pub description: Option<String>,
     pub name: Option<String>,
     pub attributes: Option<Vec<Trait>>,
     pub background_color: Option<String>,
-    /// NOTE: Empty string is handled as None
     pub animation_url: Option<String>,
-    /// NOTE: Empty string is handled as None
     pub youtube_url: Option<String>,
 }
 
@@ -985,6 +1639,23 @@ impl From<NftExtension> for NftExtensionMsg {
     }
 }
 
+impl NftExtensionMsg {
+    /// Creates the attributes, where an empty list is handled as None.
+    fn create_attributes(
+        &self,
+        deps: Deps,
+        env: &Env,
+        info: Option<&MessageInfo>,
+    ) -> Result<Option<Vec<Trait>>, Cw721ContractError> {
+        match &self.attributes {
+            Some(attributes) if !attributes.is_empty() => {
+                Ok(Some(attributes.create(deps, env, info, None)?))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
 impl StateFactory<NftExtension> for NftExtensionMsg {
     fn create(
         &self,
@@ -1014,10 +1685,7 @@ impl StateFactory<NftExtension> for NftExtensionMsg {
                     updated.name = empty_as_none(self.name.clone());
                 }
                 if self.attributes.is_some() {
-                    updated.attributes = match self.attributes.clone() {
-                        Some(attributes) => Some(attributes.create(deps, env, info, None)?),
-                        None => None,
-                    };
+                    updated.attributes = self.create_attributes(deps, env, info)?;
                 }
                 if self.background_color.is_some() {
                     updated.background_color = empty_as_none(self.background_color.clone())
@@ -1032,13 +1700,17 @@ impl StateFactory<NftExtension> for NftExtensionMsg {
             }
             // None: create new metadata, note: msg is of same type as metadata, so we can clone it
             None => {
-                let mut new_metadata: NftExtension = self.clone().into();
-                if self.attributes.is_some() {
-                    new_metadata.attributes = match self.attributes.clone() {
-                        Some(attributes) => Some(attributes.create(deps, env, info, None)?),
-                        None => None,
-                    };
-                }
+                let new_metadata = NftExtension {
+                    image: empty_as_none(self.image.clone()),
+                    image_data: empty_as_none(self.image_data.clone()),
+                    external_url: empty_as_none(self.external_url.clone()),
+                    description: empty_as_none(self.description.clone()),
+                    name: empty_as_none(self.name.clone()),
+                    attributes: self.create_attributes(deps, env, info)?,
+                    background_color: empty_as_none(self.background_color.clone()),
+                    animation_url: empty_as_none(self.animation_url.clone()),
+                    youtube_url: empty_as_none(self.youtube_url.clone()),
+                };
                 Ok(new_metadata)
             }
         }
@@ -1,27 +1,38 @@
+use std::collections::BTreeMap;
+
 use cosmwasm_std::{
-    Addr, BlockInfo, CustomMsg, Deps, Empty, Env, Order, StdError, StdResult, Storage,
+    to_json_binary, Addr, Binary, BlockInfo, Coin, CustomMsg, Deps, Empty, Env, MessageInfo, Order,
+    StdError, StdResult, Storage, Uint128,
 };
 use cw_ownable::Ownership;
 use cw_storage_plus::Bound;
-use cw_utils::{maybe_addr, Expiration};
+use cw_utils::{maybe_addr, Duration, Expiration};
 
 use crate::{
     error::Cw721ContractError,
+    execute::{check_can_transfer, check_mint},
     extension::{
         Cw721BaseExtensions, Cw721EmptyExtensions, Cw721Extensions, Cw721OnchainExtensions,
     },
+    helpers::{resolve_ipfs_url, resolve_token_uri},
     msg::{
-        AllInfoResponse, AllNftInfoResponse, ApprovalResponse, ApprovalsResponse,
-        CollectionInfoAndExtensionResponse, ConfigResponse, MinterResponse, NftInfoResponse,
-        NumTokensResponse, OperatorResponse, OperatorsResponse, OwnerOfResponse, TokensResponse,
+        AllApprovalsResponse, AllInfoResponse, AllNftInfoResponse, ApprovalResponse,
+        ApprovalsResponse, CanTransferResponse, ClassMetadataResponse,
+        CollectionInfoAndExtensionResponse, ConfigResponse, MintMsg, MinterResponse,
+        NftInfoResponse, NumTokensResponse, OperableTokensResponse, OperatorResponse,
+        OperatorsResponse, OwnerOfAtResponse, OwnerOfResponse, RoyaltyAmountResponse,
+        RoyaltyPayment, RoyaltySummaryResponse, SupplyStatsResponse, TokenApprovals,
+        TokenByIndexResponse, TokenRoyalty, TokenStateResponse, TokensResponse,
+        TraitCountsResponse, TransferCooldownResponse, ValidateMintResponse,
     },
     state::{
-        Approval, CollectionExtensionAttributes, CollectionInfo, Cw721Config, NftInfo, CREATOR,
-        MINTER,
+        Approval, CollectionExtensionAttributes, CollectionInfo, Cw721Config, NftInfo, SwapOffer,
+        ATTRIBUTE_EXTERNAL_LINK, CREATOR, MINTER,
     },
-    traits::{Contains, Cw721CustomMsg, Cw721Query, Cw721State, FromAttributesState},
+    traits::{Contains, Cw721CustomMsg, Cw721Query, Cw721State, FromAttributesState, StateFactory},
     DefaultOptionalCollectionExtension, DefaultOptionalNftExtension,
-    EmptyOptionalCollectionExtension, EmptyOptionalNftExtension,
+    DefaultOptionalNftExtensionMsg, EmptyOptionalCollectionExtension, EmptyOptionalNftExtension,
+    EmptyOptionalNftExtensionMsg,
 };
 
 pub const DEFAULT_LIMIT: u32 = 10;
@@ -126,11 +137,40 @@ where
     let collection_info = query_collection_info(deps.storage)?;
     let attributes = query_collection_extension_attributes(deps)?;
     let extension = FromAttributesState::from_attributes_state(&attributes)?;
+    let collection_metadata_uri = Cw721Config::<Option<Empty>>::default()
+        .collection_metadata_uri
+        .may_load(deps.storage)?;
     Ok(CollectionInfoAndExtensionResponse {
         name: collection_info.name,
         symbol: collection_info.symbol,
         updated_at: collection_info.updated_at,
         extension,
+        collection_metadata_uri,
+    })
+}
+
+pub fn query_class_metadata<TCollectionExtension>(
+    deps: Deps,
+    env: &Env,
+) -> Result<ClassMetadataResponse, Cw721ContractError>
+where
+    TCollectionExtension: Cw721State + FromAttributesState,
+{
+    let collection = query_collection_info_and_extension::<TCollectionExtension>(deps)?;
+    let class_uri = match &collection.collection_metadata_uri {
+        Some(collection_metadata_uri) => Some(collection_metadata_uri.clone()),
+        None => match Cw721Config::<Option<Empty>>::default()
+            .collection_extension
+            .may_load(deps.storage, ATTRIBUTE_EXTERNAL_LINK.to_string())?
+        {
+            Some(attribute) => attribute.value::<Option<String>>()?,
+            None => None,
+        },
+    };
+    Ok(ClassMetadataResponse {
+        class_id: env.contract.address.to_string(),
+        class_uri,
+        class_data: Some(to_json_binary(&collection)?),
     })
 }
 
@@ -154,24 +194,73 @@ pub fn query_num_tokens(storage: &dyn Storage) -> StdResult<NumTokensResponse> {
     Ok(NumTokensResponse { count })
 }
 
-pub fn query_nft_info<TNftExtension>(
+pub fn query_supply_stats(storage: &dyn Storage) -> StdResult<SupplyStatsResponse> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    Ok(SupplyStatsResponse {
+        current: config.token_count(storage)?,
+        total_minted: config.total_minted(storage)?,
+        total_burned: config.total_burned(storage)?,
+    })
+}
+
+pub fn query_trait_counts(
     storage: &dyn Storage,
+    trait_type: String,
+) -> StdResult<TraitCountsResponse> {
+    let counts = Cw721Config::<Option<Empty>>::default()
+        .trait_counts
+        .prefix(trait_type.as_str())
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(TraitCountsResponse { counts })
+}
+
+pub fn query_nft_info<TNftExtension>(
+    deps: Deps,
+    env: &Env,
     token_id: String,
 ) -> StdResult<NftInfoResponse<TNftExtension>>
 where
     TNftExtension: Cw721State,
 {
-    let info = Cw721Config::<TNftExtension>::default()
-        .nft_info
-        .load(storage, &token_id)?;
+    let config = Cw721Config::<TNftExtension>::default();
+    let info = config.nft_info.load(deps.storage, &token_id)?;
+    let base_token_uri = config.base_token_uri.may_load(deps.storage)?;
+    let expired = info.is_expired(&env.block);
     Ok(NftInfoResponse {
-        token_uri: info.token_uri,
+        token_uri: resolve_token_uri(info.token_uri, base_token_uri.as_deref(), &token_id),
         extension: info.extension,
+        ibc_data: info.ibc_data,
+        minter: info.minter.map(Addr::into_string),
+        expired,
     })
 }
 
+pub fn query_resolved_nft_info<TNftExtension>(
+    deps: Deps,
+    env: &Env,
+    token_id: String,
+) -> StdResult<NftInfoResponse<TNftExtension>>
+where
+    TNftExtension: Cw721State,
+{
+    let mut info = query_nft_info::<TNftExtension>(deps, env, token_id)?;
+    let ipfs_gateway = Cw721Config::<TNftExtension>::default()
+        .ipfs_gateway
+        .may_load(deps.storage)?;
+    if let Some(gateway) = ipfs_gateway {
+        info.token_uri = info
+            .token_uri
+            .as_deref()
+            .map(|url| resolve_ipfs_url(url, &gateway));
+        info.extension.resolve_ipfs(&gateway);
+    }
+    Ok(info)
+}
+
 pub fn query_nft_by_extension<TNftExtension>(
-    storage: &dyn Storage,
+    deps: Deps,
+    env: &Env,
     extension: TNftExtension,
     start_after: Option<String>,
     limit: Option<u32>,
@@ -182,14 +271,16 @@ where
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
     let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
 
-    let nfts: Vec<Option<NftInfo<TNftExtension>>> = Cw721Config::<TNftExtension>::default()
+    let config = Cw721Config::<TNftExtension>::default();
+    let base_token_uri = config.base_token_uri.may_load(deps.storage)?;
+    let nfts: Vec<Option<(String, NftInfo<TNftExtension>)>> = config
         .nft_info
-        .range(storage, start, None, Order::Ascending)
+        .range(deps.storage, start, None, Order::Ascending)
         .take(limit)
         .map(|kv| {
-            let nft = kv?.1;
+            let (token_id, nft) = kv?;
             let result = if nft.extension.contains(&extension) {
-                Some(nft)
+                Some((token_id, nft))
             } else {
                 None
             };
@@ -197,11 +288,14 @@ where
         })
         .collect::<StdResult<_>>()?;
     let filtered = nfts
-        .iter()
-        .filter_map(|n| n.clone())
-        .map(|n| NftInfoResponse {
-            token_uri: n.token_uri,
+        .into_iter()
+        .flatten()
+        .map(|(token_id, n)| NftInfoResponse {
+            expired: n.is_expired(&env.block),
+            token_uri: resolve_token_uri(n.token_uri, base_token_uri.as_deref(), &token_id),
             extension: n.extension,
+            ibc_data: n.ibc_data,
+            minter: n.minter.map(Addr::into_string),
         })
         .collect::<Vec<NftInfoResponse<TNftExtension>>>();
     if filtered.is_empty() {
@@ -223,6 +317,105 @@ pub fn query_owner_of(
     Ok(OwnerOfResponse {
         owner: nft_info.owner.to_string(),
         approvals: humanize_approvals(&env.block, &nft_info, include_expired_approval),
+        expired: nft_info.is_expired(&env.block),
+    })
+}
+
+/// owner, lock state and live approvals of a token
+pub fn query_token_state(deps: Deps, env: &Env, token_id: String) -> StdResult<TokenStateResponse> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    let nft_info = config.nft_info.load(deps.storage, &token_id)?;
+    let timelocked_until = nft_info
+        .transferable_after
+        .filter(|_| nft_info.is_timelocked(&env.block))
+        .map(Expiration::AtTime);
+    let swap_locked_until = match config.swap_locks.may_load(deps.storage, &token_id)? {
+        Some(offer_id) => config
+            .swap_offers
+            .may_load(deps.storage, offer_id)?
+            .filter(|offer| !offer.is_expired(&env.block))
+            .map(|offer| offer.expires),
+        None => None,
+    };
+    let locked_until = timelocked_until.or(swap_locked_until);
+    Ok(TokenStateResponse {
+        owner: nft_info.owner.to_string(),
+        locked: locked_until.is_some(),
+        locked_until,
+        approvals: humanize_approvals(&env.block, &nft_info, false),
+    })
+}
+
+/// owner of a token at the beginning of the given block height
+pub fn query_owner_of_at(
+    deps: Deps,
+    token_id: String,
+    height: u64,
+) -> StdResult<OwnerOfAtResponse> {
+    let owner = Cw721Config::<Option<Empty>>::default()
+        .owner_history
+        .may_load_at_height(deps.storage, &token_id, height)?;
+    Ok(OwnerOfAtResponse {
+        owner: owner.map(|a| a.to_string()),
+    })
+}
+
+/// whether sender is allowed to transfer a token, using the same checks as `transfer_nft`
+pub fn query_can_transfer(
+    deps: Deps,
+    env: &Env,
+    token_id: String,
+    sender: String,
+) -> StdResult<CanTransferResponse> {
+    let sender = deps.api.addr_validate(&sender)?;
+    let token = Cw721Config::<Option<Empty>>::default()
+        .nft_info
+        .load(deps.storage, &token_id)?;
+    Ok(
+        match check_can_transfer(deps, env, sender.as_str(), &token_id, &token) {
+            Ok(()) => CanTransferResponse {
+                allowed: true,
+                reason: None,
+            },
+            Err(err) => CanTransferResponse {
+                allowed: false,
+                reason: Some(err.to_string()),
+            },
+        },
+    )
+}
+
+/// whether a mint would succeed, using the same checks as `mint`
+pub fn query_validate_mint<TNftExtension, TNftExtensionMsg>(
+    deps: Deps,
+    env: &Env,
+    sender: String,
+    msg: MintMsg<TNftExtensionMsg>,
+    funds: Vec<Coin>,
+) -> StdResult<ValidateMintResponse>
+where
+    TNftExtension: Cw721State,
+    TNftExtensionMsg: Cw721CustomMsg + StateFactory<TNftExtension>,
+{
+    let info = MessageInfo {
+        sender: deps.api.addr_validate(&sender)?,
+        funds,
+    };
+    let errors = match check_mint::<TNftExtension, TNftExtensionMsg>(
+        deps,
+        env,
+        &info,
+        &msg.token_id,
+        msg.owner,
+        msg.token_uri,
+        msg.extension,
+    ) {
+        Ok(_) => vec![],
+        Err(errors) => errors.iter().map(ToString::to_string).collect(),
+    };
+    Ok(ValidateMintResponse {
+        valid: errors.is_empty(),
+        errors,
     })
 }
 
@@ -347,15 +540,128 @@ pub fn query_approvals(
     Ok(ApprovalsResponse { approvals })
 }
 
+pub fn query_all_approvals(
+    deps: Deps,
+    env: &Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    include_expired_approval: bool,
+) -> StdResult<AllApprovalsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+
+    // limit applies to tokens scanned, not tokens returned
+    let tokens = Cw721Config::<Option<Empty>>::default()
+        .nft_info
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    let last_token_id = match tokens.last() {
+        Some((token_id, _)) if tokens.len() == limit => Some(token_id.clone()),
+        _ => None,
+    };
+    let approvals = tokens
+        .into_iter()
+        .filter_map(|(token_id, token)| {
+            let approvals: Vec<_> = token
+                .approvals
+                .into_iter()
+                .filter(|a| include_expired_approval || !a.is_expired(&env.block))
+                .collect();
+            (!approvals.is_empty()).then_some(TokenApprovals {
+                token_id,
+                approvals,
+            })
+        })
+        .collect();
+
+    Ok(AllApprovalsResponse {
+        approvals,
+        last_token_id,
+    })
+}
+
+pub fn query_operable_tokens(
+    deps: Deps,
+    env: &Env,
+    operator: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<OperableTokensResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+    let operator_addr = deps.api.addr_validate(&operator)?;
+
+    // limit applies to tokens scanned, not tokens returned
+    let config = Cw721Config::<Option<Empty>>::default();
+    let scanned = config
+        .nft_info
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    let last_token_id = match scanned.last() {
+        Some((token_id, _)) if scanned.len() == limit => Some(token_id.clone()),
+        _ => None,
+    };
+
+    // live approve all of an owner for operator, loaded once per owner
+    let mut approved_owners: BTreeMap<Addr, bool> = BTreeMap::new();
+    let mut tokens = vec![];
+    for (token_id, token) in scanned {
+        let approved = token
+            .approvals
+            .iter()
+            .any(|a| a.spender == operator_addr && !a.is_expired(&env.block));
+        let approved = approved
+            || match approved_owners.get(&token.owner) {
+                Some(approved) => *approved,
+                None => {
+                    let approved = config
+                        .operators
+                        .may_load(deps.storage, (&token.owner, &operator_addr))?
+                        .map_or(false, |expires| !expires.is_expired(&env.block));
+                    approved_owners.insert(token.owner, approved);
+                    approved
+                }
+            };
+        if approved {
+            tokens.push(token_id);
+        }
+    }
+
+    Ok(OperableTokensResponse {
+        tokens,
+        last_token_id,
+    })
+}
+
+/// Start of a token page: `cursor` (if given) takes precedence over `start_after`.
+fn tokens_start(start_after: Option<String>, cursor: Option<Binary>) -> Option<Vec<u8>> {
+    cursor
+        .map(|cursor| cursor.to_vec())
+        .or_else(|| start_after.map(|s| s.into_bytes()))
+}
+
+/// Opaque cursor of the next page, the storage key of the last token. `None` on the last page.
+fn tokens_next_cursor(tokens: &[String], limit: usize) -> Option<Binary> {
+    if tokens.len() < limit {
+        return None;
+    }
+    tokens
+        .last()
+        .map(|token_id| Binary::from(token_id.as_bytes()))
+}
+
 pub fn query_tokens(
     deps: Deps,
     _env: &Env,
     owner: String,
     start_after: Option<String>,
     limit: Option<u32>,
+    cursor: Option<Binary>,
 ) -> StdResult<TokensResponse> {
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+    let start = tokens_start(start_after, cursor).map(Bound::ExclusiveRaw);
 
     let owner_addr = deps.api.addr_validate(&owner)?;
     let tokens: Vec<String> = Cw721Config::<Option<Empty>>::default()
@@ -367,7 +673,11 @@ pub fn query_tokens(
         .take(limit)
         .collect::<StdResult<Vec<_>>>()?;
 
-    Ok(TokensResponse { tokens })
+    let next_cursor = tokens_next_cursor(&tokens, limit);
+    Ok(TokensResponse {
+        tokens,
+        next_cursor,
+    })
 }
 
 pub fn query_all_tokens(
@@ -375,9 +685,10 @@ pub fn query_all_tokens(
     _env: &Env,
     start_after: Option<String>,
     limit: Option<u32>,
+    cursor: Option<Binary>,
 ) -> StdResult<TokensResponse> {
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+    let start = tokens_start(start_after, cursor).map(Bound::ExclusiveRaw);
 
     let tokens = Cw721Config::<Option<Empty>>::default()
         .nft_info
@@ -386,7 +697,29 @@ pub fn query_all_tokens(
         .map(|item| item.map(|(k, _)| k))
         .collect::<StdResult<Vec<_>>>()?;
 
-    Ok(TokensResponse { tokens })
+    let next_cursor = tokens_next_cursor(&tokens, limit);
+    Ok(TokensResponse {
+        tokens,
+        next_cursor,
+    })
+}
+
+pub fn query_token_by_index(deps: Deps, index: u64) -> StdResult<TokenByIndexResponse> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    if config
+        .token_index_backfill
+        .may_load(deps.storage)?
+        .is_some()
+    {
+        return Err(StdError::generic_err(
+            "Token index unavailable until BackfillTokenIndex migration is complete",
+        ));
+    }
+    if index >= config.token_count(deps.storage)? {
+        return Err(StdError::not_found("Token index"));
+    }
+    let token_id = config.token_by_index.load(deps.storage, index)?;
+    Ok(TokenByIndexResponse { token_id })
 }
 
 pub fn query_all_nft_info<TNftExtension>(
@@ -398,17 +731,22 @@ pub fn query_all_nft_info<TNftExtension>(
 where
     TNftExtension: Cw721State,
 {
-    let nft_info = Cw721Config::<TNftExtension>::default()
-        .nft_info
-        .load(deps.storage, &token_id)?;
+    let config = Cw721Config::<TNftExtension>::default();
+    let nft_info = config.nft_info.load(deps.storage, &token_id)?;
+    let base_token_uri = config.base_token_uri.may_load(deps.storage)?;
+    let expired = nft_info.is_expired(&env.block);
     Ok(AllNftInfoResponse {
         access: OwnerOfResponse {
             owner: nft_info.owner.to_string(),
             approvals: humanize_approvals(&env.block, &nft_info, include_expired_approval),
+            expired,
         },
         info: NftInfoResponse {
-            token_uri: nft_info.token_uri,
+            token_uri: resolve_token_uri(nft_info.token_uri, base_token_uri.as_deref(), &token_id),
             extension: nft_info.extension,
+            ibc_data: nft_info.ibc_data,
+            minter: nft_info.minter.map(Addr::into_string),
+            expired,
         },
     })
 }
@@ -419,18 +757,199 @@ pub fn query_withdraw_address(deps: Deps) -> StdResult<Option<String>> {
         .may_load(deps.storage)
 }
 
-impl<'a> Cw721Query<DefaultOptionalNftExtension, DefaultOptionalCollectionExtension, Empty>
-    for Cw721OnchainExtensions<'a>
+pub fn query_is_uri_used(storage: &dyn Storage, token_uri: String) -> StdResult<bool> {
+    Ok(Cw721Config::<Option<Empty>>::default()
+        .token_uris
+        .has(storage, &token_uri))
+}
+
+/// All native balances of the contract: fees and mint payments are never held on behalf of
+/// others, so all of it can be withdrawn.
+pub fn query_withdrawable_balance(deps: Deps, env: &Env) -> StdResult<Vec<Coin>> {
+    deps.querier.query_all_balances(&env.contract.address)
+}
+
+/// Resolves royalty recipient by precedence: royalty payment address, withdraw address, creator.
+/// Royalty split is ignored, see `query_royalty_info` for the split recipients.
+pub fn query_royalty_recipient(deps: Deps) -> StdResult<Option<String>> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    if let Some(royalty_info) = config.royalty_info(deps.storage)? {
+        return Ok(Some(royalty_info.payment_address.to_string()));
+    }
+    if let Some(withdraw_address) = config.withdraw_address.may_load(deps.storage)? {
+        return Ok(Some(withdraw_address));
+    }
+    Ok(CREATOR
+        .get_ownership(deps.storage)?
+        .owner
+        .map(|owner| owner.to_string()))
+}
+
+pub fn query_metadata_schema_hash(deps: Deps) -> StdResult<Option<String>> {
+    Cw721Config::<Option<Empty>>::default()
+        .metadata_schema_hash
+        .may_load(deps.storage)
+}
+
+pub fn query_swap_offer(deps: Deps, offer_id: u64) -> StdResult<SwapOffer> {
+    Cw721Config::<Option<Empty>>::default()
+        .swap_offers
+        .load(deps.storage, offer_id)
+}
+
+pub fn query_accepted_denoms(deps: Deps) -> StdResult<Vec<String>> {
+    Ok(Cw721Config::<Option<Empty>>::default()
+        .accepted_denoms
+        .may_load(deps.storage)?
+        .unwrap_or_default())
+}
+
+pub fn query_voucher_signer(deps: Deps) -> StdResult<Option<Binary>> {
+    Cw721Config::<Option<Empty>>::default()
+        .voucher_signer
+        .may_load(deps.storage)
+}
+
+pub fn query_burn_fee(deps: Deps) -> StdResult<Option<Coin>> {
+    Cw721Config::<Option<Empty>>::default()
+        .burn_fee
+        .may_load(deps.storage)
+}
+
+pub fn query_max_royalty_bps(deps: Deps) -> StdResult<Option<u16>> {
+    Cw721Config::<Option<Empty>>::default()
+        .max_royalty_bps
+        .may_load(deps.storage)
+}
+
+pub fn query_token_minter(deps: Deps, token_id: String) -> StdResult<Option<String>> {
+    let token = Cw721Config::<Option<Empty>>::default()
+        .nft_info
+        .load(deps.storage, &token_id)?;
+    // tokens minted before their minter was recorded fall back to the collection minter
+    let minter = match token.minter {
+        Some(minter) => Some(minter),
+        None => MINTER.get_ownership(deps.storage)?.owner,
+    };
+    Ok(minter.map(Addr::into_string))
+}
+
+pub fn query_default_approval_expiry(deps: Deps) -> StdResult<Option<Duration>> {
+    Cw721Config::<Option<Empty>>::default()
+        .default_approval_expiry
+        .may_load(deps.storage)
+}
+
+pub fn query_transfer_fee(deps: Deps) -> StdResult<Option<Coin>> {
+    Cw721Config::<Option<Empty>>::default()
+        .transfer_fee
+        .may_load(deps.storage)
+}
+
+pub fn query_transfer_cooldown(
+    deps: Deps,
+    env: &Env,
+    token_id: String,
+) -> StdResult<TransferCooldownResponse> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    let transfer_cooldown = config.transfer_cooldown.may_load(deps.storage)?;
+    let ready_at = match transfer_cooldown {
+        Some(transfer_cooldown) => config
+            .last_transfer_at
+            .may_load(deps.storage, &token_id)?
+            .map(|last_transfer_at| last_transfer_at.plus_seconds(transfer_cooldown))
+            .filter(|ready_at| env.block.time < *ready_at),
+        None => None,
+    };
+    Ok(TransferCooldownResponse {
+        transfer_cooldown,
+        ready_at,
+    })
+}
+
+/// Royalty owed for a sale of `token_id` at `sale_price`, token royalty taking precedence.
+pub fn query_royalty_info(
+    deps: Deps,
+    token_id: String,
+    sale_price: Uint128,
+) -> StdResult<RoyaltyAmountResponse> {
+    let config = Cw721Config::<Option<Empty>>::default();
+    // make sure token exists
+    config.nft_info.load(deps.storage, &token_id)?;
+    let payments: Vec<RoyaltyPayment> = config
+        .royalty_payments(deps.storage, &token_id, sale_price)?
+        .into_iter()
+        .map(|(address, amount)| RoyaltyPayment {
+            address: address.to_string(),
+            amount,
+        })
+        .collect();
+    // a split royalty has no single recipient
+    let address = match payments.as_slice() {
+        [payment] => Some(payment.address.clone()),
+        _ => None,
+    };
+    Ok(RoyaltyAmountResponse {
+        address,
+        royalty_amount: payments.iter().map(|payment| payment.amount).sum(),
+        payments,
+    })
+}
+
+/// Collection royalty and royalty overrides of tokens after `start_after`.
+pub fn query_royalty_summary(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<RoyaltySummaryResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+
+    let config = Cw721Config::<Option<Empty>>::default();
+    let token_royalties = config
+        .token_royalties
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(token_id, royalty_info)| TokenRoyalty {
+                token_id,
+                royalty_info: royalty_info.into(),
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(RoyaltySummaryResponse {
+        royalty_info: config.royalty_info(deps.storage)?.map(Into::into),
+        token_royalties,
+    })
+}
+
+impl<'a>
+    Cw721Query<
+        DefaultOptionalNftExtension,
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtension,
+        Empty,
+    > for Cw721OnchainExtensions<'a>
 {
 }
 
-impl<'a> Cw721Query<EmptyOptionalNftExtension, DefaultOptionalCollectionExtension, Empty>
-    for Cw721BaseExtensions<'a>
+impl<'a>
+    Cw721Query<
+        EmptyOptionalNftExtension,
+        EmptyOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtension,
+        Empty,
+    > for Cw721BaseExtensions<'a>
 {
 }
 
-impl<'a> Cw721Query<EmptyOptionalNftExtension, EmptyOptionalCollectionExtension, Empty>
-    for Cw721EmptyExtensions<'a>
+impl<'a>
+    Cw721Query<
+        EmptyOptionalNftExtension,
+        EmptyOptionalNftExtensionMsg,
+        EmptyOptionalCollectionExtension,
+        Empty,
+    > for Cw721EmptyExtensions<'a>
 {
 }
 
@@ -443,7 +962,7 @@ impl<
         TExtensionMsg,
         TExtensionQueryMsg,
         TCustomResponseMsg,
-    > Cw721Query<TNftExtension, TCollectionExtension, TExtensionQueryMsg>
+    > Cw721Query<TNftExtension, TNftExtensionMsg, TCollectionExtension, TExtensionQueryMsg>
     for Cw721Extensions<
         'a,
         TNftExtension,
@@ -456,7 +975,7 @@ impl<
     >
 where
     TNftExtension: Cw721State + Contains,
-    TNftExtensionMsg: Cw721CustomMsg,
+    TNftExtensionMsg: Cw721CustomMsg + StateFactory<TNftExtension>,
     TCollectionExtension: Cw721State + FromAttributesState,
     TCollectionExtensionMsg: Cw721CustomMsg,
     TExtensionQueryMsg: Cw721CustomMsg,
@@ -1,14 +1,17 @@
+use std::collections::BTreeSet;
+
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    from_json, to_json_binary, Addr, Binary, BlockInfo, Decimal, Deps, Empty, Env, MessageInfo,
-    StdResult, Storage, Timestamp,
+    from_json, to_json_binary, Addr, Binary, BlockInfo, Coin, Decimal, Deps, Empty, Env,
+    MessageInfo, StdResult, Storage, Timestamp, Uint128,
 };
 use cw_ownable::{OwnershipStore, OWNERSHIP};
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
-use cw_utils::Expiration;
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, SnapshotMap, Strategy};
+use cw_utils::{Duration, Expiration};
 use serde::de::DeserializeOwned;
 
 use crate::error::Cw721ContractError;
+use crate::helpers::resolve_ipfs_url;
 use crate::traits::{Contains, Cw721CustomMsg, Cw721State, FromAttributesState, ToAttributesState};
 use crate::{traits::StateFactory, NftExtensionMsg};
 
@@ -26,12 +29,20 @@ pub const MINTER: OwnershipStore = OwnershipStore::new("collection_minter");
 // Custom contracts may also provide different collection extension.
 // Please also note, each element in the collection extension is stored as a separate `Attribute`.
 
+/// Maximum length of the collection name.
+pub const MAX_COLLECTION_NAME_LENGTH: u32 = 128;
+/// Maximum length of the collection symbol.
+pub const MAX_COLLECTION_SYMBOL_LENGTH: u32 = 16;
 /// Maximum length of the description field in the collection info.
 pub const MAX_COLLECTION_DESCRIPTION_LENGTH: u32 = 512;
 /// Max increase/decrease of of royalty share percentage.
 pub const MAX_ROYALTY_SHARE_DELTA_PCT: u64 = 2;
 /// Max royalty share percentage.
 pub const MAX_ROYALTY_SHARE_PCT: u64 = 10;
+/// Length of hex-encoded metadata schema hash (SHA-256).
+pub const METADATA_SCHEMA_HASH_LENGTH: usize = 64;
+/// Max number of the previous owner's tokens checked for expired approvals on each transfer.
+pub const MAX_APPROVAL_SWEEP: usize = 10;
 // ----------------------
 pub const ATTRIBUTE_DESCRIPTION: &str = "description";
 pub const ATTRIBUTE_IMAGE: &str = "image";
@@ -57,6 +68,83 @@ pub struct Cw721Config<
     pub operators: Map<'a, (&'a Addr, &'a Addr), Expiration>,
     pub nft_info: IndexedMap<'a, &'a str, NftInfo<TNftExtension>, TokenIndexes<'a, TNftExtension>>,
     pub withdraw_address: Item<'a, String>,
+    /// Hex-encoded hash of the JSON schema NFT metadata should conform to.
+    /// NOTE: informational only for off-chain validators, metadata is not validated on-chain!
+    pub metadata_schema_hash: Item<'a, String>,
+    /// Fee to be paid for burning a token, forwarded to withdraw address (if set).
+    pub burn_fee: Item<'a, Coin>,
+    /// Fee to be paid for each token transfer, forwarded to withdraw address (if set).
+    pub transfer_fee: Item<'a, Coin>,
+    /// Owner of each token, snapshotted on every block it changes (mint, transfer, burn).
+    /// NOTE: history is not pruned, each ownership change adds one changelog entry.
+    /// Tokens minted before this store was introduced have no history until their next transfer.
+    pub owner_history: SnapshotMap<'a, &'a str, Addr>,
+    /// Number of tokens per (trait_type, value), maintained on mint, burn and NFT info update.
+    /// NOTE: tokens minted before this store was introduced are not counted.
+    pub trait_counts: Map<'a, (&'a str, &'a str), u64>,
+    /// Pending swap offers by offer id.
+    pub swap_offers: Map<'a, u64, SwapOffer>,
+    /// Last used swap offer id.
+    pub swap_offer_count: Item<'a, u64>,
+    /// Tokens locked by a pending swap offer, stored as token id -> offer id.
+    pub swap_locks: Map<'a, &'a str, u64>,
+    /// Who besides owner and approved spenders may burn tokens. Defaults to `BurnPolicy::OwnerOnly` if not set.
+    pub burn_policy: Item<'a, BurnPolicy>,
+    /// Denoms accepted for any fee payment. If not set, all denoms are accepted.
+    pub accepted_denoms: Item<'a, Vec<String>>,
+    /// HTTP gateway used for resolving `ipfs://` URLs in `ResolvedNftInfo` query.
+    pub ipfs_gateway: Item<'a, String>,
+    /// secp256k1 public key lazy mint vouchers must be signed with.
+    pub voucher_signer: Item<'a, Binary>,
+    /// Hashes of redeemed vouchers, preventing double redemption.
+    pub redeemed_vouchers: Map<'a, &'a [u8], Empty>,
+    /// Last token id assigned by `MintAuto`.
+    pub auto_token_id: Item<'a, u64>,
+    /// Price anyone but the minter pays for minting, if `public_minting` is enabled.
+    pub mint_price: Item<'a, Coin>,
+    /// Whether anyone may mint by paying `mint_price`. Defaults to minter-only minting.
+    pub public_minting: Item<'a, bool>,
+    /// Number of tokens ever minted. If not set (e.g. before migration), it is derived from
+    /// `num_tokens` and `total_burned`.
+    pub total_minted: Item<'a, u64>,
+    /// Number of tokens ever burned.
+    pub total_burned: Item<'a, u64>,
+    /// Royalty share cap in basis points, on top of `MAX_ROYALTY_SHARE_PCT`. Can only be lowered once set.
+    pub max_royalty_bps: Item<'a, u16>,
+    /// SHA-256 commitment of the metadata assignment, see `RevealCommit`. Removed once revealed.
+    pub commit_hash: Item<'a, Binary>,
+    /// Whether burned token ids are recorded in `burned_ids`, preventing their reuse.
+    pub track_burned_ids: Item<'a, bool>,
+    /// Ids of burned tokens, if `track_burned_ids` is enabled.
+    pub burned_ids: Map<'a, &'a str, Empty>,
+    /// Minimum time in seconds between transfers of the same token, see `SetTransferCooldown`.
+    pub transfer_cooldown: Item<'a, u64>,
+    /// Time of last transfer per token, only recorded while `transfer_cooldown` is set.
+    pub last_transfer_at: Map<'a, &'a str, Timestamp>,
+    /// Per-token royalty overrides, taking precedence over the collection royalty, see `SetTokenRoyalty`.
+    pub token_royalties: Map<'a, &'a str, RoyaltyInfo>,
+    /// Prefix for token URIs of tokens minted without `token_uri`, see `helpers::resolve_token_uri`.
+    pub base_token_uri: Item<'a, String>,
+    /// Enables `ForceTransferAll`, set on instantiation only.
+    pub allow_force_transfer: Item<'a, bool>,
+    /// Token ids by contiguous position `0..num_tokens`, see `TokenByIndex`.
+    pub token_by_index: Map<'a, u64, String>,
+    /// Reverse of `token_by_index`, stored as token id -> position.
+    pub token_indexes: Map<'a, &'a str, u64>,
+    /// Expiration applied to `ApproveAll` without `expires`, see `SetDefaultApprovalExpiry`.
+    pub default_approval_expiry: Item<'a, Duration>,
+    /// Recipients the collection royalty is split among, see `SetRoyaltySplit`.
+    pub royalty_splits: Item<'a, Vec<RoyaltySplit>>,
+    /// Contract-level metadata URI (OpenSea `contractURI`, ics721 `classUri`), stored apart from collection extension.
+    pub collection_metadata_uri: Item<'a, String>,
+    /// Allows minting tokens to the contract itself, see `Cw721InstantiateMsg::allow_mint_to_contract`.
+    pub allow_mint_to_contract: Item<'a, bool>,
+    /// Rejects mints and updates reusing a token uri, see `Cw721InstantiateMsg::unique_token_uris`.
+    pub unique_token_uris: Item<'a, bool>,
+    /// Token uri -> token id, only maintained if `unique_token_uris` is set.
+    pub token_uris: Map<'a, &'a str, String>,
+    /// Set while the token index is (re)built, see `Cw721MigrateMsg::BackfillTokenIndex`.
+    pub token_index_backfill: Item<'a, TokenIndexBackfill>,
 }
 
 impl<TNftExtension> Default for Cw721Config<'static, TNftExtension>
@@ -73,6 +161,44 @@ where
             "tokens",
             "tokens__owner",
             "withdraw_address",
+            "cw721_metadata_schema_hash",
+            "cw721_burn_fee",
+            "cw721_owner_history",
+            "cw721_owner_history__checkpoints",
+            "cw721_owner_history__changelog",
+            "cw721_trait_counts",
+            "cw721_swap_offers",
+            "cw721_swap_offer_count",
+            "cw721_swap_locks",
+            "cw721_burn_policy",
+            "cw721_accepted_denoms",
+            "cw721_ipfs_gateway",
+            "cw721_voucher_signer",
+            "cw721_redeemed_vouchers",
+            "cw721_transfer_fee",
+            "cw721_auto_token_id",
+            "cw721_mint_price",
+            "cw721_public_minting",
+            "cw721_total_minted",
+            "cw721_total_burned",
+            "cw721_max_royalty_bps",
+            "cw721_commit_hash",
+            "cw721_track_burned_ids",
+            "cw721_burned_ids",
+            "cw721_transfer_cooldown",
+            "cw721_last_transfer_at",
+            "cw721_token_royalties",
+            "cw721_base_token_uri",
+            "cw721_allow_force_transfer",
+            "cw721_token_by_index",
+            "cw721_token_indexes",
+            "cw721_default_approval_expiry",
+            "cw721_royalty_splits",
+            "cw721_collection_metadata_uri",
+            "cw721_allow_mint_to_contract",
+            "cw721_unique_token_uris",
+            "cw721_token_uris",
+            "cw721_token_index_backfill",
         )
     }
 }
@@ -81,6 +207,7 @@ impl<'a, TNftExtension> Cw721Config<'a, TNftExtension>
 where
     TNftExtension: Cw721State,
 {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         collection_info_key: &'a str,
         collection_info_extension_key: &'a str,
@@ -89,6 +216,44 @@ where
         nft_info_key: &'a str,
         nft_info_owner_key: &'a str,
         withdraw_address_key: &'a str,
+        metadata_schema_hash_key: &'a str,
+        burn_fee_key: &'a str,
+        owner_history_key: &'a str,
+        owner_history_checkpoints_key: &'a str,
+        owner_history_changelog_key: &'a str,
+        trait_counts_key: &'a str,
+        swap_offers_key: &'a str,
+        swap_offer_count_key: &'a str,
+        swap_locks_key: &'a str,
+        burn_policy_key: &'a str,
+        accepted_denoms_key: &'a str,
+        ipfs_gateway_key: &'a str,
+        voucher_signer_key: &'a str,
+        redeemed_vouchers_key: &'a str,
+        transfer_fee_key: &'a str,
+        auto_token_id_key: &'a str,
+        mint_price_key: &'a str,
+        public_minting_key: &'a str,
+        total_minted_key: &'a str,
+        total_burned_key: &'a str,
+        max_royalty_bps_key: &'a str,
+        commit_hash_key: &'a str,
+        track_burned_ids_key: &'a str,
+        burned_ids_key: &'a str,
+        transfer_cooldown_key: &'a str,
+        last_transfer_at_key: &'a str,
+        token_royalties_key: &'a str,
+        base_token_uri_key: &'a str,
+        allow_force_transfer_key: &'a str,
+        token_by_index_key: &'a str,
+        token_indexes_key: &'a str,
+        default_approval_expiry_key: &'a str,
+        royalty_splits_key: &'a str,
+        collection_metadata_uri_key: &'a str,
+        allow_mint_to_contract_key: &'a str,
+        unique_token_uris_key: &'a str,
+        token_uris_key: &'a str,
+        token_index_backfill_key: &'a str,
     ) -> Self {
         let indexes = TokenIndexes {
             owner: MultiIndex::new(token_owner_idx, nft_info_key, nft_info_owner_key),
@@ -99,7 +264,48 @@ where
             operators: Map::new(operator_key),
             nft_info: IndexedMap::new(nft_info_key, indexes),
             withdraw_address: Item::new(withdraw_address_key),
+            metadata_schema_hash: Item::new(metadata_schema_hash_key),
+            burn_fee: Item::new(burn_fee_key),
+            transfer_fee: Item::new(transfer_fee_key),
             collection_extension: Map::new(collection_info_extension_key),
+            owner_history: SnapshotMap::new(
+                owner_history_key,
+                owner_history_checkpoints_key,
+                owner_history_changelog_key,
+                Strategy::EveryBlock,
+            ),
+            trait_counts: Map::new(trait_counts_key),
+            swap_offers: Map::new(swap_offers_key),
+            swap_offer_count: Item::new(swap_offer_count_key),
+            swap_locks: Map::new(swap_locks_key),
+            burn_policy: Item::new(burn_policy_key),
+            accepted_denoms: Item::new(accepted_denoms_key),
+            ipfs_gateway: Item::new(ipfs_gateway_key),
+            voucher_signer: Item::new(voucher_signer_key),
+            redeemed_vouchers: Map::new(redeemed_vouchers_key),
+            auto_token_id: Item::new(auto_token_id_key),
+            mint_price: Item::new(mint_price_key),
+            public_minting: Item::new(public_minting_key),
+            total_minted: Item::new(total_minted_key),
+            total_burned: Item::new(total_burned_key),
+            max_royalty_bps: Item::new(max_royalty_bps_key),
+            commit_hash: Item::new(commit_hash_key),
+            track_burned_ids: Item::new(track_burned_ids_key),
+            burned_ids: Map::new(burned_ids_key),
+            transfer_cooldown: Item::new(transfer_cooldown_key),
+            last_transfer_at: Map::new(last_transfer_at_key),
+            token_royalties: Map::new(token_royalties_key),
+            base_token_uri: Item::new(base_token_uri_key),
+            allow_force_transfer: Item::new(allow_force_transfer_key),
+            token_by_index: Map::new(token_by_index_key),
+            token_indexes: Map::new(token_indexes_key),
+            default_approval_expiry: Item::new(default_approval_expiry_key),
+            royalty_splits: Item::new(royalty_splits_key),
+            collection_metadata_uri: Item::new(collection_metadata_uri_key),
+            allow_mint_to_contract: Item::new(allow_mint_to_contract_key),
+            unique_token_uris: Item::new(unique_token_uris_key),
+            token_uris: Map::new(token_uris_key),
+            token_index_backfill: Item::new(token_index_backfill_key),
         }
     }
 
@@ -107,17 +313,245 @@ where
         Ok(self.num_tokens.may_load(storage)?.unwrap_or_default())
     }
 
+    /// Royalty info of the default collection extension, if set.
+    pub fn royalty_info(&self, storage: &dyn Storage) -> StdResult<Option<RoyaltyInfo>> {
+        Ok(self
+            .collection_extension
+            .may_load(storage, ATTRIBUTE_ROYALTY_INFO.to_string())?
+            .map(|attr| attr.value::<Option<RoyaltyInfo>>())
+            .transpose()?
+            .flatten())
+    }
+
+    /// Royalty info applying to `token_id`: the token override if set, else the collection royalty.
+    pub fn token_royalty_info(
+        &self,
+        storage: &dyn Storage,
+        token_id: &str,
+    ) -> StdResult<Option<RoyaltyInfo>> {
+        match self.token_royalties.may_load(storage, token_id)? {
+            Some(royalty_info) => Ok(Some(royalty_info)),
+            None => self.royalty_info(storage),
+        }
+    }
+
+    /// Royalty owed per recipient for a sale of `token_id` at `sale_price`. Token royalty is paid to
+    /// its payment address. Collection royalty is split among `royalty_splits` (if set), rounding
+    /// remainder and any share not covered by the splits go to the first recipient.
+    /// Empty if no royalty applies.
+    pub fn royalty_payments(
+        &self,
+        storage: &dyn Storage,
+        token_id: &str,
+        sale_price: Uint128,
+    ) -> StdResult<Vec<(Addr, Uint128)>> {
+        if let Some(royalty_info) = self.token_royalties.may_load(storage, token_id)? {
+            return Ok(vec![(
+                royalty_info.payment_address,
+                sale_price * royalty_info.share,
+            )]);
+        }
+        let royalty_info = match self.royalty_info(storage)? {
+            Some(royalty_info) => royalty_info,
+            None => return Ok(vec![]),
+        };
+        let total = sale_price * royalty_info.share;
+        let splits = self.royalty_splits.may_load(storage)?.unwrap_or_default();
+        if splits.is_empty() {
+            return Ok(vec![(royalty_info.payment_address, total)]);
+        }
+        let mut payments: Vec<(Addr, Uint128)> = splits
+            .into_iter()
+            .map(|split| (split.address, total * split.share))
+            .collect();
+        let distributed: Uint128 = payments.iter().map(|(_, amount)| amount).sum();
+        payments[0].1 += total - distributed;
+        Ok(payments)
+    }
+
+    /// Fails if `share` exceeds `max_royalty_bps` (if set).
+    pub fn assert_royalty_cap(
+        &self,
+        storage: &dyn Storage,
+        share: Decimal,
+    ) -> Result<(), Cw721ContractError> {
+        if let Some(max_royalty_bps) = self.max_royalty_bps.may_load(storage)? {
+            if share > Decimal::from_ratio(max_royalty_bps, 10_000u16) {
+                return Err(Cw721ContractError::RoyaltyExceedsCap {});
+            }
+        }
+        Ok(())
+    }
+
+    pub fn total_minted(&self, storage: &dyn Storage) -> StdResult<u64> {
+        match self.total_minted.may_load(storage)? {
+            Some(total_minted) => Ok(total_minted),
+            None => Ok(self.token_count(storage)? + self.total_burned(storage)?),
+        }
+    }
+
+    pub fn total_burned(&self, storage: &dyn Storage) -> StdResult<u64> {
+        Ok(self.total_burned.may_load(storage)?.unwrap_or_default())
+    }
+
     pub fn increment_tokens(&self, storage: &mut dyn Storage) -> StdResult<u64> {
+        let total_minted = self.total_minted(storage)? + 1;
+        self.total_minted.save(storage, &total_minted)?;
         let val = self.token_count(storage)? + 1;
         self.num_tokens.save(storage, &val)?;
         Ok(val)
     }
 
     pub fn decrement_tokens(&self, storage: &mut dyn Storage) -> StdResult<u64> {
+        let total_burned = self.total_burned(storage)? + 1;
+        self.total_burned.save(storage, &total_burned)?;
         let val = self.token_count(storage)? - 1;
         self.num_tokens.save(storage, &val)?;
         Ok(val)
     }
+
+    /// Number of tokens with a position in the token index. Equals `token_count`, unless the
+    /// index is being backfilled.
+    pub fn num_indexed_tokens(&self, storage: &dyn Storage) -> StdResult<u64> {
+        match self.token_index_backfill.may_load(storage)? {
+            Some(backfill) => Ok(backfill.num_indexed),
+            None => self.token_count(storage),
+        }
+    }
+
+    /// Position of `token_id` in the token index, if indexed. Entries left over from before a
+    /// rebuild (see `Cw721MigrateMsg::RecomputeNumTokens`) are ignored.
+    pub fn token_index_position(
+        &self,
+        storage: &dyn Storage,
+        token_id: &str,
+        num_indexed: u64,
+    ) -> StdResult<Option<u64>> {
+        let index = match self.token_indexes.may_load(storage, token_id)? {
+            Some(index) if index < num_indexed => index,
+            _ => return Ok(None),
+        };
+        let indexed_token_id = self.token_by_index.may_load(storage, index)?;
+        Ok((indexed_token_id.as_deref() == Some(token_id)).then_some(index))
+    }
+
+    /// Appends a newly minted token at the last position. Must be called after `increment_tokens`.
+    pub fn push_token_index(&self, storage: &mut dyn Storage, token_id: &str) -> StdResult<()> {
+        let index = match self.token_index_backfill.may_load(storage)? {
+            Some(mut backfill) => {
+                let index = backfill.num_indexed;
+                backfill.num_indexed += 1;
+                self.token_index_backfill.save(storage, &backfill)?;
+                index
+            }
+            None => self.token_count(storage)? - 1,
+        };
+        self.token_by_index
+            .save(storage, index, &token_id.to_string())?;
+        self.token_indexes.save(storage, token_id, &index)
+    }
+
+    /// Removes a burned token, moving the last token into its position so positions stay
+    /// contiguous. Must be called before `decrement_tokens`.
+    pub fn remove_token_index(&self, storage: &mut dyn Storage, token_id: &str) -> StdResult<()> {
+        let num_indexed = self.num_indexed_tokens(storage)?;
+        // tokens not backfilled yet have no position
+        let index = match self.token_index_position(storage, token_id, num_indexed)? {
+            Some(index) => index,
+            None => return Ok(()),
+        };
+        self.token_indexes.remove(storage, token_id);
+        let last_index = num_indexed - 1;
+        if let Some(mut backfill) = self.token_index_backfill.may_load(storage)? {
+            backfill.num_indexed = last_index;
+            self.token_index_backfill.save(storage, &backfill)?;
+        }
+        if index != last_index {
+            let last_token_id = self.token_by_index.load(storage, last_index)?;
+            self.token_by_index.save(storage, index, &last_token_id)?;
+            self.token_indexes.save(storage, &last_token_id, &index)?;
+        }
+        self.token_by_index.remove(storage, last_index);
+        Ok(())
+    }
+
+    /// Counts a token once for each distinct (trait_type, value) in `traits`.
+    pub fn increment_trait_counts(
+        &self,
+        storage: &mut dyn Storage,
+        traits: &[Trait],
+    ) -> StdResult<()> {
+        for key in distinct_traits(traits) {
+            let count = self
+                .trait_counts
+                .may_load(storage, key)?
+                .unwrap_or_default();
+            self.trait_counts.save(storage, key, &(count + 1))?;
+        }
+        Ok(())
+    }
+
+    /// Reverts `increment_trait_counts`, removing counts dropping to zero.
+    pub fn decrement_trait_counts(
+        &self,
+        storage: &mut dyn Storage,
+        traits: &[Trait],
+    ) -> StdResult<()> {
+        for key in distinct_traits(traits) {
+            match self.trait_counts.may_load(storage, key)? {
+                Some(count) if count > 1 => self.trait_counts.save(storage, key, &(count - 1))?,
+                _ => self.trait_counts.remove(storage, key),
+            }
+        }
+        Ok(())
+    }
+
+    /// Fails if unique token uris are enforced and `token_uri` is used by any token.
+    pub fn assert_token_uri_unused(
+        &self,
+        storage: &dyn Storage,
+        token_uri: Option<&str>,
+    ) -> Result<(), Cw721ContractError> {
+        if let Some(token_uri) = token_uri {
+            if self.unique_token_uris.may_load(storage)?.unwrap_or(false)
+                && self.token_uris.has(storage, token_uri)
+            {
+                return Err(Cw721ContractError::DuplicateTokenUri {});
+            }
+        }
+        Ok(())
+    }
+
+    /// Indexes `token_uri` of a token, if unique token uris are enforced.
+    /// Fails if it is used by any token, so previous uri of same token must be unindexed before.
+    pub fn index_token_uri(
+        &self,
+        storage: &mut dyn Storage,
+        token_id: &str,
+        token_uri: Option<&str>,
+    ) -> Result<(), Cw721ContractError> {
+        self.assert_token_uri_unused(storage, token_uri)?;
+        if let Some(token_uri) = token_uri {
+            if self.unique_token_uris.may_load(storage)?.unwrap_or(false) {
+                self.token_uris
+                    .save(storage, token_uri, &token_id.to_string())?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn unindex_token_uri(&self, storage: &mut dyn Storage, token_uri: Option<&str>) {
+        if let Some(token_uri) = token_uri {
+            self.token_uris.remove(storage, token_uri);
+        }
+    }
+}
+
+fn distinct_traits(traits: &[Trait]) -> BTreeSet<(&str, &str)> {
+    traits
+        .iter()
+        .map(|t| (t.trait_type.as_str(), t.value.as_str()))
+        .collect()
 }
 
 pub fn token_owner_idx<TNftExtension>(_pk: &[u8], d: &NftInfo<TNftExtension>) -> Addr {
@@ -138,6 +572,36 @@ pub struct NftInfo<TNftExtension> {
 
     /// You can add any custom metadata here when you extend cw721-base
     pub extension: TNftExtension,
+
+    /// Opaque ics721 `tokenData`, so tokens bridged back keep their origin-chain data
+    #[serde(default)]
+    pub ibc_data: Option<Binary>,
+
+    /// Address that minted the token, `None` for tokens minted before this was recorded.
+    /// `TokenMinter` query falls back to the current collection minter for these tokens.
+    #[serde(default)]
+    pub minter: Option<Addr>,
+
+    /// Set via `MintWithExpiration`. Expired tokens can't be transferred or sent anymore and
+    /// anyone may burn them via `BurnExpired`.
+    #[serde(default)]
+    pub expires: Option<Expiration>,
+
+    /// Set via `MintTimelocked`. Token can't be transferred, sent or burned before this time.
+    #[serde(default)]
+    pub transferable_after: Option<Timestamp>,
+}
+
+impl<TNftExtension> NftInfo<TNftExtension> {
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        self.expires
+            .is_some_and(|expires| expires.is_expired(block))
+    }
+
+    pub fn is_timelocked(&self, block: &BlockInfo) -> bool {
+        self.transferable_after
+            .is_some_and(|transferable_after| block.time < transferable_after)
+    }
 }
 
 #[cw_serde]
@@ -154,6 +618,46 @@ impl Approval {
     }
 }
 
+/// Progress of building the token index for existing tokens, see `Cw721MigrateMsg::BackfillTokenIndex`.
+#[cw_serde]
+pub struct TokenIndexBackfill {
+    /// Last token id visited, tokens are visited in ascending order.
+    pub start_after: Option<String>,
+    /// Number of tokens indexed so far, i.e. the next free position.
+    pub num_indexed: u64,
+}
+
+/// Offer to swap proposer's token for counterparty's token.
+#[cw_serde]
+pub struct SwapOffer {
+    pub proposer: Addr,
+    /// Token given by proposer, locked until offer is accepted, cancelled or expired
+    pub my_token: String,
+    pub counterparty: Addr,
+    /// Token wanted from counterparty
+    pub want_token: String,
+    pub expires: Expiration,
+}
+
+impl SwapOffer {
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        self.expires.is_expired(block)
+    }
+}
+
+/// Defines who is authorized to burn a token.
+#[cw_serde]
+#[derive(Default)]
+pub enum BurnPolicy {
+    /// Only owner, approved spenders and operators may burn.
+    #[default]
+    OwnerOnly,
+    /// Minter may additionally burn any token.
+    OwnerOrMinter,
+    /// Creator may additionally burn any token.
+    OwnerOrCreator,
+}
+
 pub struct TokenIndexes<'a, TNftExtension>
 where
     TNftExtension: Cw721State,
@@ -207,6 +711,13 @@ pub struct RoyaltyInfo {
 impl Cw721State for RoyaltyInfo {}
 impl Cw721CustomMsg for RoyaltyInfo {}
 
+/// Recipient of `share` of the collection royalty, see `SetRoyaltySplit`.
+#[cw_serde]
+pub struct RoyaltySplit {
+    pub address: Addr,
+    pub share: Decimal,
+}
+
 impl ToAttributesState for Empty {
     fn to_attributes_state(&self) -> Result<Vec<Attribute>, Cw721ContractError> {
         Ok(vec![])
@@ -268,7 +779,22 @@ pub struct NftExtension {
     pub youtube_url: Option<String>,
 }
 
-impl Cw721State for NftExtension {}
+impl Cw721State for NftExtension {
+    fn traits(&self) -> Vec<Trait> {
+        self.attributes.clone().unwrap_or_default()
+    }
+
+    fn resolve_ipfs(&mut self, gateway: &str) {
+        self.image = self
+            .image
+            .as_deref()
+            .map(|url| resolve_ipfs_url(url, gateway));
+        self.animation_url = self
+            .animation_url
+            .as_deref()
+            .map(|url| resolve_ipfs_url(url, gateway));
+    }
+}
 
 impl From<NftExtensionMsg> for NftExtension {
     fn from(msg: NftExtensionMsg) -> Self {
@@ -1,19 +1,28 @@
-use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info,
+};
 
 use cosmwasm_std::{
-    from_json, to_json_binary, Addr, Coin, CosmosMsg, DepsMut, Empty, Response, StdError,
-    Timestamp, WasmMsg,
+    from_json, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Empty, Env,
+    Event, Response, StdError, StdResult, Timestamp, Uint128, WasmMsg,
 };
 
 use crate::error::Cw721ContractError;
 use crate::extension::Cw721OnchainExtensions;
+use crate::helpers::{commit_hash, voucher_hash};
+use crate::msg::{
+    ApprovalResponse, CanTransferResponse, ClassMetadataResponse, Clearable,
+    CollectionExtensionMsg, MintMsg, MintVoucher, NftExtensionMsg, NftInfoResponse, NftInfoUpdate,
+    NftTransfer, OperatorResponse, OperatorsResponse, OwnerOfAtResponse, OwnerOfResponse,
+    RevealAssignment, RoyaltyAmountResponse, RoyaltyInfoResponse, RoyaltyPayment, RoyaltySplitMsg,
+    RoyaltySummaryResponse, SupplyStatsResponse, TokenApprovals, TokenRoyalty, TokenStateResponse,
+    TransferCooldownResponse, ValidateMintResponse,
+};
 use crate::msg::{
-    ApprovalResponse, CollectionExtensionMsg, NftExtensionMsg, NftInfoResponse, OperatorResponse,
-    OperatorsResponse, OwnerOfResponse, RoyaltyInfoResponse,
+    CollectionInfoMsg, Cw721ExecuteMsg, Cw721InstantiateMsg, Cw721QueryMsg, TraitCountsResponse,
 };
-use crate::msg::{CollectionInfoMsg, Cw721ExecuteMsg, Cw721InstantiateMsg, Cw721QueryMsg};
 use crate::receiver::Cw721ReceiveMsg;
-use crate::state::{NftExtension, Trait, CREATOR, MINTER};
+use crate::state::{BurnPolicy, NftExtension, SwapOffer, Trait, CREATOR, MINTER};
 use crate::{
     traits::{Cw721Execute, Cw721Query},
     Approval, DefaultOptionalCollectionExtensionMsg, DefaultOptionalNftExtension,
@@ -21,6 +30,8 @@ use crate::{
 };
 use crate::{CollectionExtension, CollectionInfoAndExtensionResponse, RoyaltyInfo};
 use cw_ownable::{Action, Ownership, OwnershipError};
+use cw_utils::Duration;
+use k256::ecdsa::{signature::hazmat::PrehashSigner, Signature, SigningKey};
 
 const MINTER_ADDR: &str = "minter";
 const CREATOR_ADDR: &str = "creator";
@@ -29,13 +40,29 @@ const SYMBOL: &str = "MGK";
 
 fn setup_contract(deps: DepsMut<'_>) -> Cw721OnchainExtensions<'static> {
     let contract = Cw721OnchainExtensions::default();
-    let msg = Cw721InstantiateMsg::<DefaultOptionalCollectionExtensionMsg> {
+    let msg = Cw721InstantiateMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+    > {
         name: CONTRACT_NAME.to_string(),
         symbol: SYMBOL.to_string(),
         collection_info_extension: None,
         minter: Some(String::from(MINTER_ADDR)),
         creator: Some(String::from(CREATOR_ADDR)),
         withdraw_address: None,
+        burn_policy: None,
+        ipfs_gateway: None,
+        mint_price: None,
+        public_minting: false,
+        max_royalty_bps: None,
+        commit_hash: None,
+        initial_mints: None,
+        track_burned_ids: false,
+        base_token_uri: None,
+        allow_force_transfer: false,
+        collection_metadata_uri: None,
+        allow_mint_to_contract: false,
+        unique_token_uris: false,
     };
     let info_creator = mock_info(CREATOR_ADDR, &[]);
     let res = contract
@@ -64,6 +91,19 @@ fn test_instantiate() {
         minter: Some(String::from(MINTER_ADDR)),
         creator: Some(String::from(CREATOR_ADDR)),
         withdraw_address: Some(String::from(CREATOR_ADDR)),
+        burn_policy: None,
+        ipfs_gateway: None,
+        mint_price: None,
+        public_minting: false,
+        max_royalty_bps: None,
+        commit_hash: None,
+        initial_mints: None,
+        track_burned_ids: false,
+        base_token_uri: None,
+        allow_force_transfer: false,
+        collection_metadata_uri: None,
+        allow_mint_to_contract: false,
+        unique_token_uris: false,
     };
     let info = mock_info("creator", &[]);
     let env = mock_env();
@@ -96,6 +136,7 @@ fn test_instantiate() {
             symbol: SYMBOL.to_string(),
             extension: None,
             updated_at: env.block.time
+            collection_metadata_uri: None,
         }
     );
 
@@ -111,7 +152,7 @@ fn test_instantiate() {
 
     // list the token_ids
     let tokens = contract
-        .query_all_tokens(deps.as_ref(), &env, None, None)
+        .query_all_tokens(deps.as_ref(), &env, None, None, None)
         .unwrap();
     assert_eq!(0, tokens.tokens.len());
 }
@@ -124,21 +165,37 @@ fn test_instantiate_with_collection_info_and_extension() {
     let collection_info_extension_msg = Some(CollectionExtensionMsg {
         description: Some("description".to_string()),
         image: Some("https://moonphases.org".to_string()),
-        explicit_content: Some(true),
-        external_link: Some("https://moonphases.org/".to_string()),
-        start_trading_time: Some(Timestamp::from_seconds(0)),
-        royalty_info: Some(RoyaltyInfoResponse {
+        explicit_content: Clearable::Set(true),
+        external_link: Clearable::Set("https://moonphases.org/".to_string()),
+        start_trading_time: Clearable::Set(Timestamp::from_seconds(0)),
+        royalty_info: Clearable::Set(RoyaltyInfoResponse {
             payment_address: "payment_address".into(),
             share: "0.1".parse().unwrap(),
         }),
     });
-    let msg = Cw721InstantiateMsg::<DefaultOptionalCollectionExtensionMsg> {
+    let msg = Cw721InstantiateMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+    > {
         name: CONTRACT_NAME.to_string(),
         symbol: SYMBOL.to_string(),
         collection_info_extension: collection_info_extension_msg,
         minter: Some(String::from(MINTER_ADDR)),
         creator: Some(String::from(CREATOR_ADDR)),
         withdraw_address: Some(String::from(CREATOR_ADDR)),
+        burn_policy: None,
+        ipfs_gateway: None,
+        mint_price: None,
+        public_minting: false,
+        max_royalty_bps: None,
+        commit_hash: None,
+        initial_mints: None,
+        track_burned_ids: false,
+        base_token_uri: None,
+        allow_force_transfer: false,
+        collection_metadata_uri: None,
+        allow_mint_to_contract: false,
+        unique_token_uris: false,
     };
     let info = mock_info("creator", &[]);
     let env = mock_env();
@@ -182,6 +239,18 @@ fn test_instantiate_with_collection_info_and_extension() {
             symbol: SYMBOL.to_string(),
             extension: collection_info_extension_expected,
             updated_at: env.block.time
+            collection_metadata_uri: None,
+        }
+    );
+
+    // ics721 class metadata is derived from collection info and extension
+    let class_metadata = contract.query_class_metadata(deps.as_ref(), &env).unwrap();
+    assert_eq!(
+        class_metadata,
+        ClassMetadataResponse {
+            class_id: env.contract.address.to_string(),
+            class_uri: Some("https://moonphases.org/".to_string()),
+            class_data: Some(to_json_binary(&info).unwrap()),
         }
     );
 
@@ -197,11 +266,85 @@ fn test_instantiate_with_collection_info_and_extension() {
 
     // list the token_ids
     let tokens = contract
-        .query_all_tokens(deps.as_ref(), &env, None, None)
+        .query_all_tokens(deps.as_ref(), &env, None, None, None)
         .unwrap();
     assert_eq!(0, tokens.tokens.len());
 }
 
+#[test]
+fn test_instantiate_with_invalid_collection_urls() {
+    let contract = Cw721OnchainExtensions::default();
+    let info = mock_info(CREATOR_ADDR, &[]);
+    let instantiate_msg = |image: &str, external_link: &str| Cw721InstantiateMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+    > {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        collection_info_extension: Some(CollectionExtensionMsg {
+            description: Some("description".to_string()),
+            image: Some(image.to_string()),
+            explicit_content: Clearable::NoChange,
+            external_link: Clearable::Set(external_link.to_string()),
+            start_trading_time: Clearable::NoChange,
+            royalty_info: Clearable::NoChange,
+        }),
+        minter: Some(String::from(MINTER_ADDR)),
+        creator: Some(String::from(CREATOR_ADDR)),
+        withdraw_address: None,
+        burn_policy: None,
+        ipfs_gateway: None,
+        mint_price: None,
+        public_minting: false,
+        max_royalty_bps: None,
+        commit_hash: None,
+        initial_mints: None,
+        track_burned_ids: false,
+        base_token_uri: None,
+        allow_force_transfer: false,
+        collection_metadata_uri: None,
+        allow_mint_to_contract: false,
+        unique_token_uris: false,
+    };
+
+    // malformed image
+    let err = contract
+        .instantiate(
+            mock_dependencies().as_mut(),
+            &mock_env(),
+            &info,
+            instantiate_msg("moonphases.org", "https://moonphases.org/"),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::ParseError(url::ParseError::RelativeUrlWithoutBase)
+    );
+
+    // malformed external link
+    let err = contract
+        .instantiate(
+            mock_dependencies().as_mut(),
+            &mock_env(),
+            &info,
+            instantiate_msg("https://moonphases.org", "moonphases.org"),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::ParseError(url::ParseError::RelativeUrlWithoutBase)
+    );
+
+    contract
+        .instantiate(
+            mock_dependencies().as_mut(),
+            &mock_env(),
+            &info,
+            instantiate_msg("https://moonphases.org", "https://moonphases.org/"),
+        )
+        .unwrap();
+}
+
 #[test]
 fn test_instantiate_with_minimal_collection_info_and_extension() {
     let mut deps = mock_dependencies();
@@ -210,18 +353,34 @@ fn test_instantiate_with_minimal_collection_info_and_extension() {
     let collection_info_extension_msg = Some(CollectionExtensionMsg {
         description: Some("description".to_string()),
         image: Some("https://moonphases.org".to_string()),
-        explicit_content: None,
-        external_link: None,
-        start_trading_time: None,
-        royalty_info: None,
+        explicit_content: Clearable::NoChange,
+        external_link: Clearable::NoChange,
+        start_trading_time: Clearable::NoChange,
+        royalty_info: Clearable::NoChange,
     });
-    let msg = Cw721InstantiateMsg::<DefaultOptionalCollectionExtensionMsg> {
+    let msg = Cw721InstantiateMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+    > {
         name: CONTRACT_NAME.to_string(),
         symbol: SYMBOL.to_string(),
         collection_info_extension: collection_info_extension_msg,
         minter: Some(String::from(MINTER_ADDR)),
         creator: Some(String::from(CREATOR_ADDR)),
         withdraw_address: Some(String::from(CREATOR_ADDR)),
+        burn_policy: None,
+        ipfs_gateway: None,
+        mint_price: None,
+        public_minting: false,
+        max_royalty_bps: None,
+        commit_hash: None,
+        initial_mints: None,
+        track_burned_ids: false,
+        base_token_uri: None,
+        allow_force_transfer: false,
+        collection_metadata_uri: None,
+        allow_mint_to_contract: false,
+        unique_token_uris: false,
     };
     let info = mock_info("creator", &[]);
     let env = mock_env();
@@ -262,10 +421,235 @@ fn test_instantiate_with_minimal_collection_info_and_extension() {
             symbol: SYMBOL.to_string(),
             extension: collection_info_extension_expected,
             updated_at: env.block.time
+            collection_metadata_uri: None,
         }
     );
 }
 
+#[test]
+fn test_instantiate_with_initial_mints() {
+    let contract = Cw721OnchainExtensions::default();
+    let env = mock_env();
+    let info = mock_info(CREATOR_ADDR, &[]);
+    let initial_mint = |token_id: &str, owner: &str, image: Option<&str>| MintMsg {
+        token_id: token_id.to_string(),
+        owner: owner.to_string(),
+        token_uri: Some(format!("https://example.com/{token_id}.json")),
+        extension: image.map(|image| NftExtensionMsg {
+            image: Some(image.to_string()),
+            ..NftExtensionMsg::default()
+        }),
+    };
+    let instantiate_msg = |initial_mints| Cw721InstantiateMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+    > {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        collection_info_extension: None,
+        minter: Some(String::from(MINTER_ADDR)),
+        creator: Some(String::from(CREATOR_ADDR)),
+        withdraw_address: None,
+        burn_policy: None,
+        ipfs_gateway: None,
+        // minter mints for free, even on public minting
+        mint_price: Some(Coin::new(100, "uark")),
+        public_minting: true,
+        max_royalty_bps: None,
+        commit_hash: None,
+        initial_mints: Some(initial_mints),
+        track_burned_ids: false,
+        base_token_uri: None,
+        allow_force_transfer: false,
+        collection_metadata_uri: None,
+        allow_mint_to_contract: false,
+        unique_token_uris: false,
+    };
+
+    // any invalid mint reverts instantiation
+    let mut deps = mock_dependencies();
+    let err = contract
+        .instantiate(
+            deps.as_mut(),
+            &env,
+            &info,
+            instantiate_msg(vec![
+                initial_mint("1", "medusa", None),
+                initial_mint("1", "venus", None),
+            ]),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Claimed {});
+    let mut deps = mock_dependencies();
+    let err = contract
+        .instantiate(
+            deps.as_mut(),
+            &env,
+            &info,
+            instantiate_msg(vec![initial_mint("1", "medusa", Some("invalid_image"))]),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::ParseError(url::ParseError::RelativeUrlWithoutBase)
+    );
+
+    let mut deps = mock_dependencies();
+    let res = contract
+        .instantiate(
+            deps.as_mut(),
+            &env,
+            &info,
+            instantiate_msg(vec![
+                initial_mint("1", "medusa", None),
+                initial_mint("2", "venus", Some("https://example.com/2.png")),
+            ]),
+        )
+        .unwrap();
+    assert_eq!(0, res.messages.len());
+    assert!(res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "initial_mints" && attr.value == "2"));
+
+    let count = contract.query_num_tokens(deps.as_ref().storage).unwrap();
+    assert_eq!(2, count.count);
+    for (token_id, owner, image) in [
+        ("1", "medusa", None),
+        ("2", "venus", Some("https://example.com/2.png")),
+    ] {
+        let owner_of = contract
+            .query_owner_of(deps.as_ref(), &env, token_id.to_string(), false)
+            .unwrap();
+        assert_eq!(owner_of.owner, owner);
+        let nft_info = contract
+            .query_nft_info(deps.as_ref(), &mock_env(), token_id.to_string())
+            .unwrap();
+        assert_eq!(
+            nft_info,
+            NftInfoResponse {
+                token_uri: Some(format!("https://example.com/{token_id}.json")),
+                extension: image.map(|image| NftExtension {
+                    image: Some(image.to_string()),
+                    ..NftExtension::default()
+                }),
+                ibc_data: None,
+                minter: Some(MINTER_ADDR.to_string()),
+                expired: false,
+            }
+        );
+    }
+}
+
+#[test]
+fn test_base_token_uri() {
+    let contract = Cw721OnchainExtensions::default();
+    let env = mock_env();
+    let info = mock_info(CREATOR_ADDR, &[]);
+    let instantiate_msg = |base_token_uri: &str| Cw721InstantiateMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+    > {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        collection_info_extension: None,
+        minter: Some(String::from(MINTER_ADDR)),
+        creator: Some(String::from(CREATOR_ADDR)),
+        withdraw_address: None,
+        burn_policy: None,
+        ipfs_gateway: None,
+        mint_price: None,
+        public_minting: false,
+        max_royalty_bps: None,
+        commit_hash: None,
+        initial_mints: None,
+        track_burned_ids: false,
+        base_token_uri: Some(base_token_uri.to_string()),
+        allow_force_transfer: false,
+        collection_metadata_uri: None,
+        allow_mint_to_contract: false,
+        unique_token_uris: false,
+    };
+
+    // invalid base
+    let mut deps = mock_dependencies();
+    let err = contract
+        .instantiate(deps.as_mut(), &env, &info, instantiate_msg("no-url"))
+        .unwrap_err();
+    assert!(matches!(err, Cw721ContractError::ParseError(_)));
+
+    let mut deps = mock_dependencies();
+    contract
+        .instantiate(
+            deps.as_mut(),
+            &env,
+            &info,
+            instantiate_msg("https://example.com/meta/"),
+        )
+        .unwrap();
+    let minter = mock_info(MINTER_ADDR, &[]);
+    for (token_id, token_uri) in [("1", None), ("2", Some("https://example.com/custom.json"))] {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: "medusa".to_string(),
+            token_uri: token_uri.map(str::to_string),
+            extension: None,
+            ibc_data: None,
+        };
+        contract
+            .execute(deps.as_mut(), &env, &minter, mint_msg)
+            .unwrap();
+    }
+
+    // without explicit value, uri is resolved from base, but not stored
+    let nft_info = contract
+        .query_nft_info(deps.as_ref(), &mock_env(), "1".to_string())
+        .unwrap();
+    assert_eq!(
+        nft_info.token_uri,
+        Some("https://example.com/meta/1".to_string())
+    );
+    let all_nft_info = contract
+        .query_all_nft_info(deps.as_ref(), &env, "1".to_string(), false)
+        .unwrap();
+    assert_eq!(all_nft_info.info, nft_info);
+    let stored = contract
+        .config
+        .nft_info
+        .load(deps.as_ref().storage, "1")
+        .unwrap();
+    assert_eq!(stored.token_uri, None);
+
+    // explicit value takes precedence
+    let nft_info = contract
+        .query_nft_info(deps.as_ref(), &mock_env(), "2".to_string())
+        .unwrap();
+    assert_eq!(
+        nft_info.token_uri,
+        Some("https://example.com/custom.json".to_string())
+    );
+    let all_nft_info = contract
+        .query_all_nft_info(deps.as_ref(), &env, "2".to_string(), false)
+        .unwrap();
+    assert_eq!(all_nft_info.info, nft_info);
+
+    // search by extension resolves the same way
+    let token_uris: Vec<Option<String>> = contract
+        .query_nft_by_extension(deps.as_ref(), &env, None, None, None)
+        .unwrap()
+        .unwrap()
+        .into_iter()
+        .map(|nft| nft.token_uri)
+        .collect();
+    assert_eq!(
+        token_uris,
+        vec![
+            Some("https://example.com/meta/1".to_string()),
+            Some("https://example.com/custom.json".to_string())
+        ]
+    );
+}
+
 #[test]
 fn test_mint() {
     let mut deps = mock_dependencies();
@@ -277,6 +661,7 @@ fn test_mint() {
         owner: String::from("medusa"),
         token_uri: Some("invalid_uri".to_string()),
         extension: None,
+        ibc_data: None,
     };
 
     // invalid token uri
@@ -298,6 +683,7 @@ fn test_mint() {
         owner: String::from("medusa"),
         token_uri: Some(token_uri.clone()),
         extension: None,
+        ibc_data: None,
     };
     let err = contract
         .execute(deps.as_mut(), &env, &info_random, mint_msg.clone())
@@ -316,18 +702,20 @@ fn test_mint() {
 
     // unknown nft returns error
     let _ = contract
-        .query_nft_info(deps.as_ref().storage, "unknown".to_string())
+        .query_nft_info(deps.as_ref(), &mock_env(), "unknown".to_string())
         .unwrap_err();
 
     // this nft info is correct
     let info = contract
-        .query_nft_info(deps.as_ref().storage, token_id1.clone())
+        .query_nft_info(deps.as_ref(), &mock_env(), token_id1.clone())
         .unwrap();
     assert_eq!(
         info,
         NftInfoResponse::<DefaultOptionalNftExtension> {
             token_uri: Some(token_uri),
             extension: None,
+            ibc_data: None,
+            minter: Some(MINTER_ADDR.to_string()),
         }
     );
 
@@ -340,6 +728,7 @@ fn test_mint() {
         OwnerOfResponse {
             owner: String::from("medusa"),
             approvals: vec![],
+            expired: false,
         }
     );
 
@@ -349,6 +738,7 @@ fn test_mint() {
         owner: String::from("hercules"),
         token_uri: None,
         extension: None,
+        ibc_data: None,
     };
 
     let allowed = mock_info(MINTER_ADDR, &[]);
@@ -359,7 +749,7 @@ fn test_mint() {
 
     // list the token_ids
     let tokens = contract
-        .query_all_tokens(deps.as_ref(), &env, None, None)
+        .query_all_tokens(deps.as_ref(), &env, None, None, None)
         .unwrap();
     assert_eq!(1, tokens.tokens.len());
     assert_eq!(vec![token_id1.clone()], tokens.tokens);
@@ -371,10 +761,21 @@ fn test_mint() {
         owner: String::from("medusa"),
         token_uri: Some("".to_string()), // empty token uri
         extension: None,
+        ibc_data: None,
     };
-    let _ = contract
+    let res = contract
         .execute(deps.as_mut(), &env, &info_minter, mint_msg)
         .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "mint")
+            .add_attribute("minter", MINTER_ADDR)
+            .add_attribute("owner", "medusa")
+            .add_attribute("token_id", "id2")
+            .add_attribute("token_uri", "empty")
+            .add_attribute("num_tokens", "2")
+    );
 
     // ensure num tokens increases
     let count = contract.query_num_tokens(deps.as_ref().storage).unwrap();
@@ -382,18 +783,20 @@ fn test_mint() {
 
     // unknown nft returns error
     let _ = contract
-        .query_nft_info(deps.as_ref().storage, "unknown".to_string())
+        .query_nft_info(deps.as_ref(), &mock_env(), "unknown".to_string())
         .unwrap_err();
 
     // this nft info is correct
     let info = contract
-        .query_nft_info(deps.as_ref().storage, token_id2.clone())
+        .query_nft_info(deps.as_ref(), &mock_env(), token_id2.clone())
         .unwrap();
     assert_eq!(
         info,
         NftInfoResponse::<DefaultOptionalNftExtension> {
             token_uri: None,
             extension: None,
+            ibc_data: None,
+            minter: Some(MINTER_ADDR.to_string()),
         }
     );
 
@@ -406,12 +809,13 @@ fn test_mint() {
         OwnerOfResponse {
             owner: String::from("medusa"),
             approvals: vec![],
+            expired: false,
         }
     );
 
     // list the token_ids
     let tokens = contract
-        .query_all_tokens(deps.as_ref(), &env, None, None)
+        .query_all_tokens(deps.as_ref(), &env, None, None, None)
         .unwrap();
     assert_eq!(2, tokens.tokens.len());
     assert_eq!(vec![token_id2.clone(), token_id1.clone()], tokens.tokens);
@@ -423,6 +827,7 @@ fn test_mint() {
         owner: String::from("medusa"),
         token_uri: None, // empty token uri
         extension: None,
+        ibc_data: None,
     };
     let _ = contract
         .execute(deps.as_mut(), &env, &info_minter, mint_msg)
@@ -434,18 +839,20 @@ fn test_mint() {
 
     // unknown nft returns error
     let _ = contract
-        .query_nft_info(deps.as_ref().storage, "unknown".to_string())
+        .query_nft_info(deps.as_ref(), &mock_env(), "unknown".to_string())
         .unwrap_err();
 
     // this nft info is correct
     let info = contract
-        .query_nft_info(deps.as_ref().storage, token_id3.clone())
+        .query_nft_info(deps.as_ref(), &mock_env(), token_id3.clone())
         .unwrap();
     assert_eq!(
         info,
         NftInfoResponse::<DefaultOptionalNftExtension> {
             token_uri: None,
             extension: None,
+            ibc_data: None,
+            minter: Some(MINTER_ADDR.to_string()),
         }
     );
 
@@ -458,1354 +865,6720 @@ fn test_mint() {
         OwnerOfResponse {
             owner: String::from("medusa"),
             approvals: vec![],
+            expired: false,
         }
     );
 
     // list the token_ids
     let tokens = contract
-        .query_all_tokens(deps.as_ref(), &env, None, None)
+        .query_all_tokens(deps.as_ref(), &env, None, None, None)
         .unwrap();
     assert_eq!(3, tokens.tokens.len());
     assert_eq!(vec![token_id2, token_id3, token_id1], tokens.tokens);
 }
 
 #[test]
-fn test_update_nft_info() {
+fn test_mint_auto() {
     let mut deps = mock_dependencies();
     let contract = setup_contract(deps.as_mut());
-
-    let token_id = "1".to_string();
-    let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
-        owner: String::from("owner"),
-        token_uri: Some("ipfs://foo.bar".to_string()),
-        extension: None,
-    };
-
-    // mint nft
-    let info_minter = mock_info(MINTER_ADDR, &[]);
     let env = mock_env();
-    contract
-        .execute(deps.as_mut(), &env, &info_minter, mint_msg)
-        .unwrap();
-
-    // minter update unknown nft info
-    let update_msg = Cw721ExecuteMsg::<
-        DefaultOptionalNftExtensionMsg,
-        DefaultOptionalCollectionExtensionMsg,
-        Empty,
-    >::UpdateNftInfo {
-        token_id: "unknown".to_string(),
-        token_uri: Some("ipfs://to.the.moon".to_string()),
+    let info_minter = mock_info(MINTER_ADDR, &[]);
+    let mint_auto_msg = Cw721ExecuteMsg::MintAuto {
+        owner: String::from("medusa"),
+        token_uri: None,
         extension: None,
     };
-    // throws NotFound error
-    contract
-        .execute(deps.as_mut(), &env, &info_minter, update_msg)
-        .unwrap_err();
 
-    // minter udpate nft info
-    let update_msg_without_extension = Cw721ExecuteMsg::<
-        DefaultOptionalNftExtensionMsg,
-        DefaultOptionalCollectionExtensionMsg,
-        Empty,
-    >::UpdateNftInfo {
-        token_id: token_id.clone(),
-        token_uri: Some("".to_string()), // sets token uri to none
-        extension: None,
-    };
+    // random cannot mint
     let err = contract
         .execute(
             deps.as_mut(),
             &env,
-            &info_minter,
-            update_msg_without_extension.clone(),
+            &mock_info("random", &[]),
+            mint_auto_msg.clone(),
         )
         .unwrap_err();
-    assert_eq!(err, Cw721ContractError::NotCreator {});
+    assert_eq!(err, Cw721ContractError::NotMinter {});
 
-    // other udpate nft metadata extension
-    let update_msg_only_extension = Cw721ExecuteMsg::<
-        DefaultOptionalNftExtensionMsg,
-        DefaultOptionalCollectionExtensionMsg,
-        Empty,
-    >::UpdateNftInfo {
-        token_id: token_id.clone(),
-        token_uri: None,
-        extension: Some(NftExtensionMsg {
-            image: Some("ipfs://foo.bar/image.png".to_string()),
-            image_data: None,
-            external_url: None,
-            description: None,
-            name: None,
-            attributes: None,
-            background_color: None,
-            animation_url: None,
-            youtube_url: None,
-        }),
-    };
-    let info_other = mock_info("other", &[]);
+    // first auto id is 1
+    let res = contract
+        .execute(deps.as_mut(), &env, &info_minter, mint_auto_msg.clone())
+        .unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "token_id" && attr.value == "1"));
+
+    // manually minted ids are skipped
+    for token_id in ["2", "3"] {
+        contract
+            .execute(
+                deps.as_mut(),
+                &env,
+                &info_minter,
+                Cw721ExecuteMsg::Mint {
+                    token_id: token_id.to_string(),
+                    owner: String::from("medusa"),
+                    token_uri: None,
+                    extension: None,
+                    ibc_data: None,
+                },
+            )
+            .unwrap();
+    }
+    let res = contract
+        .execute(deps.as_mut(), &env, &info_minter, mint_auto_msg.clone())
+        .unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "token_id" && attr.value == "4"));
+
+    // manual mint can't claim an auto id
     let err = contract
         .execute(
             deps.as_mut(),
             &env,
-            &info_other,
-            update_msg_only_extension.clone(),
+            &info_minter,
+            Cw721ExecuteMsg::Mint {
+                token_id: "4".to_string(),
+                owner: String::from("medusa"),
+                token_uri: None,
+                extension: None,
+                ibc_data: None,
+            },
         )
         .unwrap_err();
-    assert_eq!(err, Cw721ContractError::NotCreator {});
+    assert_eq!(err, Cw721ContractError::Claimed {});
 
-    // creator updates nft info
-    contract
-        .execute(
-            deps.as_mut(),
+    let tokens = contract
+        .query_all_tokens(deps.as_ref(), &env, None, None, None)
+        .unwrap();
+    assert_eq!(tokens.tokens, vec!["1", "2", "3", "4"]);
+    let count = contract.query_num_tokens(deps.as_ref().storage).unwrap();
+    assert_eq!(4, count.count);
+}
+
+#[test]
+fn test_public_minting() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721OnchainExtensions::default();
+    let env = mock_env();
+    let price = Coin::new(100, "uark");
+    contract
+        .instantiate_with_version(
+            deps.as_mut(),
             &env,
             &mock_info(CREATOR_ADDR, &[]),
-            update_msg_without_extension,
+            Cw721InstantiateMsg::<
+                DefaultOptionalNftExtensionMsg,
+                DefaultOptionalCollectionExtensionMsg,
+            > {
+                name: CONTRACT_NAME.to_string(),
+                symbol: SYMBOL.to_string(),
+                collection_info_extension: None,
+                minter: Some(String::from(MINTER_ADDR)),
+                creator: Some(String::from(CREATOR_ADDR)),
+                withdraw_address: Some(String::from(CREATOR_ADDR)),
+                burn_policy: None,
+                ipfs_gateway: None,
+                mint_price: Some(price.clone()),
+                public_minting: true,
+                max_royalty_bps: None,
+                commit_hash: None,
+                initial_mints: None,
+                track_burned_ids: false,
+                base_token_uri: None,
+                allow_force_transfer: false,
+                collection_metadata_uri: None,
+                allow_mint_to_contract: false,
+                unique_token_uris: false,
+            },
+            "contract_name",
+            "contract_version",
         )
         .unwrap();
+    let mint_msg = |token_id: &str| Cw721ExecuteMsg::Mint {
+        token_id: token_id.to_string(),
+        owner: String::from("medusa"),
+        token_uri: None,
+        extension: None,
+        ibc_data: None,
+    };
+
+    // missing payment
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("medusa", &[]),
+            mint_msg("1"),
+        )
+        .unwrap_err();
     assert_eq!(
-        contract
-            .query_nft_info(deps.as_ref().storage, token_id.clone())
-            .unwrap(),
-        NftInfoResponse {
-            token_uri: None,
-            extension: None,
+        err,
+        Cw721ContractError::MissingPayment {
+            expected: price.clone()
         }
     );
 
-    // creator updates nft metadata extension
-    contract
+    // insufficient, excess or other denoms are rejected
+    for funds in [
+        vec![Coin::new(99, "uark")],
+        vec![Coin::new(101, "uark")],
+        vec![Coin::new(100, "uatom")],
+        vec![Coin::new(100, "uark"), Coin::new(1, "uatom")],
+    ] {
+        let err = contract
+            .execute(
+                deps.as_mut(),
+                &env,
+                &mock_info("medusa", &funds),
+                mint_msg("1"),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            Cw721ContractError::WrongPayment {
+                expected: price.clone()
+            }
+        );
+    }
+
+    // anyone can mint by paying, price is forwarded to withdraw address
+    let res = contract
         .execute(
             deps.as_mut(),
             &env,
-            &mock_info(CREATOR_ADDR, &[]),
-            update_msg_only_extension,
+            &mock_info("medusa", &[price.clone()]),
+            mint_msg("1"),
         )
         .unwrap();
+    assert_eq!(res.messages.len(), 1);
     assert_eq!(
-        contract
-            .query_nft_info(deps.as_ref().storage, token_id)
-            .unwrap(),
-        NftInfoResponse {
-            token_uri: None,
-            extension: Some(NftExtension {
-                image: Some("ipfs://foo.bar/image.png".to_string()),
-                image_data: None,
-                external_url: None,
-                description: None,
-                name: None,
-                attributes: None,
-                background_color: None,
-                animation_url: None,
-                youtube_url: None,
-            }),
-        }
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: CREATOR_ADDR.to_string(),
+            amount: vec![price.clone()],
+        })
     );
+    let owner = contract
+        .query_owner_of(deps.as_ref(), &env, "1".to_string(), false)
+        .unwrap();
+    assert_eq!(owner.owner, "medusa");
+
+    // minter still mints for free
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(MINTER_ADDR, &[]),
+            mint_msg("2"),
+        )
+        .unwrap();
+    assert!(res.messages.is_empty());
+
+    // only minter can set ibc data, even when paying
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("medusa", &[price]),
+            Cw721ExecuteMsg::Mint {
+                token_id: "3".to_string(),
+                owner: String::from("medusa"),
+                token_uri: None,
+                extension: None,
+                ibc_data: Some(Binary::from(b"origin data")),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotMinter {});
 }
 
 #[test]
-fn test_mint_with_metadata() {
-    // case 1: mint with valid metadata
-    {
-        let mut deps = mock_dependencies();
-        let contract = setup_contract(deps.as_mut());
+fn test_public_minting_requires_mint_price() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721OnchainExtensions::default();
+    let err = contract
+        .instantiate_with_version(
+            deps.as_mut(),
+            &mock_env(),
+            &mock_info(CREATOR_ADDR, &[]),
+            Cw721InstantiateMsg::<
+                DefaultOptionalNftExtensionMsg,
+                DefaultOptionalCollectionExtensionMsg,
+            > {
+                name: CONTRACT_NAME.to_string(),
+                symbol: SYMBOL.to_string(),
+                collection_info_extension: None,
+                minter: Some(String::from(MINTER_ADDR)),
+                creator: Some(String::from(CREATOR_ADDR)),
+                withdraw_address: None,
+                burn_policy: None,
+                ipfs_gateway: None,
+                mint_price: None,
+                public_minting: true,
+                max_royalty_bps: None,
+                commit_hash: None,
+                initial_mints: None,
+                track_burned_ids: false,
+                base_token_uri: None,
+                allow_force_transfer: false,
+                collection_metadata_uri: None,
+                allow_mint_to_contract: false,
+                unique_token_uris: false,
+            },
+            "contract_name",
+            "contract_version",
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::MissingMintPrice {});
+}
 
-        let token_id = "1".to_string();
-        let token_uri = "ipfs://foo.bar".to_string();
-        let valid_extension_msg = NftExtensionMsg {
-            image: Some("ipfs://foo.bar/image.png".to_string()),
-            image_data: Some("image data".to_string()),
-            external_url: Some("https://github.com".to_string()),
-            description: Some("description".to_string()),
-            name: Some("name".to_string()),
-            attributes: Some(vec![Trait {
-                trait_type: "trait_type".to_string(),
-                value: "value".to_string(),
-                display_type: Some("display_type".to_string()),
-            }]),
-            background_color: Some("background_color".to_string()),
-            animation_url: Some("ssl://animation_url".to_string()),
-            youtube_url: Some("file://youtube_url".to_string()),
-        };
-        let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
-            owner: String::from("medusa"),
-            token_uri: Some(token_uri),
-            extension: Some(valid_extension_msg.clone()),
-        };
+#[test]
+fn test_mint_ibc_data() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let info_minter = mock_info(MINTER_ADDR, &[]);
+    let ibc_data = Binary::from(br#"{"origin":"stars"}"#);
 
-        let info_minter = mock_info(MINTER_ADDR, &[]);
-        let env = mock_env();
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_minter,
+            Cw721ExecuteMsg::Mint {
+                token_id: "bridged".to_string(),
+                owner: String::from("medusa"),
+                token_uri: None,
+                extension: None,
+                ibc_data: Some(ibc_data.clone()),
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_minter,
+            Cw721ExecuteMsg::Mint {
+                token_id: "native".to_string(),
+                owner: String::from("medusa"),
+                token_uri: None,
+                extension: None,
+                ibc_data: None,
+            },
+        )
+        .unwrap();
+
+    let info = contract
+        .query_nft_info(deps.as_ref(), &mock_env(), "bridged".to_string())
+        .unwrap();
+    assert_eq!(info.ibc_data, Some(ibc_data.clone()));
+    let all_info = contract
+        .query_all_nft_info(deps.as_ref(), &env, "bridged".to_string(), false)
+        .unwrap();
+    assert_eq!(all_info.info.ibc_data, Some(ibc_data.clone()));
+    let info = contract
+        .query_nft_info(deps.as_ref(), &mock_env(), "native".to_string())
+        .unwrap();
+    assert_eq!(info.ibc_data, None);
+
+    // kept on transfer
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("medusa", &[]),
+            Cw721ExecuteMsg::TransferNft {
+                recipient: String::from("random"),
+                token_id: "bridged".to_string(),
+                expected_owner: None,
+            },
+        )
+        .unwrap();
+    let info = contract
+        .query_nft_info(deps.as_ref(), &mock_env(), "bridged".to_string())
+        .unwrap();
+    assert_eq!(info.ibc_data, Some(ibc_data));
+}
+
+#[test]
+fn test_token_minter() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let mint = |deps: DepsMut, sender: &str, token_id: &str| {
         contract
-            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+            .execute(
+                deps,
+                &env,
+                &mock_info(sender, &[]),
+                Cw721ExecuteMsg::Mint {
+                    token_id: token_id.to_string(),
+                    owner: String::from("medusa"),
+                    token_uri: None,
+                    extension: None,
+                    ibc_data: None,
+                },
+            )
             .unwrap();
-        assert_eq!(
-            contract
-                .query_nft_info(deps.as_ref().storage, token_id)
-                .unwrap(),
-            NftInfoResponse {
-                token_uri: Some("ipfs://foo.bar".to_string()),
-                extension: Some(valid_extension_msg.clone().into()),
-            }
-        );
+    };
 
-        // mint with empty token uri and empty extension
-        let mint_msg = Cw721ExecuteMsg::<
-            DefaultOptionalNftExtensionMsg,
-            DefaultOptionalCollectionExtensionMsg,
-            Empty,
-        >::Mint {
-            token_id: "2".to_string(),
-            owner: String::from("medusa"),
-            token_uri: None,
-            extension: Some(NftExtensionMsg {
-                image: None,
-                image_data: None,
-                external_url: None,
-                description: None,
-                name: None,
-                attributes: None,
-                background_color: None,
-                animation_url: None,
-                youtube_url: None,
+    mint(deps.as_mut(), MINTER_ADDR, "1");
+
+    // hand over minter role
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(MINTER_ADDR, &[]),
+            Cw721ExecuteMsg::UpdateMinterOwnership(Action::TransferOwnership {
+                new_owner: "new_minter".to_string(),
+                expiry: None,
             }),
-        };
-        contract
-            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
-            .unwrap();
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("new_minter", &[]),
+            Cw721ExecuteMsg::UpdateMinterOwnership(Action::AcceptOwnership),
+        )
+        .unwrap();
+    mint(deps.as_mut(), "new_minter", "2");
+
+    // each token records its own minter
+    for (token_id, minter) in [("1", MINTER_ADDR), ("2", "new_minter")] {
         assert_eq!(
             contract
-                .query_nft_info(deps.as_ref().storage, "2".to_string())
+                .query_token_minter(deps.as_ref(), token_id.to_string())
                 .unwrap(),
-            NftInfoResponse {
-                token_uri: None,
-                extension: Some(NftExtension {
-                    image: None,
-                    image_data: None,
-                    external_url: None,
-                    description: None,
-                    name: None,
-                    attributes: None,
-                    background_color: None,
-                    animation_url: None,
-                    youtube_url: None,
-                }),
-            }
+            Some(minter.to_string())
         );
-        // empty description
-        let token_id = "3".to_string();
-        let mut metadata = valid_extension_msg.clone();
-        metadata.description = Some("".to_string());
-        let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
-            owner: String::from("medusa"),
-            token_uri: None,
-            extension: Some(metadata),
-        };
-        contract
-            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
-            .unwrap();
-        // empty name
-        let token_id = "4".to_string();
-        let mut metadata = valid_extension_msg.clone();
-        metadata.name = Some("".to_string());
-        let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
-            owner: String::from("medusa"),
-            token_uri: None,
-            extension: Some(metadata),
-        };
-        contract
-            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+        let info = contract
+            .query_nft_info(deps.as_ref(), &mock_env(), token_id.to_string())
             .unwrap();
-        // empty background color
-        let token_id = "5".to_string();
+        assert_eq!(info.minter, Some(minter.to_string()));
+    }
+
+    // tokens minted before minter was recorded fall back to collection minter
+    let mut token = contract.config.nft_info.load(&deps.storage, "1").unwrap();
+    token.minter = None;
+    contract
+        .config
+        .nft_info
+        .save(deps.as_mut().storage, "1", &token)
+        .unwrap();
+    assert_eq!(
+        contract
+            .query_token_minter(deps.as_ref(), "1".to_string())
+            .unwrap(),
+        Some("new_minter".to_string())
+    );
+
+    // unknown token
+    contract
+        .query_token_minter(deps.as_ref(), "unknown".to_string())
+        .unwrap_err();
+}
+
+#[test]
+fn test_mint_to_contract() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: "1".to_string(),
+        owner: env.contract.address.to_string(),
+        token_uri: None,
+        extension: None,
+        ibc_data: None,
+    };
+
+    // rejected by default, also in dry run
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(MINTER_ADDR, &[]),
+            mint_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::CannotMintToContract {});
+    let res = contract
+        .query_validate_mint(
+            deps.as_ref(),
+            &env,
+            MINTER_ADDR.to_string(),
+            MintMsg {
+                token_id: "1".to_string(),
+                owner: env.contract.address.to_string(),
+                token_uri: None,
+                extension: None,
+            },
+            vec![],
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        ValidateMintResponse {
+            valid: false,
+            errors: vec![Cw721ContractError::CannotMintToContract {}.to_string()],
+        }
+    );
+
+    // allowed with override on instantiation
+    let mut deps = mock_dependencies();
+    contract
+        .instantiate(
+            deps.as_mut(),
+            &env,
+            &mock_info(CREATOR_ADDR, &[]),
+            Cw721InstantiateMsg::<
+                DefaultOptionalNftExtensionMsg,
+                DefaultOptionalCollectionExtensionMsg,
+            > {
+                name: CONTRACT_NAME.to_string(),
+                symbol: SYMBOL.to_string(),
+                collection_info_extension: None,
+                minter: Some(String::from(MINTER_ADDR)),
+                creator: Some(String::from(CREATOR_ADDR)),
+                withdraw_address: None,
+                burn_policy: None,
+                ipfs_gateway: None,
+                mint_price: None,
+                public_minting: false,
+                max_royalty_bps: None,
+                commit_hash: None,
+                initial_mints: None,
+                track_burned_ids: false,
+                base_token_uri: None,
+                allow_force_transfer: false,
+                collection_metadata_uri: None,
+                allow_mint_to_contract: true,
+                unique_token_uris: false,
+            },
+        )
+        .unwrap();
+    contract
+        .execute(deps.as_mut(), &env, &mock_info(MINTER_ADDR, &[]), mint_msg)
+        .unwrap();
+    let owner = contract
+        .query_owner_of(deps.as_ref(), &env, "1".to_string(), false)
+        .unwrap();
+    assert_eq!(owner.owner, env.contract.address.to_string());
+}
+
+#[test]
+fn test_safe_transfer_nft() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: "1".to_string(),
+        owner: "venus".to_string(),
+        token_uri: None,
+        extension: None,
+        ibc_data: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &mock_info(MINTER_ADDR, &[]), mint_msg)
+        .unwrap();
+
+    // transfer to contract itself is rejected
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[]),
+            Cw721ExecuteMsg::SafeTransferNft {
+                recipient: env.contract.address.to_string(),
+                token_id: "1".to_string(),
+                expected_owner: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::InvalidRecipient {});
+
+    // invalid address is rejected
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[]),
+            Cw721ExecuteMsg::SafeTransferNft {
+                recipient: "".to_string(),
+                token_id: "1".to_string(),
+                expected_owner: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::InvalidRecipient {});
+    let owner = contract
+        .query_owner_of(deps.as_ref(), &env, "1".to_string(), false)
+        .unwrap();
+    assert_eq!(owner.owner, "venus");
+
+    // valid recipient works
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[]),
+            Cw721ExecuteMsg::SafeTransferNft {
+                recipient: "random".to_string(),
+                token_id: "1".to_string(),
+                expected_owner: Some("venus".to_string()),
+            },
+        )
+        .unwrap();
+    let owner = contract
+        .query_owner_of(deps.as_ref(), &env, "1".to_string(), false)
+        .unwrap();
+    assert_eq!(owner.owner, "random");
+}
+
+#[test]
+fn test_mint_with_expiration() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let mut env = mock_env();
+    let expires_at = env.block.time.plus_seconds(3600);
+    let mint_msg = |token_id: &str, expires| Cw721ExecuteMsg::MintWithExpiration {
+        token_id: token_id.to_string(),
+        owner: "venus".to_string(),
+        token_uri: None,
+        extension: None,
+        expires,
+    };
+
+    // only minter can mint, expiration must be in the future
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[]),
+            mint_msg("ticket", Expiration::AtTime(expires_at)),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotMinter {});
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(MINTER_ADDR, &[]),
+            mint_msg("ticket", Expiration::AtTime(env.block.time)),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Expired {});
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(MINTER_ADDR, &[]),
+            mint_msg("ticket", Expiration::AtTime(expires_at)),
+        )
+        .unwrap();
+
+    // not expired yet: transferable, but can't be burned by anyone
+    let owner = contract
+        .query_owner_of(deps.as_ref(), &env, "ticket".to_string(), false)
+        .unwrap();
+    assert!(!owner.expired);
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("random", &[]),
+            Cw721ExecuteMsg::BurnExpired {
+                token_id: "ticket".to_string(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::TokenNotExpired {});
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[]),
+            Cw721ExecuteMsg::TransferNft {
+                recipient: "mars".to_string(),
+                token_id: "ticket".to_string(),
+                expected_owner: None,
+            },
+        )
+        .unwrap();
+
+    // expired: flagged in queries and no longer transferable
+    env.block.time = expires_at;
+    let owner = contract
+        .query_owner_of(deps.as_ref(), &env, "ticket".to_string(), false)
+        .unwrap();
+    assert_eq!(
+        owner,
+        OwnerOfResponse {
+            owner: "mars".to_string(),
+            approvals: vec![],
+            expired: true,
+        }
+    );
+    let info = contract
+        .query_nft_info(deps.as_ref(), &env, "ticket".to_string())
+        .unwrap();
+    assert!(info.expired);
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("mars", &[]),
+            Cw721ExecuteMsg::TransferNft {
+                recipient: "venus".to_string(),
+                token_id: "ticket".to_string(),
+                expected_owner: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::TokenExpired {});
+
+    // anyone can burn it
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("random", &[]),
+            Cw721ExecuteMsg::BurnExpired {
+                token_id: "ticket".to_string(),
+            },
+        )
+        .unwrap();
+    contract
+        .query_owner_of(deps.as_ref(), &env, "ticket".to_string(), false)
+        .unwrap_err();
+    let count = contract.query_num_tokens(deps.as_ref().storage).unwrap();
+    assert_eq!(count.count, 0);
+}
+
+#[test]
+fn test_unique_token_uris() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let minter = mock_info(MINTER_ADDR, &[]);
+    let mint_msg = |token_id: &str, token_uri: &str| Cw721ExecuteMsg::Mint {
+        token_id: token_id.to_string(),
+        owner: "venus".to_string(),
+        token_uri: Some(token_uri.to_string()),
+        extension: None,
+        ibc_data: None,
+    };
+
+    // duplicates are allowed and not tracked by default
+    let contract = setup_contract(deps.as_mut());
+    for token_id in ["1", "2"] {
+        contract
+            .execute(deps.as_mut(), &env, &minter, mint_msg(token_id, "ipfs://a"))
+            .unwrap();
+    }
+    let used = contract
+        .query_is_uri_used(deps.as_ref().storage, "ipfs://a".to_string())
+        .unwrap();
+    assert!(!used);
+
+    let mut deps = mock_dependencies();
+    contract
+        .instantiate(
+            deps.as_mut(),
+            &env,
+            &mock_info(CREATOR_ADDR, &[]),
+            Cw721InstantiateMsg::<
+                DefaultOptionalNftExtensionMsg,
+                DefaultOptionalCollectionExtensionMsg,
+            > {
+                name: CONTRACT_NAME.to_string(),
+                symbol: SYMBOL.to_string(),
+                collection_info_extension: None,
+                minter: Some(String::from(MINTER_ADDR)),
+                creator: Some(String::from(CREATOR_ADDR)),
+                withdraw_address: None,
+                burn_policy: None,
+                ipfs_gateway: None,
+                mint_price: None,
+                public_minting: false,
+                max_royalty_bps: None,
+                commit_hash: None,
+                initial_mints: None,
+                track_burned_ids: false,
+                base_token_uri: None,
+                allow_force_transfer: false,
+                collection_metadata_uri: None,
+                allow_mint_to_contract: false,
+                unique_token_uris: true,
+            },
+        )
+        .unwrap();
+    contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg("1", "ipfs://a"))
+        .unwrap();
+    let used = contract
+        .query_is_uri_used(deps.as_ref().storage, "ipfs://a".to_string())
+        .unwrap();
+    assert!(used);
+    let err = contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg("2", "ipfs://a"))
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::DuplicateTokenUri {});
+    contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg("2", "ipfs://b"))
+        .unwrap();
+
+    // updates are checked as well, previous uri is released
+    let update_msg = |token_uri: &str| Cw721ExecuteMsg::UpdateNftInfo {
+        token_id: "2".to_string(),
+        token_uri: Some(token_uri.to_string()),
+        extension: None,
+    };
+    let creator = mock_info(CREATOR_ADDR, &[]);
+    let err = contract
+        .execute(deps.as_mut(), &env, &creator, update_msg("ipfs://a"))
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::DuplicateTokenUri {});
+    contract
+        .execute(deps.as_mut(), &env, &creator, update_msg("ipfs://c"))
+        .unwrap();
+    let used = contract
+        .query_is_uri_used(deps.as_ref().storage, "ipfs://b".to_string())
+        .unwrap();
+    assert!(!used);
+
+    // burn releases uri
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[]),
+            Cw721ExecuteMsg::Burn {
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg("3", "ipfs://a"))
+        .unwrap();
+}
+
+#[test]
+fn test_reveal_commit() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721OnchainExtensions::default();
+    let env = mock_env();
+    let creator = mock_info(CREATOR_ADDR, &[]);
+    let assignments = vec![
+        RevealAssignment {
+            token_id: "1".to_string(),
+            token_uri: "https://example.com/7.json".to_string(),
+        },
+        RevealAssignment {
+            token_id: "2".to_string(),
+            token_uri: "https://example.com/3.json".to_string(),
+        },
+    ];
+    let seed = Binary::from(b"secret seed");
+    let instantiate_msg = |commit_hash: Binary| Cw721InstantiateMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+    > {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        collection_info_extension: None,
+        minter: Some(String::from(MINTER_ADDR)),
+        creator: Some(String::from(CREATOR_ADDR)),
+        withdraw_address: None,
+        burn_policy: None,
+        ipfs_gateway: None,
+        mint_price: None,
+        public_minting: false,
+        max_royalty_bps: None,
+        commit_hash: Some(commit_hash),
+        initial_mints: None,
+        track_burned_ids: false,
+        base_token_uri: None,
+        allow_force_transfer: false,
+        collection_metadata_uri: None,
+        allow_mint_to_contract: false,
+        unique_token_uris: false,
+    };
+
+    // commit must be a SHA-256 hash
+    let err = contract
+        .instantiate(
+            deps.as_mut(),
+            &env,
+            &creator,
+            instantiate_msg(Binary::from(b"too short")),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::InvalidCommitHash {});
+
+    let mut deps = mock_dependencies();
+    let commit = commit_hash(&seed, &assignments).unwrap();
+    contract
+        .instantiate(
+            deps.as_mut(),
+            &env,
+            &creator,
+            instantiate_msg(commit.into()),
+        )
+        .unwrap();
+    for token_id in ["1", "2"] {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: String::from("medusa"),
+            token_uri: None,
+            extension: None,
+            ibc_data: None,
+        };
+        contract
+            .execute(deps.as_mut(), &env, &mock_info(MINTER_ADDR, &[]), mint_msg)
+            .unwrap();
+    }
+
+    // only creator can reveal
+    let reveal_msg = Cw721ExecuteMsg::RevealCommit {
+        seed: seed.clone(),
+        assignments: assignments.clone(),
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(MINTER_ADDR, &[]),
+            reveal_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    // incorrect reveal
+    let mut swapped = assignments.clone();
+    swapped.swap(0, 1);
+    for (seed, assignments) in [
+        (Binary::from(b"other seed"), assignments.clone()),
+        (seed.clone(), swapped),
+    ] {
+        let err = contract
+            .execute(
+                deps.as_mut(),
+                &env,
+                &creator,
+                Cw721ExecuteMsg::RevealCommit { seed, assignments },
+            )
+            .unwrap_err();
+        assert_eq!(err, Cw721ContractError::CommitMismatch {});
+    }
+
+    // correct reveal
+    contract
+        .execute(deps.as_mut(), &env, &creator, reveal_msg.clone())
+        .unwrap();
+    for RevealAssignment {
+        token_id,
+        token_uri,
+    } in &assignments
+    {
+        let nft_info = contract
+            .query_nft_info(deps.as_ref(), &mock_env(), token_id.clone())
+            .unwrap();
+        assert_eq!(nft_info.token_uri.as_ref(), Some(token_uri));
+    }
+
+    // reveal only once
+    let err = contract
+        .execute(deps.as_mut(), &env, &creator, reveal_msg)
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NoCommit {});
+}
+
+#[test]
+fn test_nft_extension_set_clear_keep() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let creator = mock_info(CREATOR_ADDR, &[]);
+    let trait_value = |value: &str| Trait {
+        display_type: None,
+        trait_type: "trait_type".to_string(),
+        value: value.to_string(),
+    };
+    let update = |contract: &Cw721OnchainExtensions,
+                  mut deps: DepsMut,
+                  extension: NftExtensionMsg|
+     -> NftExtension {
+        contract
+            .execute(
+                deps.branch(),
+                &env,
+                &creator,
+                Cw721ExecuteMsg::UpdateNftInfo {
+                    token_id: "1".to_string(),
+                    token_uri: None,
+                    extension: Some(extension),
+                },
+            )
+            .unwrap();
+        contract
+            .query_nft_info(deps.as_ref(), &env, "1".to_string())
+            .unwrap()
+            .extension
+            .unwrap()
+    };
+
+    // empty values on mint are stored as None
+    let empty = NftExtensionMsg {
+        image: Some("".to_string()),
+        image_data: Some("".to_string()),
+        external_url: Some("".to_string()),
+        description: Some("".to_string()),
+        name: Some("".to_string()),
+        attributes: Some(vec![]),
+        background_color: Some("".to_string()),
+        animation_url: Some("".to_string()),
+        youtube_url: Some("".to_string()),
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(MINTER_ADDR, &[]),
+            Cw721ExecuteMsg::Mint {
+                token_id: "1".to_string(),
+                owner: String::from("medusa"),
+                token_uri: None,
+                extension: Some(empty.clone()),
+                ibc_data: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        contract
+            .query_nft_info(deps.as_ref(), &mock_env(), "1".to_string())
+            .unwrap()
+            .extension,
+        Some(NftExtension::default())
+    );
+
+    // set
+    let set = NftExtensionMsg {
+        image: Some("ipfs://foo.bar/image.png".to_string()),
+        image_data: Some("image data".to_string()),
+        external_url: Some("https://github.com".to_string()),
+        description: Some("description".to_string()),
+        name: Some("name".to_string()),
+        attributes: Some(vec![trait_value("value")]),
+        background_color: Some("background_color".to_string()),
+        animation_url: Some("ssl://animation_url".to_string()),
+        youtube_url: Some("file://youtube_url".to_string()),
+    };
+    let expected: NftExtension = set.clone().into();
+    assert_eq!(update(&contract, deps.as_mut(), set), expected);
+
+    // keep
+    assert_eq!(
+        update(&contract, deps.as_mut(), NftExtensionMsg::default()),
+        expected
+    );
+
+    // each field is set and cleared on its own, other fields are kept
+    let mut expected = expected;
+    let fields: Vec<(fn(&mut NftExtensionMsg, bool), fn(&mut NftExtension, bool))> = vec![
+        (
+            |msg, clear| msg.image = Some(if clear { "" } else { "https://image.png" }.into()),
+            |ext, clear| ext.image = (!clear).then(|| "https://image.png".into()),
+        ),
+        (
+            |msg, clear| msg.image_data = Some(if clear { "" } else { "other data" }.into()),
+            |ext, clear| ext.image_data = (!clear).then(|| "other data".into()),
+        ),
+        (
+            |msg, clear| {
+                msg.external_url = Some(if clear { "" } else { "https://other.com" }.into())
+            },
+            |ext, clear| ext.external_url = (!clear).then(|| "https://other.com".into()),
+        ),
+        (
+            |msg, clear| msg.description = Some(if clear { "" } else { "other" }.into()),
+            |ext, clear| ext.description = (!clear).then(|| "other".into()),
+        ),
+        (
+            |msg, clear| msg.name = Some(if clear { "" } else { "other" }.into()),
+            |ext, clear| ext.name = (!clear).then(|| "other".into()),
+        ),
+        (
+            |msg, clear| {
+                msg.attributes = Some(if clear {
+                    vec![]
+                } else {
+                    vec![Trait {
+                        display_type: None,
+                        trait_type: "trait_type".to_string(),
+                        value: "other".to_string(),
+                    }]
+                })
+            },
+            |ext, clear| {
+                ext.attributes = (!clear).then(|| {
+                    vec![Trait {
+                        display_type: None,
+                        trait_type: "trait_type".to_string(),
+                        value: "other".to_string(),
+                    }]
+                })
+            },
+        ),
+        (
+            |msg, clear| msg.background_color = Some(if clear { "" } else { "other" }.into()),
+            |ext, clear| ext.background_color = (!clear).then(|| "other".into()),
+        ),
+        (
+            |msg, clear| {
+                msg.animation_url = Some(if clear { "" } else { "https://other.mp4" }.into())
+            },
+            |ext, clear| ext.animation_url = (!clear).then(|| "https://other.mp4".into()),
+        ),
+        (
+            |msg, clear| {
+                msg.youtube_url = Some(if clear { "" } else { "https://youtu.be/other" }.into())
+            },
+            |ext, clear| ext.youtube_url = (!clear).then(|| "https://youtu.be/other".into()),
+        ),
+    ];
+    for (set_msg, set_expected) in fields {
+        for clear in [false, true] {
+            let mut msg = NftExtensionMsg::default();
+            set_msg(&mut msg, clear);
+            set_expected(&mut expected, clear);
+            assert_eq!(update(&contract, deps.as_mut(), msg), expected);
+        }
+    }
+    assert_eq!(expected, NftExtension::default());
+
+    // clearing everything at once
+    let set = NftExtensionMsg {
+        name: Some("name".to_string()),
+        ..NftExtensionMsg::default()
+    };
+    update(&contract, deps.as_mut(), set);
+    assert_eq!(
+        update(&contract, deps.as_mut(), empty),
+        NftExtension::default()
+    );
+}
+
+#[test]
+fn test_resolved_nft_info() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721OnchainExtensions::default();
+    let env = mock_env();
+    let creator = mock_info(CREATOR_ADDR, &[]);
+    let instantiate_msg = |ipfs_gateway: Option<&str>| Cw721InstantiateMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+    > {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        collection_info_extension: None,
+        minter: Some(String::from(MINTER_ADDR)),
+        creator: Some(String::from(CREATOR_ADDR)),
+        withdraw_address: None,
+        burn_policy: None,
+        ipfs_gateway: ipfs_gateway.map(String::from),
+        mint_price: None,
+        public_minting: false,
+        max_royalty_bps: None,
+        commit_hash: None,
+        initial_mints: None,
+        track_burned_ids: false,
+        base_token_uri: None,
+        allow_force_transfer: false,
+        collection_metadata_uri: None,
+        allow_mint_to_contract: false,
+        unique_token_uris: false,
+    };
+
+    // invalid gateway
+    let err = contract
+        .instantiate(
+            mock_dependencies().as_mut(),
+            &env,
+            &creator,
+            instantiate_msg(Some("ipfs.io")),
+        )
+        .unwrap_err();
+    assert!(matches!(err, Cw721ContractError::ParseError(_)));
+
+    contract
+        .instantiate(
+            deps.as_mut(),
+            &env,
+            &creator,
+            instantiate_msg(Some("https://ipfs.io/ipfs/")),
+        )
+        .unwrap();
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: "1".to_string(),
+        owner: String::from("medusa"),
+        token_uri: Some("ipfs://QmMetadata/1.json".to_string()),
+        extension: Some(NftExtensionMsg {
+            image: Some("ipfs://QmImage/1.png".to_string()),
+            animation_url: Some("https://example.com/1.mp4".to_string()),
+            ..NftExtensionMsg::default()
+        }),
+        ibc_data: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &mock_info(MINTER_ADDR, &[]), mint_msg)
+        .unwrap();
+
+    // ipfs URLs are rewritten, http URLs are left alone
+    let res = contract
+        .query_resolved_nft_info(deps.as_ref(), &mock_env(), "1".to_string())
+        .unwrap();
+    assert_eq!(
+        res,
+        NftInfoResponse {
+            token_uri: Some("https://ipfs.io/ipfs/QmMetadata/1.json".to_string()),
+            extension: Some(NftExtension {
+                image: Some("https://ipfs.io/ipfs/QmImage/1.png".to_string()),
+                animation_url: Some("https://example.com/1.mp4".to_string()),
+                ..NftExtension::default()
+            }),
+            ibc_data: None,
+            minter: Some(MINTER_ADDR.to_string()),
+            expired: false,
+        }
+    );
+
+    // stored data is untouched
+    let res = contract
+        .query_nft_info(deps.as_ref(), &mock_env(), "1".to_string())
+        .unwrap();
+    assert_eq!(res.token_uri, Some("ipfs://QmMetadata/1.json".to_string()));
+    assert_eq!(
+        res.extension.unwrap().image,
+        Some("ipfs://QmImage/1.png".to_string())
+    );
+
+    // without gateway, nothing is rewritten
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: "1".to_string(),
+        owner: String::from("medusa"),
+        token_uri: Some("ipfs://QmMetadata/1.json".to_string()),
+        extension: None,
+        ibc_data: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &mock_info(MINTER_ADDR, &[]), mint_msg)
+        .unwrap();
+    let res = contract
+        .query_resolved_nft_info(deps.as_ref(), &mock_env(), "1".to_string())
+        .unwrap();
+    assert_eq!(res.token_uri, Some("ipfs://QmMetadata/1.json".to_string()));
+}
+
+#[test]
+fn test_update_nft_info() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let token_id = "1".to_string();
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: token_id.clone(),
+        owner: String::from("owner"),
+        token_uri: Some("ipfs://foo.bar".to_string()),
+        extension: None,
+        ibc_data: None,
+    };
+
+    // mint nft
+    let info_minter = mock_info(MINTER_ADDR, &[]);
+    let env = mock_env();
+    contract
+        .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+        .unwrap();
+
+    // minter update unknown nft info
+    let update_msg = Cw721ExecuteMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+        Empty,
+    >::UpdateNftInfo {
+        token_id: "unknown".to_string(),
+        token_uri: Some("ipfs://to.the.moon".to_string()),
+        extension: None,
+    };
+    // throws NotFound error
+    contract
+        .execute(deps.as_mut(), &env, &info_minter, update_msg)
+        .unwrap_err();
+
+    // minter udpate nft info
+    let update_msg_without_extension = Cw721ExecuteMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+        Empty,
+    >::UpdateNftInfo {
+        token_id: token_id.clone(),
+        token_uri: Some("".to_string()), // sets token uri to none
+        extension: None,
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_minter,
+            update_msg_without_extension.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotCreator {});
+
+    // other udpate nft metadata extension
+    let update_msg_only_extension = Cw721ExecuteMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+        Empty,
+    >::UpdateNftInfo {
+        token_id: token_id.clone(),
+        token_uri: None,
+        extension: Some(NftExtensionMsg {
+            image: Some("ipfs://foo.bar/image.png".to_string()),
+            image_data: None,
+            external_url: None,
+            description: None,
+            name: None,
+            attributes: None,
+            background_color: None,
+            animation_url: None,
+            youtube_url: None,
+        }),
+    };
+    let info_other = mock_info("other", &[]);
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info_other,
+            update_msg_only_extension.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotCreator {});
+
+    // creator updates nft info
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(CREATOR_ADDR, &[]),
+            update_msg_without_extension,
+        )
+        .unwrap();
+    assert_eq!(
+        contract
+            .query_nft_info(deps.as_ref(), &mock_env(), token_id.clone())
+            .unwrap(),
+        NftInfoResponse {
+            token_uri: None,
+            extension: None,
+            ibc_data: None,
+            minter: Some(MINTER_ADDR.to_string()),
+            expired: false,
+        }
+    );
+
+    // creator updates nft metadata extension
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(CREATOR_ADDR, &[]),
+            update_msg_only_extension,
+        )
+        .unwrap();
+    assert_eq!(
+        contract
+            .query_nft_info(deps.as_ref(), &mock_env(), token_id)
+            .unwrap(),
+        NftInfoResponse {
+            token_uri: None,
+            extension: Some(NftExtension {
+                image: Some("ipfs://foo.bar/image.png".to_string()),
+                image_data: None,
+                external_url: None,
+                description: None,
+                name: None,
+                attributes: None,
+                background_color: None,
+                animation_url: None,
+                youtube_url: None,
+            }),
+            ibc_data: None,
+            minter: Some(MINTER_ADDR.to_string()),
+            expired: false,
+        }
+    );
+}
+
+#[test]
+fn test_batch_update_nft_info() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let creator = mock_info(CREATOR_ADDR, &[]);
+    for token_id in ["1", "2", "3"] {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: "owner".to_string(),
+            token_uri: Some("https://example.com/placeholder.json".to_string()),
+            extension: None,
+            ibc_data: None,
+        };
+        contract
+            .execute(deps.as_mut(), &env, &mock_info(MINTER_ADDR, &[]), mint_msg)
+            .unwrap();
+    }
+    let update = |token_id: &str| NftInfoUpdate::<DefaultOptionalNftExtensionMsg> {
+        token_id: token_id.to_string(),
+        token_uri: Some(format!("https://example.com/{token_id}.json")),
+        extension: None,
+    };
+    let batch_msg = |updates| Cw721ExecuteMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+        Empty,
+    >::BatchUpdateNftInfo {
+        updates,
+    };
+    let token_uri = |deps: Deps, token_id: &str| {
+        contract
+            .query_nft_info(deps, &mock_env(), token_id.to_string())
+            .unwrap()
+            .token_uri
+    };
+
+    // other than creator cant update
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(MINTER_ADDR, &[]),
+            batch_msg(vec![update("1")]),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotCreator {});
+
+    // any failing update fails the whole batch
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &creator,
+            batch_msg(vec![update("unknown"), update("1")]),
+        )
+        .unwrap_err();
+    assert_eq!(
+        token_uri(deps.as_ref(), "1"),
+        Some("https://example.com/placeholder.json".to_string())
+    );
+
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &creator,
+            batch_msg(vec![update("1"), update("2")]),
+        )
+        .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![("action", "batch_update_nft_info"), ("num_tokens", "2"),]
+    );
+    assert_eq!(
+        token_uri(deps.as_ref(), "1"),
+        Some("https://example.com/1.json".to_string())
+    );
+    assert_eq!(
+        token_uri(deps.as_ref(), "2"),
+        Some("https://example.com/2.json".to_string())
+    );
+    assert_eq!(
+        token_uri(deps.as_ref(), "3"),
+        Some("https://example.com/placeholder.json".to_string())
+    );
+}
+
+#[test]
+fn test_mint_with_metadata() {
+    // case 1: mint with valid metadata
+    {
+        let mut deps = mock_dependencies();
+        let contract = setup_contract(deps.as_mut());
+
+        let token_id = "1".to_string();
+        let token_uri = "ipfs://foo.bar".to_string();
+        let valid_extension_msg = NftExtensionMsg {
+            image: Some("ipfs://foo.bar/image.png".to_string()),
+            image_data: Some("image data".to_string()),
+            external_url: Some("https://github.com".to_string()),
+            description: Some("description".to_string()),
+            name: Some("name".to_string()),
+            attributes: Some(vec![Trait {
+                trait_type: "trait_type".to_string(),
+                value: "value".to_string(),
+                display_type: Some("display_type".to_string()),
+            }]),
+            background_color: Some("background_color".to_string()),
+            animation_url: Some("ssl://animation_url".to_string()),
+            youtube_url: Some("file://youtube_url".to_string()),
+        };
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.clone(),
+            owner: String::from("medusa"),
+            token_uri: Some(token_uri),
+            extension: Some(valid_extension_msg.clone()),
+            ibc_data: None,
+        };
+
+        let info_minter = mock_info(MINTER_ADDR, &[]);
+        let env = mock_env();
+        contract
+            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+            .unwrap();
+        assert_eq!(
+            contract
+                .query_nft_info(deps.as_ref(), &mock_env(), token_id)
+                .unwrap(),
+            NftInfoResponse {
+                token_uri: Some("ipfs://foo.bar".to_string()),
+                extension: Some(valid_extension_msg.clone().into()),
+                ibc_data: None,
+                minter: Some(MINTER_ADDR.to_string()),
+                expired: false,
+            }
+        );
+
+        // mint with empty token uri and empty extension
+        let mint_msg = Cw721ExecuteMsg::<
+            DefaultOptionalNftExtensionMsg,
+            DefaultOptionalCollectionExtensionMsg,
+            Empty,
+        >::Mint {
+            token_id: "2".to_string(),
+            owner: String::from("medusa"),
+            token_uri: None,
+            extension: Some(NftExtensionMsg {
+                image: None,
+                image_data: None,
+                external_url: None,
+                description: None,
+                name: None,
+                attributes: None,
+                background_color: None,
+                animation_url: None,
+                youtube_url: None,
+            }),
+            ibc_data: None,
+        };
+        contract
+            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+            .unwrap();
+        assert_eq!(
+            contract
+                .query_nft_info(deps.as_ref(), &mock_env(), "2".to_string())
+                .unwrap(),
+            NftInfoResponse {
+                token_uri: None,
+                extension: Some(NftExtension {
+                    image: None,
+                    image_data: None,
+                    external_url: None,
+                    description: None,
+                    name: None,
+                    attributes: None,
+                    background_color: None,
+                    animation_url: None,
+                    youtube_url: None,
+                }),
+                ibc_data: None,
+                minter: Some(MINTER_ADDR.to_string()),
+                expired: false,
+            }
+        );
+        // empty description
+        let token_id = "3".to_string();
+        let mut metadata = valid_extension_msg.clone();
+        metadata.description = Some("".to_string());
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.clone(),
+            owner: String::from("medusa"),
+            token_uri: None,
+            extension: Some(metadata),
+            ibc_data: None,
+        };
+        contract
+            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+            .unwrap();
+        // empty name
+        let token_id = "4".to_string();
+        let mut metadata = valid_extension_msg.clone();
+        metadata.name = Some("".to_string());
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.clone(),
+            owner: String::from("medusa"),
+            token_uri: None,
+            extension: Some(metadata),
+            ibc_data: None,
+        };
+        contract
+            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+            .unwrap();
+        // empty background color
+        let token_id = "5".to_string();
+        let mut metadata = valid_extension_msg.clone();
+        metadata.background_color = Some("".to_string());
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.clone(),
+            owner: String::from("medusa"),
+            token_uri: None,
+            extension: Some(metadata),
+            ibc_data: None,
+        };
+        contract
+            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+            .unwrap();
+    }
+    // case 2: mint with invalid metadata
+    {
+        let mut deps = mock_dependencies();
+        let contract = setup_contract(deps.as_mut());
+
+        let token_id = "1".to_string();
+        let token_uri = "ipfs://foo.bar".to_string();
+        let info_minter = mock_info(MINTER_ADDR, &[]);
+        let env = mock_env();
+
+        let valid_extension_msg = NftExtensionMsg {
+            image: Some("ipfs://foo.bar/image.png".to_string()),
+            image_data: Some("image data".to_string()),
+            external_url: Some("https://github.com".to_string()),
+            description: Some("description".to_string()),
+            name: Some("name".to_string()),
+            attributes: Some(vec![Trait {
+                trait_type: "trait_type".to_string(),
+                value: "value".to_string(),
+                display_type: Some("display_type".to_string()),
+            }]),
+            background_color: Some("background_color".to_string()),
+            animation_url: Some("ssl://animation_url".to_string()),
+            youtube_url: Some("file://youtube_url".to_string()),
+        };
+
+        // invalid image
+        let mut metadata = valid_extension_msg.clone();
+        metadata.image = Some("invalid".to_string());
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.clone(),
+            owner: String::from("medusa"),
+            token_uri: Some(token_uri.clone()),
+            extension: Some(metadata),
+            ibc_data: None,
+        };
+        let err = contract
+            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            Cw721ContractError::ParseError(url::ParseError::RelativeUrlWithoutBase)
+        );
+        // invalid external url
+        let mut metadata = valid_extension_msg.clone();
+        metadata.external_url = Some("invalid".to_string());
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.clone(),
+            owner: String::from("medusa"),
+            token_uri: Some(token_uri.clone()),
+            extension: Some(metadata),
+            ibc_data: None,
+        };
+        let err = contract
+            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            Cw721ContractError::ParseError(url::ParseError::RelativeUrlWithoutBase)
+        );
+        // invalid animation url
+        let mut metadata = valid_extension_msg.clone();
+        metadata.animation_url = Some("invalid".to_string());
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.clone(),
+            owner: String::from("medusa"),
+            token_uri: Some(token_uri.clone()),
+            extension: Some(metadata),
+            ibc_data: None,
+        };
+        let err = contract
+            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            Cw721ContractError::ParseError(url::ParseError::RelativeUrlWithoutBase)
+        );
+        // invalid youtube url
+        let mut metadata = valid_extension_msg.clone();
+        metadata.youtube_url = Some("invalid".to_string());
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.clone(),
+            owner: String::from("medusa"),
+            token_uri: Some(token_uri.clone()),
+            extension: Some(metadata),
+            ibc_data: None,
+        };
+        let err = contract
+            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            Cw721ContractError::ParseError(url::ParseError::RelativeUrlWithoutBase)
+        );
+
+        // empty image data
+        let mut metadata = valid_extension_msg.clone();
+        metadata.image_data = Some("".to_string());
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.clone(),
+            owner: String::from("medusa"),
+            token_uri: Some(token_uri.clone()),
+            extension: Some(metadata),
+            ibc_data: None,
+        };
+        contract
+            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+            .unwrap();
+        // trait type empty
+        let mut metadata = valid_extension_msg.clone();
+        metadata.attributes = Some(vec![Trait {
+            trait_type: "".to_string(),
+            value: "value".to_string(),
+            display_type: Some("display_type".to_string()),
+        }]);
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.clone(),
+            owner: String::from("medusa"),
+            token_uri: Some(token_uri.clone()),
+            extension: Some(metadata),
+            ibc_data: None,
+        };
+        let err = contract
+            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+            .unwrap_err();
+        assert_eq!(err, Cw721ContractError::TraitTypeEmpty {});
+        // trait value empty
         let mut metadata = valid_extension_msg.clone();
-        metadata.background_color = Some("".to_string());
+        metadata.attributes = Some(vec![Trait {
+            trait_type: "trait_type".to_string(),
+            value: "".to_string(),
+            display_type: Some("display_type".to_string()),
+        }]);
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.clone(),
+            owner: String::from("medusa"),
+            token_uri: Some(token_uri.clone()),
+            extension: Some(metadata),
+            ibc_data: None,
+        };
+        let err = contract
+            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+            .unwrap_err();
+        assert_eq!(err, Cw721ContractError::TraitValueEmpty {});
+        // display type empty
+        let mut metadata = valid_extension_msg;
+        metadata.attributes = Some(vec![Trait {
+            trait_type: "trait_type".to_string(),
+            value: "value".to_string(),
+            display_type: Some("".to_string()),
+        }]);
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id,
+            owner: String::from("medusa"),
+            token_uri: Some(token_uri),
+            extension: Some(metadata),
+            ibc_data: None,
+        };
+        let err = contract
+            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+            .unwrap_err();
+        assert_eq!(err, Cw721ContractError::TraitDisplayTypeEmpty {});
+    }
+}
+
+#[test]
+fn test_update_collection_info() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let update_collection_info_msg = Cw721ExecuteMsg::UpdateCollectionInfo {
+        collection_info: CollectionInfoMsg {
+            name: Some("new name".to_string()),
+            symbol: Some("NEW".to_string()),
+            extension: None,
+            collection_metadata_uri: Clearable::NoChange,
+            transfer_fee: Clearable::NoChange,
+        },
+    };
+
+    // Creator can update collection info
+    let creator_info = mock_info(CREATOR_ADDR, &[]);
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &creator_info,
+            update_collection_info_msg,
+        )
+        .unwrap();
+
+    // Update the owner to "random". The new owner should be able to
+    // mint new tokens, the old one should not.
+    contract
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &creator_info,
+            Cw721ExecuteMsg::UpdateCreatorOwnership(Action::TransferOwnership {
+                new_owner: "random".to_string(),
+                expiry: None,
+            }),
+        )
+        .unwrap();
+
+    // Creator does not change until ownership transfer completes.
+    // Pending ownership transfer should be discoverable via query.
+    let ownership: Ownership<Addr> = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                &mock_env(),
+                Cw721QueryMsg::GetCreatorOwnership {},
+            )
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        ownership,
+        Ownership::<Addr> {
+            owner: Some(Addr::unchecked(CREATOR_ADDR)),
+            pending_owner: Some(Addr::unchecked("random")),
+            pending_expiry: None,
+        }
+    );
+
+    // Accept the ownership transfer.
+    let random_info = mock_info("random", &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &random_info,
+            Cw721ExecuteMsg::UpdateCreatorOwnership(Action::AcceptOwnership),
+        )
+        .unwrap();
+
+    // Creator changes after ownership transfer is accepted.
+    let creator_ownership: Ownership<Addr> = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                &mock_env(),
+                Cw721QueryMsg::GetCreatorOwnership {},
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(creator_ownership.owner, Some(random_info.sender.clone()));
+
+    let update_collection_info_msg = Cw721ExecuteMsg::UpdateCollectionInfo {
+        collection_info: CollectionInfoMsg {
+            name: Some("new name".to_string()),
+            symbol: Some("NEW".to_string()),
+            extension: None,
+            collection_metadata_uri: Clearable::NoChange,
+            transfer_fee: Clearable::NoChange,
+        },
+    };
+
+    // Old owner can not update.
+    let err: Cw721ContractError = contract
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &creator_info,
+            update_collection_info_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotCreator {});
+
+    // New owner can update.
+    let _ = contract
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &random_info,
+            update_collection_info_msg,
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_update_collection_info_clearable() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721OnchainExtensions::default();
+    let env = mock_env();
+    let creator = mock_info(CREATOR_ADDR, &[]);
+    let minter = mock_info(MINTER_ADDR, &[]);
+
+    let msg = Cw721InstantiateMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+    > {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        collection_info_extension: Some(CollectionExtensionMsg {
+            description: Some("description".to_string()),
+            image: Some("https://moonphases.org".to_string()),
+            explicit_content: Clearable::Set(true),
+            external_link: Clearable::Set("https://moonphases.org/".to_string()),
+            start_trading_time: Clearable::Set(Timestamp::from_seconds(0)),
+            royalty_info: Clearable::Set(RoyaltyInfoResponse {
+                payment_address: "payment_address".into(),
+                share: "0.1".parse().unwrap(),
+            }),
+        }),
+        minter: Some(String::from(MINTER_ADDR)),
+        creator: Some(String::from(CREATOR_ADDR)),
+        withdraw_address: None,
+        burn_policy: None,
+        ipfs_gateway: None,
+        mint_price: None,
+        public_minting: false,
+        max_royalty_bps: None,
+        commit_hash: None,
+        initial_mints: None,
+        track_burned_ids: false,
+        base_token_uri: None,
+        allow_force_transfer: false,
+        collection_metadata_uri: None,
+        allow_mint_to_contract: false,
+        unique_token_uris: false,
+    };
+    contract
+        .instantiate(deps.as_mut(), &env, &creator, msg)
+        .unwrap();
+    let mut expected = CollectionExtension {
+        description: "description".to_string(),
+        image: "https://moonphases.org".to_string(),
+        explicit_content: Some(true),
+        external_link: Some("https://moonphases.org/".to_string()),
+        start_trading_time: Some(Timestamp::from_seconds(0)),
+        royalty_info: Some(RoyaltyInfo {
+            payment_address: Addr::unchecked("payment_address"),
+            share: "0.1".parse().unwrap(),
+        }),
+    };
+
+    // omitted fields are left unchanged
+    let extension_msg: CollectionExtensionMsg<RoyaltyInfoResponse> =
+        from_json(r#"{"description":null,"image":null,"external_link":"clear"}"#).unwrap();
+    assert_eq!(
+        extension_msg,
+        CollectionExtensionMsg {
+            description: None,
+            image: None,
+            explicit_content: Clearable::NoChange,
+            external_link: Clearable::Clear,
+            start_trading_time: Clearable::NoChange,
+            royalty_info: Clearable::NoChange,
+        }
+    );
+
+    let update_msg = |extension: CollectionExtensionMsg<RoyaltyInfoResponse>| {
+        Cw721ExecuteMsg::UpdateCollectionInfo {
+            collection_info: CollectionInfoMsg {
+                name: None,
+                symbol: None,
+                extension: Some(extension),
+                collection_metadata_uri: Clearable::NoChange,
+                transfer_fee: Clearable::NoChange,
+            },
+        }
+    };
+
+    // clear external link, without touching description
+    contract
+        .execute(deps.as_mut(), &env, &creator, update_msg(extension_msg))
+        .unwrap();
+    expected.external_link = None;
+    let info = contract
+        .query_collection_info_and_extension(deps.as_ref())
+        .unwrap();
+    assert_eq!(info.extension, Some(expected.clone()));
+
+    // set explicit content and clear royalty info
+    let extension_msg = CollectionExtensionMsg {
+        description: None,
+        image: None,
+        explicit_content: Clearable::Set(false),
+        external_link: Clearable::NoChange,
+        start_trading_time: Clearable::NoChange,
+        royalty_info: Clearable::Clear,
+    };
+    // only creator can clear royalty info
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &minter,
+            update_msg(extension_msg.clone()),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotCreator {});
+    contract
+        .execute(deps.as_mut(), &env, &creator, update_msg(extension_msg))
+        .unwrap();
+    expected.explicit_content = Some(false);
+    expected.royalty_info = None;
+    let info = contract
+        .query_collection_info_and_extension(deps.as_ref())
+        .unwrap();
+    assert_eq!(info.extension, Some(expected.clone()));
+
+    // clear start trading time by minter, set external link by creator
+    let extension_msg = CollectionExtensionMsg {
+        description: None,
+        image: None,
+        explicit_content: Clearable::NoChange,
+        external_link: Clearable::NoChange,
+        start_trading_time: Clearable::Clear,
+        royalty_info: Clearable::NoChange,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &minter, update_msg(extension_msg))
+        .unwrap();
+    let extension_msg = CollectionExtensionMsg {
+        description: None,
+        image: None,
+        explicit_content: Clearable::NoChange,
+        external_link: Clearable::Set("https://github.com/CosmWasm/cw-nfts".to_string()),
+        start_trading_time: Clearable::NoChange,
+        royalty_info: Clearable::NoChange,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &creator, update_msg(extension_msg))
+        .unwrap();
+    expected.start_trading_time = None;
+    expected.external_link = Some("https://github.com/CosmWasm/cw-nfts".to_string());
+    let info = contract
+        .query_collection_info_and_extension(deps.as_ref())
+        .unwrap();
+    assert_eq!(info.extension, Some(expected));
+}
+
+#[test]
+fn test_collection_metadata_uri() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721OnchainExtensions::default();
+    let env = mock_env();
+    let creator = mock_info(CREATOR_ADDR, &[]);
+    let minter = mock_info(MINTER_ADDR, &[]);
+    let instantiate_msg = |collection_metadata_uri: Option<&str>| Cw721InstantiateMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+    > {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        collection_info_extension: None,
+        minter: Some(String::from(MINTER_ADDR)),
+        creator: Some(String::from(CREATOR_ADDR)),
+        withdraw_address: None,
+        burn_policy: None,
+        ipfs_gateway: None,
+        mint_price: None,
+        public_minting: false,
+        max_royalty_bps: None,
+        commit_hash: None,
+        initial_mints: None,
+        track_burned_ids: false,
+        base_token_uri: None,
+        allow_force_transfer: false,
+        collection_metadata_uri: collection_metadata_uri.map(str::to_string),
+        allow_mint_to_contract: false,
+        unique_token_uris: false,
+    };
+    let update_msg =
+        |collection_metadata_uri: Clearable<String>| Cw721ExecuteMsg::UpdateCollectionInfo {
+            collection_info: CollectionInfoMsg {
+                name: None,
+                symbol: None,
+                extension: None,
+                collection_metadata_uri,
+                transfer_fee: Clearable::NoChange,
+            },
+        };
+
+    // must be a URL
+    let err = contract
+        .instantiate(
+            deps.as_mut(),
+            &env,
+            &creator,
+            instantiate_msg(Some("collection.json")),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::ParseError(url::ParseError::RelativeUrlWithoutBase)
+    );
+
+    // set on instantiation
+    contract
+        .instantiate(
+            deps.as_mut(),
+            &env,
+            &creator,
+            instantiate_msg(Some("ipfs://collection.json")),
+        )
+        .unwrap();
+    let info = contract
+        .query_collection_info_and_extension(deps.as_ref())
+        .unwrap();
+    assert_eq!(
+        info.collection_metadata_uri,
+        Some("ipfs://collection.json".to_string())
+    );
+    let class_metadata = contract.query_class_metadata(deps.as_ref(), &env).unwrap();
+    assert_eq!(
+        class_metadata.class_uri,
+        Some("ipfs://collection.json".to_string())
+    );
+
+    // only creator can update
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &minter,
+            update_msg(Clearable::Set(
+                "https://moonphases.org/collection.json".to_string(),
+            )),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotCreator {});
+
+    // must be a URL
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &creator,
+            update_msg(Clearable::Set("collection.json".to_string())),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::ParseError(url::ParseError::RelativeUrlWithoutBase)
+    );
+
+    // update
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &creator,
+            update_msg(Clearable::Set(
+                "https://moonphases.org/collection.json".to_string(),
+            )),
+        )
+        .unwrap();
+    let info = contract
+        .query_collection_info_and_extension(deps.as_ref())
+        .unwrap();
+    assert_eq!(
+        info.collection_metadata_uri,
+        Some("https://moonphases.org/collection.json".to_string())
+    );
+
+    // left unchanged on other updates
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &creator,
+            Cw721ExecuteMsg::UpdateCollectionInfo {
+                collection_info: CollectionInfoMsg {
+                    name: Some("Moon Phases".to_string()),
+                    symbol: None,
+                    extension: None,
+                    collection_metadata_uri: Clearable::NoChange,
+                    transfer_fee: Clearable::NoChange,
+                },
+            },
+        )
+        .unwrap();
+    let info = contract
+        .query_collection_info_and_extension(deps.as_ref())
+        .unwrap();
+    assert_eq!(
+        info.collection_metadata_uri,
+        Some("https://moonphases.org/collection.json".to_string())
+    );
+
+    // clear
+    contract
+        .execute(deps.as_mut(), &env, &creator, update_msg(Clearable::Clear))
+        .unwrap();
+    let info = contract
+        .query_collection_info_and_extension(deps.as_ref())
+        .unwrap();
+    assert_eq!(info.collection_metadata_uri, None);
+    let class_metadata = contract.query_class_metadata(deps.as_ref(), &env).unwrap();
+    assert_eq!(class_metadata.class_uri, None);
+}
+
+#[test]
+fn test_update_minter() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let token_id = "petrify".to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
+
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id,
+        owner: String::from("medusa"),
+        token_uri: Some(token_uri.clone()),
+        extension: None,
+        ibc_data: None,
+    };
+
+    // Minter can mint
+    let current_minter_info = mock_info(MINTER_ADDR, &[]);
+    let _ = contract
+        .execute(deps.as_mut(), &mock_env(), &current_minter_info, mint_msg)
+        .unwrap();
+
+    // Update the owner to "random". The new owner should be able to
+    // mint new tokens, the old one should not.
+    contract
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &current_minter_info,
+            Cw721ExecuteMsg::UpdateMinterOwnership(Action::TransferOwnership {
+                new_owner: "random".to_string(),
+                expiry: None,
+            }),
+        )
+        .unwrap();
+
+    // Minter does not change until ownership transfer completes.
+    // Pending ownership transfer should be discoverable via query.
+    let ownership: Ownership<Addr> = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                &mock_env(),
+                Cw721QueryMsg::GetMinterOwnership {},
+            )
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        ownership,
+        Ownership::<Addr> {
+            owner: Some(Addr::unchecked(MINTER_ADDR)),
+            pending_owner: Some(Addr::unchecked("random")),
+            pending_expiry: None,
+        }
+    );
+
+    // Accept the ownership transfer.
+    let new_minter_info = mock_info("random", &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &new_minter_info,
+            Cw721ExecuteMsg::UpdateMinterOwnership(Action::AcceptOwnership),
+        )
+        .unwrap();
+
+    // Minter changes after ownership transfer is accepted.
+    let minter_ownership: Ownership<Addr> = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                &mock_env(),
+                Cw721QueryMsg::GetMinterOwnership {},
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(minter_ownership.owner, Some(new_minter_info.sender.clone()));
+
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: "randoms_token".to_string(),
+        owner: String::from("medusa"),
+        token_uri: Some(token_uri),
+        extension: None,
+        ibc_data: None,
+    };
+
+    // Old owner can not mint.
+    let err: Cw721ContractError = contract
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &current_minter_info,
+            mint_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotMinter {});
+
+    // New owner can mint.
+    let _ = contract
+        .execute(deps.as_mut(), &mock_env(), &new_minter_info, mint_msg)
+        .unwrap();
+}
+
+#[test]
+fn test_burn() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let token_id = "petrify".to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
+
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: token_id.clone(),
+        owner: MINTER_ADDR.to_string(),
+        token_uri: Some(token_uri),
+        extension: None,
+        ibc_data: None,
+    };
+
+    let burn_msg = Cw721ExecuteMsg::Burn { token_id };
+
+    // mint some NFT
+    let allowed = mock_info(MINTER_ADDR, &[]);
+    let _ = contract
+        .execute(deps.as_mut(), &mock_env(), &allowed, mint_msg)
+        .unwrap();
+
+    // random not allowed to burn
+    let random = mock_info("random", &[]);
+    let env = mock_env();
+    let err = contract
+        .execute(deps.as_mut(), &env, &random, burn_msg.clone())
+        .unwrap_err();
+
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    let _ = contract
+        .execute(deps.as_mut(), &env, &allowed, burn_msg)
+        .unwrap();
+
+    // ensure num tokens decreases
+    let count = contract.query_num_tokens(deps.as_ref().storage).unwrap();
+    assert_eq!(0, count.count);
+
+    // trying to get nft returns error
+    let _ = contract
+        .query_nft_info(deps.as_ref(), &mock_env(), "petrify".to_string())
+        .unwrap_err();
+
+    // list the token_ids
+    let tokens = contract
+        .query_all_tokens(deps.as_ref(), &env, None, None, None)
+        .unwrap();
+    assert!(tokens.tokens.is_empty());
+}
+
+#[test]
+fn test_track_burned_ids() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let minter = mock_info(MINTER_ADDR, &[]);
+    let mint_msg = |token_id: &str| Cw721ExecuteMsg::Mint {
+        token_id: token_id.to_string(),
+        owner: MINTER_ADDR.to_string(),
+        token_uri: None,
+        extension: None,
+        ibc_data: None,
+    };
+    let burn_msg = |token_id: &str| Cw721ExecuteMsg::Burn {
+        token_id: token_id.to_string(),
+    };
+
+    // by default, burned ids can be reused
+    contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg("1"))
+        .unwrap();
+    contract
+        .execute(deps.as_mut(), &env, &minter, burn_msg("1"))
+        .unwrap();
+    contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg("1"))
+        .unwrap();
+
+    // once tracked, burned ids cannot be minted again
+    contract
+        .config
+        .track_burned_ids
+        .save(deps.as_mut().storage, &true)
+        .unwrap();
+    contract
+        .execute(deps.as_mut(), &env, &minter, burn_msg("1"))
+        .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg("1"))
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::TokenIdWasBurned {});
+
+    // non-minters still fail with `NotMinter` first
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("random", &[]),
+            mint_msg("1"),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotMinter {});
+
+    // existing ids still fail with `Claimed`
+    contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg("2"))
+        .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg("2"))
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Claimed {});
+
+    // auto ids skip burned ids
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &minter,
+            Cw721ExecuteMsg::MintAuto {
+                owner: MINTER_ADDR.to_string(),
+                token_uri: None,
+                extension: None,
+            },
+        )
+        .unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "token_id" && attr.value == "3"));
+}
+
+#[test]
+fn test_supply_stats() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let minter = mock_info(MINTER_ADDR, &[]);
+    let mint = |deps: DepsMut, token_id: &str| {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: MINTER_ADDR.to_string(),
+            token_uri: None,
+            extension: None,
+            ibc_data: None,
+        };
+        contract.execute(deps, &env, &minter, mint_msg).unwrap();
+    };
+    let burn = |deps: DepsMut, token_id: &str| {
+        let burn_msg = Cw721ExecuteMsg::Burn {
+            token_id: token_id.to_string(),
+        };
+        contract.execute(deps, &env, &minter, burn_msg).unwrap();
+    };
+
+    let res = contract.query_supply_stats(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        res,
+        SupplyStatsResponse {
+            current: 0,
+            total_minted: 0,
+            total_burned: 0,
+        }
+    );
+
+    mint(deps.as_mut(), "1");
+    mint(deps.as_mut(), "2");
+    mint(deps.as_mut(), "3");
+    burn(deps.as_mut(), "2");
+    mint(deps.as_mut(), "4");
+    burn(deps.as_mut(), "1");
+    // burned id can be minted again, counting as another mint
+    mint(deps.as_mut(), "1");
+
+    let res: SupplyStatsResponse = from_json(
+        contract
+            .query(deps.as_ref(), &env, Cw721QueryMsg::SupplyStats {})
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        SupplyStatsResponse {
+            current: 3,
+            total_minted: 5,
+            total_burned: 2,
+        }
+    );
+    let count = contract.query_num_tokens(deps.as_ref().storage).unwrap();
+    assert_eq!(count.count, res.total_minted - res.total_burned);
+}
+
+#[test]
+fn test_burn_policy() {
+    let token_id = "petrify".to_string();
+    let burn_msg = Cw721ExecuteMsg::Burn {
+        token_id: token_id.clone(),
+    };
+    let owner = mock_info("owner", &[]);
+    let minter = mock_info(MINTER_ADDR, &[]);
+    let creator = mock_info(CREATOR_ADDR, &[]);
+    let random = mock_info("random", &[]);
+
+    // instantiates contract with given burn policy and mints a token to owner
+    let setup = |mut deps: DepsMut<'_>, burn_policy: Option<BurnPolicy>| {
+        let contract = Cw721OnchainExtensions::default();
+        let msg = Cw721InstantiateMsg::<
+            DefaultOptionalNftExtensionMsg,
+            DefaultOptionalCollectionExtensionMsg,
+        > {
+            name: CONTRACT_NAME.to_string(),
+            symbol: SYMBOL.to_string(),
+            collection_info_extension: None,
+            minter: Some(String::from(MINTER_ADDR)),
+            creator: Some(String::from(CREATOR_ADDR)),
+            withdraw_address: None,
+            burn_policy,
+            ipfs_gateway: None,
+            mint_price: None,
+            public_minting: false,
+            max_royalty_bps: None,
+            commit_hash: None,
+            initial_mints: None,
+            track_burned_ids: false,
+            base_token_uri: None,
+            allow_force_transfer: false,
+            collection_metadata_uri: None,
+            allow_mint_to_contract: false,
+            unique_token_uris: false,
+        };
+        contract
+            .instantiate(deps.branch(), &mock_env(), &creator, msg)
+            .unwrap();
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.clone(),
+            owner: "owner".to_string(),
+            token_uri: None,
+            extension: None,
+            ibc_data: None,
+        };
+        contract
+            .execute(deps, &mock_env(), &minter, mint_msg)
+            .unwrap();
+        contract
+    };
+
+    // default: owner only
+    let mut deps = mock_dependencies();
+    let contract = setup(deps.as_mut(), None);
+    for info in [&minter, &creator, &random] {
+        let err = contract
+            .execute(deps.as_mut(), &mock_env(), info, burn_msg.clone())
+            .unwrap_err();
+        assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    }
+    contract
+        .execute(deps.as_mut(), &mock_env(), &owner, burn_msg.clone())
+        .unwrap();
+
+    // owner or minter
+    let mut deps = mock_dependencies();
+    let contract = setup(deps.as_mut(), Some(BurnPolicy::OwnerOrMinter));
+    for info in [&creator, &random] {
+        let err = contract
+            .execute(deps.as_mut(), &mock_env(), info, burn_msg.clone())
+            .unwrap_err();
+        assert_eq!(err, Cw721ContractError::Unauthorized {});
+    }
+    contract
+        .execute(deps.as_mut(), &mock_env(), &minter, burn_msg.clone())
+        .unwrap();
+    let count = contract.query_num_tokens(deps.as_ref().storage).unwrap();
+    assert_eq!(0, count.count);
+
+    // owner or creator
+    let mut deps = mock_dependencies();
+    let contract = setup(deps.as_mut(), Some(BurnPolicy::OwnerOrCreator));
+    for info in [&minter, &random] {
+        let err = contract
+            .execute(deps.as_mut(), &mock_env(), info, burn_msg.clone())
+            .unwrap_err();
+        assert_eq!(err, Cw721ContractError::Unauthorized {});
+    }
+    contract
+        .execute(deps.as_mut(), &mock_env(), &creator, burn_msg.clone())
+        .unwrap();
+    let count = contract.query_num_tokens(deps.as_ref().storage).unwrap();
+    assert_eq!(0, count.count);
+
+    // owner can still burn with non-default policy
+    let mut deps = mock_dependencies();
+    let contract = setup(deps.as_mut(), Some(BurnPolicy::OwnerOrMinter));
+    contract
+        .execute(deps.as_mut(), &mock_env(), &owner, burn_msg)
+        .unwrap();
+}
+
+#[test]
+fn test_transfer_nft() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    // Mint a token
+    let token_id = "melt".to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/melt".to_string();
+
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: token_id.clone(),
+        owner: String::from("venus"),
+        token_uri: Some(token_uri),
+        extension: None,
+        ibc_data: None,
+    };
+
+    let minter = mock_info(MINTER_ADDR, &[]);
+    contract
+        .execute(deps.as_mut(), &mock_env(), &minter, mint_msg)
+        .unwrap();
+
+    // random cannot transfer
+    let random = mock_info("random", &[]);
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("random"),
+        token_id: token_id.clone(),
+        expected_owner: None,
+    };
+
+    let err = contract
+        .execute(deps.as_mut(), &mock_env(), &random, transfer_msg)
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    // owner can
+    let random = mock_info("venus", &[]);
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("random"),
+        token_id: token_id.clone(),
+        expected_owner: None,
+    };
+
+    let res = contract
+        .execute(deps.as_mut(), &mock_env(), &random, transfer_msg)
+        .unwrap();
+
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "transfer_nft")
+            .add_attribute("sender", "venus")
+            .add_attribute("recipient", "random")
+            .add_attribute("token_id", token_id)
+    );
+}
+
+#[test]
+fn test_transfer_cooldown() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let mut env = mock_env();
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: "melt".to_string(),
+        owner: "venus".to_string(),
+        token_uri: None,
+        extension: None,
+        ibc_data: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &mock_info(MINTER_ADDR, &[]), mint_msg)
+        .unwrap();
+    let transfer_msg = |recipient: &str| Cw721ExecuteMsg::TransferNft {
+        recipient: recipient.to_string(),
+        token_id: "melt".to_string(),
+    };
+    let set_cooldown_msg = Cw721ExecuteMsg::SetTransferCooldown {
+        transfer_cooldown: Some(60),
+    };
+
+    // other than creator cant set
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[]),
+            set_cooldown_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(CREATOR_ADDR, &[]),
+            set_cooldown_msg,
+        )
+        .unwrap();
+
+    // first transfer is not throttled, next one is
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[]),
+            transfer_msg("mars"),
+        )
+        .unwrap();
+    let ready_at = env.block.time.plus_seconds(60);
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("mars", &[]),
+            transfer_msg("venus"),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::TransferCooldown { ready_at });
+    let res: TransferCooldownResponse = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                &env,
+                Cw721QueryMsg::TransferCooldown {
+                    token_id: "melt".to_string(),
+                },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        TransferCooldownResponse {
+            transfer_cooldown: Some(60),
+            ready_at: Some(ready_at),
+        }
+    );
+
+    // minter is exempt
+    let approve_all_msg = Cw721ExecuteMsg::ApproveAll {
+        operator: MINTER_ADDR.to_string(),
+        expires: None,
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("mars", &[]),
+            approve_all_msg,
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(MINTER_ADDR, &[]),
+            transfer_msg("mars"),
+        )
+        .unwrap();
+
+    // after cooldown
+    env.block.time = env.block.time.plus_seconds(60);
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("mars", &[]),
+            transfer_msg("venus"),
+        )
+        .unwrap();
+    let res = contract
+        .query_transfer_cooldown(deps.as_ref(), &env, "melt".to_string())
+        .unwrap();
+    assert_eq!(res.ready_at, Some(env.block.time.plus_seconds(60)));
+}
+
+#[test]
+fn test_transfer_nft_expected_owner() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    // Mint a token
+    let token_id = "melt".to_string();
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: token_id.clone(),
+        owner: String::from("venus"),
+        token_uri: None,
+        extension: None,
+        ibc_data: None,
+    };
+    let minter = mock_info(MINTER_ADDR, &[]);
+    contract
+        .execute(deps.as_mut(), &mock_env(), &minter, mint_msg)
+        .unwrap();
+
+    // market is operator for venus and mars
+    for owner in ["venus", "mars"] {
+        let approve_all_msg = Cw721ExecuteMsg::ApproveAll {
+            operator: String::from("market"),
+            expires: None,
+        };
+        contract
+            .execute(
+                deps.as_mut(),
+                &mock_env(),
+                &mock_info(owner, &[]),
+                approve_all_msg,
+            )
+            .unwrap();
+    }
+
+    // transfer succeeds as long as owner matches
+    let market = mock_info("market", &[]);
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("mars"),
+        token_id: token_id.clone(),
+        expected_owner: Some(String::from("venus")),
+    };
+    contract
+        .execute(deps.as_mut(), &mock_env(), &market, transfer_msg)
+        .unwrap();
+
+    // stale expected owner is rejected, token stays with mars
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("random"),
+        token_id: token_id.clone(),
+        expected_owner: Some(String::from("venus")),
+    };
+    let err = contract
+        .execute(deps.as_mut(), &mock_env(), &market, transfer_msg)
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::OwnerMismatch {});
+
+    let send_msg = Cw721ExecuteMsg::SendNft {
+        contract: String::from("another_contract"),
+        token_id: token_id.clone(),
+        msg: to_json_binary("You now have the melting power").unwrap(),
+        expected_owner: Some(String::from("venus")),
+    };
+    let err = contract
+        .execute(deps.as_mut(), &mock_env(), &market, send_msg)
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::OwnerMismatch {});
+
+    let res = contract
+        .query_owner_of(deps.as_ref(), &mock_env(), token_id, false)
+        .unwrap();
+    assert_eq!(res.owner, "mars");
+}
+
+#[test]
+fn test_validate_mint() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: "1".to_string(),
+        owner: "medusa".to_string(),
+        token_uri: None,
+        extension: None,
+        ibc_data: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &mock_info(MINTER_ADDR, &[]), mint_msg)
+        .unwrap();
+    let validate_mint_with_extension = |deps: Deps,
+                                        sender: &str,
+                                        token_id: &str,
+                                        token_uri: Option<&str>,
+                                        extension: DefaultOptionalNftExtensionMsg|
+     -> ValidateMintResponse {
+        from_json(
+            contract
+                .query(
+                    deps,
+                    &env,
+                    Cw721QueryMsg::ValidateMint {
+                        sender: sender.to_string(),
+                        msg: MintMsg {
+                            token_id: token_id.to_string(),
+                            owner: "medusa".to_string(),
+                            token_uri: token_uri.map(str::to_string),
+                            extension,
+                        },
+                        funds: vec![],
+                    },
+                )
+                .unwrap(),
+        )
+        .unwrap()
+    };
+    let validate_mint = |deps: Deps, sender: &str, token_id: &str, token_uri: Option<&str>| {
+        validate_mint_with_extension(deps, sender, token_id, token_uri, None)
+    };
+    let invalid = |err: Cw721ContractError| ValidateMintResponse {
+        valid: false,
+        errors: vec![err.to_string()],
+    };
+
+    assert_eq!(
+        validate_mint(
+            deps.as_ref(),
+            MINTER_ADDR,
+            "2",
+            Some("https://example.com/2")
+        ),
+        ValidateMintResponse {
+            valid: true,
+            errors: vec![],
+        }
+    );
+    assert_eq!(
+        validate_mint(deps.as_ref(), "random", "2", None),
+        invalid(Cw721ContractError::NotMinter {})
+    );
+    assert_eq!(
+        validate_mint(deps.as_ref(), MINTER_ADDR, "1", None),
+        invalid(Cw721ContractError::Claimed {})
+    );
+    assert!(!validate_mint(deps.as_ref(), MINTER_ADDR, "2", Some("invalid_uri")).valid);
+
+    // extension is validated, and all failing checks are returned
+    let extension = Some(NftExtensionMsg {
+        image: Some("invalid_image".to_string()),
+        ..NftExtensionMsg::default()
+    });
+    assert_eq!(
+        validate_mint_with_extension(
+            deps.as_ref(),
+            MINTER_ADDR,
+            "1",
+            Some("invalid_uri"),
+            extension
+        ),
+        ValidateMintResponse {
+            valid: false,
+            errors: vec![
+                Cw721ContractError::ParseError(url::ParseError::RelativeUrlWithoutBase).to_string(),
+                Cw721ContractError::ParseError(url::ParseError::RelativeUrlWithoutBase).to_string(),
+                Cw721ContractError::Claimed {}.to_string(),
+            ],
+        }
+    );
+
+    // nothing is written
+    let count = contract.query_num_tokens(deps.as_ref().storage).unwrap();
+    assert_eq!(count.count, 1);
+}
+
+#[test]
+fn test_can_transfer() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+
+    // Mint tokens
+    let minter = mock_info(MINTER_ADDR, &[]);
+    for (token_id, owner) in [("melt", "venus"), ("ice", "mars")] {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: owner.to_string(),
+            token_uri: None,
+            extension: None,
+            ibc_data: None,
+        };
+        contract
+            .execute(deps.as_mut(), &env, &minter, mint_msg)
+            .unwrap();
+    }
+    let query_can_transfer = |deps: Deps, env: &Env, sender: &str| {
+        contract
+            .query_can_transfer(deps, env, "melt".to_string(), sender.to_string())
+            .unwrap()
+    };
+    let can_transfer =
+        |deps: Deps, env: &Env, sender: &str| query_can_transfer(deps, env, sender).allowed;
+
+    // only owner
+    assert_eq!(
+        query_can_transfer(deps.as_ref(), &env, "venus"),
+        CanTransferResponse {
+            allowed: true,
+            reason: None,
+        }
+    );
+    assert_eq!(
+        query_can_transfer(deps.as_ref(), &env, "random"),
+        CanTransferResponse {
+            allowed: false,
+            reason: Some(Cw721ContractError::Ownership(OwnershipError::NotOwner).to_string()),
+        }
+    );
+    assert!(!can_transfer(deps.as_ref(), &env, MINTER_ADDR));
+
+    // token approval, until expired
+    let approve_msg = Cw721ExecuteMsg::Approve {
+        spender: String::from("random"),
+        token_id: String::from("melt"),
+        expires: Some(Expiration::AtHeight(env.block.height + 1)),
+    };
+    contract
+        .execute(deps.as_mut(), &env, &mock_info("venus", &[]), approve_msg)
+        .unwrap();
+    assert!(can_transfer(deps.as_ref(), &env, "random"));
+    let mut later = mock_env();
+    later.block.height += 1;
+    assert!(!can_transfer(deps.as_ref(), &later, "random"));
+
+    // operator
+    let approve_all_msg = Cw721ExecuteMsg::ApproveAll {
+        operator: String::from("market"),
+        expires: None,
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[]),
+            approve_all_msg,
+        )
+        .unwrap();
+    assert!(can_transfer(deps.as_ref(), &env, "market"));
+
+    // nobody while locked by swap offer
+    let propose_msg = Cw721ExecuteMsg::ProposeSwap {
+        my_token: "melt".to_string(),
+        want_token: "ice".to_string(),
+        counterparty: "mars".to_string(),
+        expires: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &mock_info("venus", &[]), propose_msg)
+        .unwrap();
+    assert_eq!(
+        query_can_transfer(deps.as_ref(), &env, "venus"),
+        CanTransferResponse {
+            allowed: false,
+            reason: Some(
+                Cw721ContractError::TokenLocked {
+                    token_id: "melt".to_string(),
+                }
+                .to_string()
+            ),
+        }
+    );
+    assert!(!can_transfer(deps.as_ref(), &env, "market"));
+
+    // unknown token
+    contract
+        .query_can_transfer(
+            deps.as_ref(),
+            &env,
+            "unknown".to_string(),
+            "venus".to_string(),
+        )
+        .unwrap_err();
+}
+
+#[test]
+fn test_token_state() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let mut env = mock_env();
+    let minter = mock_info(MINTER_ADDR, &[]);
+    let venus = mock_info("venus", &[]);
+
+    // token 1 is timelocked for a day, token 2 is unlocked
+    let transferable_after = env.block.time.plus_days(1);
+    let mint_msg = Cw721ExecuteMsg::MintTimelocked {
+        token_id: "1".to_string(),
+        owner: "venus".to_string(),
+        token_uri: None,
+        extension: None,
+        transferable_after,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg)
+        .unwrap();
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: "2".to_string(),
+        owner: "venus".to_string(),
+        token_uri: None,
+        extension: None,
+        ibc_data: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg)
+        .unwrap();
+    // one active and one expiring approval
+    for (spender, expires) in [
+        ("alice", None),
+        ("bob", Some(Expiration::AtHeight(env.block.height + 1))),
+    ] {
+        let approve_msg = Cw721ExecuteMsg::Approve {
+            spender: spender.to_string(),
+            token_id: "1".to_string(),
+            expires,
+        };
+        contract
+            .execute(deps.as_mut(), &env, &venus, approve_msg)
+            .unwrap();
+    }
+    env.block.height += 1;
+
+    let res: TokenStateResponse = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                &env,
+                Cw721QueryMsg::TokenState {
+                    token_id: "1".to_string(),
+                },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        TokenStateResponse {
+            owner: "venus".to_string(),
+            locked: true,
+            locked_until: Some(Expiration::AtTime(transferable_after)),
+            approvals: vec![Approval {
+                spender: Addr::unchecked("alice"),
+                expires: Expiration::Never {},
+            }],
+        }
+    );
+
+    // unlocked token without approvals
+    let res = contract
+        .query_token_state(deps.as_ref(), &env, "2".to_string())
+        .unwrap();
+    assert_eq!(
+        res,
+        TokenStateResponse {
+            owner: "venus".to_string(),
+            locked: false,
+            locked_until: None,
+            approvals: vec![],
+        }
+    );
+
+    // token locked by pending swap offer
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: "3".to_string(),
+        owner: "mars".to_string(),
+        token_uri: None,
+        extension: None,
+        ibc_data: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg)
+        .unwrap();
+    let swap_expires = Expiration::AtHeight(env.block.height + 10);
+    let propose_swap_msg = Cw721ExecuteMsg::ProposeSwap {
+        my_token: "2".to_string(),
+        want_token: "3".to_string(),
+        counterparty: "mars".to_string(),
+        expires: Some(swap_expires),
+    };
+    contract
+        .execute(deps.as_mut(), &env, &venus, propose_swap_msg)
+        .unwrap();
+    let res = contract
+        .query_token_state(deps.as_ref(), &env, "2".to_string())
+        .unwrap();
+    assert!(res.locked);
+    assert_eq!(res.locked_until, Some(swap_expires));
+
+    // timelock ends
+    env.block.time = transferable_after;
+    let res = contract
+        .query_token_state(deps.as_ref(), &env, "1".to_string())
+        .unwrap();
+    assert!(!res.locked);
+    assert_eq!(res.locked_until, None);
+}
+
+#[test]
+fn test_owner_of_at() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    // Mint a token at initial height
+    let token_id = "melt".to_string();
+    let mut env = mock_env();
+    let mint_height = env.block.height;
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: token_id.clone(),
+        owner: String::from("venus"),
+        token_uri: None,
+        extension: None,
+        ibc_data: None,
+    };
+    let minter = mock_info(MINTER_ADDR, &[]);
+    contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg)
+        .unwrap();
+
+    // transfer to mars 10 blocks later
+    env.block.height = mint_height + 10;
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("mars"),
+        token_id: token_id.clone(),
+        expected_owner: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &mock_info("venus", &[]), transfer_msg)
+        .unwrap();
+
+    // burn 10 blocks later
+    env.block.height = mint_height + 20;
+    let burn_msg = Cw721ExecuteMsg::Burn {
+        token_id: token_id.clone(),
+    };
+    contract
+        .execute(deps.as_mut(), &env, &mock_info("mars", &[]), burn_msg)
+        .unwrap();
+
+    // owner is resolved at the beginning of the given height
+    let owner_at = |height: u64| {
+        contract
+            .query_owner_of_at(deps.as_ref(), token_id.clone(), height)
+            .unwrap()
+            .owner
+    };
+    assert_eq!(owner_at(mint_height), None);
+    assert_eq!(owner_at(mint_height + 1), Some("venus".to_string()));
+    assert_eq!(owner_at(mint_height + 10), Some("venus".to_string()));
+    assert_eq!(owner_at(mint_height + 11), Some("mars".to_string()));
+    assert_eq!(owner_at(mint_height + 20), Some("mars".to_string()));
+    assert_eq!(owner_at(mint_height + 21), None);
+
+    // same via query msg
+    let res: OwnerOfAtResponse = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                &env,
+                Cw721QueryMsg::OwnerOfAt {
+                    token_id,
+                    height: mint_height + 15,
+                },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.owner, Some("mars".to_string()));
+}
+
+#[test]
+fn test_trait_counts() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let trait_of = |trait_type: &str, value: &str| Trait {
+        display_type: None,
+        trait_type: trait_type.to_string(),
+        value: value.to_string(),
+    };
+    let trait_counts = |deps: &cosmwasm_std::OwnedDeps<_, _, _>, trait_type: &str| {
+        let res: TraitCountsResponse = from_json(
+            contract
+                .query(
+                    deps.as_ref(),
+                    &env,
+                    Cw721QueryMsg::TraitCounts {
+                        trait_type: trait_type.to_string(),
+                    },
+                )
+                .unwrap(),
+        )
+        .unwrap();
+        res.counts
+    };
+
+    // mint tokens with traits
+    let minter = mock_info(MINTER_ADDR, &[]);
+    for (token_id, attributes) in [
+        (
+            "1",
+            vec![trait_of("background", "gold"), trait_of("eyes", "blue")],
+        ),
+        ("2", vec![trait_of("background", "gold")]),
+        ("3", vec![trait_of("background", "silver")]),
+    ] {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: String::from("venus"),
+            token_uri: None,
+            extension: Some(NftExtensionMsg {
+                attributes: Some(attributes),
+                ..NftExtensionMsg::default()
+            }),
+            ibc_data: None,
+        };
+        contract
+            .execute(deps.as_mut(), &env, &minter, mint_msg)
+            .unwrap();
+    }
+    assert_eq!(
+        trait_counts(&deps, "background"),
+        vec![("gold".to_string(), 2), ("silver".to_string(), 1)]
+    );
+    assert_eq!(trait_counts(&deps, "eyes"), vec![("blue".to_string(), 1)]);
+    assert_eq!(trait_counts(&deps, "hat"), vec![]);
+
+    // updating metadata moves token to new trait value
+    let update_msg = Cw721ExecuteMsg::UpdateNftInfo {
+        token_id: "2".to_string(),
+        token_uri: None,
+        extension: Some(NftExtensionMsg {
+            attributes: Some(vec![trait_of("background", "silver")]),
+            ..NftExtensionMsg::default()
+        }),
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(CREATOR_ADDR, &[]),
+            update_msg,
+        )
+        .unwrap();
+    assert_eq!(
+        trait_counts(&deps, "background"),
+        vec![("gold".to_string(), 1), ("silver".to_string(), 2)]
+    );
+
+    // burning removes token from counts
+    let burn_msg = Cw721ExecuteMsg::Burn {
+        token_id: "1".to_string(),
+    };
+    contract
+        .execute(deps.as_mut(), &env, &mock_info("venus", &[]), burn_msg)
+        .unwrap();
+    assert_eq!(
+        trait_counts(&deps, "background"),
+        vec![("silver".to_string(), 2)]
+    );
+    assert_eq!(trait_counts(&deps, "eyes"), vec![]);
+}
+
+#[test]
+fn test_distribute_nfts() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    // Mint 3 tokens to venus and 1 to mars
+    let minter = mock_info(MINTER_ADDR, &[]);
+    for (token_id, owner) in [
+        ("1", "venus"),
+        ("2", "venus"),
+        ("3", "venus"),
+        ("4", "mars"),
+    ] {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: owner.to_string(),
+            token_uri: None,
+            extension: None,
+            ibc_data: None,
+        };
+        contract
+            .execute(deps.as_mut(), &mock_env(), &minter, mint_msg)
+            .unwrap();
+    }
+
+    // venus cannot distribute token owned by mars, nothing is transferred
+    let distribute_msg = Cw721ExecuteMsg::DistributeNfts {
+        transfers: vec![
+            NftTransfer {
+                recipient: String::from("alice"),
+                token_id: String::from("1"),
+            },
+            NftTransfer {
+                recipient: String::from("bob"),
+                token_id: String::from("4"),
+            },
+        ],
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &mock_info("venus", &[]),
+            distribute_msg,
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    let res = contract
+        .query_owner_of(deps.as_ref(), &mock_env(), "1".to_string(), false)
+        .unwrap();
+    assert_eq!(res.owner, "venus");
+
+    // same token cannot be distributed twice
+    let distribute_msg = Cw721ExecuteMsg::DistributeNfts {
+        transfers: vec![
+            NftTransfer {
+                recipient: String::from("alice"),
+                token_id: String::from("1"),
+            },
+            NftTransfer {
+                recipient: String::from("bob"),
+                token_id: String::from("1"),
+            },
+        ],
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &mock_info("venus", &[]),
+            distribute_msg,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::DuplicateTokenId {
+            token_id: String::from("1")
+        }
+    );
+
+    // venus distributes 3 tokens to 3 recipients
+    let distribute_msg = Cw721ExecuteMsg::DistributeNfts {
+        transfers: vec![
+            NftTransfer {
+                recipient: String::from("alice"),
+                token_id: String::from("1"),
+            },
+            NftTransfer {
+                recipient: String::from("bob"),
+                token_id: String::from("2"),
+            },
+            NftTransfer {
+                recipient: String::from("carol"),
+                token_id: String::from("3"),
+            },
+        ],
+    };
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &mock_info("venus", &[]),
+            distribute_msg,
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "distribute_nfts")
+            .add_attribute("sender", "venus")
+            .add_attribute("num_tokens", "3")
+            .add_attribute("num_recipients", "3")
+            .add_attribute("token_id", "1")
+            .add_attribute("recipient", "alice")
+            .add_attribute("token_id", "2")
+            .add_attribute("recipient", "bob")
+            .add_attribute("token_id", "3")
+            .add_attribute("recipient", "carol")
+    );
+    for (token_id, owner) in [("1", "alice"), ("2", "bob"), ("3", "carol")] {
+        let res = contract
+            .query_owner_of(deps.as_ref(), &mock_env(), token_id.to_string(), false)
+            .unwrap();
+        assert_eq!(res.owner, owner);
+    }
+}
+
+#[test]
+fn test_transfer_and_approve() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: "1".to_string(),
+        owner: String::from("venus"),
+        token_uri: None,
+        extension: None,
+        ibc_data: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &mock_info(MINTER_ADDR, &[]), mint_msg)
+        .unwrap();
+    let approve_msg = Cw721ExecuteMsg::Approve {
+        spender: String::from("random"),
+        token_id: "1".to_string(),
+        expires: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &mock_info("venus", &[]), approve_msg)
+        .unwrap();
+
+    // only eligible senders can transfer, no approval is recorded
+    let expires = Expiration::AtHeight(env.block.height + 100);
+    let transfer_and_approve_msg = Cw721ExecuteMsg::TransferAndApprove {
+        recipient: String::from("escrow"),
+        token_id: "1".to_string(),
+        spender: String::from("settlement"),
+        expires: Some(expires),
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("mars", &[]),
+            transfer_and_approve_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    // expired approval is rejected before transferring
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[]),
+            Cw721ExecuteMsg::TransferAndApprove {
+                recipient: String::from("escrow"),
+                token_id: "1".to_string(),
+                spender: String::from("settlement"),
+                expires: Some(Expiration::AtHeight(env.block.height)),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Expired {});
+    let res = contract
+        .query_owner_of(deps.as_ref(), &env, "1".to_string(), false)
+        .unwrap();
+    assert_eq!(res.owner, "venus");
+
+    // owner transfers to escrow and approves settlement
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[]),
+            transfer_and_approve_msg,
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "transfer_and_approve")
+            .add_attribute("sender", "venus")
+            .add_attribute("recipient", "escrow")
+            .add_attribute("token_id", "1")
+            .add_attribute("spender", "settlement")
+    );
+    // previous approvals are cleared by the transfer
+    let res = contract
+        .query_owner_of(deps.as_ref(), &env, "1".to_string(), false)
+        .unwrap();
+    assert_eq!(
+        res,
+        OwnerOfResponse {
+            owner: String::from("escrow"),
+            approvals: vec![Approval {
+                spender: Addr::unchecked("settlement"),
+                expires,
+            }],
+            expired: false,
+        }
+    );
+
+    // settlement can transfer on behalf of escrow
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("buyer"),
+        token_id: "1".to_string(),
+        expected_owner: None,
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("settlement", &[]),
+            transfer_msg,
+        )
+        .unwrap();
+    let res = contract
+        .query_owner_of(deps.as_ref(), &env, "1".to_string(), false)
+        .unwrap();
+    assert_eq!(res.owner, "buyer");
+}
+
+#[test]
+fn test_redeem_voucher() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let minter = mock_info(MINTER_ADDR, &[]);
+    let buyer = mock_info("buyer", &[]);
+
+    let signing_key = SigningKey::from_slice(&[7u8; 32]).unwrap();
+    let pubkey = Binary::from(
+        signing_key
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes(),
+    );
+    let sign = |voucher: &MintVoucher<DefaultOptionalNftExtensionMsg>| {
+        let hash = voucher_hash(&env.contract.address, voucher).unwrap();
+        let signature: Signature = signing_key.sign_prehash(&hash).unwrap();
+        Binary::from(signature.to_bytes().as_slice())
+    };
+    let voucher = MintVoucher {
+        token_id: "1".to_string(),
+        owner: "buyer".to_string(),
+        token_uri: Some("ipfs://foo.bar".to_string()),
+        extension: None,
+    };
+    let redeem_msg = Cw721ExecuteMsg::RedeemVoucher {
+        voucher: voucher.clone(),
+        signature: sign(&voucher),
+    };
+
+    // no signer set
+    let err = contract
+        .execute(deps.as_mut(), &env, &buyer, redeem_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NoVoucherSigner {});
+
+    // only minter can set signer
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(CREATOR_ADDR, &[]),
+            Cw721ExecuteMsg::SetVoucherSigner {
+                pubkey: Some(pubkey.clone()),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NotMinter {});
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &minter,
+            Cw721ExecuteMsg::SetVoucherSigner {
+                pubkey: Some(Binary::from(b"invalid")),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::InvalidPublicKey {});
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &minter,
+            Cw721ExecuteMsg::SetVoucherSigner {
+                pubkey: Some(pubkey.clone()),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        contract.query_voucher_signer(deps.as_ref()).unwrap(),
+        Some(pubkey)
+    );
+
+    // voucher can only be redeemed by its owner
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("other", &[]),
+            redeem_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Unauthorized {});
+
+    // signature must match the voucher
+    let other_voucher = MintVoucher {
+        token_id: "2".to_string(),
+        ..voucher.clone()
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &buyer,
+            Cw721ExecuteMsg::RedeemVoucher {
+                voucher: other_voucher,
+                signature: sign(&voucher),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::InvalidVoucherSignature {});
+
+    // buyer redeems
+    let res = contract
+        .execute(deps.as_mut(), &env, &buyer, redeem_msg.clone())
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "mint")
+            .add_attribute("minter", MINTER_ADDR)
+            .add_attribute("owner", "buyer")
+            .add_attribute("token_id", "1")
+            .add_attribute("token_uri", "ipfs://foo.bar")
+            .add_attribute("num_tokens", "1")
+            .add_attribute("redeemer", "buyer")
+    );
+    let res = contract
+        .query_owner_of(deps.as_ref(), &env, "1".to_string(), false)
+        .unwrap();
+    assert_eq!(res.owner, "buyer");
+
+    // voucher can't be redeemed twice
+    let err = contract
+        .execute(deps.as_mut(), &env, &buyer, redeem_msg)
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::VoucherRedeemed {});
+}
+
+#[test]
+fn test_force_transfer_all() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let creator = mock_info(CREATOR_ADDR, &[]);
+    for token_id in ["1", "2", "3"] {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: "lost".to_string(),
+            token_uri: None,
+            extension: None,
+            ibc_data: None,
+        };
+        contract
+            .execute(deps.as_mut(), &env, &mock_info(MINTER_ADDR, &[]), mint_msg)
+            .unwrap();
+    }
+    // "lost" offered token "1" in a swap before losing its key
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: "4".to_string(),
+        owner: "mars".to_string(),
+        token_uri: None,
+        extension: None,
+        ibc_data: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &mock_info(MINTER_ADDR, &[]), mint_msg)
+        .unwrap();
+    let propose_msg = Cw721ExecuteMsg::ProposeSwap {
+        my_token: "1".to_string(),
+        want_token: "4".to_string(),
+        counterparty: "mars".to_string(),
+        expires: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &mock_info("lost", &[]), propose_msg)
+        .unwrap();
+    let force_transfer_msg = Cw721ExecuteMsg::ForceTransferAll {
+        from: "lost".to_string(),
+        to: "recovered".to_string(),
+        limit: Some(2),
+    };
+    let tokens = |deps: Deps, owner: &str| {
+        contract
+            .query_tokens(deps, &env, owner.to_string(), None, None, None)
+            .unwrap()
+            .tokens
+    };
+
+    // disabled by default
+    let err = contract
+        .execute(deps.as_mut(), &env, &creator, force_transfer_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::ForceTransferDisabled {});
+
+    contract
+        .config
+        .allow_force_transfer
+        .save(deps.as_mut().storage, &true)
+        .unwrap();
+
+    // other than creator cant force transfer
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(MINTER_ADDR, &[]),
+            force_transfer_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    // paginated by token id
+    let res = contract
+        .execute(deps.as_mut(), &env, &creator, force_transfer_msg.clone())
+        .unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "num_tokens" && attr.value == "2"));
+    assert_eq!(
+        res.events,
+        vec![
+            Event::new("force_transfer")
+                .add_attribute("token_id", "1")
+                .add_attribute("cancelled_offer_id", "1"),
+            Event::new("force_transfer").add_attribute("token_id", "2"),
+        ]
+    );
+    assert_eq!(tokens(deps.as_ref(), "lost"), vec!["3".to_string()]);
+    assert_eq!(
+        tokens(deps.as_ref(), "recovered"),
+        vec!["1".to_string(), "2".to_string()]
+    );
+
+    // swap offer of previous owner is cancelled, so recovered token is no longer locked
+    contract.query_swap_offer(deps.as_ref(), 1).unwrap_err();
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("recovered", &[]),
+            Cw721ExecuteMsg::TransferNft {
+                recipient: "venus".to_string(),
+                token_id: "1".to_string(),
+                expected_owner: None,
+            },
+        )
+        .unwrap();
+
+    contract
+        .execute(deps.as_mut(), &env, &creator, force_transfer_msg.clone())
+        .unwrap();
+    assert!(tokens(deps.as_ref(), "lost").is_empty());
+    assert_eq!(tokens(deps.as_ref(), "recovered").len(), 2);
+
+    // nothing left to transfer
+    let res = contract
+        .execute(deps.as_mut(), &env, &creator, force_transfer_msg)
+        .unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "num_tokens" && attr.value == "0"));
+}
+
+#[test]
+fn test_recover_nft() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+
+    // one token stranded on the contract, one owned by venus
+    let minter = mock_info(MINTER_ADDR, &[]);
+    for token_id in ["1", "2"] {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: String::from("venus"),
+            token_uri: None,
+            extension: None,
+            ibc_data: None,
+        };
+        contract
+            .execute(deps.as_mut(), &env, &minter, mint_msg)
+            .unwrap();
+    }
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[]),
+            Cw721ExecuteMsg::TransferNft {
+                recipient: env.contract.address.to_string(),
+                token_id: "1".to_string(),
+                expected_owner: None,
+            },
+        )
+        .unwrap();
+
+    // only creator can recover
+    let recover_msg = Cw721ExecuteMsg::RecoverNft {
+        token_id: "1".to_string(),
+        recipient: String::from("mars"),
+    };
+    let err = contract
+        .execute(deps.as_mut(), &env, &minter, recover_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    // tokens not owned by the contract cannot be recovered
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(CREATOR_ADDR, &[]),
+            Cw721ExecuteMsg::RecoverNft {
+                token_id: "2".to_string(),
+                recipient: String::from("mars"),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::NotContractOwned {
+            token_id: "2".to_string()
+        }
+    );
+
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(CREATOR_ADDR, &[]),
+            recover_msg,
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "recover_nft")
+            .add_attribute("sender", CREATOR_ADDR)
+            .add_attribute("recipient", "mars")
+            .add_attribute("token_id", "1")
+    );
+    let res = contract
+        .query_owner_of(deps.as_ref(), &env, "1".to_string(), false)
+        .unwrap();
+    assert_eq!(res.owner, "mars");
+}
+
+#[test]
+fn test_swap() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let mut env = mock_env();
+
+    // venus owns "a" and "c", mars owns "b"
+    let minter = mock_info(MINTER_ADDR, &[]);
+    for (token_id, owner) in [("a", "venus"), ("b", "mars"), ("c", "venus")] {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: owner.to_string(),
+            token_uri: None,
+            extension: None,
+            ibc_data: None,
+        };
+        contract
+            .execute(deps.as_mut(), &env, &minter, mint_msg)
+            .unwrap();
+    }
+    let venus = mock_info("venus", &[]);
+    let mars = mock_info("mars", &[]);
+    let propose_msg = |my_token: &str, expires: Option<Expiration>| Cw721ExecuteMsg::ProposeSwap {
+        my_token: my_token.to_string(),
+        want_token: "b".to_string(),
+        counterparty: "mars".to_string(),
+        expires,
+    };
+
+    // only owner can propose
+    let err = contract
+        .execute(deps.as_mut(), &env, &mars, propose_msg("a", None))
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    // propose locks offered token
+    contract
+        .execute(deps.as_mut(), &env, &venus, propose_msg("a", None))
+        .unwrap();
+    assert_eq!(
+        contract.query_swap_offer(deps.as_ref(), 1).unwrap(),
+        SwapOffer {
+            proposer: Addr::unchecked("venus"),
+            my_token: "a".to_string(),
+            counterparty: Addr::unchecked("mars"),
+            want_token: "b".to_string(),
+            expires: Expiration::Never {},
+        }
+    );
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("random"),
+        token_id: "a".to_string(),
+        expected_owner: None,
+    };
+    let err = contract
+        .execute(deps.as_mut(), &env, &venus, transfer_msg)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::TokenLocked {
+            token_id: "a".to_string()
+        }
+    );
+    let burn_msg = Cw721ExecuteMsg::Burn {
+        token_id: "a".to_string(),
+    };
+    let err = contract
+        .execute(deps.as_mut(), &env, &venus, burn_msg)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::TokenLocked {
+            token_id: "a".to_string()
+        }
+    );
+
+    // only counterparty can accept
+    let accept_msg = Cw721ExecuteMsg::AcceptSwap { offer_id: 1 };
+    let err = contract
+        .execute(deps.as_mut(), &env, &venus, accept_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    // accept swaps both tokens
+    contract
+        .execute(deps.as_mut(), &env, &mars, accept_msg.clone())
+        .unwrap();
+    let owner_of = |deps: &cosmwasm_std::OwnedDeps<_, _, _>, token_id: &str| {
+        contract
+            .query_owner_of(deps.as_ref(), &mock_env(), token_id.to_string(), false)
+            .unwrap()
+            .owner
+    };
+    assert_eq!(owner_of(&deps, "a"), "mars");
+    assert_eq!(owner_of(&deps, "b"), "venus");
+    assert!(contract.query_swap_offer(deps.as_ref(), 1).is_err());
+    // offer can only be accepted once
+    contract
+        .execute(deps.as_mut(), &env, &mars, accept_msg)
+        .unwrap_err();
+
+    // mars got "a", so venus can only offer "c" for "b"
+    let propose_msg = |expires: Option<Expiration>| Cw721ExecuteMsg::ProposeSwap {
+        my_token: "c".to_string(),
+        want_token: "a".to_string(),
+        counterparty: "mars".to_string(),
+        expires,
+    };
+
+    // proposer can cancel, unlocking token
+    contract
+        .execute(deps.as_mut(), &env, &venus, propose_msg(None))
+        .unwrap();
+    let cancel_msg = Cw721ExecuteMsg::CancelSwap { offer_id: 2 };
+    let err = contract
+        .execute(deps.as_mut(), &env, &mars, cancel_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    contract
+        .execute(deps.as_mut(), &env, &venus, cancel_msg)
+        .unwrap();
+    assert!(contract.query_swap_offer(deps.as_ref(), 2).is_err());
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mars,
+            Cw721ExecuteMsg::AcceptSwap { offer_id: 2 },
+        )
+        .unwrap_err();
+
+    // expired offer cannot be accepted and no longer locks token
+    let expires = Expiration::AtHeight(env.block.height + 10);
+    contract
+        .execute(deps.as_mut(), &env, &venus, propose_msg(Some(expires)))
+        .unwrap();
+    env.block.height += 10;
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mars,
+            Cw721ExecuteMsg::AcceptSwap { offer_id: 3 },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::SwapOfferExpired {});
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("random"),
+        token_id: "c".to_string(),
+        expected_owner: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &venus, transfer_msg)
+        .unwrap();
+    assert_eq!(owner_of(&deps, "c"), "random");
+    // anyone can clean up expired offer
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("random", &[]),
+            Cw721ExecuteMsg::CancelSwap { offer_id: 3 },
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_swap_checks_transfers() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let mut env = mock_env();
+    let minter = mock_info(MINTER_ADDR, &[]);
+    let expires_at = env.block.time.plus_seconds(3600);
+
+    // venus owns "ticket" expiring in an hour, mars owns "a" and "b"
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &minter,
+            Cw721ExecuteMsg::MintWithExpiration {
+                token_id: "ticket".to_string(),
+                owner: "venus".to_string(),
+                token_uri: None,
+                extension: None,
+                expires: Expiration::AtTime(expires_at),
+            },
+        )
+        .unwrap();
+    for token_id in ["a", "b"] {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: "mars".to_string(),
+            token_uri: None,
+            extension: None,
+            ibc_data: None,
+        };
+        contract
+            .execute(deps.as_mut(), &env, &minter, mint_msg)
+            .unwrap();
+    }
+    let venus = mock_info("venus", &[]);
+    let propose_msg = |my_token: &str, want_token: &str| Cw721ExecuteMsg::ProposeSwap {
+        my_token: my_token.to_string(),
+        want_token: want_token.to_string(),
+        counterparty: "mars".to_string(),
+        expires: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &venus, propose_msg("ticket", "a"))
+        .unwrap();
+
+    // expired token can neither be swapped nor offered
+    env.block.time = expires_at;
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("mars", &[]),
+            Cw721ExecuteMsg::AcceptSwap { offer_id: 1 },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::TokenExpired {});
+    let err = contract
+        .execute(deps.as_mut(), &env, &venus, propose_msg("ticket", "b"))
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::TokenExpired {});
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("mars", &[]),
+            Cw721ExecuteMsg::ProposeSwap {
+                my_token: "b".to_string(),
+                want_token: "ticket".to_string(),
+                counterparty: "venus".to_string(),
+                expires: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::TokenExpired {});
+}
+
+#[test]
+fn test_send_nft() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    // Mint a token
+    let token_id = "melt".to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/melt".to_string();
+
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: token_id.clone(),
+        owner: String::from("venus"),
+        token_uri: Some(token_uri),
+        extension: None,
+        ibc_data: None,
+    };
+
+    let minter = mock_info(MINTER_ADDR, &[]);
+    contract
+        .execute(deps.as_mut(), &mock_env(), &minter, mint_msg)
+        .unwrap();
+
+    let msg = to_json_binary("You now have the melting power").unwrap();
+    let target = String::from("another_contract");
+    let send_msg = Cw721ExecuteMsg::SendNft {
+        contract: target.clone(),
+        token_id: token_id.clone(),
+        msg: msg.clone(),
+        expected_owner: None,
+    };
+
+    let random = mock_info("random", &[]);
+    let err = contract
+        .execute(deps.as_mut(), &mock_env(), &random, send_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    // but owner can
+    let random = mock_info("venus", &[]);
+    let res = contract
+        .execute(deps.as_mut(), &mock_env(), &random, send_msg)
+        .unwrap();
+
+    let payload = Cw721ReceiveMsg {
+        sender: String::from("venus"),
+        token_id: token_id.clone(),
+        msg,
+    };
+    let expected = payload.into_cosmos_msg(target.clone()).unwrap();
+    // ensure expected serializes as we think it should
+    match &expected {
+        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => {
+            assert_eq!(contract_addr, &target)
+        }
+        m => panic!("Unexpected message type: {m:?}"),
+    }
+    // and make sure this is the request sent by the contract
+    assert_eq!(
+        res,
+        Response::new()
+            .add_message(expected)
+            .add_attribute("action", "send_nft")
+            .add_attribute("sender", "venus")
+            .add_attribute("recipient", "another_contract")
+            .add_attribute("token_id", token_id)
+    );
+}
+
+#[test]
+fn test_approve_revoke() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    // Mint a token
+    let token_id = "grow".to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/grow".to_string();
+
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: token_id.clone(),
+        owner: String::from("demeter"),
+        token_uri: Some(token_uri),
+        extension: None,
+        ibc_data: None,
+    };
+
+    let minter = mock_info(MINTER_ADDR, &[]);
+    contract
+        .execute(deps.as_mut(), &mock_env(), &minter, mint_msg)
+        .unwrap();
+
+    // token owner shows in approval query
+    let res = contract
+        .query_approval(
+            deps.as_ref(),
+            &mock_env(),
+            token_id.clone(),
+            String::from("demeter"),
+            false,
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        ApprovalResponse {
+            approval: Approval {
+                spender: Addr::unchecked("demeter"),
+                expires: Expiration::Never {}
+            }
+        }
+    );
+
+    // Give random transferring power
+    let approve_msg = Cw721ExecuteMsg::Approve {
+        spender: String::from("random"),
+        token_id: token_id.clone(),
+        expires: None,
+    };
+    let owner = mock_info("demeter", &[]);
+    let res = contract
+        .execute(deps.as_mut(), &mock_env(), &owner, approve_msg)
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "approve")
+            .add_attribute("sender", "demeter")
+            .add_attribute("spender", "random")
+            .add_attribute("token_id", token_id.clone())
+    );
+
+    // test approval query
+    let res = contract
+        .query_approval(
+            deps.as_ref(),
+            &mock_env(),
+            token_id.clone(),
+            String::from("random"),
+            true,
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        ApprovalResponse {
+            approval: Approval {
+                spender: Addr::unchecked("random"),
+                expires: Expiration::Never {}
+            }
+        }
+    );
+
+    // random can now transfer
+    let random = mock_info("random", &[]);
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("person"),
+        token_id: token_id.clone(),
+        expected_owner: None,
+    };
+    contract
+        .execute(deps.as_mut(), &mock_env(), &random, transfer_msg)
+        .unwrap();
+
+    // Approvals are removed / cleared
+    let query_msg = Cw721QueryMsg::OwnerOf {
+        token_id: token_id.clone(),
+        include_expired: None,
+    };
+    let res: OwnerOfResponse = from_json(
+        contract
+            .query(deps.as_ref(), &mock_env(), query_msg.clone())
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        OwnerOfResponse {
+            owner: String::from("person"),
+            approvals: vec![],
+            expired: false,
+        }
+    );
+
+    // Approve, revoke, and check for empty, to test revoke
+    let approve_msg = Cw721ExecuteMsg::Approve {
+        spender: String::from("random"),
+        token_id: token_id.clone(),
+        expires: None,
+    };
+    let owner = mock_info("person", &[]);
+    contract
+        .execute(deps.as_mut(), &mock_env(), &owner, approve_msg)
+        .unwrap();
+
+    let revoke_msg = Cw721ExecuteMsg::Revoke {
+        spender: String::from("random"),
+        token_id,
+    };
+    contract
+        .execute(deps.as_mut(), &mock_env(), &owner, revoke_msg)
+        .unwrap();
+
+    // Approvals are now removed / cleared
+    let res: OwnerOfResponse = from_json(
+        contract
+            .query(deps.as_ref(), &mock_env(), query_msg)
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        OwnerOfResponse {
+            owner: String::from("person"),
+            approvals: vec![],
+            expired: false,
+        }
+    );
+}
+
+#[test]
+fn test_all_approvals() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let mut env = mock_env();
+
+    let minter = mock_info(MINTER_ADDR, &[]);
+    for token_id in ["1", "2", "3", "4"] {
         let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
-            owner: String::from("medusa"),
+            token_id: token_id.to_string(),
+            owner: String::from("venus"),
             token_uri: None,
-            extension: Some(metadata),
+            extension: None,
+            ibc_data: None,
         };
         contract
-            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+            .execute(deps.as_mut(), &env, &minter, mint_msg)
             .unwrap();
     }
-    // case 2: mint with invalid metadata
-    {
-        let mut deps = mock_dependencies();
-        let contract = setup_contract(deps.as_mut());
+    // token 2 has no approvals, token 3 has an approval expiring next block
+    for (token_id, spender, expires) in [
+        ("1", "alice", None),
+        ("3", "bob", Some(Expiration::AtHeight(env.block.height + 1))),
+        ("4", "carol", None),
+    ] {
+        let approve_msg = Cw721ExecuteMsg::Approve {
+            spender: spender.to_string(),
+            token_id: token_id.to_string(),
+            expires,
+        };
+        contract
+            .execute(deps.as_mut(), &env, &mock_info("venus", &[]), approve_msg)
+            .unwrap();
+    }
+    let token_approvals = |token_id: &str, spender: &str, expires: Expiration| TokenApprovals {
+        token_id: token_id.to_string(),
+        approvals: vec![Approval {
+            spender: Addr::unchecked(spender),
+            expires,
+        }],
+    };
 
-        let token_id = "1".to_string();
-        let token_uri = "ipfs://foo.bar".to_string();
-        let info_minter = mock_info(MINTER_ADDR, &[]);
-        let env = mock_env();
+    let res = contract
+        .query_all_approvals(deps.as_ref(), &env, None, None, false)
+        .unwrap();
+    assert_eq!(
+        res.approvals,
+        vec![
+            token_approvals("1", "alice", Expiration::Never {}),
+            token_approvals("3", "bob", Expiration::AtHeight(env.block.height + 1)),
+            token_approvals("4", "carol", Expiration::Never {}),
+        ]
+    );
 
-        let valid_extension_msg = NftExtensionMsg {
-            image: Some("ipfs://foo.bar/image.png".to_string()),
-            image_data: Some("image data".to_string()),
-            external_url: Some("https://github.com".to_string()),
-            description: Some("description".to_string()),
-            name: Some("name".to_string()),
-            attributes: Some(vec![Trait {
-                trait_type: "trait_type".to_string(),
-                value: "value".to_string(),
-                display_type: Some("display_type".to_string()),
-            }]),
-            background_color: Some("background_color".to_string()),
-            animation_url: Some("ssl://animation_url".to_string()),
-            youtube_url: Some("file://youtube_url".to_string()),
-        };
+    assert_eq!(res.last_token_id, None);
 
-        // invalid image
-        let mut metadata = valid_extension_msg.clone();
-        metadata.image = Some("invalid".to_string());
-        let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
-            owner: String::from("medusa"),
-            token_uri: Some(token_uri.clone()),
-            extension: Some(metadata),
-        };
-        let err = contract
-            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
-            .unwrap_err();
-        assert_eq!(
-            err,
-            Cw721ContractError::ParseError(url::ParseError::RelativeUrlWithoutBase)
-        );
-        // invalid external url
-        let mut metadata = valid_extension_msg.clone();
-        metadata.external_url = Some("invalid".to_string());
+    // paginated, limit caps tokens scanned
+    let res = contract
+        .query_all_approvals(deps.as_ref(), &env, Some("1".to_string()), Some(1), false)
+        .unwrap();
+    assert_eq!(res.approvals, vec![]);
+    assert_eq!(res.last_token_id, Some("2".to_string()));
+    let res = contract
+        .query_all_approvals(deps.as_ref(), &env, res.last_token_id, Some(1), false)
+        .unwrap();
+    assert_eq!(
+        res.approvals,
+        vec![token_approvals(
+            "3",
+            "bob",
+            Expiration::AtHeight(env.block.height + 1)
+        )]
+    );
+    assert_eq!(res.last_token_id, Some("3".to_string()));
+
+    // expired approvals are skipped, unless included
+    env.block.height += 1;
+    let res = contract
+        .query_all_approvals(deps.as_ref(), &env, None, None, false)
+        .unwrap();
+    assert_eq!(
+        res.approvals,
+        vec![
+            token_approvals("1", "alice", Expiration::Never {}),
+            token_approvals("4", "carol", Expiration::Never {}),
+        ]
+    );
+    let res = contract
+        .query_all_approvals(deps.as_ref(), &env, None, None, true)
+        .unwrap();
+    assert_eq!(res.approvals.len(), 3);
+}
+
+#[test]
+fn test_operable_tokens() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let mut env = mock_env();
+
+    let minter = mock_info(MINTER_ADDR, &[]);
+    for (token_id, owner) in [
+        ("1", "venus"),
+        ("2", "mars"),
+        ("3", "venus"),
+        ("4", "pluto"),
+        ("5", "mars"),
+        ("6", "neptune"),
+    ] {
         let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
-            owner: String::from("medusa"),
-            token_uri: Some(token_uri.clone()),
-            extension: Some(metadata),
+            token_id: token_id.to_string(),
+            owner: owner.to_string(),
+            token_uri: None,
+            extension: None,
+            ibc_data: None,
         };
-        let err = contract
-            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
-            .unwrap_err();
-        assert_eq!(
-            err,
-            Cw721ContractError::ParseError(url::ParseError::RelativeUrlWithoutBase)
-        );
-        // invalid animation url
-        let mut metadata = valid_extension_msg.clone();
-        metadata.animation_url = Some("invalid".to_string());
-        let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
-            owner: String::from("medusa"),
-            token_uri: Some(token_uri.clone()),
-            extension: Some(metadata),
+        contract
+            .execute(deps.as_mut(), &env, &minter, mint_msg)
+            .unwrap();
+    }
+    // venus approves all, pluto approves all until next block
+    for (owner, expires) in [
+        ("venus", None),
+        ("pluto", Some(Expiration::AtHeight(env.block.height + 1))),
+    ] {
+        let approve_all_msg = Cw721ExecuteMsg::ApproveAll {
+            operator: "operator".to_string(),
+            expires,
         };
-        let err = contract
-            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
-            .unwrap_err();
-        assert_eq!(
-            err,
-            Cw721ContractError::ParseError(url::ParseError::RelativeUrlWithoutBase)
-        );
-        // invalid youtube url
-        let mut metadata = valid_extension_msg.clone();
-        metadata.youtube_url = Some("invalid".to_string());
-        let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
-            owner: String::from("medusa"),
-            token_uri: Some(token_uri.clone()),
-            extension: Some(metadata),
+        contract
+            .execute(deps.as_mut(), &env, &mock_info(owner, &[]), approve_all_msg)
+            .unwrap();
+    }
+    // mars and neptune approve all for another operator, interleaving operator entries
+    for owner in ["mars", "neptune"] {
+        let approve_all_msg = Cw721ExecuteMsg::ApproveAll {
+            operator: "other".to_string(),
+            expires: None,
         };
-        let err = contract
-            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
-            .unwrap_err();
-        assert_eq!(
-            err,
-            Cw721ContractError::ParseError(url::ParseError::RelativeUrlWithoutBase)
-        );
+        contract
+            .execute(deps.as_mut(), &env, &mock_info(owner, &[]), approve_all_msg)
+            .unwrap();
+    }
+    // mars approves token 5 directly
+    let approve_msg = Cw721ExecuteMsg::Approve {
+        spender: "operator".to_string(),
+        token_id: "5".to_string(),
+        expires: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &mock_info("mars", &[]), approve_msg)
+        .unwrap();
+
+    // ordered by token id, incl. direct approvals
+    let res = contract
+        .query_operable_tokens(deps.as_ref(), &env, "operator".to_string(), None, None)
+        .unwrap();
+    assert_eq!(res.tokens, vec!["1", "3", "4", "5"]);
+    assert_eq!(res.last_token_id, None);
+
+    // limit caps tokens scanned, pages continue after the last token scanned
+    let res = contract
+        .query_operable_tokens(deps.as_ref(), &env, "operator".to_string(), None, Some(2))
+        .unwrap();
+    assert_eq!(res.tokens, vec!["1"]);
+    assert_eq!(res.last_token_id, Some("2".to_string()));
+    let res = contract
+        .query_operable_tokens(
+            deps.as_ref(),
+            &env,
+            "operator".to_string(),
+            res.last_token_id,
+            Some(2),
+        )
+        .unwrap();
+    assert_eq!(res.tokens, vec!["3", "4"]);
+    assert_eq!(res.last_token_id, Some("4".to_string()));
+    let res = contract
+        .query_operable_tokens(
+            deps.as_ref(),
+            &env,
+            "operator".to_string(),
+            res.last_token_id,
+            Some(2),
+        )
+        .unwrap();
+    assert_eq!(res.tokens, vec!["5"]);
+    assert_eq!(res.last_token_id, Some("6".to_string()));
+    let res = contract
+        .query_operable_tokens(
+            deps.as_ref(),
+            &env,
+            "operator".to_string(),
+            res.last_token_id,
+            Some(2),
+        )
+        .unwrap();
+    assert!(res.tokens.is_empty());
+    assert_eq!(res.last_token_id, None);
 
-        // empty image data
-        let mut metadata = valid_extension_msg.clone();
-        metadata.image_data = Some("".to_string());
+    // expired approve all is skipped
+    env.block.height += 1;
+    let res = contract
+        .query_operable_tokens(deps.as_ref(), &env, "operator".to_string(), None, None)
+        .unwrap();
+    assert_eq!(res.tokens, vec!["1", "3", "5"]);
+
+    // the other operator only moves tokens of mars and neptune
+    let res = contract
+        .query_operable_tokens(deps.as_ref(), &env, "other".to_string(), None, None)
+        .unwrap();
+    assert_eq!(res.tokens, vec!["2", "5", "6"]);
+
+    // unknown operators can't move anything
+    let res = contract
+        .query_operable_tokens(deps.as_ref(), &env, "random".to_string(), None, None)
+        .unwrap();
+    assert!(res.tokens.is_empty());
+}
+
+#[test]
+fn test_transfer_sweeps_expired_approvals() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let minter = mock_info(MINTER_ADDR, &[]);
+    for token_id in ["grow", "harvest"] {
         let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
-            owner: String::from("medusa"),
-            token_uri: Some(token_uri.clone()),
-            extension: Some(metadata),
+            token_id: token_id.to_string(),
+            owner: String::from("demeter"),
+            token_uri: None,
+            extension: None,
+            ibc_data: None,
         };
         contract
-            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+            .execute(deps.as_mut(), &mock_env(), &minter, mint_msg)
             .unwrap();
-        // trait type empty
-        let mut metadata = valid_extension_msg.clone();
-        metadata.attributes = Some(vec![Trait {
-            trait_type: "".to_string(),
-            value: "value".to_string(),
-            display_type: Some("display_type".to_string()),
-        }]);
-        let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
-            owner: String::from("medusa"),
-            token_uri: Some(token_uri.clone()),
-            extension: Some(metadata),
-        };
-        let err = contract
-            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
-            .unwrap_err();
-        assert_eq!(err, Cw721ContractError::TraitTypeEmpty {});
-        // trait value empty
-        let mut metadata = valid_extension_msg.clone();
-        metadata.attributes = Some(vec![Trait {
-            trait_type: "trait_type".to_string(),
-            value: "".to_string(),
-            display_type: Some("display_type".to_string()),
-        }]);
-        let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id: token_id.clone(),
-            owner: String::from("medusa"),
-            token_uri: Some(token_uri.clone()),
-            extension: Some(metadata),
+    }
+
+    // approval for random on harvest expires at next block
+    let env = mock_env();
+    let owner = mock_info("demeter", &[]);
+    let approve_msg = Cw721ExecuteMsg::Approve {
+        spender: String::from("random"),
+        token_id: "harvest".to_string(),
+        expires: Some(Expiration::AtHeight(env.block.height + 1)),
+    };
+    contract
+        .execute(deps.as_mut(), &env, &owner, approve_msg)
+        .unwrap();
+
+    // unrelated transfer of grow in a later block drops the expired approval on harvest
+    let mut later = mock_env();
+    later.block.height += 1;
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("persephone"),
+        token_id: "grow".to_string(),
+        expected_owner: None,
+    };
+    contract
+        .execute(deps.as_mut(), &later, &owner, transfer_msg)
+        .unwrap();
+
+    let res = contract
+        .query_owner_of(deps.as_ref(), &later, "harvest".to_string(), true)
+        .unwrap();
+    assert_eq!(res.approvals, vec![]);
+}
+
+#[test]
+fn test_approve_self_or_contract() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let owner = mock_info("demeter", &[]);
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: "1".to_string(),
+        owner: String::from("demeter"),
+        token_uri: None,
+        extension: None,
+        ibc_data: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &mock_info(MINTER_ADDR, &[]), mint_msg)
+        .unwrap();
+
+    for (spender, expected) in [
+        ("demeter", Cw721ContractError::CannotApproveSelf {}),
+        (
+            env.contract.address.as_str(),
+            Cw721ContractError::CannotApproveContract {},
+        ),
+    ] {
+        let approve_msg = Cw721ExecuteMsg::Approve {
+            spender: spender.to_string(),
+            token_id: "1".to_string(),
+            expires: None,
         };
         let err = contract
-            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+            .execute(deps.as_mut(), &env, &owner, approve_msg)
             .unwrap_err();
-        assert_eq!(err, Cw721ContractError::TraitValueEmpty {});
-        // display type empty
-        let mut metadata = valid_extension_msg;
-        metadata.attributes = Some(vec![Trait {
-            trait_type: "trait_type".to_string(),
-            value: "value".to_string(),
-            display_type: Some("".to_string()),
-        }]);
-        let mint_msg = Cw721ExecuteMsg::Mint {
-            token_id,
-            owner: String::from("medusa"),
-            token_uri: Some(token_uri),
-            extension: Some(metadata),
+        assert_eq!(err, expected);
+
+        let approve_all_msg = Cw721ExecuteMsg::ApproveAll {
+            operator: spender.to_string(),
+            expires: None,
         };
         let err = contract
-            .execute(deps.as_mut(), &env, &info_minter, mint_msg)
+            .execute(deps.as_mut(), &env, &owner, approve_all_msg)
             .unwrap_err();
-        assert_eq!(err, Cw721ContractError::TraitDisplayTypeEmpty {});
+        assert_eq!(err, expected);
     }
 }
 
-#[test]
-fn test_update_collection_info() {
-    let mut deps = mock_dependencies();
-    let contract = setup_contract(deps.as_mut());
+#[test]
+fn test_default_approval_expiry() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let owner = mock_info("demeter", &[]);
+    let set_default_msg = |default_approval_expiry| Cw721ExecuteMsg::SetDefaultApprovalExpiry {
+        default_approval_expiry,
+    };
+    let approve_all = |deps: DepsMut, operator: &str, expires| {
+        contract
+            .execute(
+                deps,
+                &env,
+                &owner,
+                Cw721ExecuteMsg::ApproveAll {
+                    operator: operator.to_string(),
+                    expires,
+                },
+            )
+            .unwrap();
+    };
+    let operator_expires = |deps: Deps, operator: &str| {
+        contract
+            .query_operator(
+                deps,
+                &env,
+                "demeter".to_string(),
+                operator.to_string(),
+                true,
+            )
+            .unwrap()
+            .approval
+            .expires
+    };
+
+    // unset by default, so approvals never expire
+    assert_eq!(
+        contract
+            .query_default_approval_expiry(deps.as_ref())
+            .unwrap(),
+        None
+    );
+    approve_all(deps.as_mut(), "random", None);
+    assert_eq!(
+        operator_expires(deps.as_ref(), "random"),
+        Expiration::Never {}
+    );
+
+    // other than creator cant set
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &owner,
+            set_default_msg(Some(Duration::Time(30 * 24 * 60 * 60))),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(CREATOR_ADDR, &[]),
+            set_default_msg(Some(Duration::Time(30 * 24 * 60 * 60))),
+        )
+        .unwrap();
+    assert_eq!(
+        contract
+            .query_default_approval_expiry(deps.as_ref())
+            .unwrap(),
+        Some(Duration::Time(30 * 24 * 60 * 60))
+    );
+
+    // default applies if expires is omitted
+    approve_all(deps.as_mut(), "operator", None);
+    assert_eq!(
+        operator_expires(deps.as_ref(), "operator"),
+        Expiration::AtTime(env.block.time.plus_days(30))
+    );
+    // explicit expires is kept
+    approve_all(deps.as_mut(), "operator", Some(Expiration::Never {}));
+    assert_eq!(
+        operator_expires(deps.as_ref(), "operator"),
+        Expiration::Never {}
+    );
+
+    // removing reverts to never
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(CREATOR_ADDR, &[]),
+            set_default_msg(None),
+        )
+        .unwrap();
+    approve_all(deps.as_mut(), "operator", None);
+    assert_eq!(
+        operator_expires(deps.as_ref(), "operator"),
+        Expiration::Never {}
+    );
+}
+
+#[test]
+fn test_approve_all_revoke_all() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    // Mint a couple tokens (from the same owner)
+    let token_id1 = "grow1".to_string();
+    let token_uri1 = "https://www.merriam-webster.com/dictionary/grow1".to_string();
+
+    let token_id2 = "grow2".to_string();
+    let token_uri2 = "https://www.merriam-webster.com/dictionary/grow2".to_string();
+
+    let mint_msg1 = Cw721ExecuteMsg::Mint {
+        token_id: token_id1.clone(),
+        owner: String::from("demeter"),
+        token_uri: Some(token_uri1),
+        extension: None,
+        ibc_data: None,
+    };
+
+    let minter = mock_info(MINTER_ADDR, &[]);
+    contract
+        .execute(deps.as_mut(), &mock_env(), &minter, mint_msg1)
+        .unwrap();
+
+    let mint_msg2 = Cw721ExecuteMsg::Mint {
+        token_id: token_id2.clone(),
+        owner: String::from("demeter"),
+        token_uri: Some(token_uri2),
+        extension: None,
+        ibc_data: None,
+    };
+
+    let env = mock_env();
+    contract
+        .execute(deps.as_mut(), &env, &minter, mint_msg2)
+        .unwrap();
+
+    // paginate the token_ids
+    let tokens = contract
+        .query_all_tokens(deps.as_ref(), &env, None, Some(1), None)
+        .unwrap();
+    assert_eq!(1, tokens.tokens.len());
+    assert_eq!(vec![token_id1.clone()], tokens.tokens);
+    let tokens = contract
+        .query_all_tokens(deps.as_ref(), &env, Some(token_id1.clone()), Some(3), None)
+        .unwrap();
+    assert_eq!(1, tokens.tokens.len());
+    assert_eq!(vec![token_id2.clone()], tokens.tokens);
+
+    // demeter gives random full (operator) power over her tokens
+    let approve_all_msg = Cw721ExecuteMsg::ApproveAll {
+        operator: String::from("random"),
+        expires: None,
+    };
+    let owner = mock_info("demeter", &[]);
+    let res = contract
+        .execute(deps.as_mut(), &mock_env(), &owner, approve_all_msg)
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "approve_all")
+            .add_attribute("sender", "demeter")
+            .add_attribute("operator", "random")
+            .add_attribute("expires", "expiration: never")
+    );
+
+    // random can now transfer
+    let random = mock_info("random", &[]);
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("person"),
+        token_id: token_id1,
+        expected_owner: None,
+    };
+    contract
+        .execute(deps.as_mut(), &mock_env(), &random, transfer_msg)
+        .unwrap();
+
+    // random can now send
+    let inner_msg = WasmMsg::Execute {
+        contract_addr: "another_contract".into(),
+        msg: to_json_binary("You now also have the growing power").unwrap(),
+        funds: vec![],
+    };
+    let msg: CosmosMsg = CosmosMsg::Wasm(inner_msg);
+
+    let send_msg = Cw721ExecuteMsg::SendNft {
+        contract: String::from("another_contract"),
+        token_id: token_id2,
+        msg: to_json_binary(&msg).unwrap(),
+        expected_owner: None,
+    };
+    contract
+        .execute(deps.as_mut(), &mock_env(), &random, send_msg)
+        .unwrap();
 
-    let update_collection_info_msg = Cw721ExecuteMsg::UpdateCollectionInfo {
-        collection_info: CollectionInfoMsg {
-            name: Some("new name".to_string()),
-            symbol: Some("NEW".to_string()),
-            extension: None,
-        },
+    // Approve_all, revoke_all, and check for empty, to test revoke_all
+    let approve_all_msg = Cw721ExecuteMsg::ApproveAll {
+        operator: String::from("operator"),
+        expires: None,
     };
+    // person is now the owner of the tokens
+    let owner = mock_info("person", &[]);
+    contract
+        .execute(deps.as_mut(), &mock_env(), &owner, approve_all_msg)
+        .unwrap();
 
-    // Creator can update collection info
-    let creator_info = mock_info(CREATOR_ADDR, &[]);
-    let _ = contract
-        .execute(
-            deps.as_mut(),
+    // query for operator should return approval
+    let res = contract
+        .query_operator(
+            deps.as_ref(),
             &mock_env(),
-            &creator_info,
-            update_collection_info_msg,
+            String::from("person"),
+            String::from("operator"),
+            true,
         )
         .unwrap();
+    assert_eq!(
+        res,
+        OperatorResponse {
+            approval: Approval {
+                spender: Addr::unchecked("operator"),
+                expires: Expiration::Never {}
+            }
+        }
+    );
 
-    // Update the owner to "random". The new owner should be able to
-    // mint new tokens, the old one should not.
-    contract
-        .execute(
-            deps.as_mut(),
+    // query for other should throw error
+    let res = contract.query_operator(
+        deps.as_ref(),
+        &mock_env(),
+        String::from("person"),
+        String::from("other"),
+        true,
+    );
+    match res {
+        Err(StdError::NotFound { kind }) => assert_eq!(kind, "Approval not found"),
+        _ => panic!("Unexpected error"),
+    }
+
+    let res = contract
+        .query_operators(
+            deps.as_ref(),
             &mock_env(),
-            &creator_info,
-            Cw721ExecuteMsg::UpdateCreatorOwnership(Action::TransferOwnership {
-                new_owner: "random".to_string(),
-                expiry: None,
-            }),
+            String::from("person"),
+            true,
+            None,
+            None,
         )
         .unwrap();
+    assert_eq!(
+        res,
+        OperatorsResponse {
+            operators: vec![Approval {
+                spender: Addr::unchecked("operator"),
+                expires: Expiration::Never {}
+            }]
+        }
+    );
 
-    // Creator does not change until ownership transfer completes.
-    // Pending ownership transfer should be discoverable via query.
-    let ownership: Ownership<Addr> = from_json(
-        contract
-            .query(
-                deps.as_ref(),
-                &mock_env(),
-                Cw721QueryMsg::GetCreatorOwnership {},
-            )
-            .unwrap(),
-    )
-    .unwrap();
+    // second approval
+    let buddy_expires = Expiration::AtHeight(1234567);
+    let approve_all_msg = Cw721ExecuteMsg::ApproveAll {
+        operator: String::from("buddy"),
+        expires: Some(buddy_expires),
+    };
+    let owner = mock_info("person", &[]);
+    contract
+        .execute(deps.as_mut(), &mock_env(), &owner, approve_all_msg)
+        .unwrap();
 
+    // and paginate queries
+    let res = contract
+        .query_operators(
+            deps.as_ref(),
+            &mock_env(),
+            String::from("person"),
+            true,
+            None,
+            Some(1),
+        )
+        .unwrap();
     assert_eq!(
-        ownership,
-        Ownership::<Addr> {
-            owner: Some(Addr::unchecked(CREATOR_ADDR)),
-            pending_owner: Some(Addr::unchecked("random")),
-            pending_expiry: None,
+        res,
+        OperatorsResponse {
+            operators: vec![Approval {
+                spender: Addr::unchecked("buddy"),
+                expires: buddy_expires,
+            }]
+        }
+    );
+    let res = contract
+        .query_operators(
+            deps.as_ref(),
+            &mock_env(),
+            String::from("person"),
+            true,
+            Some(String::from("buddy")),
+            Some(2),
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        OperatorsResponse {
+            operators: vec![Approval {
+                spender: Addr::unchecked("operator"),
+                expires: Expiration::Never {}
+            }]
         }
     );
 
-    // Accept the ownership transfer.
-    let random_info = mock_info("random", &[]);
+    let revoke_all_msg = Cw721ExecuteMsg::RevokeAll {
+        operator: String::from("operator"),
+    };
     contract
-        .execute(
-            deps.as_mut(),
+        .execute(deps.as_mut(), &mock_env(), &owner, revoke_all_msg)
+        .unwrap();
+
+    // query for operator should return error
+    let res = contract.query_operator(
+        deps.as_ref(),
+        &mock_env(),
+        String::from("person"),
+        String::from("operator"),
+        true,
+    );
+    match res {
+        Err(StdError::NotFound { kind }) => assert_eq!(kind, "Approval not found"),
+        _ => panic!("Unexpected error"),
+    }
+
+    // Approvals are removed / cleared without affecting others
+    let res = contract
+        .query_operators(
+            deps.as_ref(),
             &mock_env(),
-            &random_info,
-            Cw721ExecuteMsg::UpdateCreatorOwnership(Action::AcceptOwnership),
+            String::from("person"),
+            false,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        OperatorsResponse {
+            operators: vec![Approval {
+                spender: Addr::unchecked("buddy"),
+                expires: buddy_expires,
+            }]
+        }
+    );
+
+    // ensure the filter works (nothing should be here
+    let mut late_env = mock_env();
+    late_env.block.height = 1234568; //expired
+    let res = contract
+        .query_operators(
+            deps.as_ref(),
+            &late_env,
+            String::from("person"),
+            false,
+            None,
+            None,
         )
         .unwrap();
+    assert_eq!(0, res.operators.len());
 
-    // Creator changes after ownership transfer is accepted.
-    let creator_ownership: Ownership<Addr> = from_json(
-        contract
-            .query(
-                deps.as_ref(),
-                &mock_env(),
-                Cw721QueryMsg::GetCreatorOwnership {},
-            )
-            .unwrap(),
-    )
-    .unwrap();
-    assert_eq!(creator_ownership.owner, Some(random_info.sender.clone()));
+    // query operator should also return error
+    let res = contract.query_operator(
+        deps.as_ref(),
+        &late_env,
+        String::from("person"),
+        String::from("buddy"),
+        false,
+    );
 
-    let update_collection_info_msg = Cw721ExecuteMsg::UpdateCollectionInfo {
-        collection_info: CollectionInfoMsg {
-            name: Some("new name".to_string()),
-            symbol: Some("NEW".to_string()),
-            extension: None,
-        },
+    match res {
+        Err(StdError::NotFound { kind }) => assert_eq!(kind, "Approval not found"),
+        _ => panic!("Unexpected error"),
+    }
+}
+
+#[test]
+fn test_approve_all_expires_attribute() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let owner = mock_info("demeter", &[]);
+
+    let expires_attribute = |res: Response| {
+        res.attributes
+            .into_iter()
+            .find(|attr| attr.key == "expires")
+            .unwrap()
+            .value
     };
+    let cases = [
+        (
+            Expiration::AtHeight(env.block.height + 10),
+            format!("expiration height: {}", env.block.height + 10),
+        ),
+        (
+            Expiration::AtTime(env.block.time.plus_seconds(10)),
+            format!("expiration time: {}", env.block.time.plus_seconds(10)),
+        ),
+        (Expiration::Never {}, "expiration: never".to_string()),
+    ];
+    for (expires, expected) in cases {
+        let res = contract
+            .execute(
+                deps.as_mut(),
+                &env,
+                &owner,
+                Cw721ExecuteMsg::ApproveAll {
+                    operator: String::from("random"),
+                    expires: Some(expires),
+                },
+            )
+            .unwrap();
+        assert_eq!(expires_attribute(res), expected);
+    }
+}
+
+#[test]
+fn test_set_withdraw_address() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
 
-    // Old owner can not update.
-    let err: Cw721ContractError = contract
-        .execute(
+    // other than creator cant set
+    let err = contract
+        .set_withdraw_address(
             deps.as_mut(),
-            &mock_env(),
-            &creator_info,
-            update_collection_info_msg.clone(),
+            &Addr::unchecked(MINTER_ADDR),
+            "foo".to_string(),
         )
         .unwrap_err();
-    assert_eq!(err, Cw721ContractError::NotCreator {});
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
 
-    // New owner can update.
-    let _ = contract
-        .execute(
+    // creator can set
+    contract
+        .set_withdraw_address(
             deps.as_mut(),
-            &mock_env(),
-            &random_info,
-            update_collection_info_msg,
+            &Addr::unchecked(CREATOR_ADDR),
+            "foo".to_string(),
         )
         .unwrap();
+
+    let withdraw_address = contract
+        .config
+        .withdraw_address
+        .load(deps.as_ref().storage)
+        .unwrap();
+    assert_eq!(withdraw_address, "foo".to_string())
 }
 
 #[test]
-fn test_update_minter() {
+fn test_remove_withdraw_address() {
     let mut deps = mock_dependencies();
     let contract = setup_contract(deps.as_mut());
 
-    let token_id = "petrify".to_string();
-    let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
+    // other than creator cant remove
+    let err = contract
+        .remove_withdraw_address(deps.as_mut().storage, &Addr::unchecked(MINTER_ADDR))
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
 
-    let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id,
-        owner: String::from("medusa"),
-        token_uri: Some(token_uri.clone()),
-        extension: None,
-    };
+    // no withdraw address set yet
+    let err = contract
+        .remove_withdraw_address(deps.as_mut().storage, &Addr::unchecked(CREATOR_ADDR))
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NoWithdrawAddress {});
 
-    // Minter can mint
-    let current_minter_info = mock_info(MINTER_ADDR, &[]);
-    let _ = contract
-        .execute(deps.as_mut(), &mock_env(), &current_minter_info, mint_msg)
+    // set and remove
+    contract
+        .set_withdraw_address(
+            deps.as_mut(),
+            &Addr::unchecked(CREATOR_ADDR),
+            "foo".to_string(),
+        )
+        .unwrap();
+    contract
+        .remove_withdraw_address(deps.as_mut().storage, &Addr::unchecked(CREATOR_ADDR))
         .unwrap();
+    assert!(!contract
+        .config
+        .withdraw_address
+        .exists(deps.as_ref().storage));
 
-    // Update the owner to "random". The new owner should be able to
-    // mint new tokens, the old one should not.
+    // test that we can set again
     contract
-        .execute(
+        .set_withdraw_address(
             deps.as_mut(),
-            &mock_env(),
-            &current_minter_info,
-            Cw721ExecuteMsg::UpdateMinterOwnership(Action::TransferOwnership {
-                new_owner: "random".to_string(),
-                expiry: None,
-            }),
+            &Addr::unchecked(CREATOR_ADDR),
+            "foo".to_string(),
         )
         .unwrap();
+    let withdraw_address = contract
+        .config
+        .withdraw_address
+        .load(deps.as_ref().storage)
+        .unwrap();
+    assert_eq!(withdraw_address, "foo".to_string())
+}
 
-    // Minter does not change until ownership transfer completes.
-    // Pending ownership transfer should be discoverable via query.
-    let ownership: Ownership<Addr> = from_json(
-        contract
-            .query(
-                deps.as_ref(),
-                &mock_env(),
-                Cw721QueryMsg::GetMinterOwnership {},
-            )
-            .unwrap(),
-    )
-    .unwrap();
+#[test]
+fn test_withdraw_funds() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
 
-    assert_eq!(
-        ownership,
-        Ownership::<Addr> {
-            owner: Some(Addr::unchecked(MINTER_ADDR)),
-            pending_owner: Some(Addr::unchecked("random")),
-            pending_expiry: None,
-        }
-    );
+    // no withdraw address set
+    let err = contract
+        .withdraw_funds(deps.as_mut().storage, &Coin::new(100, "uark"))
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NoWithdrawAddress {});
 
-    // Accept the ownership transfer.
-    let new_minter_info = mock_info("random", &[]);
+    // set and withdraw by non-creator
     contract
-        .execute(
+        .set_withdraw_address(
             deps.as_mut(),
-            &mock_env(),
-            &new_minter_info,
-            Cw721ExecuteMsg::UpdateMinterOwnership(Action::AcceptOwnership),
+            &Addr::unchecked(CREATOR_ADDR),
+            "foo".to_string(),
         )
         .unwrap();
+    contract
+        .withdraw_funds(deps.as_mut().storage, &Coin::new(100, "uark"))
+        .unwrap();
+}
 
-    // Minter changes after ownership transfer is accepted.
-    let minter_ownership: Ownership<Addr> = from_json(
-        contract
-            .query(
-                deps.as_ref(),
-                &mock_env(),
-                Cw721QueryMsg::GetMinterOwnership {},
-            )
-            .unwrap(),
-    )
-    .unwrap();
-    assert_eq!(minter_ownership.owner, Some(new_minter_info.sender.clone()));
-
-    let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: "randoms_token".to_string(),
-        owner: String::from("medusa"),
-        token_uri: Some(token_uri),
-        extension: None,
-    };
+#[test]
+fn test_withdraw_all() {
+    let balances = vec![Coin::new(100, "uark"), Coin::new(5, "uatom")];
+    let mut deps = mock_dependencies_with_balance(&balances);
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let random = mock_info("random", &[]);
 
-    // Old owner can not mint.
-    let err: Cw721ContractError = contract
+    // no withdraw address set
+    let err = contract
         .execute(
             deps.as_mut(),
-            &mock_env(),
-            &current_minter_info,
-            mint_msg.clone(),
+            &env,
+            &random,
+            Cw721ExecuteMsg::WithdrawAll {},
         )
         .unwrap_err();
-    assert_eq!(err, Cw721ContractError::NotMinter {});
+    assert_eq!(err, Cw721ContractError::NoWithdrawAddress {});
 
-    // New owner can mint.
-    let _ = contract
-        .execute(deps.as_mut(), &mock_env(), &new_minter_info, mint_msg)
+    // anyone can sweep all denoms in one message
+    contract
+        .set_withdraw_address(
+            deps.as_mut(),
+            &Addr::unchecked(CREATOR_ADDR),
+            "foo".to_string(),
+        )
         .unwrap();
-}
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &random,
+            Cw721ExecuteMsg::WithdrawAll {},
+        )
+        .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "foo".to_string(),
+            amount: balances,
+        })
+    );
+    assert_eq!(res.messages.len(), 1);
 
-#[test]
-fn test_burn() {
+    // nothing to sweep
     let mut deps = mock_dependencies();
     let contract = setup_contract(deps.as_mut());
-
-    let token_id = "petrify".to_string();
-    let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
-
-    let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
-        owner: MINTER_ADDR.to_string(),
-        token_uri: Some(token_uri),
-        extension: None,
-    };
-
-    let burn_msg = Cw721ExecuteMsg::Burn { token_id };
-
-    // mint some NFT
-    let allowed = mock_info(MINTER_ADDR, &[]);
-    let _ = contract
-        .execute(deps.as_mut(), &mock_env(), &allowed, mint_msg)
+    contract
+        .set_withdraw_address(
+            deps.as_mut(),
+            &Addr::unchecked(CREATOR_ADDR),
+            "foo".to_string(),
+        )
+        .unwrap();
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &random,
+            Cw721ExecuteMsg::WithdrawAll {},
+        )
         .unwrap();
+    assert!(res.messages.is_empty());
+}
 
-    // random not allowed to burn
-    let random = mock_info("random", &[]);
+#[test]
+fn test_withdrawable_balance() {
+    let balances = vec![Coin::new(100, "uark"), Coin::new(5, "uatom")];
+    let mut deps = mock_dependencies_with_balance(&balances);
+    let contract = setup_contract(deps.as_mut());
     let env = mock_env();
-    let err = contract
-        .execute(deps.as_mut(), &env, &random, burn_msg.clone())
-        .unwrap_err();
-
-    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
-
-    let _ = contract
-        .execute(deps.as_mut(), &env, &allowed, burn_msg)
+    let res = contract
+        .query_withdrawable_balance(deps.as_ref(), &env)
         .unwrap();
+    assert_eq!(res, balances);
 
-    // ensure num tokens decreases
-    let count = contract.query_num_tokens(deps.as_ref().storage).unwrap();
-    assert_eq!(0, count.count);
-
-    // trying to get nft returns error
-    let _ = contract
-        .query_nft_info(deps.as_ref().storage, "petrify".to_string())
-        .unwrap_err();
+    contract
+        .set_withdraw_address(
+            deps.as_mut(),
+            &Addr::unchecked(CREATOR_ADDR),
+            "foo".to_string(),
+        )
+        .unwrap();
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("random", &[]),
+            Cw721ExecuteMsg::WithdrawFunds {
+                amount: Coin::new(40, "uark"),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "foo".to_string(),
+            amount: vec![Coin::new(40, "uark")],
+        })
+    );
 
-    // list the token_ids
-    let tokens = contract
-        .query_all_tokens(deps.as_ref(), &env, None, None)
+    // balance drops once the bank send is executed
+    deps.querier.update_balance(
+        env.contract.address.clone(),
+        vec![Coin::new(60, "uark"), Coin::new(5, "uatom")],
+    );
+    let res = contract
+        .query_withdrawable_balance(deps.as_ref(), &env)
         .unwrap();
-    assert!(tokens.tokens.is_empty());
+    assert_eq!(res, vec![Coin::new(60, "uark"), Coin::new(5, "uatom")]);
 }
 
 #[test]
-fn test_transfer_nft() {
+fn test_burn_fee() {
     let mut deps = mock_dependencies();
     let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let fee = Coin::new(100, "uark");
 
-    // Mint a token
-    let token_id = "melt".to_string();
-    let token_uri = "https://www.merriam-webster.com/dictionary/melt".to_string();
+    let minter = mock_info(MINTER_ADDR, &[]);
+    for token_id in ["1", "2"] {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: String::from("venus"),
+            token_uri: None,
+            extension: None,
+            ibc_data: None,
+        };
+        contract
+            .execute(deps.as_mut(), &env, &minter, mint_msg)
+            .unwrap();
+    }
 
-    let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
-        owner: String::from("venus"),
-        token_uri: Some(token_uri),
-        extension: None,
-    };
+    // other than creator cant set
+    let err = contract
+        .set_burn_fee(
+            deps.as_mut().storage,
+            &Addr::unchecked(MINTER_ADDR),
+            Some(fee.clone()),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
 
-    let minter = mock_info(MINTER_ADDR, &[]);
+    // creator can set
+    let set_burn_fee_msg = Cw721ExecuteMsg::SetBurnFee {
+        burn_fee: Some(fee.clone()),
+    };
     contract
-        .execute(deps.as_mut(), &mock_env(), &minter, mint_msg)
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(CREATOR_ADDR, &[]),
+            set_burn_fee_msg,
+        )
         .unwrap();
+    let res: Option<Coin> = from_json(
+        contract
+            .query(deps.as_ref(), &env, Cw721QueryMsg::GetBurnFee {})
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res, Some(fee.clone()));
 
-    // random cannot transfer
-    let random = mock_info("random", &[]);
-    let transfer_msg = Cw721ExecuteMsg::TransferNft {
-        recipient: String::from("random"),
-        token_id: token_id.clone(),
+    // missing, insufficient or excess fee
+    let burn_msg = Cw721ExecuteMsg::Burn {
+        token_id: "1".to_string(),
     };
-
     let err = contract
-        .execute(deps.as_mut(), &mock_env(), &random, transfer_msg)
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[]),
+            burn_msg.clone(),
+        )
         .unwrap_err();
-    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    assert_eq!(
+        err,
+        Cw721ContractError::MissingPayment {
+            expected: fee.clone()
+        }
+    );
+    for funds in [
+        vec![Coin::new(99, "uark")],
+        vec![Coin::new(101, "uark")],
+        vec![Coin::new(100, "uatom")],
+    ] {
+        let err = contract
+            .execute(
+                deps.as_mut(),
+                &env,
+                &mock_info("venus", &funds),
+                burn_msg.clone(),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            Cw721ContractError::WrongPayment {
+                expected: fee.clone()
+            }
+        );
+    }
 
-    // owner can
-    let random = mock_info("venus", &[]);
-    let transfer_msg = Cw721ExecuteMsg::TransferNft {
-        recipient: String::from("random"),
-        token_id: token_id.clone(),
+    // without withdraw address, fee stays in contract
+    let burn_msg = Cw721ExecuteMsg::Burn {
+        token_id: "1".to_string(),
     };
-
     let res = contract
-        .execute(deps.as_mut(), &mock_env(), &random, transfer_msg)
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[fee.clone()]),
+            burn_msg,
+        )
         .unwrap();
+    assert!(res.messages.is_empty());
 
+    // fee is forwarded to withdraw address
+    contract
+        .set_withdraw_address(
+            deps.as_mut(),
+            &Addr::unchecked(CREATOR_ADDR),
+            "foo".to_string(),
+        )
+        .unwrap();
+    let burn_msg = Cw721ExecuteMsg::Burn {
+        token_id: "2".to_string(),
+    };
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[fee.clone()]),
+            burn_msg,
+        )
+        .unwrap();
     assert_eq!(
-        res,
-        Response::new()
-            .add_attribute("action", "transfer_nft")
-            .add_attribute("sender", "venus")
-            .add_attribute("recipient", "random")
-            .add_attribute("token_id", token_id)
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "foo".to_string(),
+            amount: vec![fee],
+        })
+    );
+    assert_eq!(
+        contract
+            .query_num_tokens(deps.as_ref().storage)
+            .unwrap()
+            .count,
+        0
     );
 }
 
 #[test]
-fn test_send_nft() {
+fn test_transfer_fee() {
     let mut deps = mock_dependencies();
     let contract = setup_contract(deps.as_mut());
-
-    // Mint a token
-    let token_id = "melt".to_string();
-    let token_uri = "https://www.merriam-webster.com/dictionary/melt".to_string();
-
-    let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
-        owner: String::from("venus"),
-        token_uri: Some(token_uri),
-        extension: None,
-    };
+    let env = mock_env();
+    let fee = Coin::new(10, "uark");
 
     let minter = mock_info(MINTER_ADDR, &[]);
-    contract
-        .execute(deps.as_mut(), &mock_env(), &minter, mint_msg)
-        .unwrap();
+    for token_id in ["1", "2", "3"] {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: String::from("venus"),
+            token_uri: None,
+            extension: None,
+            ibc_data: None,
+        };
+        contract
+            .execute(deps.as_mut(), &env, &minter, mint_msg)
+            .unwrap();
+    }
 
-    let msg = to_json_binary("You now have the melting power").unwrap();
-    let target = String::from("another_contract");
-    let send_msg = Cw721ExecuteMsg::SendNft {
-        contract: target.clone(),
-        token_id: token_id.clone(),
-        msg: msg.clone(),
+    let update_fee_msg = |transfer_fee: Clearable<Coin>| Cw721ExecuteMsg::UpdateCollectionInfo {
+        collection_info: CollectionInfoMsg {
+            name: None,
+            symbol: None,
+            extension: None,
+            collection_metadata_uri: Clearable::NoChange,
+            transfer_fee,
+        },
     };
 
-    let random = mock_info("random", &[]);
+    // other than creator cant set
     let err = contract
-        .execute(deps.as_mut(), &mock_env(), &random, send_msg.clone())
+        .execute(
+            deps.as_mut(),
+            &env,
+            &minter,
+            update_fee_msg(Clearable::Set(fee.clone())),
+        )
         .unwrap_err();
-    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    assert_eq!(err, Cw721ContractError::NotCreator {});
 
-    // but owner can
-    let random = mock_info("venus", &[]);
-    let res = contract
-        .execute(deps.as_mut(), &mock_env(), &random, send_msg)
+    // creator can set
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(CREATOR_ADDR, &[]),
+            update_fee_msg(Clearable::Set(fee.clone())),
+        )
         .unwrap();
+    let res: Option<Coin> = from_json(
+        contract
+            .query(deps.as_ref(), &env, Cw721QueryMsg::GetTransferFee {})
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res, Some(fee.clone()));
 
-    let payload = Cw721ReceiveMsg {
-        sender: String::from("venus"),
-        token_id: token_id.clone(),
-        msg,
+    // missing or insufficient fee
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("mars"),
+        token_id: "1".to_string(),
+        expected_owner: None,
     };
-    let expected = payload.into_cosmos_msg(target.clone()).unwrap();
-    // ensure expected serializes as we think it should
-    match &expected {
-        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => {
-            assert_eq!(contract_addr, &target)
-        }
-        m => panic!("Unexpected message type: {m:?}"),
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[]),
+            transfer_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::TransferFeeRequired {});
+    for funds in [
+        vec![Coin::new(9, "uark")],
+        vec![Coin::new(11, "uark")],
+        vec![Coin::new(10, "uatom")],
+    ] {
+        let err = contract
+            .execute(
+                deps.as_mut(),
+                &env,
+                &mock_info("venus", &funds),
+                transfer_msg.clone(),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            Cw721ContractError::WrongPayment {
+                expected: fee.clone()
+            }
+        );
     }
-    // and make sure this is the request sent by the contract
-    assert_eq!(
-        res,
-        Response::new()
-            .add_message(expected)
-            .add_attribute("action", "send_nft")
-            .add_attribute("sender", "venus")
-            .add_attribute("recipient", "another_contract")
-            .add_attribute("token_id", token_id)
-    );
-}
-
-#[test]
-fn test_approve_revoke() {
-    let mut deps = mock_dependencies();
-    let contract = setup_contract(deps.as_mut());
-
-    // Mint a token
-    let token_id = "grow".to_string();
-    let token_uri = "https://www.merriam-webster.com/dictionary/grow".to_string();
 
-    let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
-        owner: String::from("demeter"),
-        token_uri: Some(token_uri),
-        extension: None,
-    };
-
-    let minter = mock_info(MINTER_ADDR, &[]);
+    // fee is forwarded to withdraw address
     contract
-        .execute(deps.as_mut(), &mock_env(), &minter, mint_msg)
+        .set_withdraw_address(
+            deps.as_mut(),
+            &Addr::unchecked(CREATOR_ADDR),
+            "foo".to_string(),
+        )
         .unwrap();
-
-    // token owner shows in approval query
     let res = contract
-        .query_approval(
-            deps.as_ref(),
-            &mock_env(),
-            token_id.clone(),
-            String::from("demeter"),
-            false,
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[fee.clone()]),
+            transfer_msg,
         )
         .unwrap();
     assert_eq!(
-        res,
-        ApprovalResponse {
-            approval: Approval {
-                spender: Addr::unchecked("demeter"),
-                expires: Expiration::Never {}
-            }
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "foo".to_string(),
+            amount: vec![fee.clone()],
+        })
+    );
+
+    // fee is paid per distributed token
+    let distribute_msg = Cw721ExecuteMsg::DistributeNfts {
+        transfers: vec![
+            NftTransfer {
+                recipient: String::from("alice"),
+                token_id: String::from("2"),
+            },
+            NftTransfer {
+                recipient: String::from("bob"),
+                token_id: String::from("3"),
+            },
+        ],
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[fee.clone()]),
+            distribute_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::WrongPayment {
+            expected: Coin::new(20, "uark")
         }
     );
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[Coin::new(20, "uark")]),
+            distribute_msg,
+        )
+        .unwrap();
 
-    // Give random transferring power
-    let approve_msg = Cw721ExecuteMsg::Approve {
-        spender: String::from("random"),
-        token_id: token_id.clone(),
+    // fee for both tokens of a swap is paid by counterparty on accept
+    let propose_msg = Cw721ExecuteMsg::ProposeSwap {
+        my_token: "2".to_string(),
+        want_token: "3".to_string(),
+        counterparty: "bob".to_string(),
         expires: None,
-    };
-    let owner = mock_info("demeter", &[]);
-    let res = contract
-        .execute(deps.as_mut(), &mock_env(), &owner, approve_msg)
+    };
+    contract
+        .execute(deps.as_mut(), &env, &mock_info("alice", &[]), propose_msg)
         .unwrap();
+    let accept_msg = Cw721ExecuteMsg::AcceptSwap { offer_id: 1 };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("bob", &[fee.clone()]),
+            accept_msg.clone(),
+        )
+        .unwrap_err();
     assert_eq!(
-        res,
-        Response::new()
-            .add_attribute("action", "approve")
-            .add_attribute("sender", "demeter")
-            .add_attribute("spender", "random")
-            .add_attribute("token_id", token_id.clone())
+        err,
+        Cw721ContractError::WrongPayment {
+            expected: Coin::new(20, "uark")
+        }
     );
-
-    // test approval query
     let res = contract
-        .query_approval(
-            deps.as_ref(),
-            &mock_env(),
-            token_id.clone(),
-            String::from("random"),
-            true,
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("bob", &[Coin::new(20, "uark")]),
+            accept_msg,
         )
         .unwrap();
     assert_eq!(
-        res,
-        ApprovalResponse {
-            approval: Approval {
-                spender: Addr::unchecked("random"),
-                expires: Expiration::Never {}
-            }
-        }
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "foo".to_string(),
+            amount: vec![Coin::new(20, "uark")],
+        })
     );
 
-    // random can now transfer
-    let random = mock_info("random", &[]);
+    // without fee, no funds are needed
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(CREATOR_ADDR, &[]),
+            update_fee_msg(Clearable::Clear),
+        )
+        .unwrap();
     let transfer_msg = Cw721ExecuteMsg::TransferNft {
-        recipient: String::from("person"),
-        token_id: token_id.clone(),
+        recipient: String::from("venus"),
+        token_id: "1".to_string(),
+        expected_owner: None,
+    };
+    let res = contract
+        .execute(deps.as_mut(), &env, &mock_info("mars", &[]), transfer_msg)
+        .unwrap();
+    assert!(res.messages.is_empty());
+}
+
+#[test]
+fn test_accepted_denoms() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let creator = mock_info(CREATOR_ADDR, &[]);
+
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: "1".to_string(),
+        owner: String::from("venus"),
+        token_uri: None,
+        extension: None,
+        ibc_data: None,
     };
     contract
-        .execute(deps.as_mut(), &mock_env(), &random, transfer_msg)
+        .execute(deps.as_mut(), &env, &mock_info(MINTER_ADDR, &[]), mint_msg)
         .unwrap();
 
-    // Approvals are removed / cleared
-    let query_msg = Cw721QueryMsg::OwnerOf {
-        token_id: token_id.clone(),
-        include_expired: None,
+    // other than creator cant set
+    let set_accepted_denoms_msg = Cw721ExecuteMsg::SetAcceptedDenoms {
+        accepted_denoms: vec!["uark".to_string()],
     };
-    let res: OwnerOfResponse = from_json(
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(MINTER_ADDR, &[]),
+            set_accepted_denoms_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    // burn fee set before denom got delisted
+    let set_burn_fee_msg = Cw721ExecuteMsg::SetBurnFee {
+        burn_fee: Some(Coin::new(100, "uatom")),
+    };
+    contract
+        .execute(deps.as_mut(), &env, &creator, set_burn_fee_msg.clone())
+        .unwrap();
+    contract
+        .execute(deps.as_mut(), &env, &creator, set_accepted_denoms_msg)
+        .unwrap();
+    let res: Vec<String> = from_json(
         contract
-            .query(deps.as_ref(), &mock_env(), query_msg.clone())
+            .query(deps.as_ref(), &env, Cw721QueryMsg::GetAcceptedDenoms {})
             .unwrap(),
     )
     .unwrap();
+    assert_eq!(res, vec!["uark".to_string()]);
+
+    // rejected denom: neither burn fee can be set nor paid
+    let err = contract
+        .execute(deps.as_mut(), &env, &creator, set_burn_fee_msg)
+        .unwrap_err();
     assert_eq!(
-        res,
-        OwnerOfResponse {
-            owner: String::from("person"),
-            approvals: vec![],
+        err,
+        Cw721ContractError::UnsupportedDenom {
+            denom: "uatom".to_string()
+        }
+    );
+    let burn_msg = Cw721ExecuteMsg::Burn {
+        token_id: "1".to_string(),
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[Coin::new(100, "uatom")]),
+            burn_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::UnsupportedDenom {
+            denom: "uatom".to_string()
         }
     );
 
-    // Approve, revoke, and check for empty, to test revoke
-    let approve_msg = Cw721ExecuteMsg::Approve {
-        spender: String::from("random"),
-        token_id: token_id.clone(),
-        expires: None,
+    // accepted denom: burn fee can be set and paid
+    let set_burn_fee_msg = Cw721ExecuteMsg::SetBurnFee {
+        burn_fee: Some(Coin::new(100, "uark")),
     };
-    let owner = mock_info("person", &[]);
     contract
-        .execute(deps.as_mut(), &mock_env(), &owner, approve_msg)
+        .execute(deps.as_mut(), &env, &creator, set_burn_fee_msg)
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[Coin::new(100, "uark")]),
+            burn_msg,
+        )
         .unwrap();
 
-    let revoke_msg = Cw721ExecuteMsg::Revoke {
-        spender: String::from("random"),
-        token_id,
+    // empty list accepts all denoms
+    let set_accepted_denoms_msg = Cw721ExecuteMsg::SetAcceptedDenoms {
+        accepted_denoms: vec![],
     };
     contract
-        .execute(deps.as_mut(), &mock_env(), &owner, revoke_msg)
+        .execute(deps.as_mut(), &env, &creator, set_accepted_denoms_msg)
+        .unwrap();
+    let set_burn_fee_msg = Cw721ExecuteMsg::SetBurnFee {
+        burn_fee: Some(Coin::new(100, "uatom")),
+    };
+    contract
+        .execute(deps.as_mut(), &env, &creator, set_burn_fee_msg)
         .unwrap();
-
-    // Approvals are now removed / cleared
-    let res: OwnerOfResponse = from_json(
-        contract
-            .query(deps.as_ref(), &mock_env(), query_msg)
-            .unwrap(),
-    )
-    .unwrap();
-    assert_eq!(
-        res,
-        OwnerOfResponse {
-            owner: String::from("person"),
-            approvals: vec![],
-        }
-    );
 }
 
 #[test]
-fn test_approve_all_revoke_all() {
+fn test_update_royalty_recipient() {
     let mut deps = mock_dependencies();
     let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let creator = mock_info(CREATOR_ADDR, &[]);
+    let update_msg = Cw721ExecuteMsg::UpdateRoyaltyRecipient {
+        address: "treasury".to_string(),
+    };
 
-    // Mint a couple tokens (from the same owner)
-    let token_id1 = "grow1".to_string();
-    let token_uri1 = "https://www.merriam-webster.com/dictionary/grow1".to_string();
-
-    let token_id2 = "grow2".to_string();
-    let token_uri2 = "https://www.merriam-webster.com/dictionary/grow2".to_string();
+    // no royalty set
+    let err = contract
+        .execute(deps.as_mut(), &env, &creator, update_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NoRoyaltyInfo {});
 
-    let mint_msg1 = Cw721ExecuteMsg::Mint {
-        token_id: token_id1.clone(),
-        owner: String::from("demeter"),
-        token_uri: Some(token_uri1),
-        extension: None,
+    let set_royalty_msg = Cw721ExecuteMsg::UpdateCollectionInfo {
+        collection_info: CollectionInfoMsg {
+            name: None,
+            symbol: None,
+            extension: Some(CollectionExtensionMsg {
+                description: Some("description".to_string()),
+                image: Some("https://moonphases.org".to_string()),
+                explicit_content: Clearable::Set(true),
+                external_link: Clearable::NoChange,
+                start_trading_time: Clearable::NoChange,
+                royalty_info: Clearable::Set(RoyaltyInfoResponse {
+                    payment_address: "payment_address".into(),
+                    share: "0.05".parse().unwrap(),
+                }),
+            }),
+            collection_metadata_uri: Clearable::NoChange,
+            transfer_fee: Clearable::NoChange,
+        },
     };
-
-    let minter = mock_info(MINTER_ADDR, &[]);
     contract
-        .execute(deps.as_mut(), &mock_env(), &minter, mint_msg1)
+        .execute(deps.as_mut(), &env, &creator, set_royalty_msg)
+        .unwrap();
+    let before = contract
+        .query_collection_info_and_extension(deps.as_ref())
         .unwrap();
 
-    let mint_msg2 = Cw721ExecuteMsg::Mint {
-        token_id: token_id2.clone(),
-        owner: String::from("demeter"),
-        token_uri: Some(token_uri2),
-        extension: None,
-    };
+    // other than creator cant update
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(MINTER_ADDR, &[]),
+            update_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
 
-    let env = mock_env();
+    // only recipient changes
     contract
-        .execute(deps.as_mut(), &env, &minter, mint_msg2)
-        .unwrap();
-
-    // paginate the token_ids
-    let tokens = contract
-        .query_all_tokens(deps.as_ref(), &env, None, Some(1))
+        .execute(deps.as_mut(), &env, &creator, update_msg)
         .unwrap();
-    assert_eq!(1, tokens.tokens.len());
-    assert_eq!(vec![token_id1.clone()], tokens.tokens);
-    let tokens = contract
-        .query_all_tokens(deps.as_ref(), &env, Some(token_id1.clone()), Some(3))
+    let after = contract
+        .query_collection_info_and_extension(deps.as_ref())
         .unwrap();
-    assert_eq!(1, tokens.tokens.len());
-    assert_eq!(vec![token_id2.clone()], tokens.tokens);
+    let mut expected = before.extension.unwrap();
+    expected.royalty_info = Some(RoyaltyInfo {
+        payment_address: Addr::unchecked("treasury"),
+        share: "0.05".parse().unwrap(),
+    });
+    assert_eq!(after.extension, Some(expected));
+    assert_eq!(after.name, before.name);
+    assert_eq!(after.symbol, before.symbol);
+}
 
-    // demeter gives random full (operator) power over her tokens
-    let approve_all_msg = Cw721ExecuteMsg::ApproveAll {
-        operator: String::from("random"),
-        expires: None,
+#[test]
+fn test_token_royalty() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let creator = mock_info(CREATOR_ADDR, &[]);
+    for token_id in ["melt", "grow"] {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: "venus".to_string(),
+            token_uri: None,
+            extension: None,
+            ibc_data: None,
+        };
+        contract
+            .execute(deps.as_mut(), &env, &mock_info(MINTER_ADDR, &[]), mint_msg)
+            .unwrap();
+    }
+    let royalty_info = |deps: Deps, token_id: &str| -> RoyaltyAmountResponse {
+        from_json(
+            contract
+                .query(
+                    deps,
+                    &env,
+                    Cw721QueryMsg::RoyaltyInfo {
+                        token_id: token_id.to_string(),
+                        sale_price: Uint128::new(1000),
+                    },
+                )
+                .unwrap(),
+        )
+        .unwrap()
     };
-    let owner = mock_info("demeter", &[]);
-    let res = contract
-        .execute(deps.as_mut(), &mock_env(), &owner, approve_all_msg)
-        .unwrap();
+    let set_token_royalty_msg = |share: &str| Cw721ExecuteMsg::SetTokenRoyalty {
+        token_id: "melt".to_string(),
+        royalty: Some(RoyaltyInfoResponse {
+            payment_address: "artist".to_string(),
+            share: share.parse().unwrap(),
+        }),
+    };
+
+    // no royalty at all
     assert_eq!(
-        res,
-        Response::new()
-            .add_attribute("action", "approve_all")
-            .add_attribute("sender", "demeter")
-            .add_attribute("operator", "random")
+        royalty_info(deps.as_ref(), "melt"),
+        RoyaltyAmountResponse {
+            address: None,
+            royalty_amount: Uint128::zero(),
+            payments: vec![],
+        }
     );
 
-    // random can now transfer
-    let random = mock_info("random", &[]);
-    let transfer_msg = Cw721ExecuteMsg::TransferNft {
-        recipient: String::from("person"),
-        token_id: token_id1,
+    // collection royalty applies to all tokens
+    let set_royalty_msg = Cw721ExecuteMsg::UpdateCollectionInfo {
+        collection_info: CollectionInfoMsg {
+            name: None,
+            symbol: None,
+            extension: Some(CollectionExtensionMsg {
+                description: Some("description".to_string()),
+                image: Some("https://moonphases.org".to_string()),
+                explicit_content: Clearable::NoChange,
+                external_link: Clearable::NoChange,
+                start_trading_time: Clearable::NoChange,
+                royalty_info: Clearable::Set(RoyaltyInfoResponse {
+                    payment_address: "payment_address".into(),
+                    share: "0.05".parse().unwrap(),
+                }),
+            }),
+            collection_metadata_uri: Clearable::NoChange,
+            transfer_fee: Clearable::NoChange,
+        },
     };
     contract
-        .execute(deps.as_mut(), &mock_env(), &random, transfer_msg)
+        .execute(deps.as_mut(), &env, &creator, set_royalty_msg)
         .unwrap();
-
-    // random can now send
-    let inner_msg = WasmMsg::Execute {
-        contract_addr: "another_contract".into(),
-        msg: to_json_binary("You now also have the growing power").unwrap(),
-        funds: vec![],
+    let collection_royalty = RoyaltyAmountResponse {
+        address: Some("payment_address".to_string()),
+        royalty_amount: Uint128::new(50),
+        payments: vec![RoyaltyPayment {
+            address: "payment_address".to_string(),
+            amount: Uint128::new(50),
+        }],
     };
-    let msg: CosmosMsg = CosmosMsg::Wasm(inner_msg);
+    assert_eq!(royalty_info(deps.as_ref(), "melt"), collection_royalty);
 
-    let send_msg = Cw721ExecuteMsg::SendNft {
-        contract: String::from("another_contract"),
-        token_id: token_id2,
-        msg: to_json_binary(&msg).unwrap(),
-    };
-    contract
-        .execute(deps.as_mut(), &mock_env(), &random, send_msg)
-        .unwrap();
+    // other than creator cant set
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(MINTER_ADDR, &[]),
+            set_token_royalty_msg("0.07"),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    // unknown token
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &creator,
+            Cw721ExecuteMsg::SetTokenRoyalty {
+                token_id: "unknown".to_string(),
+                royalty: None,
+            },
+        )
+        .unwrap_err();
+    assert!(matches!(err, Cw721ContractError::Std(_)));
 
-    // Approve_all, revoke_all, and check for empty, to test revoke_all
-    let approve_all_msg = Cw721ExecuteMsg::ApproveAll {
-        operator: String::from("operator"),
-        expires: None,
-    };
-    // person is now the owner of the tokens
-    let owner = mock_info("person", &[]);
+    // share increase is limited, relative to collection royalty
+    let err = contract
+        .execute(deps.as_mut(), &env, &creator, set_token_royalty_msg("0.2"))
+        .unwrap_err();
+    assert!(matches!(err, Cw721ContractError::InvalidRoyalties(_)));
+
+    // token royalty takes precedence, other tokens keep collection royalty
     contract
-        .execute(deps.as_mut(), &mock_env(), &owner, approve_all_msg)
+        .execute(deps.as_mut(), &env, &creator, set_token_royalty_msg("0.07"))
         .unwrap();
+    assert_eq!(
+        royalty_info(deps.as_ref(), "melt"),
+        RoyaltyAmountResponse {
+            address: Some("artist".to_string()),
+            royalty_amount: Uint128::new(70),
+            payments: vec![RoyaltyPayment {
+                address: "artist".to_string(),
+                amount: Uint128::new(70),
+            }],
+        }
+    );
+    assert_eq!(royalty_info(deps.as_ref(), "grow"), collection_royalty);
 
-    // query for operator should return approval
-    let res = contract
-        .query_operator(
-            deps.as_ref(),
-            &mock_env(),
-            String::from("person"),
-            String::from("operator"),
-            true,
+    // paid royalty goes to token royalty recipient
+    let res: Response = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("market", &[Coin::new(1000, "uatom")]),
+            Cw721ExecuteMsg::PayRoyalty {
+                token_id: "melt".to_string(),
+            },
         )
         .unwrap();
     assert_eq!(
-        res,
-        OperatorResponse {
-            approval: Approval {
-                spender: Addr::unchecked("operator"),
-                expires: Expiration::Never {}
-            }
-        }
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "artist".to_string(),
+            amount: vec![Coin::new(70, "uatom")],
+        })
     );
 
-    // query for other should throw error
-    let res = contract.query_operator(
-        deps.as_ref(),
-        &mock_env(),
-        String::from("person"),
-        String::from("other"),
-        true,
-    );
-    match res {
-        Err(StdError::NotFound { kind }) => assert_eq!(kind, "Approval not found"),
-        _ => panic!("Unexpected error"),
-    }
+    // token royalty respects the cap
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &creator,
+            Cw721ExecuteMsg::SetMaxRoyaltyBps {
+                max_royalty_bps: 800,
+            },
+        )
+        .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), &env, &creator, set_token_royalty_msg("0.09"))
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::RoyaltyExceedsCap {});
 
-    let res = contract
-        .query_operators(
-            deps.as_ref(),
-            &mock_env(),
-            String::from("person"),
-            true,
-            None,
-            None,
+    // clearing reverts to collection royalty
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &creator,
+            Cw721ExecuteMsg::SetTokenRoyalty {
+                token_id: "melt".to_string(),
+                royalty: None,
+            },
         )
         .unwrap();
+    assert_eq!(royalty_info(deps.as_ref(), "melt"), collection_royalty);
+}
+
+#[test]
+fn test_royalty_summary() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let creator = mock_info(CREATOR_ADDR, &[]);
+    for token_id in ["grow", "harvest", "melt"] {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: "venus".to_string(),
+            token_uri: None,
+            extension: None,
+            ibc_data: None,
+        };
+        contract
+            .execute(deps.as_mut(), &env, &mock_info(MINTER_ADDR, &[]), mint_msg)
+            .unwrap();
+    }
+    let royalty_summary =
+        |deps: Deps, start_after: Option<&str>, limit: Option<u32>| -> RoyaltySummaryResponse {
+            from_json(
+                contract
+                    .query(
+                        deps,
+                        &env,
+                        Cw721QueryMsg::RoyaltySummary {
+                            start_after: start_after.map(str::to_string),
+                            limit,
+                        },
+                    )
+                    .unwrap(),
+            )
+            .unwrap()
+        };
+    let royalty = |payment_address: &str, share: &str| RoyaltyInfoResponse {
+        payment_address: payment_address.to_string(),
+        share: share.parse().unwrap(),
+    };
+    let token_royalty = |token_id: &str, share: &str| TokenRoyalty {
+        token_id: token_id.to_string(),
+        royalty_info: royalty("artist", share),
+    };
+    let set_token_royalty = |deps: DepsMut, token_id: &str, share: Option<&str>| {
+        contract
+            .execute(
+                deps,
+                &env,
+                &creator,
+                Cw721ExecuteMsg::SetTokenRoyalty {
+                    token_id: token_id.to_string(),
+                    royalty: share.map(|share| royalty("artist", share)),
+                },
+            )
+            .unwrap();
+    };
+
+    // no royalty at all
     assert_eq!(
-        res,
-        OperatorsResponse {
-            operators: vec![Approval {
-                spender: Addr::unchecked("operator"),
-                expires: Expiration::Never {}
-            }]
+        royalty_summary(deps.as_ref(), None, None),
+        RoyaltySummaryResponse {
+            royalty_info: None,
+            token_royalties: vec![],
         }
     );
 
-    // second approval
-    let buddy_expires = Expiration::AtHeight(1234567);
-    let approve_all_msg = Cw721ExecuteMsg::ApproveAll {
-        operator: String::from("buddy"),
-        expires: Some(buddy_expires),
+    // collection royalty is the default, overrides are listed by token id
+    let set_royalty_msg = Cw721ExecuteMsg::UpdateCollectionInfo {
+        collection_info: CollectionInfoMsg {
+            name: None,
+            symbol: None,
+            extension: Some(CollectionExtensionMsg {
+                description: Some("description".to_string()),
+                image: Some("https://moonphases.org".to_string()),
+                explicit_content: Clearable::NoChange,
+                external_link: Clearable::NoChange,
+                start_trading_time: Clearable::NoChange,
+                royalty_info: Clearable::Set(royalty("payment_address", "0.05")),
+            }),
+            collection_metadata_uri: Clearable::NoChange,
+            transfer_fee: Clearable::NoChange,
+        },
     };
-    let owner = mock_info("person", &[]);
     contract
-        .execute(deps.as_mut(), &mock_env(), &owner, approve_all_msg)
-        .unwrap();
-
-    // and paginate queries
-    let res = contract
-        .query_operators(
-            deps.as_ref(),
-            &mock_env(),
-            String::from("person"),
-            true,
-            None,
-            Some(1),
-        )
+        .execute(deps.as_mut(), &env, &creator, set_royalty_msg)
         .unwrap();
+    set_token_royalty(deps.as_mut(), "melt", Some("0.07"));
+    set_token_royalty(deps.as_mut(), "grow", Some("0.03"));
     assert_eq!(
-        res,
-        OperatorsResponse {
-            operators: vec![Approval {
-                spender: Addr::unchecked("buddy"),
-                expires: buddy_expires,
-            }]
+        royalty_summary(deps.as_ref(), None, None),
+        RoyaltySummaryResponse {
+            royalty_info: Some(royalty("payment_address", "0.05")),
+            token_royalties: vec![token_royalty("grow", "0.03"), token_royalty("melt", "0.07")],
         }
     );
-    let res = contract
-        .query_operators(
-            deps.as_ref(),
-            &mock_env(),
-            String::from("person"),
-            true,
-            Some(String::from("buddy")),
-            Some(2),
+
+    // paginated
+    let page = royalty_summary(deps.as_ref(), None, Some(1));
+    assert_eq!(page.token_royalties, vec![token_royalty("grow", "0.03")]);
+    let page = royalty_summary(deps.as_ref(), Some("grow"), Some(1));
+    assert_eq!(page.token_royalties, vec![token_royalty("melt", "0.07")]);
+    let page = royalty_summary(deps.as_ref(), Some("melt"), Some(1));
+    assert_eq!(page.token_royalties, vec![]);
+
+    // cleared and burned tokens are no longer listed
+    set_token_royalty(deps.as_mut(), "melt", None);
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("venus", &[]),
+            Cw721ExecuteMsg::Burn {
+                token_id: "grow".to_string(),
+            },
         )
         .unwrap();
     assert_eq!(
-        res,
-        OperatorsResponse {
-            operators: vec![Approval {
-                spender: Addr::unchecked("operator"),
-                expires: Expiration::Never {}
-            }]
+        royalty_summary(deps.as_ref(), None, None),
+        RoyaltySummaryResponse {
+            royalty_info: Some(royalty("payment_address", "0.05")),
+            token_royalties: vec![],
         }
     );
+}
 
-    let revoke_all_msg = Cw721ExecuteMsg::RevokeAll {
-        operator: String::from("operator"),
+#[test]
+fn test_royalty_split() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let creator = mock_info(CREATOR_ADDR, &[]);
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: "melt".to_string(),
+        owner: "venus".to_string(),
+        token_uri: None,
+        extension: None,
+        ibc_data: None,
     };
     contract
-        .execute(deps.as_mut(), &mock_env(), &owner, revoke_all_msg)
+        .execute(deps.as_mut(), &env, &mock_info(MINTER_ADDR, &[]), mint_msg)
+        .unwrap();
+    let set_royalty_msg = Cw721ExecuteMsg::UpdateCollectionInfo {
+        collection_info: CollectionInfoMsg {
+            name: None,
+            symbol: None,
+            extension: Some(CollectionExtensionMsg {
+                description: Some("description".to_string()),
+                image: Some("https://moonphases.org".to_string()),
+                explicit_content: Clearable::NoChange,
+                external_link: Clearable::NoChange,
+                start_trading_time: Clearable::NoChange,
+                royalty_info: Clearable::Set(RoyaltyInfoResponse {
+                    payment_address: "payment_address".into(),
+                    share: "0.05".parse().unwrap(),
+                }),
+            }),
+            collection_metadata_uri: Clearable::NoChange,
+            transfer_fee: Clearable::NoChange,
+        },
+    };
+    contract
+        .execute(deps.as_mut(), &env, &creator, set_royalty_msg)
+        .unwrap();
+    let set_split_msg = |splits: &[(&str, &str)]| Cw721ExecuteMsg::SetRoyaltySplit {
+        splits: Some(
+            splits
+                .iter()
+                .map(|(address, share)| RoyaltySplitMsg {
+                    address: address.to_string(),
+                    share: share.parse().unwrap(),
+                })
+                .collect(),
+        ),
+    };
+    let payments = |deps: Deps, sale_price: u128| -> Vec<(String, u128)> {
+        let res: RoyaltyAmountResponse = from_json(
+            contract
+                .query(
+                    deps,
+                    &env,
+                    Cw721QueryMsg::RoyaltyInfo {
+                        token_id: "melt".to_string(),
+                        sale_price: Uint128::new(sale_price),
+                    },
+                )
+                .unwrap(),
+        )
         .unwrap();
+        res.payments
+            .into_iter()
+            .map(|payment| (payment.address, payment.amount.u128()))
+            .collect()
+    };
 
-    // query for operator should return error
-    let res = contract.query_operator(
-        deps.as_ref(),
-        &mock_env(),
-        String::from("person"),
-        String::from("operator"),
-        true,
-    );
-    match res {
-        Err(StdError::NotFound { kind }) => assert_eq!(kind, "Approval not found"),
-        _ => panic!("Unexpected error"),
-    }
+    // other than creator cant set
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info(MINTER_ADDR, &[]),
+            set_split_msg(&[("alice", "0.6"), ("bob", "0.4")]),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
 
-    // Approvals are removed / cleared without affecting others
-    let res = contract
-        .query_operators(
-            deps.as_ref(),
-            &mock_env(),
-            String::from("person"),
-            false,
-            None,
-            None,
+    // shares cannot exceed 100%
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &creator,
+            set_split_msg(&[("alice", "0.6"), ("bob", "0.5")]),
+        )
+        .unwrap_err();
+    assert!(matches!(err, Cw721ContractError::InvalidRoyalties(_)));
+
+    // two-way split: 5% of 1020 is 51, split 30.6/20.4, remainder goes to first recipient
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &creator,
+            set_split_msg(&[("alice", "0.6"), ("bob", "0.4")]),
         )
         .unwrap();
     assert_eq!(
-        res,
-        OperatorsResponse {
-            operators: vec![Approval {
-                spender: Addr::unchecked("buddy"),
-                expires: buddy_expires,
-            }]
-        }
+        payments(deps.as_ref(), 1020),
+        vec![("alice".to_string(), 31), ("bob".to_string(), 20)]
     );
-
-    // ensure the filter works (nothing should be here
-    let mut late_env = mock_env();
-    late_env.block.height = 1234568; //expired
-    let res = contract
-        .query_operators(
-            deps.as_ref(),
-            &late_env,
-            String::from("person"),
-            false,
-            None,
-            None,
+    // split royalty has no single payment address
+    let res: RoyaltyAmountResponse = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                &env,
+                Cw721QueryMsg::RoyaltyInfo {
+                    token_id: "melt".to_string(),
+                    sale_price: Uint128::new(1020),
+                },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.address, None);
+    assert_eq!(res.royalty_amount, Uint128::new(51));
+    let res: Response = contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &mock_info("market", &[Coin::new(1020, "uatom")]),
+            Cw721ExecuteMsg::PayRoyalty {
+                token_id: "melt".to_string(),
+            },
         )
         .unwrap();
-    assert_eq!(0, res.operators.len());
+    assert_eq!(
+        res.messages
+            .iter()
+            .map(|msg| msg.msg.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "alice".to_string(),
+                amount: vec![Coin::new(31, "uatom")],
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "bob".to_string(),
+                amount: vec![Coin::new(20, "uatom")],
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "market".to_string(),
+                amount: vec![Coin::new(969, "uatom")],
+            }),
+        ]
+    );
 
-    // query operator should also return error
-    let res = contract.query_operator(
-        deps.as_ref(),
-        &late_env,
-        String::from("person"),
-        String::from("buddy"),
-        false,
+    // three-way split: 5% of 1000 is 50, split 16.67 each, remainder goes to first recipient
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &creator,
+            set_split_msg(&[
+                ("alice", "0.333333333333333333"),
+                ("bob", "0.333333333333333333"),
+                ("carol", "0.333333333333333333"),
+            ]),
+        )
+        .unwrap();
+    assert_eq!(
+        payments(deps.as_ref(), 1000),
+        vec![
+            ("alice".to_string(), 18),
+            ("bob".to_string(), 16),
+            ("carol".to_string(), 16),
+        ]
     );
 
-    match res {
-        Err(StdError::NotFound { kind }) => assert_eq!(kind, "Approval not found"),
-        _ => panic!("Unexpected error"),
-    }
+    // removing pays collection royalty address again
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &creator,
+            Cw721ExecuteMsg::SetRoyaltySplit { splits: None },
+        )
+        .unwrap();
+    assert_eq!(
+        payments(deps.as_ref(), 1000),
+        vec![("payment_address".to_string(), 50)]
+    );
 }
 
 #[test]
-fn test_set_withdraw_address() {
+fn test_max_royalty_bps() {
     let mut deps = mock_dependencies();
     let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let creator = mock_info(CREATOR_ADDR, &[]);
+    let set_royalty_msg = |share: &str| Cw721ExecuteMsg::UpdateCollectionInfo {
+        collection_info: CollectionInfoMsg {
+            name: None,
+            symbol: None,
+            extension: Some(CollectionExtensionMsg {
+                description: Some("description".to_string()),
+                image: Some("https://moonphases.org".to_string()),
+                explicit_content: Clearable::NoChange,
+                external_link: Clearable::NoChange,
+                start_trading_time: Clearable::NoChange,
+                royalty_info: Clearable::Set(RoyaltyInfoResponse {
+                    payment_address: "payment_address".into(),
+                    share: share.parse().unwrap(),
+                }),
+            }),
+            collection_metadata_uri: Clearable::NoChange,
+            transfer_fee: Clearable::NoChange,
+        },
+    };
 
     // other than creator cant set
     let err = contract
-        .set_withdraw_address(
+        .execute(
             deps.as_mut(),
-            &Addr::unchecked(MINTER_ADDR),
-            "foo".to_string(),
+            &env,
+            &mock_info(MINTER_ADDR, &[]),
+            Cw721ExecuteMsg::SetMaxRoyaltyBps {
+                max_royalty_bps: 500,
+            },
         )
         .unwrap_err();
     assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
 
-    // creator can set
+    // creator caps royalties at 5%
     contract
-        .set_withdraw_address(
+        .execute(
             deps.as_mut(),
-            &Addr::unchecked(CREATOR_ADDR),
-            "foo".to_string(),
+            &env,
+            &creator,
+            Cw721ExecuteMsg::SetMaxRoyaltyBps {
+                max_royalty_bps: 500,
+            },
         )
         .unwrap();
+    let res: Option<u16> = from_json(
+        contract
+            .query(deps.as_ref(), &env, Cw721QueryMsg::GetMaxRoyaltyBps {})
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res, Some(500));
 
-    let withdraw_address = contract
-        .config
-        .withdraw_address
-        .load(deps.as_ref().storage)
-        .unwrap();
-    assert_eq!(withdraw_address, "foo".to_string())
-}
+    // above cap is rejected
+    let err = contract
+        .execute(deps.as_mut(), &env, &creator, set_royalty_msg("0.0501"))
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::RoyaltyExceedsCap {});
 
-#[test]
-fn test_remove_withdraw_address() {
-    let mut deps = mock_dependencies();
-    let contract = setup_contract(deps.as_mut());
+    // at cap is accepted
+    contract
+        .execute(deps.as_mut(), &env, &creator, set_royalty_msg("0.05"))
+        .unwrap();
 
-    // other than creator cant remove
+    // cap cannot be raised
     let err = contract
-        .remove_withdraw_address(deps.as_mut().storage, &Addr::unchecked(MINTER_ADDR))
+        .execute(
+            deps.as_mut(),
+            &env,
+            &creator,
+            Cw721ExecuteMsg::SetMaxRoyaltyBps {
+                max_royalty_bps: 600,
+            },
+        )
         .unwrap_err();
-    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    assert!(matches!(err, Cw721ContractError::InvalidRoyalties(_)));
 
-    // no withdraw address set yet
+    // cap cannot be lowered below current royalty
     let err = contract
-        .remove_withdraw_address(deps.as_mut().storage, &Addr::unchecked(CREATOR_ADDR))
+        .execute(
+            deps.as_mut(),
+            &env,
+            &creator,
+            Cw721ExecuteMsg::SetMaxRoyaltyBps {
+                max_royalty_bps: 400,
+            },
+        )
         .unwrap_err();
-    assert_eq!(err, Cw721ContractError::NoWithdrawAddress {});
+    assert_eq!(err, Cw721ContractError::RoyaltyExceedsCap {});
 
-    // set and remove
+    // under cap is accepted
     contract
-        .set_withdraw_address(
+        .execute(deps.as_mut(), &env, &creator, set_royalty_msg("0.04"))
+        .unwrap();
+    contract
+        .execute(
             deps.as_mut(),
-            &Addr::unchecked(CREATOR_ADDR),
-            "foo".to_string(),
+            &env,
+            &creator,
+            Cw721ExecuteMsg::SetMaxRoyaltyBps {
+                max_royalty_bps: 400,
+            },
         )
         .unwrap();
+}
+
+#[test]
+fn test_royalty_recipient() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let creator = mock_info(CREATOR_ADDR, &[]);
+
+    // creator by default
+    let res: Option<String> = from_json(
+        contract
+            .query(deps.as_ref(), &env, Cw721QueryMsg::RoyaltyRecipient {})
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res, Some(CREATOR_ADDR.to_string()));
+
+    // withdraw address takes precedence over creator
+    let set_withdraw_address_msg = Cw721ExecuteMsg::SetWithdrawAddress {
+        address: "withdraw_address".to_string(),
+    };
     contract
-        .remove_withdraw_address(deps.as_mut().storage, &Addr::unchecked(CREATOR_ADDR))
+        .execute(deps.as_mut(), &env, &creator, set_withdraw_address_msg)
         .unwrap();
-    assert!(!contract
-        .config
-        .withdraw_address
-        .exists(deps.as_ref().storage));
+    let res = contract.query_royalty_recipient(deps.as_ref()).unwrap();
+    assert_eq!(res, Some("withdraw_address".to_string()));
 
-    // test that we can set again
+    // royalty payment address takes precedence over withdraw address
+    let update_collection_info_msg = Cw721ExecuteMsg::UpdateCollectionInfo {
+        collection_info: CollectionInfoMsg {
+            name: None,
+            symbol: None,
+            extension: Some(CollectionExtensionMsg {
+                description: Some("description".to_string()),
+                image: Some("https://moonphases.org".to_string()),
+                explicit_content: Clearable::NoChange,
+                external_link: Clearable::NoChange,
+                start_trading_time: Clearable::NoChange,
+                royalty_info: Clearable::Set(RoyaltyInfoResponse {
+                    payment_address: "payment_address".into(),
+                    share: "0.1".parse().unwrap(),
+                }),
+            }),
+            collection_metadata_uri: Clearable::NoChange,
+            transfer_fee: Clearable::NoChange,
+        },
+    };
     contract
-        .set_withdraw_address(
+        .execute(deps.as_mut(), &env, &creator, update_collection_info_msg)
+        .unwrap();
+    let res = contract.query_royalty_recipient(deps.as_ref()).unwrap();
+    assert_eq!(res, Some("payment_address".to_string()));
+}
+
+#[test]
+fn test_pay_royalty() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let creator = mock_info(CREATOR_ADDR, &[]);
+
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: "1".to_string(),
+        owner: String::from("venus"),
+        token_uri: None,
+        extension: None,
+        ibc_data: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &mock_info(MINTER_ADDR, &[]), mint_msg)
+        .unwrap();
+    let pay_royalty_msg = Cw721ExecuteMsg::PayRoyalty {
+        token_id: "1".to_string(),
+    };
+    let market = mock_info("market", &[Coin::new(1005, "uark"), Coin::new(5, "uatom")]);
+
+    // no royalty info
+    let err = contract
+        .execute(deps.as_mut(), &env, &market, pay_royalty_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NoRoyaltyInfo {});
+
+    let update_collection_info_msg = Cw721ExecuteMsg::UpdateCollectionInfo {
+        collection_info: CollectionInfoMsg {
+            name: None,
+            symbol: None,
+            extension: Some(CollectionExtensionMsg {
+                description: Some("description".to_string()),
+                image: Some("https://moonphases.org".to_string()),
+                explicit_content: Clearable::NoChange,
+                external_link: Clearable::NoChange,
+                start_trading_time: Clearable::NoChange,
+                royalty_info: Clearable::Set(RoyaltyInfoResponse {
+                    payment_address: "payment_address".into(),
+                    share: "0.1".parse().unwrap(),
+                }),
+            }),
+            collection_metadata_uri: Clearable::NoChange,
+            transfer_fee: Clearable::NoChange,
+        },
+    };
+    contract
+        .execute(deps.as_mut(), &env, &creator, update_collection_info_msg)
+        .unwrap();
+
+    // no funds
+    let err = contract
+        .execute(
             deps.as_mut(),
-            &Addr::unchecked(CREATOR_ADDR),
-            "foo".to_string(),
+            &env,
+            &mock_info("market", &[]),
+            pay_royalty_msg.clone(),
         )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::NoFunds {});
+
+    // unknown token
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &market,
+            Cw721ExecuteMsg::PayRoyalty {
+                token_id: "2".to_string(),
+            },
+        )
+        .unwrap_err();
+
+    // royalty share is forwarded, remainder returned to sender (rounded down)
+    let res = contract
+        .execute(deps.as_mut(), &env, &market, pay_royalty_msg)
         .unwrap();
-    let withdraw_address = contract
-        .config
-        .withdraw_address
-        .load(deps.as_ref().storage)
-        .unwrap();
-    assert_eq!(withdraw_address, "foo".to_string())
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "pay_royalty")
+            .add_attribute("sender", "market")
+            .add_attribute("token_id", "1")
+            .add_attribute("royalty_recipient", "payment_address")
+            .add_attribute("royalty_amount", "100uark")
+            .add_message(BankMsg::Send {
+                to_address: "payment_address".to_string(),
+                amount: vec![Coin::new(100, "uark")],
+            })
+            .add_attribute("remainder", "905uark,5uatom")
+            .add_message(BankMsg::Send {
+                to_address: "market".to_string(),
+                amount: vec![Coin::new(905, "uark"), Coin::new(5, "uatom")],
+            })
+    );
 }
 
 #[test]
-fn test_withdraw_funds() {
+fn test_set_metadata_schema_hash() {
     let mut deps = mock_dependencies();
     let contract = setup_contract(deps.as_mut());
+    let hash = "9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08".to_string();
 
-    // no withdraw address set
+    // not set yet
+    assert_eq!(
+        contract.query_metadata_schema_hash(deps.as_ref()).unwrap(),
+        None
+    );
+
+    // other than creator cant set
     let err = contract
-        .withdraw_funds(deps.as_mut().storage, &Coin::new(100, "uark"))
+        .set_metadata_schema_hash(
+            deps.as_mut().storage,
+            &Addr::unchecked(MINTER_ADDR),
+            Some(hash.clone()),
+        )
         .unwrap_err();
-    assert_eq!(err, Cw721ContractError::NoWithdrawAddress {});
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
 
-    // set and withdraw by non-creator
+    // invalid hashes are rejected
+    for invalid_hash in [
+        "",
+        "9f86d081",
+        "zz86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+        "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a0800",
+    ] {
+        let err = contract
+            .set_metadata_schema_hash(
+                deps.as_mut().storage,
+                &Addr::unchecked(CREATOR_ADDR),
+                Some(invalid_hash.to_string()),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            Cw721ContractError::InvalidMetadataSchemaHash {
+                hash: invalid_hash.to_string()
+            }
+        );
+    }
+
+    // creator can set, stored lowercase
+    let env = mock_env();
+    let info = mock_info(CREATOR_ADDR, &[]);
     contract
-        .set_withdraw_address(
+        .execute(
             deps.as_mut(),
-            &Addr::unchecked(CREATOR_ADDR),
-            "foo".to_string(),
+            &env,
+            &info,
+            Cw721ExecuteMsg::SetMetadataSchemaHash {
+                metadata_schema_hash: Some(hash.clone()),
+            },
         )
         .unwrap();
+    let res: Option<String> = from_json(
+        contract
+            .query(deps.as_ref(), &env, Cw721QueryMsg::GetMetadataSchemaHash {})
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res, Some(hash.to_lowercase()));
+
+    // creator can remove
     contract
-        .withdraw_funds(deps.as_mut().storage, &Coin::new(100, "uark"))
+        .execute(
+            deps.as_mut(),
+            &env,
+            &info,
+            Cw721ExecuteMsg::SetMetadataSchemaHash {
+                metadata_schema_hash: None,
+            },
+        )
         .unwrap();
+    assert_eq!(
+        contract.query_metadata_schema_hash(deps.as_ref()).unwrap(),
+        None
+    );
 }
 
 #[test]
@@ -1826,6 +7599,7 @@ fn query_tokens_by_owner() {
         owner: demeter.clone(),
         token_uri: None,
         extension: None,
+        ibc_data: None,
     };
     contract
         .execute(deps.as_mut(), &mock_env(), &minter, mint_msg)
@@ -1836,6 +7610,7 @@ fn query_tokens_by_owner() {
         owner: ceres.clone(),
         token_uri: None,
         extension: None,
+        ibc_data: None,
     };
     contract
         .execute(deps.as_mut(), &mock_env(), &minter, mint_msg)
@@ -1846,6 +7621,7 @@ fn query_tokens_by_owner() {
         owner: demeter.clone(),
         token_uri: None,
         extension: None,
+        ibc_data: None,
     };
     let env = mock_env();
     contract
@@ -1855,16 +7631,16 @@ fn query_tokens_by_owner() {
     // get all tokens in order:
     let expected = vec![token_id1.clone(), token_id2.clone(), token_id3.clone()];
     let tokens = contract
-        .query_all_tokens(deps.as_ref(), &env, None, None)
+        .query_all_tokens(deps.as_ref(), &env, None, None, None)
         .unwrap();
     assert_eq!(&expected, &tokens.tokens);
     // paginate
     let tokens = contract
-        .query_all_tokens(deps.as_ref(), &env, None, Some(2))
+        .query_all_tokens(deps.as_ref(), &env, None, Some(2), None)
         .unwrap();
     assert_eq!(&expected[..2], &tokens.tokens[..]);
     let tokens = contract
-        .query_all_tokens(deps.as_ref(), &env, Some(expected[1].clone()), None)
+        .query_all_tokens(deps.as_ref(), &env, Some(expected[1].clone()), None, None)
         .unwrap();
     assert_eq!(&expected[2..], &tokens.tokens[..]);
 
@@ -1873,17 +7649,17 @@ fn query_tokens_by_owner() {
     let by_demeter = vec![token_id1, token_id3];
     // all tokens by owner
     let tokens = contract
-        .query_tokens(deps.as_ref(), &env, demeter.clone(), None, None)
+        .query_tokens(deps.as_ref(), &env, demeter.clone(), None, None, None)
         .unwrap();
     assert_eq!(&by_demeter, &tokens.tokens);
     let tokens = contract
-        .query_tokens(deps.as_ref(), &env, ceres, None, None)
+        .query_tokens(deps.as_ref(), &env, ceres, None, None, None)
         .unwrap();
     assert_eq!(&by_ceres, &tokens.tokens);
 
     // paginate for demeter
     let tokens = contract
-        .query_tokens(deps.as_ref(), &env, demeter.clone(), None, Some(1))
+        .query_tokens(deps.as_ref(), &env, demeter.clone(), None, Some(1), None)
         .unwrap();
     assert_eq!(&by_demeter[..1], &tokens.tokens[..]);
     let tokens = contract
@@ -1893,7 +7669,127 @@ fn query_tokens_by_owner() {
             demeter,
             Some(by_demeter[0].clone()),
             Some(3),
+            None,
         )
         .unwrap();
     assert_eq!(&by_demeter[1..], &tokens.tokens[..]);
 }
+
+#[test]
+fn test_query_tokens_cursor() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let minter = mock_info(MINTER_ADDR, &[]);
+    let mint = |deps: DepsMut, token_id: &str| {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: "demeter".to_string(),
+            token_uri: None,
+            extension: None,
+            ibc_data: None,
+        };
+        contract.execute(deps, &env, &minter, mint_msg).unwrap();
+    };
+    // non-monotonic ids, lexicographically ordered in storage
+    for token_id in ["1", "10", "2", "3"] {
+        mint(deps.as_mut(), token_id);
+    }
+
+    let page = contract
+        .query_all_tokens(deps.as_ref(), &env, None, Some(2), None)
+        .unwrap();
+    assert_eq!(page.tokens, vec!["1".to_string(), "10".to_string()]);
+    let cursor = page.next_cursor.unwrap();
+
+    // tokens minted mid-iteration: before cursor skipped, after cursor included, no duplicates
+    mint(deps.as_mut(), "0");
+    mint(deps.as_mut(), "25");
+    let page = contract
+        .query_all_tokens(deps.as_ref(), &env, None, Some(2), Some(cursor))
+        .unwrap();
+    assert_eq!(page.tokens, vec!["2".to_string(), "25".to_string()]);
+    let page = contract
+        .query_all_tokens(deps.as_ref(), &env, None, Some(2), page.next_cursor)
+        .unwrap();
+    assert_eq!(page.tokens, vec!["3".to_string()]);
+    // last page
+    assert_eq!(page.next_cursor, None);
+
+    // cursor takes precedence over start_after, also for tokens by owner
+    let page = contract
+        .query_tokens(
+            deps.as_ref(),
+            &env,
+            "demeter".to_string(),
+            Some("0".to_string()),
+            Some(2),
+            Some(Binary::from(b"25")),
+        )
+        .unwrap();
+    assert_eq!(page.tokens, vec!["3".to_string()]);
+    assert_eq!(page.next_cursor, None);
+}
+
+#[test]
+fn test_token_by_index() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let env = mock_env();
+    let minter = mock_info(MINTER_ADDR, &[]);
+    for token_id in ["a", "b", "c"] {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: MINTER_ADDR.to_string(),
+            token_uri: None,
+            extension: None,
+            ibc_data: None,
+        };
+        contract
+            .execute(deps.as_mut(), &env, &minter, mint_msg)
+            .unwrap();
+    }
+    let token_by_index = |deps: Deps, index: u64| -> StdResult<String> {
+        Ok(contract.query_token_by_index(deps, index)?.token_id)
+    };
+
+    // mint order
+    for (index, token_id) in ["a", "b", "c"].iter().enumerate() {
+        assert_eq!(
+            token_by_index(deps.as_ref(), index as u64).unwrap(),
+            *token_id
+        );
+    }
+    token_by_index(deps.as_ref(), 3).unwrap_err();
+
+    // burning moves last token into burned position
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &minter,
+            Cw721ExecuteMsg::Burn {
+                token_id: "a".to_string(),
+            },
+        )
+        .unwrap();
+    let count = contract.query_num_tokens(deps.as_ref().storage).unwrap();
+    assert_eq!(count.count, 2);
+    assert_eq!(token_by_index(deps.as_ref(), 0).unwrap(), "c");
+    assert_eq!(token_by_index(deps.as_ref(), 1).unwrap(), "b");
+    token_by_index(deps.as_ref(), 2).unwrap_err();
+
+    // burning last token leaves others in place
+    contract
+        .execute(
+            deps.as_mut(),
+            &env,
+            &minter,
+            Cw721ExecuteMsg::Burn {
+                token_id: "b".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(token_by_index(deps.as_ref(), 0).unwrap(), "c");
+    token_by_index(deps.as_ref(), 1).unwrap_err();
+}
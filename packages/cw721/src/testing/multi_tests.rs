@@ -2,7 +2,7 @@ use crate::{
     error::Cw721ContractError,
     extension::Cw721OnchainExtensions,
     msg::{
-        CollectionExtensionMsg, ConfigResponse, Cw721ExecuteMsg, Cw721InstantiateMsg,
+        Clearable, CollectionExtensionMsg, ConfigResponse, Cw721ExecuteMsg, Cw721InstantiateMsg,
         Cw721MigrateMsg, Cw721QueryMsg, MinterResponse, NumTokensResponse, OwnerOfResponse,
         RoyaltyInfoResponse,
     },
@@ -214,7 +214,7 @@ pub fn instantiate(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    msg: Cw721InstantiateMsg<DefaultOptionalCollectionExtensionMsg>,
+    msg: Cw721InstantiateMsg<DefaultOptionalNftExtensionMsg, DefaultOptionalCollectionExtensionMsg>,
 ) -> Result<Response, Cw721ContractError> {
     let contract = Cw721OnchainExtensions::default();
     contract.instantiate_with_version(deps, &env, &info, msg, "contract_name", "contract_version")
@@ -237,7 +237,12 @@ pub fn execute(
 pub fn query(
     deps: Deps,
     env: Env,
-    msg: Cw721QueryMsg<DefaultOptionalNftExtension, DefaultOptionalCollectionExtension, Empty>,
+    msg: Cw721QueryMsg<
+        DefaultOptionalNftExtension,
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtension,
+        Empty,
+    >,
 ) -> Result<Binary, Cw721ContractError> {
     let contract = Cw721OnchainExtensions::default();
     contract.query(deps, &env, msg)
@@ -305,6 +310,7 @@ fn query_owner(querier: QuerierWrapper, cw721: &Addr, token_id: String) -> Addr
             cw721,
             &Cw721QueryMsg::<
                 DefaultOptionalNftExtension,
+                DefaultOptionalNftExtensionMsg,
                 DefaultOptionalCollectionExtension,
                 Empty,
             >::OwnerOf {
@@ -326,6 +332,7 @@ fn query_nft_info(
             cw721,
             &Cw721QueryMsg::<
                 DefaultOptionalNftExtension,
+                DefaultOptionalNftExtensionMsg,
                 DefaultOptionalCollectionExtension,
                 Empty,
             >::NftInfo {
@@ -344,6 +351,7 @@ fn query_all_collection_info(
             cw721,
             &Cw721QueryMsg::<
                 DefaultOptionalNftExtension,
+                DefaultOptionalNftExtensionMsg,
                 DefaultOptionalCollectionExtension,
                 Empty,
             >::GetConfig {},
@@ -360,6 +368,7 @@ fn mint_transfer_and_burn(app: &mut MockApp, cw721: Addr, sender: Addr, token_id
             owner: sender.to_string(),
             token_uri: None,
             extension: Empty::default(),
+            ibc_data: None,
         },
         &[],
     )
@@ -375,6 +384,7 @@ fn mint_transfer_and_burn(app: &mut MockApp, cw721: Addr, sender: Addr, token_id
         &Cw721ExecuteMsg::<Empty, Empty, Empty>::TransferNft {
             recipient: burner.to_string(),
             token_id: token_id.clone(),
+            expected_owner: None,
         },
         &[],
     )
@@ -405,13 +415,29 @@ fn test_operator() {
         .instantiate_contract(
             code_id,
             other.clone(),
-            &Cw721InstantiateMsg::<DefaultOptionalCollectionExtension> {
+            &Cw721InstantiateMsg::<
+                DefaultOptionalNftExtensionMsg,
+                DefaultOptionalCollectionExtension,
+            > {
                 name: "collection".to_string(),
                 symbol: "symbol".to_string(),
                 minter: Some(minter.to_string()),
                 creator: Some(creator.to_string()),
                 collection_info_extension: None,
                 withdraw_address: None,
+                burn_policy: None,
+                ipfs_gateway: None,
+                mint_price: None,
+                public_minting: false,
+                max_royalty_bps: None,
+                commit_hash: None,
+                initial_mints: None,
+                track_burned_ids: false,
+                base_token_uri: None,
+                allow_force_transfer: false,
+                collection_metadata_uri: None,
+                allow_mint_to_contract: false,
+                unique_token_uris: false,
             },
             &[],
             "cw721-base",
@@ -428,6 +454,7 @@ fn test_operator() {
             owner: nft_owner.to_string(),
             token_uri: Some("".to_string()), // empty uri, response contains attribute with value "empty"
             extension: Empty::default(),
+            ibc_data: None,
         },
         &[],
     )
@@ -453,6 +480,7 @@ fn test_operator() {
         &Cw721ExecuteMsg::<Empty, Empty, Empty>::TransferNft {
             recipient: other.to_string(),
             token_id: "1".to_string(),
+            expected_owner: None,
         },
         &[],
     )
@@ -464,6 +492,7 @@ fn test_operator() {
             &cw721,
             &Cw721QueryMsg::<
                 DefaultOptionalNftExtension,
+                DefaultOptionalNftExtensionMsg,
                 DefaultOptionalCollectionExtension,
                 Empty,
             >::OwnerOf {
@@ -481,6 +510,7 @@ fn test_operator() {
             &Cw721ExecuteMsg::<Empty, Empty, Empty>::TransferNft {
                 recipient: other.to_string(),
                 token_id: "1".to_string(),
+                expected_owner: None,
             },
             &[],
         )
@@ -496,6 +526,7 @@ fn test_operator() {
         &Cw721ExecuteMsg::<Empty, Empty, Empty>::TransferNft {
             recipient: nft_owner.to_string(),
             token_id: "1".to_string(),
+            expected_owner: None,
         },
         &[],
     )
@@ -507,6 +538,7 @@ fn test_operator() {
             &cw721,
             &Cw721QueryMsg::<
                 DefaultOptionalNftExtension,
+                DefaultOptionalNftExtensionMsg,
                 DefaultOptionalCollectionExtension,
                 Empty,
             >::OwnerOf {
@@ -524,6 +556,7 @@ fn test_operator() {
         &Cw721ExecuteMsg::<Empty, Empty, Empty>::TransferNft {
             recipient: other.to_string(),
             token_id: "1".to_string(),
+            expected_owner: None,
         },
         &[],
     )
@@ -535,6 +568,7 @@ fn test_operator() {
             &cw721,
             &Cw721QueryMsg::<
                 DefaultOptionalNftExtension,
+                DefaultOptionalNftExtensionMsg,
                 DefaultOptionalCollectionExtension,
                 Empty,
             >::OwnerOf {
@@ -553,6 +587,7 @@ fn test_operator() {
         &Cw721ExecuteMsg::<Empty, Empty, Empty>::TransferNft {
             recipient: nft_owner.to_string(),
             token_id: "1".to_string(),
+            expected_owner: None,
         },
         &[],
     )
@@ -577,6 +612,7 @@ fn test_operator() {
             &Cw721ExecuteMsg::<Empty, Empty, Empty>::TransferNft {
                 recipient: other.to_string(),
                 token_id: "1".to_string(),
+                expected_owner: None,
             },
             &[],
         )
@@ -586,6 +622,120 @@ fn test_operator() {
     assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
 }
 
+#[test]
+fn test_timelocked_token() {
+    // --- setup ---
+    let mut app = new();
+    let admin = app.api().addr_make(ADMIN_ADDR);
+    let creator = app.api().addr_make(CREATOR_ADDR);
+    let minter = app.api().addr_make(MINTER_ADDR);
+    let code_id = app.store_code(cw721_base_latest_contract());
+    let cw721 = app
+        .instantiate_contract(
+            code_id,
+            creator.clone(),
+            &Cw721InstantiateMsg::<
+                DefaultOptionalNftExtensionMsg,
+                DefaultOptionalCollectionExtension,
+            > {
+                name: "collection".to_string(),
+                symbol: "symbol".to_string(),
+                minter: Some(minter.to_string()),
+                creator: Some(creator.to_string()),
+                collection_info_extension: None,
+                withdraw_address: None,
+                burn_policy: None,
+                ipfs_gateway: None,
+                mint_price: None,
+                public_minting: false,
+                max_royalty_bps: None,
+                commit_hash: None,
+                initial_mints: None,
+                track_burned_ids: false,
+                base_token_uri: None,
+                allow_force_transfer: false,
+                collection_metadata_uri: None,
+                allow_mint_to_contract: false,
+                unique_token_uris: false,
+            },
+            &[],
+            "cw721-base",
+            Some(admin.to_string()),
+        )
+        .unwrap();
+    // mint token vesting in one day
+    let nft_owner = app.api().addr_make(NFT_OWNER_ADDR);
+    let other = app.api().addr_make(OTHER1_ADDR);
+    let cliff = app.block_info().time.plus_days(1);
+    let mint_msg = Cw721ExecuteMsg::<Empty, Empty, Empty>::MintTimelocked {
+        token_id: "1".to_string(),
+        owner: nft_owner.to_string(),
+        token_uri: None,
+        extension: Empty::default(),
+        transferable_after: cliff,
+    };
+    // only minter may mint timelocked tokens
+    let err: Cw721ContractError = app
+        .execute_contract(other.clone(), cw721.clone(), &mint_msg, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, Cw721ContractError::NotMinter {});
+    app.execute_contract(minter, cw721.clone(), &mint_msg, &[])
+        .unwrap();
+
+    // --- before cliff: neither transfer nor burn ---
+    let transfer_msg = Cw721ExecuteMsg::<Empty, Empty, Empty>::TransferNft {
+        recipient: other.to_string(),
+        token_id: "1".to_string(),
+        expected_owner: None,
+    };
+    let err: Cw721ContractError = app
+        .execute_contract(nft_owner.clone(), cw721.clone(), &transfer_msg, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, Cw721ContractError::TokenTimelocked {});
+    let err: Cw721ContractError = app
+        .execute_contract(
+            nft_owner.clone(),
+            cw721.clone(),
+            &Cw721ExecuteMsg::<Empty, Empty, Empty>::Burn {
+                token_id: "1".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, Cw721ContractError::TokenTimelocked {});
+
+    // one second before cliff still locked
+    app.update_block(|block| block.time = cliff.minus_seconds(1));
+    let err: Cw721ContractError = app
+        .execute_contract(nft_owner.clone(), cw721.clone(), &transfer_msg, &[])
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(err, Cw721ContractError::TokenTimelocked {});
+
+    // --- at cliff: transfer and burn are allowed ---
+    app.update_block(|block| block.time = cliff);
+    app.execute_contract(nft_owner, cw721.clone(), &transfer_msg, &[])
+        .unwrap();
+    let owner = query_owner(app.wrap(), &cw721, "1".to_string());
+    assert_eq!(owner, other.to_string());
+    app.execute_contract(
+        other,
+        cw721,
+        &Cw721ExecuteMsg::<Empty, Empty, Empty>::Burn {
+            token_id: "1".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+}
+
 /// Instantiates a 0.16 version of this contract and tests that tokens
 /// can be minted, transferred, and burnred after migration.
 #[test]
@@ -650,6 +800,7 @@ fn test_migration_legacy_to_latest() {
                     owner: other.to_string(),
                     token_uri: Some("ipfs://new.uri".to_string()),
                     extension: Empty::default(),
+                    ibc_data: None,
                 },
                 &[],
             )
@@ -674,6 +825,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::Minter {},
@@ -690,6 +842,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::Minter {},
@@ -704,6 +857,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::GetMinterOwnership {},
@@ -721,6 +875,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::GetCreatorOwnership {},
@@ -789,6 +944,7 @@ fn test_migration_legacy_to_latest() {
                     owner: legacy_creator_and_minter.to_string(),
                     token_uri: Some("ipfs://new.uri".to_string()),
                     extension: Empty::default(),
+                    ibc_data: None,
                 },
                 &[],
             )
@@ -808,6 +964,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::Minter {},
@@ -824,6 +981,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::Minter {},
@@ -838,6 +996,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::GetMinterOwnership {},
@@ -853,6 +1012,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::GetCreatorOwnership {},
@@ -920,6 +1080,7 @@ fn test_migration_legacy_to_latest() {
                     owner: other.to_string(),
                     token_uri: Some("ipfs://new.uri".to_string()),
                     extension: Empty::default(),
+                    ibc_data: None,
                 },
                 &[],
             )
@@ -944,6 +1105,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::Minter {},
@@ -960,6 +1122,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::Minter {},
@@ -974,6 +1137,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::GetMinterOwnership {},
@@ -991,6 +1155,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::GetCreatorOwnership {},
@@ -1059,6 +1224,7 @@ fn test_migration_legacy_to_latest() {
                     owner: legacy_creator_and_minter.to_string(),
                     token_uri: Some("ipfs://new.uri".to_string()),
                     extension: Empty::default(),
+                    ibc_data: None,
                 },
                 &[],
             )
@@ -1078,6 +1244,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::Minter {},
@@ -1094,6 +1261,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::Minter {},
@@ -1108,6 +1276,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::GetMinterOwnership {},
@@ -1123,6 +1292,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::GetCreatorOwnership {},
@@ -1190,6 +1360,7 @@ fn test_migration_legacy_to_latest() {
                     owner: other.to_string(),
                     token_uri: Some("ipfs://new.uri".to_string()),
                     extension: Empty::default(),
+                    ibc_data: None,
                 },
                 &[],
             )
@@ -1214,6 +1385,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::Minter {},
@@ -1230,6 +1402,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::Minter {},
@@ -1244,6 +1417,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::GetMinterOwnership {},
@@ -1261,6 +1435,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::GetCreatorOwnership {},
@@ -1329,6 +1504,7 @@ fn test_migration_legacy_to_latest() {
                     owner: legacy_creator_and_minter.to_string(),
                     token_uri: Some("ipfs://new.uri".to_string()),
                     extension: Empty::default(),
+                    ibc_data: None,
                 },
                 &[],
             )
@@ -1348,6 +1524,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::Minter {},
@@ -1364,6 +1541,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::Minter {},
@@ -1378,6 +1556,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::GetMinterOwnership {},
@@ -1393,6 +1572,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::GetCreatorOwnership {},
@@ -1460,6 +1640,7 @@ fn test_migration_legacy_to_latest() {
                     owner: other.to_string(),
                     token_uri: Some("ipfs://new.uri".to_string()),
                     extension: Empty::default(),
+                    ibc_data: None,
                 },
                 &[],
             )
@@ -1484,6 +1665,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::Minter {},
@@ -1500,6 +1682,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::Minter {},
@@ -1514,6 +1697,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::GetMinterOwnership {},
@@ -1531,6 +1715,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::GetCreatorOwnership {},
@@ -1599,6 +1784,7 @@ fn test_migration_legacy_to_latest() {
                     owner: legacy_creator_and_minter.to_string(),
                     token_uri: Some("ipfs://new.uri".to_string()),
                     extension: Empty::default(),
+                    ibc_data: None,
                 },
                 &[],
             )
@@ -1618,6 +1804,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::Minter {},
@@ -1634,6 +1821,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::Minter {},
@@ -1648,6 +1836,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::GetMinterOwnership {},
@@ -1663,6 +1852,7 @@ fn test_migration_legacy_to_latest() {
                 &cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::GetCreatorOwnership {},
@@ -1680,19 +1870,35 @@ fn test_instantiate() {
     let creator = app.api().addr_make(CREATOR_ADDR);
     let payment_address = app.api().addr_make(OTHER1_ADDR);
     let withdraw_addr = app.api().addr_make(OTHER2_ADDR);
-    let init_msg = Cw721InstantiateMsg {
+    let init_msg = Cw721InstantiateMsg::<
+        DefaultOptionalNftExtensionMsg,
+        DefaultOptionalCollectionExtensionMsg,
+    > {
         name: "collection".to_string(),
         symbol: "symbol".to_string(),
         minter: Some(minter.to_string()),
         creator: Some(creator.to_string()),
         withdraw_address: Some(withdraw_addr.to_string()),
+        burn_policy: None,
+        ipfs_gateway: None,
+        mint_price: None,
+        public_minting: false,
+        max_royalty_bps: None,
+        commit_hash: None,
+        initial_mints: None,
+        track_burned_ids: false,
+        base_token_uri: None,
+        allow_force_transfer: false,
+        collection_metadata_uri: None,
+        allow_mint_to_contract: false,
+        unique_token_uris: false,
         collection_info_extension: Some(CollectionExtensionMsg {
             description: Some("description".to_string()),
             image: Some("ipfs://ark.pass".to_string()),
-            explicit_content: Some(false),
-            external_link: Some("https://interchain.arkprotocol.io".to_string()),
-            start_trading_time: Some(Timestamp::from_seconds(42)),
-            royalty_info: Some(RoyaltyInfoResponse {
+            explicit_content: Clearable::Set(false),
+            external_link: Clearable::Set("https://interchain.arkprotocol.io".to_string()),
+            start_trading_time: Clearable::Set(Timestamp::from_seconds(42)),
+            royalty_info: Clearable::Set(RoyaltyInfoResponse {
                 payment_address: payment_address.to_string(),
                 share: Decimal::bps(1000),
             }),
@@ -1719,6 +1925,7 @@ fn test_instantiate() {
                 cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::GetWithdrawAddress {},
@@ -1791,10 +1998,10 @@ fn test_instantiate() {
         invalid_init_msg.collection_info_extension = Some(CollectionExtensionMsg {
             description: Some("description".to_string()),
             image: Some("ipfs://ark.pass".to_string()),
-            explicit_content: Some(false),
-            external_link: Some("https://interchain.arkprotocol.io".to_string()),
-            start_trading_time: Some(Timestamp::from_seconds(42)),
-            royalty_info: Some(RoyaltyInfoResponse {
+            explicit_content: Clearable::Set(false),
+            external_link: Clearable::Set("https://interchain.arkprotocol.io".to_string()),
+            start_trading_time: Clearable::Set(Timestamp::from_seconds(42)),
+            royalty_info: Clearable::Set(RoyaltyInfoResponse {
                 payment_address: "invalid".to_string(),
                 share: Decimal::bps(1000),
             }),
@@ -1844,6 +2051,7 @@ fn test_instantiate() {
                 cw721,
                 &Cw721QueryMsg::<
                     DefaultOptionalNftExtension,
+                    DefaultOptionalNftExtensionMsg,
                     DefaultOptionalCollectionExtension,
                     Empty,
                 >::GetWithdrawAddress {},
@@ -1865,13 +2073,29 @@ fn test_update_nft_metadata() {
         .instantiate_contract(
             code_id,
             creator.clone(),
-            &Cw721InstantiateMsg::<DefaultOptionalCollectionExtension> {
+            &Cw721InstantiateMsg::<
+                DefaultOptionalNftExtensionMsg,
+                DefaultOptionalCollectionExtension,
+            > {
                 name: "collection".to_string(),
                 symbol: "symbol".to_string(),
                 minter: Some(minter_addr.to_string()),
                 creator: None, // in case of none, sender is creator
                 collection_info_extension: None,
                 withdraw_address: None,
+                burn_policy: None,
+                ipfs_gateway: None,
+                mint_price: None,
+                public_minting: false,
+                max_royalty_bps: None,
+                commit_hash: None,
+                initial_mints: None,
+                track_burned_ids: false,
+                base_token_uri: None,
+                allow_force_transfer: false,
+                collection_metadata_uri: None,
+                allow_mint_to_contract: false,
+                unique_token_uris: false,
             },
             &[],
             "cw721-base",
@@ -1907,6 +2131,7 @@ fn test_update_nft_metadata() {
             owner: nft_owner.to_string(),
             token_uri: Some("ipfs://foo.bar/metadata.json".to_string()),
             extension: Some(nft_metadata_msg.clone()),
+            ibc_data: None,
         },
         &[],
     )
@@ -2509,6 +2734,7 @@ fn test_update_nft_metadata() {
             &cw721,
             &Cw721QueryMsg::<
                 DefaultOptionalNftExtension,
+                DefaultOptionalNftExtensionMsg,
                 DefaultOptionalCollectionExtension,
                 Empty,
             >::NumTokens {},
@@ -2530,13 +2756,29 @@ fn test_queries() {
         .instantiate_contract(
             code_id,
             creator.clone(),
-            &Cw721InstantiateMsg::<DefaultOptionalCollectionExtension> {
+            &Cw721InstantiateMsg::<
+                DefaultOptionalNftExtensionMsg,
+                DefaultOptionalCollectionExtension,
+            > {
                 name: "collection".to_string(),
                 symbol: "symbol".to_string(),
                 minter: Some(minter_addr.to_string()),
                 creator: None, // in case of none, sender is creator
                 collection_info_extension: None,
                 withdraw_address: Some(withdraw_addr.to_string()),
+                burn_policy: None,
+                ipfs_gateway: None,
+                mint_price: None,
+                public_minting: false,
+                max_royalty_bps: None,
+                commit_hash: None,
+                initial_mints: None,
+                track_burned_ids: false,
+                base_token_uri: None,
+                allow_force_transfer: false,
+                collection_metadata_uri: None,
+                allow_mint_to_contract: false,
+                unique_token_uris: false,
             },
             &[],
             "cw721-base",
@@ -2572,6 +2814,7 @@ fn test_queries() {
             owner: nft_owner.to_string(),
             token_uri: Some("ipfs://foo.bar/metadata.json".to_string()),
             extension: Some(nft_metadata_msg.clone()),
+            ibc_data: None,
         },
         &[],
     )
@@ -2608,6 +2851,7 @@ fn test_queries() {
             &cw721,
             &Cw721QueryMsg::<
                 DefaultOptionalNftExtension,
+                DefaultOptionalNftExtensionMsg,
                 DefaultOptionalCollectionExtension,
                 Empty,
             >::NumTokens {},
@@ -2621,6 +2865,7 @@ fn test_queries() {
             &cw721,
             &Cw721QueryMsg::<
                 DefaultOptionalNftExtension,
+                DefaultOptionalNftExtensionMsg,
                 DefaultOptionalCollectionExtension,
                 Empty,
             >::GetWithdrawAddress {},
@@ -1,25 +1,30 @@
 use crate::{
     error::Cw721ContractError,
     extension::Cw721OnchainExtensions,
+    helpers::{is_owner, query_owner},
     msg::{
-        CollectionExtensionMsg, CollectionInfoAndExtensionResponse, CollectionInfoMsg,
-        Cw721ExecuteMsg, Cw721InstantiateMsg, NftExtensionMsg, RoyaltyInfoResponse,
+        Clearable, CollectionExtensionMsg, CollectionInfoAndExtensionResponse, CollectionInfoMsg,
+        Cw721ExecuteMsg, Cw721InstantiateMsg, NftExtensionMsg, OwnerOfResponse,
+        RoyaltyInfoResponse, SupplyStatsResponse,
     },
     query::MAX_LIMIT,
     state::{
         NftExtension, Trait, CREATOR, MAX_COLLECTION_DESCRIPTION_LENGTH,
-        MAX_ROYALTY_SHARE_DELTA_PCT, MAX_ROYALTY_SHARE_PCT, MINTER,
+        MAX_COLLECTION_NAME_LENGTH, MAX_COLLECTION_SYMBOL_LENGTH, MAX_ROYALTY_SHARE_DELTA_PCT,
+        MAX_ROYALTY_SHARE_PCT, MINTER,
     },
     traits::{Cw721Execute, Cw721Query},
-    CollectionExtension, RoyaltyInfo,
+    CollectionExtension, DefaultCw721ExecuteMsg, DefaultCw721QueryMsg, RoyaltyInfo,
 };
+use cosmwasm_schema::{schema_for, QueryResponses};
 use cosmwasm_std::{
     testing::{mock_dependencies, mock_env, mock_info},
-    Addr, Api, Decimal, Timestamp,
+    to_json_binary, to_json_string, Addr, Api, ContractResult, Decimal, DepsMut, Response,
+    SystemError, SystemResult, Timestamp, WasmQuery,
 };
 use cw2::ContractVersion;
 use cw_ownable::Action;
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 use unit_tests::multi_tests::{CREATOR_ADDR, MINTER_ADDR, OTHER1_ADDR};
 
 use super::*;
@@ -36,11 +41,24 @@ fn test_instantiation() {
             &mock_info("mr-t", &[]),
             Cw721InstantiateMsg {
                 name: "".into(),
-                symbol: "collection_symbol".into(),
+                symbol: "SYMBOL".into(),
                 collection_info_extension: None,
                 creator: None,
                 minter: None,
                 withdraw_address: None,
+                burn_policy: None,
+                ipfs_gateway: None,
+                mint_price: None,
+                public_minting: false,
+                max_royalty_bps: None,
+                commit_hash: None,
+                initial_mints: None,
+                track_burned_ids: false,
+                base_token_uri: None,
+                allow_force_transfer: false,
+                collection_metadata_uri: None,
+                allow_mint_to_contract: false,
+                unique_token_uris: false,
             },
             "contract_name",
             "contract_version",
@@ -61,6 +79,19 @@ fn test_instantiation() {
                 creator: None,
                 minter: None,
                 withdraw_address: None,
+                burn_policy: None,
+                ipfs_gateway: None,
+                mint_price: None,
+                public_minting: false,
+                max_royalty_bps: None,
+                commit_hash: None,
+                initial_mints: None,
+                track_burned_ids: false,
+                base_token_uri: None,
+                allow_force_transfer: false,
+                collection_metadata_uri: None,
+                allow_mint_to_contract: false,
+                unique_token_uris: false,
             },
             "contract_name",
             "contract_version",
@@ -68,6 +99,122 @@ fn test_instantiation() {
         .unwrap_err();
     assert_eq!(err, Cw721ContractError::CollectionSymbolEmpty {});
 
+    // error on too long name
+    let err = Cw721OnchainExtensions::default()
+        .instantiate_with_version(
+            deps.as_mut(),
+            &mock_env(),
+            &mock_info("mr-t", &[]),
+            Cw721InstantiateMsg {
+                name: "n".repeat(MAX_COLLECTION_NAME_LENGTH as usize + 1),
+                symbol: "SYMBOL".into(),
+                collection_info_extension: None,
+                creator: None,
+                minter: None,
+                withdraw_address: None,
+                burn_policy: None,
+                ipfs_gateway: None,
+                mint_price: None,
+                public_minting: false,
+                max_royalty_bps: None,
+                commit_hash: None,
+                initial_mints: None,
+                track_burned_ids: false,
+                base_token_uri: None,
+                allow_force_transfer: false,
+                collection_metadata_uri: None,
+                allow_mint_to_contract: false,
+                unique_token_uris: false,
+            },
+            "contract_name",
+            "contract_version",
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::InvalidName {
+            max_length: MAX_COLLECTION_NAME_LENGTH
+        }
+    );
+
+    // error on too long symbol
+    let err = Cw721OnchainExtensions::default()
+        .instantiate_with_version(
+            deps.as_mut(),
+            &mock_env(),
+            &mock_info("mr-t", &[]),
+            Cw721InstantiateMsg {
+                name: "collection_name".into(),
+                symbol: "S".repeat(MAX_COLLECTION_SYMBOL_LENGTH as usize + 1),
+                collection_info_extension: None,
+                creator: None,
+                minter: None,
+                withdraw_address: None,
+                burn_policy: None,
+                ipfs_gateway: None,
+                mint_price: None,
+                public_minting: false,
+                max_royalty_bps: None,
+                commit_hash: None,
+                initial_mints: None,
+                track_burned_ids: false,
+                base_token_uri: None,
+                allow_force_transfer: false,
+                collection_metadata_uri: None,
+                allow_mint_to_contract: false,
+                unique_token_uris: false,
+            },
+            "contract_name",
+            "contract_version",
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::InvalidSymbol {
+            symbol: "S".repeat(MAX_COLLECTION_SYMBOL_LENGTH as usize + 1),
+            max_length: MAX_COLLECTION_SYMBOL_LENGTH
+        }
+    );
+
+    // error on non-alphanumeric symbol
+    let err = Cw721OnchainExtensions::default()
+        .instantiate_with_version(
+            deps.as_mut(),
+            &mock_env(),
+            &mock_info("mr-t", &[]),
+            Cw721InstantiateMsg {
+                name: "collection_name".into(),
+                symbol: "SYM-BOL".into(),
+                collection_info_extension: None,
+                creator: None,
+                minter: None,
+                withdraw_address: None,
+                burn_policy: None,
+                ipfs_gateway: None,
+                mint_price: None,
+                public_minting: false,
+                max_royalty_bps: None,
+                commit_hash: None,
+                initial_mints: None,
+                track_burned_ids: false,
+                base_token_uri: None,
+                allow_force_transfer: false,
+                collection_metadata_uri: None,
+                allow_mint_to_contract: false,
+                unique_token_uris: false,
+            },
+            "contract_name",
+            "contract_version",
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::InvalidSymbol {
+            symbol: "SYM-BOL".into(),
+            max_length: MAX_COLLECTION_SYMBOL_LENGTH
+        }
+    );
+
     Cw721OnchainExtensions::default()
         .instantiate_with_version(
             deps.as_mut(),
@@ -75,11 +222,24 @@ fn test_instantiation() {
             &mock_info("larry", &[]),
             Cw721InstantiateMsg {
                 name: "collection_name".into(),
-                symbol: "collection_symbol".into(),
+                symbol: "SYMBOL".into(),
                 collection_info_extension: None,
                 minter: Some("minter".into()),
                 creator: Some("creator".into()),
                 withdraw_address: None,
+                burn_policy: None,
+                ipfs_gateway: None,
+                mint_price: None,
+                public_minting: false,
+                max_royalty_bps: None,
+                commit_hash: None,
+                initial_mints: None,
+                track_burned_ids: false,
+                base_token_uri: None,
+                allow_force_transfer: false,
+                collection_metadata_uri: None,
+                allow_mint_to_contract: false,
+                unique_token_uris: false,
             },
             "contract_name",
             "contract_version",
@@ -101,6 +261,49 @@ fn test_instantiation() {
         .map(|a| a.into_string());
     assert_eq!(creator, Some("creator".to_string()));
 
+    // same validation applies on update
+    let err = Cw721OnchainExtensions::default()
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &mock_info("creator", &[]),
+            Cw721ExecuteMsg::UpdateCollectionInfo {
+                collection_info: CollectionInfoMsg {
+                    name: None,
+                    symbol: Some("new symbol".into()),
+                    extension: None,
+                    collection_metadata_uri: Clearable::NoChange,
+                    transfer_fee: Clearable::NoChange,
+                },
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::InvalidSymbol {
+            symbol: "new symbol".into(),
+            max_length: MAX_COLLECTION_SYMBOL_LENGTH
+        }
+    );
+
+    // length is counted in characters, not bytes
+    Cw721OnchainExtensions::default()
+        .execute(
+            deps.as_mut(),
+            &mock_env(),
+            &mock_info("creator", &[]),
+            Cw721ExecuteMsg::UpdateCollectionInfo {
+                collection_info: CollectionInfoMsg {
+                    name: Some("é".repeat(MAX_COLLECTION_NAME_LENGTH as usize)),
+                    symbol: None,
+                    extension: None,
+                    collection_metadata_uri: Clearable::NoChange,
+                    transfer_fee: Clearable::NoChange,
+                },
+            },
+        )
+        .unwrap();
+
     //
     let version = cw2::get_contract_version(deps.as_ref().storage).unwrap();
     assert_eq!(
@@ -119,18 +322,32 @@ fn test_instantiation_with_proper_minter_and_creator() {
         let mut deps = mock_dependencies();
 
         let info_minter_and_creator = mock_info("minter_and_creator", &[]);
-        Cw721OnchainExtensions::default()
+        let contract = Cw721OnchainExtensions::default();
+        contract
             .instantiate_with_version(
                 deps.as_mut(),
                 &mock_env(),
                 &info_minter_and_creator,
                 Cw721InstantiateMsg {
                     name: "collection_name".into(),
-                    symbol: "collection_symbol".into(),
+                    symbol: "SYMBOL".into(),
                     collection_info_extension: None,
                     creator: None,
                     minter: None,
                     withdraw_address: None,
+                    burn_policy: None,
+                    ipfs_gateway: None,
+                    mint_price: None,
+                    public_minting: false,
+                    max_royalty_bps: None,
+                    commit_hash: None,
+                    initial_mints: None,
+                    track_burned_ids: false,
+                    base_token_uri: None,
+                    allow_force_transfer: false,
+                    collection_metadata_uri: None,
+                    allow_mint_to_contract: false,
+                    unique_token_uris: false,
                 },
                 "contract_name",
                 "contract_version",
@@ -140,7 +357,23 @@ fn test_instantiation_with_proper_minter_and_creator() {
         let minter = MINTER.item.load(deps.as_ref().storage).unwrap().owner;
         assert_eq!(minter, Some(info_minter_and_creator.sender.clone()));
         let creator = CREATOR.item.load(deps.as_ref().storage).unwrap().owner;
-        assert_eq!(creator, Some(info_minter_and_creator.sender));
+        assert_eq!(creator, Some(info_minter_and_creator.sender.clone()));
+
+        // sender is not locked out and can mint
+        contract
+            .execute(
+                deps.as_mut(),
+                &mock_env(),
+                &info_minter_and_creator,
+                Cw721ExecuteMsg::Mint {
+                    token_id: "1".into(),
+                    owner: "owner".into(),
+                    token_uri: None,
+                    extension: None,
+                    ibc_data: None,
+                },
+            )
+            .unwrap();
     }
     // case 2: minter and creator are set
     {
@@ -154,11 +387,24 @@ fn test_instantiation_with_proper_minter_and_creator() {
                 &info,
                 Cw721InstantiateMsg {
                     name: "collection_name".into(),
-                    symbol: "collection_symbol".into(),
+                    symbol: "SYMBOL".into(),
                     collection_info_extension: None,
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    burn_policy: None,
+                    ipfs_gateway: None,
+                    mint_price: None,
+                    public_minting: false,
+                    max_royalty_bps: None,
+                    commit_hash: None,
+                    initial_mints: None,
+                    track_burned_ids: false,
+                    base_token_uri: None,
+                    allow_force_transfer: false,
+                    collection_metadata_uri: None,
+                    allow_mint_to_contract: false,
+                    unique_token_uris: false,
                 },
                 "contract_name",
                 "contract_version",
@@ -182,11 +428,24 @@ fn test_instantiation_with_proper_minter_and_creator() {
                 &info,
                 Cw721InstantiateMsg {
                     name: "collection_name".into(),
-                    symbol: "collection_symbol".into(),
+                    symbol: "SYMBOL".into(),
                     collection_info_extension: None,
                     creator: Some(CREATOR_ADDR.into()),
                     minter: None,
                     withdraw_address: None,
+                    burn_policy: None,
+                    ipfs_gateway: None,
+                    mint_price: None,
+                    public_minting: false,
+                    max_royalty_bps: None,
+                    commit_hash: None,
+                    initial_mints: None,
+                    track_burned_ids: false,
+                    base_token_uri: None,
+                    allow_force_transfer: false,
+                    collection_metadata_uri: None,
+                    allow_mint_to_contract: false,
+                    unique_token_uris: false,
                 },
                 "contract_name",
                 "contract_version",
@@ -210,11 +469,24 @@ fn test_instantiation_with_proper_minter_and_creator() {
                 &info,
                 Cw721InstantiateMsg {
                     name: "collection_name".into(),
-                    symbol: "collection_symbol".into(),
+                    symbol: "SYMBOL".into(),
                     collection_info_extension: None,
                     creator: None,
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    burn_policy: None,
+                    ipfs_gateway: None,
+                    mint_price: None,
+                    public_minting: false,
+                    max_royalty_bps: None,
+                    commit_hash: None,
+                    initial_mints: None,
+                    track_burned_ids: false,
+                    base_token_uri: None,
+                    allow_force_transfer: false,
+                    collection_metadata_uri: None,
+                    allow_mint_to_contract: false,
+                    unique_token_uris: false,
                 },
                 "contract_name",
                 "contract_version",
@@ -253,10 +525,10 @@ fn test_instantiation_with_collection_info() {
         let extension_msg = Some(CollectionExtensionMsg {
             description: Some("description".into()),
             image: Some("https://moonphases.org".to_string()),
-            explicit_content: Some(true),
-            external_link: Some("https://moonphases.org".to_string()),
-            start_trading_time: Some(Timestamp::from_seconds(0)),
-            royalty_info: Some(RoyaltyInfoResponse {
+            explicit_content: Clearable::Set(true),
+            external_link: Clearable::Set("https://moonphases.org".to_string()),
+            start_trading_time: Clearable::Set(Timestamp::from_seconds(0)),
+            royalty_info: Clearable::Set(RoyaltyInfoResponse {
                 payment_address: "payment_address".into(),
                 share: Decimal::percent(MAX_ROYALTY_SHARE_PCT)
                     .to_string()
@@ -271,11 +543,24 @@ fn test_instantiation_with_collection_info() {
                 &info_creator,
                 Cw721InstantiateMsg {
                     name: "collection_name".into(),
-                    symbol: "collection_symbol".into(),
+                    symbol: "SYMBOL".into(),
                     collection_info_extension: extension_msg,
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    burn_policy: None,
+                    ipfs_gateway: None,
+                    mint_price: None,
+                    public_minting: false,
+                    max_royalty_bps: None,
+                    commit_hash: None,
+                    initial_mints: None,
+                    track_burned_ids: false,
+                    base_token_uri: None,
+                    allow_force_transfer: false,
+                    collection_metadata_uri: None,
+                    allow_mint_to_contract: false,
+                    unique_token_uris: false,
                 },
                 "contract_name",
                 "contract_version",
@@ -287,7 +572,7 @@ fn test_instantiation_with_collection_info() {
             .query_collection_info_and_extension(deps.as_ref())
             .unwrap();
         assert_eq!(collection_info.name, "collection_name");
-        assert_eq!(collection_info.symbol, "collection_symbol");
+        assert_eq!(collection_info.symbol, "SYMBOL");
         assert_eq!(collection_info.extension, extension);
     }
     // case 2: invalid data
@@ -299,10 +584,10 @@ fn test_instantiation_with_collection_info() {
         let extension_msg = Some(CollectionExtensionMsg {
             description: Some("description".into()),
             image: Some("invalid_url".to_string()),
-            explicit_content: Some(true),
-            external_link: Some("https://moonphases.org".to_string()),
-            start_trading_time: Some(Timestamp::from_seconds(0)),
-            royalty_info: Some(RoyaltyInfoResponse {
+            explicit_content: Clearable::Set(true),
+            external_link: Clearable::Set("https://moonphases.org".to_string()),
+            start_trading_time: Clearable::Set(Timestamp::from_seconds(0)),
+            royalty_info: Clearable::Set(RoyaltyInfoResponse {
                 payment_address: "payment_address".into(),
                 share: Decimal::percent(MAX_ROYALTY_SHARE_PCT)
                     .to_string()
@@ -317,11 +602,24 @@ fn test_instantiation_with_collection_info() {
                 &info_creator,
                 Cw721InstantiateMsg {
                     name: "collection_name".into(),
-                    symbol: "collection_symbol".into(),
+                    symbol: "SYMBOL".into(),
                     collection_info_extension: extension_msg,
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    burn_policy: None,
+                    ipfs_gateway: None,
+                    mint_price: None,
+                    public_minting: false,
+                    max_royalty_bps: None,
+                    commit_hash: None,
+                    initial_mints: None,
+                    track_burned_ids: false,
+                    base_token_uri: None,
+                    allow_force_transfer: false,
+                    collection_metadata_uri: None,
+                    allow_mint_to_contract: false,
+                    unique_token_uris: false,
                 },
                 "contract_name",
                 "contract_version",
@@ -336,10 +634,10 @@ fn test_instantiation_with_collection_info() {
         let extension_msg = Some(CollectionExtensionMsg {
             description: Some("description".into()),
             image: Some("https://moonphases.org".to_string()),
-            explicit_content: Some(true),
-            external_link: Some("invalid_url".to_string()),
-            start_trading_time: Some(Timestamp::from_seconds(0)),
-            royalty_info: Some(RoyaltyInfoResponse {
+            explicit_content: Clearable::Set(true),
+            external_link: Clearable::Set("invalid_url".to_string()),
+            start_trading_time: Clearable::Set(Timestamp::from_seconds(0)),
+            royalty_info: Clearable::Set(RoyaltyInfoResponse {
                 payment_address: "payment_address".into(),
                 share: Decimal::percent(MAX_ROYALTY_SHARE_PCT)
                     .to_string()
@@ -354,11 +652,24 @@ fn test_instantiation_with_collection_info() {
                 &info_creator,
                 Cw721InstantiateMsg {
                     name: "collection_name".into(),
-                    symbol: "collection_symbol".into(),
+                    symbol: "SYMBOL".into(),
                     collection_info_extension: extension_msg,
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    burn_policy: None,
+                    ipfs_gateway: None,
+                    mint_price: None,
+                    public_minting: false,
+                    max_royalty_bps: None,
+                    commit_hash: None,
+                    initial_mints: None,
+                    track_burned_ids: false,
+                    base_token_uri: None,
+                    allow_force_transfer: false,
+                    collection_metadata_uri: None,
+                    allow_mint_to_contract: false,
+                    unique_token_uris: false,
                 },
                 "contract_name",
                 "contract_version",
@@ -373,10 +684,10 @@ fn test_instantiation_with_collection_info() {
         let extension_msg = Some(CollectionExtensionMsg {
             description: Some("".into()),
             image: Some("https://moonphases.org".to_string()),
-            explicit_content: Some(true),
-            external_link: Some("https://moonphases.org".to_string()),
-            start_trading_time: Some(Timestamp::from_seconds(0)),
-            royalty_info: Some(RoyaltyInfoResponse {
+            explicit_content: Clearable::Set(true),
+            external_link: Clearable::Set("https://moonphases.org".to_string()),
+            start_trading_time: Clearable::Set(Timestamp::from_seconds(0)),
+            royalty_info: Clearable::Set(RoyaltyInfoResponse {
                 payment_address: "payment_address".into(),
                 share: Decimal::percent(MAX_ROYALTY_SHARE_PCT)
                     .to_string()
@@ -391,11 +702,24 @@ fn test_instantiation_with_collection_info() {
                 &info_creator,
                 Cw721InstantiateMsg {
                     name: "collection_name".into(),
-                    symbol: "collection_symbol".into(),
+                    symbol: "SYMBOL".into(),
                     collection_info_extension: extension_msg,
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    burn_policy: None,
+                    ipfs_gateway: None,
+                    mint_price: None,
+                    public_minting: false,
+                    max_royalty_bps: None,
+                    commit_hash: None,
+                    initial_mints: None,
+                    track_burned_ids: false,
+                    base_token_uri: None,
+                    allow_force_transfer: false,
+                    collection_metadata_uri: None,
+                    allow_mint_to_contract: false,
+                    unique_token_uris: false,
                 },
                 "contract_name",
                 "contract_version",
@@ -407,10 +731,10 @@ fn test_instantiation_with_collection_info() {
         let extension_msg = Some(CollectionExtensionMsg {
             description: Some("a".repeat(1001)),
             image: Some("https://moonphases.org".to_string()),
-            explicit_content: Some(true),
-            external_link: Some("https://moonphases.org".to_string()),
-            start_trading_time: Some(Timestamp::from_seconds(0)),
-            royalty_info: Some(RoyaltyInfoResponse {
+            explicit_content: Clearable::Set(true),
+            external_link: Clearable::Set("https://moonphases.org".to_string()),
+            start_trading_time: Clearable::Set(Timestamp::from_seconds(0)),
+            royalty_info: Clearable::Set(RoyaltyInfoResponse {
                 payment_address: "payment_address".into(),
                 share: Decimal::percent(MAX_ROYALTY_SHARE_PCT)
                     .to_string()
@@ -425,11 +749,24 @@ fn test_instantiation_with_collection_info() {
                 &info_creator,
                 Cw721InstantiateMsg {
                     name: "collection_name".into(),
-                    symbol: "collection_symbol".into(),
+                    symbol: "SYMBOL".into(),
                     collection_info_extension: extension_msg,
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    burn_policy: None,
+                    ipfs_gateway: None,
+                    mint_price: None,
+                    public_minting: false,
+                    max_royalty_bps: None,
+                    commit_hash: None,
+                    initial_mints: None,
+                    track_burned_ids: false,
+                    base_token_uri: None,
+                    allow_force_transfer: false,
+                    collection_metadata_uri: None,
+                    allow_mint_to_contract: false,
+                    unique_token_uris: false,
                 },
                 "contract_name",
                 "contract_version",
@@ -446,10 +783,10 @@ fn test_instantiation_with_collection_info() {
         let extension_msg = Some(CollectionExtensionMsg {
             description: Some("description".into()),
             image: Some("https://moonphases.org".to_string()),
-            explicit_content: Some(true),
-            external_link: Some("https://moonphases.org".to_string()),
-            start_trading_time: Some(Timestamp::from_seconds(0)),
-            royalty_info: Some(RoyaltyInfoResponse {
+            explicit_content: Clearable::Set(true),
+            external_link: Clearable::Set("https://moonphases.org".to_string()),
+            start_trading_time: Clearable::Set(Timestamp::from_seconds(0)),
+            royalty_info: Clearable::Set(RoyaltyInfoResponse {
                 payment_address: "payment_address".into(),
                 share: (MAX_ROYALTY_SHARE_PCT * 2).to_string().parse().unwrap(),
             }),
@@ -461,11 +798,24 @@ fn test_instantiation_with_collection_info() {
                 &info_creator,
                 Cw721InstantiateMsg {
                     name: "collection_name".into(),
-                    symbol: "collection_symbol".into(),
+                    symbol: "SYMBOL".into(),
                     collection_info_extension: extension_msg,
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    burn_policy: None,
+                    ipfs_gateway: None,
+                    mint_price: None,
+                    public_minting: false,
+                    max_royalty_bps: None,
+                    commit_hash: None,
+                    initial_mints: None,
+                    track_burned_ids: false,
+                    base_token_uri: None,
+                    allow_force_transfer: false,
+                    collection_metadata_uri: None,
+                    allow_mint_to_contract: false,
+                    unique_token_uris: false,
                 },
                 "contract_name",
                 "contract_version",
@@ -505,10 +855,10 @@ fn test_collection_info_update() {
         let instantiated_extension_msg = Some(CollectionExtensionMsg {
             description: Some("description".into()),
             image: Some("https://moonphases.org".to_string()),
-            explicit_content: Some(true),
-            external_link: Some("https://moonphases.org".to_string()),
-            start_trading_time: Some(Timestamp::from_seconds(0)),
-            royalty_info: Some(RoyaltyInfoResponse {
+            explicit_content: Clearable::Set(true),
+            external_link: Clearable::Set("https://moonphases.org".to_string()),
+            start_trading_time: Clearable::Set(Timestamp::from_seconds(0)),
+            royalty_info: Clearable::Set(RoyaltyInfoResponse {
                 payment_address: "payment_address".into(),
                 share: Decimal::percent(MAX_ROYALTY_SHARE_PCT)
                     .to_string()
@@ -524,11 +874,24 @@ fn test_collection_info_update() {
                 &info_creator,
                 Cw721InstantiateMsg {
                     name: "collection_name".into(),
-                    symbol: "collection_symbol".into(),
+                    symbol: "SYMBOL".into(),
                     collection_info_extension: instantiated_extension_msg,
                     creator: Some(CREATOR_ADDR.into()),
                     minter: Some(MINTER_ADDR.into()),
                     withdraw_address: None,
+                    burn_policy: None,
+                    ipfs_gateway: None,
+                    mint_price: None,
+                    public_minting: false,
+                    max_royalty_bps: None,
+                    commit_hash: None,
+                    initial_mints: None,
+                    track_burned_ids: false,
+                    base_token_uri: None,
+                    allow_force_transfer: false,
+                    collection_metadata_uri: None,
+                    allow_mint_to_contract: false,
+                    unique_token_uris: false,
                 },
                 "contract_name",
                 "contract_version",
@@ -539,15 +902,17 @@ fn test_collection_info_update() {
         let empty_extension_msg = CollectionExtensionMsg {
             description: None,
             image: None,
-            explicit_content: None,
-            external_link: None,
-            start_trading_time: None,
-            royalty_info: None,
+            explicit_content: Clearable::NoChange,
+            external_link: Clearable::NoChange,
+            start_trading_time: Clearable::NoChange,
+            royalty_info: Clearable::NoChange,
         };
         let empty_collection_info_msg = CollectionInfoMsg {
             name: None,
             symbol: None,
             extension: Some(empty_extension_msg),
+            collection_metadata_uri: Clearable::NoChange,
+            transfer_fee: Clearable::NoChange,
         };
         contract
             .execute(
@@ -564,17 +929,17 @@ fn test_collection_info_update() {
             .query_collection_info_and_extension(deps.as_ref())
             .unwrap();
         assert_eq!(collection_info.name, "collection_name");
-        assert_eq!(collection_info.symbol, "collection_symbol");
+        assert_eq!(collection_info.symbol, "SYMBOL");
         assert_eq!(collection_info.extension, expected_instantiated_extension);
 
         // update collection with proper data by creator
         let updated_extension_msg = CollectionExtensionMsg {
             description: Some("new_description".into()),
             image: Some("https://en.wikipedia.org/wiki/Non-fungible_token".to_string()),
-            explicit_content: Some(true),
-            external_link: Some("https://github.com/CosmWasm/cw-nfts".to_string()),
-            start_trading_time: None, // start trading time belongs to minter - not creator!
-            royalty_info: Some(RoyaltyInfoResponse {
+            explicit_content: Clearable::Set(true),
+            external_link: Clearable::Set("https://github.com/CosmWasm/cw-nfts".to_string()),
+            start_trading_time: Clearable::NoChange, // start trading time belongs to minter - not creator!
+            royalty_info: Clearable::Set(RoyaltyInfoResponse {
                 payment_address: "payment_address".into(),
                 share: Decimal::percent(MAX_ROYALTY_SHARE_PCT)
                     .to_string()
@@ -584,8 +949,10 @@ fn test_collection_info_update() {
         };
         let updated_collection_info_msg = CollectionInfoMsg {
             name: Some("new_collection_name".into()),
-            symbol: Some("new_collection_symbol".into()),
+            symbol: Some("NEWSYMBOL".into()),
             extension: Some(updated_extension_msg),
+            collection_metadata_uri: Clearable::NoChange,
+            transfer_fee: Clearable::NoChange,
         };
         contract
             .execute(
@@ -603,7 +970,7 @@ fn test_collection_info_update() {
             .query_collection_info_and_extension(deps.as_ref())
             .unwrap();
         assert_eq!(collection_info.name, "new_collection_name");
-        assert_eq!(collection_info.symbol, "new_collection_symbol");
+        assert_eq!(collection_info.symbol, "NEWSYMBOL");
         assert_eq!(
             collection_info.extension,
             Some(CollectionExtension {
@@ -626,15 +993,17 @@ fn test_collection_info_update() {
         let updated_extension_msg = CollectionExtensionMsg {
             description: None,
             image: None,
-            explicit_content: None,
-            external_link: None,
-            start_trading_time: Some(Timestamp::from_seconds(1)),
-            royalty_info: None,
+            explicit_content: Clearable::NoChange,
+            external_link: Clearable::NoChange,
+            start_trading_time: Clearable::Set(Timestamp::from_seconds(1)),
+            royalty_info: Clearable::NoChange,
         };
         let updated_collection_info_msg = CollectionInfoMsg {
             name: None,
             symbol: None,
             extension: Some(updated_extension_msg),
+            collection_metadata_uri: Clearable::NoChange,
+            transfer_fee: Clearable::NoChange,
         };
         let info_minter = mock_info(MINTER_ADDR, &[]);
         contract
@@ -652,7 +1021,7 @@ fn test_collection_info_update() {
             .query_collection_info_and_extension(deps.as_ref())
             .unwrap();
         assert_eq!(collection_info.name, "new_collection_name");
-        assert_eq!(collection_info.symbol, "new_collection_symbol");
+        assert_eq!(collection_info.symbol, "NEWSYMBOL");
         assert_eq!(
             collection_info.extension,
             Some(CollectionExtension {
@@ -680,10 +1049,10 @@ fn test_collection_info_update() {
         let instantiated_extension_msg = Some(CollectionExtensionMsg {
             description: Some("description".into()),
             image: Some("https://moonphases.org".to_string()),
-            explicit_content: Some(true),
-            external_link: Some("https://moonphases.org".to_string()),
-            start_trading_time: Some(Timestamp::from_seconds(0)),
-            royalty_info: Some(RoyaltyInfoResponse {
+            explicit_content: Clearable::Set(true),
+            external_link: Clearable::Set("https://moonphases.org".to_string()),
+            start_trading_time: Clearable::Set(Timestamp::from_seconds(0)),
+            royalty_info: Clearable::Set(RoyaltyInfoResponse {
                 payment_address: "payment_address".into(),
                 share: Decimal::percent(MAX_ROYALTY_SHARE_PCT)
                     .to_string()
@@ -699,11 +1068,24 @@ fn test_collection_info_update() {
                 &info,
                 Cw721InstantiateMsg {
                     name: "collection_name".into(),
-                    symbol: "collection_symbol".into(),
+                    symbol: "SYMBOL".into(),
                     collection_info_extension: instantiated_extension_msg,
                     creator: None,
                     minter: None,
                     withdraw_address: None,
+                    burn_policy: None,
+                    ipfs_gateway: None,
+                    mint_price: None,
+                    public_minting: false,
+                    max_royalty_bps: None,
+                    commit_hash: None,
+                    initial_mints: None,
+                    track_burned_ids: false,
+                    base_token_uri: None,
+                    allow_force_transfer: false,
+                    collection_metadata_uri: None,
+                    allow_mint_to_contract: false,
+                    unique_token_uris: false,
                 },
                 "contract_name",
                 "contract_version",
@@ -714,10 +1096,10 @@ fn test_collection_info_update() {
         let updated_extension_msg = CollectionExtensionMsg {
             description: Some("".into()),
             image: Some("https://en.wikipedia.org/wiki/Non-fungible_token".to_string()),
-            explicit_content: Some(true),
-            external_link: Some("https://github.com/CosmWasm/cw-nfts".to_string()),
-            start_trading_time: Some(Timestamp::from_seconds(0)),
-            royalty_info: Some(RoyaltyInfoResponse {
+            explicit_content: Clearable::Set(true),
+            external_link: Clearable::Set("https://github.com/CosmWasm/cw-nfts".to_string()),
+            start_trading_time: Clearable::Set(Timestamp::from_seconds(0)),
+            royalty_info: Clearable::Set(RoyaltyInfoResponse {
                 payment_address: "payment_address".into(),
                 share: Decimal::percent(MAX_ROYALTY_SHARE_PCT)
                     .to_string()
@@ -727,8 +1109,10 @@ fn test_collection_info_update() {
         };
         let updated_collection_info_msg = CollectionInfoMsg {
             name: Some("new_collection_name".into()),
-            symbol: Some("new_collection_symbol".into()),
+            symbol: Some("NEWSYMBOL".into()),
             extension: Some(updated_extension_msg),
+            collection_metadata_uri: Clearable::NoChange,
+            transfer_fee: Clearable::NoChange,
         };
         let err = contract
             .execute(
@@ -746,10 +1130,10 @@ fn test_collection_info_update() {
         let updated_extension_msg = CollectionExtensionMsg {
             description: Some("a".repeat(1001)),
             image: Some("https://en.wikipedia.org/wiki/Non-fungible_token".to_string()),
-            explicit_content: Some(true),
-            external_link: Some("https://github.com/CosmWasm/cw-nfts".to_string()),
-            start_trading_time: Some(Timestamp::from_seconds(0)),
-            royalty_info: Some(RoyaltyInfoResponse {
+            explicit_content: Clearable::Set(true),
+            external_link: Clearable::Set("https://github.com/CosmWasm/cw-nfts".to_string()),
+            start_trading_time: Clearable::Set(Timestamp::from_seconds(0)),
+            royalty_info: Clearable::Set(RoyaltyInfoResponse {
                 payment_address: "payment_address".into(),
                 share: Decimal::percent(MAX_ROYALTY_SHARE_PCT)
                     .to_string()
@@ -759,8 +1143,10 @@ fn test_collection_info_update() {
         };
         let updated_collection_info_msg = CollectionInfoMsg {
             name: Some("new_collection_name".into()),
-            symbol: Some("new_collection_symbol".into()),
+            symbol: Some("NEWSYMBOL".into()),
             extension: Some(updated_extension_msg),
+            collection_metadata_uri: Clearable::NoChange,
+            transfer_fee: Clearable::NoChange,
         };
         let err = contract
             .execute(
@@ -783,10 +1169,10 @@ fn test_collection_info_update() {
         let updated_extension_msg = CollectionExtensionMsg {
             description: Some("new_description".into()),
             image: Some("invalid_url".to_string()),
-            explicit_content: Some(true),
-            external_link: Some("https://github.com/CosmWasm/cw-nfts".to_string()),
-            start_trading_time: Some(Timestamp::from_seconds(0)),
-            royalty_info: Some(RoyaltyInfoResponse {
+            explicit_content: Clearable::Set(true),
+            external_link: Clearable::Set("https://github.com/CosmWasm/cw-nfts".to_string()),
+            start_trading_time: Clearable::Set(Timestamp::from_seconds(0)),
+            royalty_info: Clearable::Set(RoyaltyInfoResponse {
                 payment_address: "payment_address".into(),
                 share: Decimal::percent(MAX_ROYALTY_SHARE_PCT)
                     .to_string()
@@ -796,8 +1182,10 @@ fn test_collection_info_update() {
         };
         let updated_collection_info_msg = CollectionInfoMsg {
             name: Some("new_collection_name".into()),
-            symbol: Some("new_collection_symbol".into()),
+            symbol: Some("NEWSYMBOL".into()),
             extension: Some(updated_extension_msg),
+            collection_metadata_uri: Clearable::NoChange,
+            transfer_fee: Clearable::NoChange,
         };
         let err = contract
             .execute(
@@ -818,10 +1206,10 @@ fn test_collection_info_update() {
         let updated_extension_msg = CollectionExtensionMsg {
             description: Some("new_description".into()),
             image: Some("https://en.wikipedia.org/wiki/Non-fungible_token".to_string()),
-            explicit_content: Some(true),
-            external_link: Some("invalid_url".to_string()),
-            start_trading_time: Some(Timestamp::from_seconds(0)),
-            royalty_info: Some(RoyaltyInfoResponse {
+            explicit_content: Clearable::Set(true),
+            external_link: Clearable::Set("invalid_url".to_string()),
+            start_trading_time: Clearable::Set(Timestamp::from_seconds(0)),
+            royalty_info: Clearable::Set(RoyaltyInfoResponse {
                 payment_address: "payment_address".into(),
                 share: Decimal::percent(MAX_ROYALTY_SHARE_PCT)
                     .to_string()
@@ -831,8 +1219,10 @@ fn test_collection_info_update() {
         };
         let updated_collection_info_msg = CollectionInfoMsg {
             name: Some("new_collection_name".into()),
-            symbol: Some("new_collection_symbol".into()),
+            symbol: Some("NEWSYMBOL".into()),
             extension: Some(updated_extension_msg),
+            collection_metadata_uri: Clearable::NoChange,
+            transfer_fee: Clearable::NoChange,
         };
         let err = contract
             .execute(
@@ -853,10 +1243,10 @@ fn test_collection_info_update() {
         let updated_extension_msg = CollectionExtensionMsg {
             description: Some("new_description".into()),
             image: Some("https://en.wikipedia.org/wiki/Non-fungible_token".to_string()),
-            explicit_content: Some(true),
-            external_link: Some("https://github.com/CosmWasm/cw-nfts".to_string()),
-            start_trading_time: Some(Timestamp::from_seconds(0)),
-            royalty_info: Some(RoyaltyInfoResponse {
+            explicit_content: Clearable::Set(true),
+            external_link: Clearable::Set("https://github.com/CosmWasm/cw-nfts".to_string()),
+            start_trading_time: Clearable::Set(Timestamp::from_seconds(0)),
+            royalty_info: Clearable::Set(RoyaltyInfoResponse {
                 payment_address: "payment_address".into(),
                 share: Decimal::percent(MAX_ROYALTY_SHARE_PCT + MAX_ROYALTY_SHARE_DELTA_PCT - 1)
                     .to_string()
@@ -866,8 +1256,10 @@ fn test_collection_info_update() {
         };
         let updated_collection_info_msg = CollectionInfoMsg {
             name: Some("new_collection_name".into()),
-            symbol: Some("new_collection_symbol".into()),
+            symbol: Some("NEWSYMBOL".into()),
             extension: Some(updated_extension_msg),
+            collection_metadata_uri: Clearable::NoChange,
+            transfer_fee: Clearable::NoChange,
         };
         let err = contract
             .execute(
@@ -890,10 +1282,10 @@ fn test_collection_info_update() {
         let updated_extension_msg = CollectionExtensionMsg {
             description: Some("new_description".into()),
             image: Some("https://en.wikipedia.org/wiki/Non-fungible_token".to_string()),
-            explicit_content: Some(true),
-            external_link: Some("https://github.com/CosmWasm/cw-nfts".to_string()),
-            start_trading_time: Some(Timestamp::from_seconds(0)),
-            royalty_info: Some(RoyaltyInfoResponse {
+            explicit_content: Clearable::Set(true),
+            external_link: Clearable::Set("https://github.com/CosmWasm/cw-nfts".to_string()),
+            start_trading_time: Clearable::Set(Timestamp::from_seconds(0)),
+            royalty_info: Clearable::Set(RoyaltyInfoResponse {
                 payment_address: "payment_address".into(),
                 share: Decimal::percent(MAX_ROYALTY_SHARE_PCT + MAX_ROYALTY_SHARE_DELTA_PCT + 1)
                     .to_string()
@@ -903,8 +1295,10 @@ fn test_collection_info_update() {
         };
         let updated_collection_info_msg = CollectionInfoMsg {
             name: Some("new_collection_name".into()),
-            symbol: Some("new_collection_symbol".into()),
+            symbol: Some("NEWSYMBOL".into()),
             extension: Some(updated_extension_msg),
+            collection_metadata_uri: Clearable::NoChange,
+            transfer_fee: Clearable::NoChange,
         };
         let err = contract
             .execute(
@@ -932,10 +1326,10 @@ fn test_collection_info_update() {
         let instantiated_extension = Some(CollectionExtensionMsg {
             description: Some("description".into()),
             image: Some("https://moonphases.org".to_string()),
-            explicit_content: Some(true),
-            external_link: Some("https://moonphases.org".to_string()),
-            start_trading_time: Some(Timestamp::from_seconds(0)),
-            royalty_info: Some(RoyaltyInfoResponse {
+            explicit_content: Clearable::Set(true),
+            external_link: Clearable::Set("https://moonphases.org".to_string()),
+            start_trading_time: Clearable::Set(Timestamp::from_seconds(0)),
+            royalty_info: Clearable::Set(RoyaltyInfoResponse {
                 payment_address: "payment_address".into(),
                 share: Decimal::percent(MAX_ROYALTY_SHARE_PCT)
                     .to_string()
@@ -951,11 +1345,24 @@ fn test_collection_info_update() {
                 &info,
                 Cw721InstantiateMsg {
                     name: "collection_name".into(),
-                    symbol: "collection_symbol".into(),
+                    symbol: "SYMBOL".into(),
                     collection_info_extension: instantiated_extension,
                     creator: None,
                     minter: None,
                     withdraw_address: None,
+                    burn_policy: None,
+                    ipfs_gateway: None,
+                    mint_price: None,
+                    public_minting: false,
+                    max_royalty_bps: None,
+                    commit_hash: None,
+                    initial_mints: None,
+                    track_burned_ids: false,
+                    base_token_uri: None,
+                    allow_force_transfer: false,
+                    collection_metadata_uri: None,
+                    allow_mint_to_contract: false,
+                    unique_token_uris: false,
                 },
                 "contract_name",
                 "contract_version",
@@ -966,10 +1373,10 @@ fn test_collection_info_update() {
         let updated_extension_msg = CollectionExtensionMsg {
             description: Some("new_description".into()),
             image: Some("https://en.wikipedia.org/wiki/Non-fungible_token".to_string()),
-            explicit_content: Some(true),
-            external_link: Some("https://github.com/CosmWasm/cw-nfts".to_string()),
-            start_trading_time: None,
-            royalty_info: Some(RoyaltyInfoResponse {
+            explicit_content: Clearable::Set(true),
+            external_link: Clearable::Set("https://github.com/CosmWasm/cw-nfts".to_string()),
+            start_trading_time: Clearable::NoChange,
+            royalty_info: Clearable::Set(RoyaltyInfoResponse {
                 payment_address: "payment_address".into(),
                 share: Decimal::percent(MAX_ROYALTY_SHARE_PCT)
                     .to_string()
@@ -979,8 +1386,10 @@ fn test_collection_info_update() {
         };
         let updated_collection_info_msg = CollectionInfoMsg {
             name: Some("new_collection_name".into()),
-            symbol: Some("new_collection_symbol".into()),
+            symbol: Some("NEWSYMBOL".into()),
             extension: Some(updated_extension_msg),
+            collection_metadata_uri: Clearable::NoChange,
+            transfer_fee: Clearable::NoChange,
         };
         let info_other = mock_info(OTHER1_ADDR, &[]);
         let err = contract
@@ -1049,10 +1458,10 @@ fn test_collection_info_update() {
         let instantiated_extension = Some(CollectionExtensionMsg {
             description: Some("description".into()),
             image: Some("https://moonphases.org".to_string()),
-            explicit_content: Some(true),
-            external_link: Some("https://moonphases.org".to_string()),
-            start_trading_time: Some(Timestamp::from_seconds(0)),
-            royalty_info: Some(RoyaltyInfoResponse {
+            explicit_content: Clearable::Set(true),
+            external_link: Clearable::Set("https://moonphases.org".to_string()),
+            start_trading_time: Clearable::Set(Timestamp::from_seconds(0)),
+            royalty_info: Clearable::Set(RoyaltyInfoResponse {
                 payment_address: "payment_address".into(),
                 share: Decimal::percent(MAX_ROYALTY_SHARE_PCT)
                     .to_string()
@@ -1068,11 +1477,24 @@ fn test_collection_info_update() {
                 &info_creator,
                 Cw721InstantiateMsg {
                     name: "collection_name".into(),
-                    symbol: "collection_symbol".into(),
+                    symbol: "SYMBOL".into(),
                     collection_info_extension: instantiated_extension,
                     creator: None, // in case of none, sender is creator
                     minter: info_minter.sender.to_string().into(),
                     withdraw_address: None,
+                    burn_policy: None,
+                    ipfs_gateway: None,
+                    mint_price: None,
+                    public_minting: false,
+                    max_royalty_bps: None,
+                    commit_hash: None,
+                    initial_mints: None,
+                    track_burned_ids: false,
+                    base_token_uri: None,
+                    allow_force_transfer: false,
+                    collection_metadata_uri: None,
+                    allow_mint_to_contract: false,
+                    unique_token_uris: false,
                 },
                 "contract_name",
                 "contract_version",
@@ -1083,15 +1505,17 @@ fn test_collection_info_update() {
         let updated_extension_msg = CollectionExtensionMsg {
             description: None,
             image: None,
-            explicit_content: None,
-            external_link: None,
-            start_trading_time: Some(Timestamp::from_seconds(1)),
-            royalty_info: None,
+            explicit_content: Clearable::NoChange,
+            external_link: Clearable::NoChange,
+            start_trading_time: Clearable::Set(Timestamp::from_seconds(1)),
+            royalty_info: Clearable::NoChange,
         };
         let updated_collection_info_msg = CollectionInfoMsg {
             name: None,
             symbol: None,
             extension: Some(updated_extension_msg),
+            collection_metadata_uri: Clearable::NoChange,
+            transfer_fee: Clearable::NoChange,
         };
         let err = contract
             .execute(
@@ -1136,11 +1560,24 @@ fn test_nft_mint() {
         let info = mock_info(CREATOR_ADDR, &[]);
         let init_msg = Cw721InstantiateMsg {
             name: "collection_name".into(),
-            symbol: "collection_symbol".into(),
+            symbol: "SYMBOL".into(),
             collection_info_extension: None,
             minter: None,
             creator: None,
             withdraw_address: None,
+            burn_policy: None,
+            ipfs_gateway: None,
+            mint_price: None,
+            public_minting: false,
+            max_royalty_bps: None,
+            commit_hash: None,
+            initial_mints: None,
+            track_burned_ids: false,
+            base_token_uri: None,
+            allow_force_transfer: false,
+            collection_metadata_uri: None,
+            allow_mint_to_contract: false,
+            unique_token_uris: false,
         };
         let env = mock_env();
         contract
@@ -1161,13 +1598,14 @@ fn test_nft_mint() {
             owner: "john".to_string(),
             token_uri: token_uri.clone(),
             extension: None,
+            ibc_data: None,
         };
         contract
             .execute(deps.as_mut(), &env, &info, exec_msg)
             .unwrap();
 
         let res = contract
-            .query_nft_info(deps.as_ref().storage, token_id.into())
+            .query_nft_info(deps.as_ref(), &mock_env(), token_id.into())
             .unwrap();
         assert_eq!(res.token_uri, token_uri);
         assert_eq!(res.extension, None);
@@ -1177,6 +1615,7 @@ fn test_nft_mint() {
             owner: "john".to_string(),
             token_uri: "".to_string().into(), // empty token_uri
             extension: None,
+            ibc_data: None,
         };
         let err = contract
             .execute(deps.as_mut(), &env, &info, exec_msg)
@@ -1189,6 +1628,7 @@ fn test_nft_mint() {
             owner: "john".to_string(),
             token_uri: Some("https://starships.example.com/Starship/Enterprise.json".into()),
             extension: None,
+            ibc_data: None,
         };
         let err = contract
             .execute(deps.as_mut(), &env, &info, exec_msg)
@@ -1203,11 +1643,24 @@ fn test_nft_mint() {
         let info = mock_info(CREATOR_ADDR, &[]);
         let init_msg = Cw721InstantiateMsg {
             name: "collection_name".into(),
-            symbol: "collection_symbol".into(),
+            symbol: "SYMBOL".into(),
             collection_info_extension: None,
             minter: None,
             creator: None,
             withdraw_address: None,
+            burn_policy: None,
+            ipfs_gateway: None,
+            mint_price: None,
+            public_minting: false,
+            max_royalty_bps: None,
+            commit_hash: None,
+            initial_mints: None,
+            track_burned_ids: false,
+            base_token_uri: None,
+            allow_force_transfer: false,
+            collection_metadata_uri: None,
+            allow_mint_to_contract: false,
+            unique_token_uris: false,
         };
         let env = mock_env();
         contract
@@ -1238,13 +1691,14 @@ fn test_nft_mint() {
             owner: "john".to_string(),
             token_uri: uri_1.clone(),
             extension: extension_1_msg.clone(),
+            ibc_data: None,
         };
         contract
             .execute(deps.as_mut(), &env, &info, exec_msg)
             .unwrap();
 
         let nft_info_1 = contract
-            .query_nft_info(deps.as_ref().storage, nft_1.into())
+            .query_nft_info(deps.as_ref(), &mock_env(), nft_1.into())
             .unwrap();
         assert_eq!(nft_info_1.token_uri, uri_1);
         assert_eq!(
@@ -1279,13 +1733,14 @@ fn test_nft_mint() {
             owner: "allen".to_string(),
             token_uri: uri_2.clone(),
             extension: extension_2_msg.clone(),
+            ibc_data: None,
         };
         contract
             .execute(deps.as_mut(), &env, &info, exec_msg)
             .unwrap();
 
         let nft_info_2 = contract
-            .query_nft_info(deps.as_ref().storage, nft_2.into())
+            .query_nft_info(deps.as_ref(), &mock_env(), nft_2.into())
             .unwrap();
         assert_eq!(nft_info_2.token_uri, uri_2);
         assert_eq!(
@@ -1305,7 +1760,8 @@ fn test_nft_mint() {
         // query for token 2 with different description
         let res = contract
             .query_nft_by_extension(
-                deps.as_ref().storage,
+                deps.as_ref(),
+                &mock_env(),
                 Some(NftExtension {
                     description: Some("other_description".into()), // only description is different compared to nft 1
                     ..NftExtension::default()
@@ -1324,7 +1780,8 @@ fn test_nft_mint() {
         // query for both tokens
         let res = contract
             .query_nft_by_extension(
-                deps.as_ref().storage,
+                deps.as_ref(),
+                &mock_env(),
                 Some(NftExtension {
                     name: Some("name1".into()), // only description is different compared to nft 1
                     attributes: Some(vec![Trait {
@@ -1409,7 +1866,7 @@ fn test_migrate_v16_onchain_metadata_contract() {
     assert_eq!(legacy_collection_info.symbol, "legacy_symbol");
     // 200 NFTs still exist
     let all_tokens = contract
-        .query_all_tokens(deps.as_ref(), &env, None, Some(MAX_LIMIT))
+        .query_all_tokens(deps.as_ref(), &env, None, Some(MAX_LIMIT), None)
         .unwrap();
     assert_eq!(all_tokens.tokens.len(), 200);
     // NFTs have proper owner
@@ -1421,9 +1878,10 @@ fn test_migrate_v16_onchain_metadata_contract() {
     }
     // check one nft
     let token = contract
-        .query_nft_info(deps.as_ref().storage, "0".into())
+        .query_nft_info(deps.as_ref(), &mock_env(), "0".into())
         .unwrap();
     assert_eq!(token.token_uri, None);
+    assert_eq!(token.minter, None);
     assert_eq!(
         token.extension,
         Some(NftExtension {
@@ -1479,19 +1937,32 @@ fn test_migrate_v16_onchain_metadata_contract() {
         symbol: "legacy_symbol".to_string(),
         extension: None,
         updated_at: env.block.time,
+        collection_metadata_uri: None,
     };
     assert_eq!(collection_info, legacy_contract_info);
 
     // assert tokens
     let all_tokens = contract
-        .query_all_tokens(deps.as_ref(), &env, None, Some(MAX_LIMIT))
+        .query_all_tokens(deps.as_ref(), &env, None, Some(MAX_LIMIT), None)
         .unwrap();
     assert_eq!(all_tokens.tokens.len(), 200);
+    // assert supply stats defaulted from legacy token count
+    let supply_stats = contract.query_supply_stats(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        supply_stats,
+        SupplyStatsResponse {
+            current: 200,
+            total_minted: 200,
+            total_burned: 0,
+        }
+    );
     // check one nft
     let token = contract
-        .query_nft_info(deps.as_ref().storage, "0".into())
+        .query_nft_info(deps.as_ref(), &mock_env(), "0".into())
         .unwrap();
     assert_eq!(token.token_uri, None);
+    // minter defaulted to collection minter
+    assert_eq!(token.minter, Some("legacy_minter".to_string()));
     assert_eq!(
         token.extension,
         Some(NftExtension {
@@ -1514,7 +1985,7 @@ fn test_migrate_v16_onchain_metadata_contract() {
     assert_eq!(legacy_collection_info.symbol, "legacy_symbol");
     // - tokens are unchanged/still exist
     let all_tokens = contract
-        .query_all_tokens(deps.as_ref(), &env, None, Some(MAX_LIMIT))
+        .query_all_tokens(deps.as_ref(), &env, None, Some(MAX_LIMIT), None)
         .unwrap();
     assert_eq!(all_tokens.tokens.len(), 200);
     for token_id in 0..200 {
@@ -1524,3 +1995,281 @@ fn test_migrate_v16_onchain_metadata_contract() {
         assert_eq!(token.owner.as_str(), "owner");
     }
 }
+
+#[test]
+fn test_migrate_backfill_token_index() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721OnchainExtensions::default();
+    let env = mock_env();
+    let info = mock_info(CREATOR_ADDR, &[]);
+    let init_msg = Cw721InstantiateMsg {
+        name: "collection_name".into(),
+        symbol: "SYMBOL".into(),
+        collection_info_extension: None,
+        minter: None,
+        creator: None,
+        withdraw_address: None,
+        burn_policy: None,
+        ipfs_gateway: None,
+        mint_price: None,
+        public_minting: false,
+        max_royalty_bps: None,
+        commit_hash: None,
+        initial_mints: None,
+        track_burned_ids: false,
+        base_token_uri: None,
+        allow_force_transfer: false,
+        collection_metadata_uri: None,
+        allow_mint_to_contract: false,
+        unique_token_uris: false,
+    };
+    contract
+        .instantiate_with_version(
+            deps.as_mut(),
+            &env,
+            &info,
+            init_msg,
+            "contract_name",
+            "contract_version",
+        )
+        .unwrap();
+    for token_id in ["1", "2", "3", "4"] {
+        let exec_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: "john".to_string(),
+            token_uri: None,
+            extension: None,
+            ibc_data: None,
+        };
+        contract
+            .execute(deps.as_mut(), &env, &info, exec_msg)
+            .unwrap();
+    }
+
+    // drop index in raw state, as for contracts migrated from a version without it
+    let token_by_index = Map::<u64, String>::new("cw721_token_by_index");
+    let token_indexes = Map::<&str, u64>::new("cw721_token_indexes");
+    for (index, token_id) in ["1", "2", "3", "4"].iter().enumerate() {
+        token_by_index.remove(deps.as_mut().storage, index as u64);
+        token_indexes.remove(deps.as_mut().storage, token_id);
+    }
+
+    let migrate = |deps: DepsMut, msg: crate::msg::Cw721MigrateMsg| {
+        contract
+            .migrate(deps, mock_env(), msg, "contract_name", "contract_version")
+            .unwrap()
+    };
+    let backfill_status = |res: &Response| {
+        res.attributes
+            .iter()
+            .find(|attr| attr.key == "token_index_backfill")
+            .map(|attr| attr.value.clone())
+    };
+
+    // regular migration starts backfill, index is unavailable until complete
+    let res = migrate(
+        deps.as_mut(),
+        crate::msg::Cw721MigrateMsg::WithUpdate {
+            minter: None,
+            creator: None,
+        },
+    );
+    assert_eq!(backfill_status(&res), Some("pending".to_string()));
+    contract.query_token_by_index(deps.as_ref(), 0).unwrap_err();
+
+    // tokens minted meanwhile are indexed right away, burned tokens are skipped
+    let exec_msg = Cw721ExecuteMsg::Mint {
+        token_id: "5".to_string(),
+        owner: "john".to_string(),
+        token_uri: None,
+        extension: None,
+        ibc_data: None,
+    };
+    contract
+        .execute(deps.as_mut(), &env, &info, exec_msg)
+        .unwrap();
+    let exec_msg = Cw721ExecuteMsg::Burn {
+        token_id: "2".to_string(),
+    };
+    contract
+        .execute(deps.as_mut(), &env, &mock_info("john", &[]), exec_msg)
+        .unwrap();
+
+    // paginated backfill
+    let backfill = crate::msg::Cw721MigrateMsg::BackfillTokenIndex { limit: 2 };
+    let res = migrate(deps.as_mut(), backfill.clone());
+    assert_eq!(backfill_status(&res), Some("pending".to_string()));
+    contract.query_token_by_index(deps.as_ref(), 0).unwrap_err();
+    let res = migrate(deps.as_mut(), backfill.clone());
+    assert_eq!(backfill_status(&res), Some("pending".to_string()));
+    let res = migrate(deps.as_mut(), backfill.clone());
+    assert_eq!(backfill_status(&res), Some("complete".to_string()));
+
+    for (index, token_id) in ["5", "1", "3", "4"].iter().enumerate() {
+        let res = contract
+            .query_token_by_index(deps.as_ref(), index as u64)
+            .unwrap();
+        assert_eq!(res.token_id, *token_id);
+    }
+    contract.query_token_by_index(deps.as_ref(), 4).unwrap_err();
+
+    // further migrations leave complete index as is
+    let res = migrate(deps.as_mut(), backfill);
+    assert_eq!(backfill_status(&res), None);
+}
+
+#[test]
+fn test_migrate_recompute_num_tokens() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721OnchainExtensions::default();
+    let env = mock_env();
+    let info = mock_info(CREATOR_ADDR, &[]);
+    let init_msg = Cw721InstantiateMsg {
+        name: "collection_name".into(),
+        symbol: "SYMBOL".into(),
+        collection_info_extension: None,
+        minter: None,
+        creator: None,
+        withdraw_address: None,
+        burn_policy: None,
+        ipfs_gateway: None,
+        mint_price: None,
+        public_minting: false,
+        max_royalty_bps: None,
+        commit_hash: None,
+        initial_mints: None,
+        track_burned_ids: false,
+        base_token_uri: None,
+        allow_force_transfer: false,
+        collection_metadata_uri: None,
+        allow_mint_to_contract: false,
+        unique_token_uris: false,
+    };
+    contract
+        .instantiate_with_version(
+            deps.as_mut(),
+            &env,
+            &info,
+            init_msg,
+            "contract_name",
+            "contract_version",
+        )
+        .unwrap();
+    for token_id in ["1", "2"] {
+        let exec_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: "john".to_string(),
+            token_uri: None,
+            extension: None,
+            ibc_data: None,
+        };
+        contract
+            .execute(deps.as_mut(), &env, &info, exec_msg)
+            .unwrap();
+    }
+
+    // corrupt counters in raw state
+    Item::<u64>::new("num_tokens")
+        .save(deps.as_mut().storage, &5)
+        .unwrap();
+    Item::<u64>::new("cw721_total_minted")
+        .save(deps.as_mut().storage, &5)
+        .unwrap();
+
+    // regular migration keeps counter as is
+    contract
+        .migrate(
+            deps.as_mut(),
+            env.clone(),
+            crate::msg::Cw721MigrateMsg::WithUpdate {
+                minter: None,
+                creator: None,
+            },
+            "contract_name",
+            "contract_version",
+        )
+        .unwrap();
+    let count = contract.query_num_tokens(deps.as_ref().storage).unwrap();
+    assert_eq!(count.count, 5);
+
+    // explicit request repairs it
+    contract
+        .migrate(
+            deps.as_mut(),
+            env.clone(),
+            crate::msg::Cw721MigrateMsg::RecomputeNumTokens {},
+            "contract_name",
+            "contract_version",
+        )
+        .unwrap();
+    let count = contract.query_num_tokens(deps.as_ref().storage).unwrap();
+    assert_eq!(count.count, 2);
+    let stats = contract.query_supply_stats(deps.as_ref().storage).unwrap();
+    assert_eq!(stats.total_minted, 2);
+
+    // token index is rebuilt
+    contract.query_token_by_index(deps.as_ref(), 0).unwrap_err();
+    contract
+        .migrate(
+            deps.as_mut(),
+            env.clone(),
+            crate::msg::Cw721MigrateMsg::BackfillTokenIndex { limit: 10 },
+            "contract_name",
+            "contract_version",
+        )
+        .unwrap();
+    for (index, token_id) in ["1", "2"].iter().enumerate() {
+        let res = contract
+            .query_token_by_index(deps.as_ref(), index as u64)
+            .unwrap();
+        assert_eq!(res.token_id, *token_id);
+    }
+    contract.query_token_by_index(deps.as_ref(), 2).unwrap_err();
+}
+
+#[test]
+fn test_query_owner_helpers() {
+    let mut deps = mock_dependencies();
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { contract_addr, .. } if contract_addr == "collection" => {
+            SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&OwnerOfResponse {
+                    owner: "owner".to_string(),
+                    approvals: vec![],
+                    expired: false,
+                })
+                .unwrap(),
+            ))
+        }
+        _ => SystemResult::Err(SystemError::NoSuchContract {
+            addr: "unknown".to_string(),
+        }),
+    });
+    let querier = deps.as_ref().querier;
+
+    let owner = query_owner(&querier, "collection", "1").unwrap();
+    assert_eq!(owner, Addr::unchecked("owner"));
+    assert!(is_owner(&querier, "collection", "1", &Addr::unchecked("owner")).unwrap());
+    assert!(!is_owner(&querier, "collection", "1", &Addr::unchecked("other")).unwrap());
+
+    // unknown collection
+    query_owner(&querier, "unknown", "1").unwrap_err();
+}
+
+#[test]
+fn test_default_schema_resolves_extensions() {
+    // execute msg contains nft extension fields
+    let schema = to_json_string(&schema_for!(DefaultCw721ExecuteMsg)).unwrap();
+    assert!(schema.contains(r#""NftExtensionMsg""#));
+    assert!(schema.contains(r#""animation_url""#));
+    assert!(schema.contains(r#""CollectionExtensionMsg_for_RoyaltyInfoResponse""#));
+
+    // query responses contain nft and collection extension fields
+    let responses = DefaultCw721QueryMsg::response_schemas().unwrap();
+    let schema = to_json_string(&responses["nft_info"]).unwrap();
+    assert!(schema.contains(r#""NftExtension""#));
+    assert!(schema.contains(r#""animation_url""#));
+    let schema = to_json_string(&responses["get_collection_info_and_extension"]).unwrap();
+    assert!(schema.contains(r#""CollectionExtension_for_RoyaltyInfo""#));
+    assert!(schema.contains(r#""external_link""#));
+}
@@ -2,10 +2,11 @@ use std::fmt::Debug;
 
 use cosmwasm_std::{
     to_json_binary, Addr, Api, Binary, Coin, CosmosMsg, CustomMsg, Deps, DepsMut, Empty, Env,
-    MessageInfo, QuerierWrapper, Response, StdResult, Storage, WasmMsg, WasmQuery,
+    MessageInfo, QuerierWrapper, Response, StdResult, Storage, Timestamp, Uint128, WasmMsg,
+    WasmQuery,
 };
 use cw_ownable::{Action, Ownership};
-use cw_utils::Expiration;
+use cw_utils::{Duration, Expiration};
 use schemars::JsonSchema;
 use serde::{de::DeserializeOwned, Serialize};
 
@@ -13,25 +14,43 @@ use serde::{de::DeserializeOwned, Serialize};
 use crate::{
     error::Cw721ContractError,
     execute::{
-        approve, approve_all, burn_nft, initialize_creator, initialize_minter, instantiate,
-        instantiate_with_version, migrate, mint, remove_withdraw_address, revoke, revoke_all,
-        send_nft, set_withdraw_address, transfer_nft, update_collection_info,
-        update_creator_ownership, update_minter_ownership, update_nft_info, withdraw_funds,
+        accept_swap, approve, approve_all, batch_update_nft_info, burn_expired, burn_nft,
+        cancel_swap, check_recipient, distribute_nfts, force_transfer_all, initialize_creator,
+        initialize_minter, instantiate, instantiate_with_version, migrate, mint, mint_auto,
+        mint_timelocked, mint_with_expiration, mint_with_ibc_data, pay_royalty, propose_swap,
+        recover_nft, redeem_voucher, remove_withdraw_address, reveal_commit, revoke, revoke_all,
+        send_nft, set_accepted_denoms, set_burn_fee, set_default_approval_expiry,
+        set_max_royalty_bps, set_metadata_schema_hash, set_royalty_split, set_token_royalty,
+        set_transfer_cooldown, set_voucher_signer, set_withdraw_address, transfer_and_approve,
+        transfer_nft, update_collection_info, update_creator_ownership, update_minter_ownership,
+        update_nft_info, update_royalty_recipient, withdraw_all, withdraw_funds,
     },
     msg::{
-        AllNftInfoResponse, ApprovalResponse, ApprovalsResponse,
-        CollectionInfoAndExtensionResponse, CollectionInfoMsg, Cw721ExecuteMsg,
-        Cw721InstantiateMsg, Cw721MigrateMsg, Cw721QueryMsg, MinterResponse, NftInfoResponse,
-        NumTokensResponse, OperatorResponse, OperatorsResponse, OwnerOfResponse, TokensResponse,
+        AllApprovalsResponse, AllNftInfoResponse, ApprovalResponse, ApprovalsResponse,
+        CanTransferResponse, ClassMetadataResponse, CollectionInfoAndExtensionResponse,
+        CollectionInfoMsg, Cw721ExecuteMsg, Cw721InstantiateMsg, Cw721MigrateMsg, Cw721QueryMsg,
+        MintMsg, MintVoucher, MinterResponse, NftInfoResponse, NftInfoUpdate, NftTransfer,
+        NumTokensResponse, OperableTokensResponse, OperatorResponse, OperatorsResponse,
+        OwnerOfAtResponse, OwnerOfResponse, RevealAssignment, RoyaltyAmountResponse,
+        RoyaltyInfoResponse, RoyaltySplitMsg, RoyaltySummaryResponse, SupplyStatsResponse,
+        TokenByIndexResponse, TokenStateResponse, TokensResponse, TraitCountsResponse,
+        TransferCooldownResponse, ValidateMintResponse,
     },
     query::{
-        query_all_nft_info, query_all_tokens, query_approval, query_approvals,
+        query_accepted_denoms, query_all_approvals, query_all_nft_info, query_all_tokens,
+        query_approval, query_approvals, query_burn_fee, query_can_transfer, query_class_metadata,
         query_collection_extension_attributes, query_collection_info,
-        query_collection_info_and_extension, query_creator_ownership, query_minter,
-        query_minter_ownership, query_nft_info, query_num_tokens, query_operator, query_operators,
-        query_owner_of, query_tokens, query_withdraw_address,
+        query_collection_info_and_extension, query_creator_ownership,
+        query_default_approval_expiry, query_is_uri_used, query_max_royalty_bps,
+        query_metadata_schema_hash, query_minter, query_minter_ownership, query_nft_info,
+        query_num_tokens, query_operable_tokens, query_operator, query_operators, query_owner_of,
+        query_owner_of_at, query_resolved_nft_info, query_royalty_info, query_royalty_recipient,
+        query_royalty_summary, query_supply_stats, query_swap_offer, query_token_by_index,
+        query_token_minter, query_token_state, query_tokens, query_trait_counts,
+        query_transfer_cooldown, query_transfer_fee, query_validate_mint, query_voucher_signer,
+        query_withdraw_address, query_withdrawable_balance,
     },
-    state::CollectionInfo,
+    state::{CollectionInfo, SwapOffer, Trait},
     Attribute,
 };
 use crate::{
@@ -51,10 +70,33 @@ use crate::{
 /// This will be removed once the `CustomMsg` trait is moved to the `cosmwasm_std` crate: https://github.com/CosmWasm/cosmwasm/issues/2056
 pub trait Cw721CustomMsg: Serialize + Clone + Debug + PartialEq + JsonSchema {}
 
-pub trait Cw721State: Serialize + DeserializeOwned + Clone + Debug {}
+pub trait Cw721State: Serialize + DeserializeOwned + Clone + Debug {
+    /// Traits of onchain NFT metadata, used for maintaining trait counts.
+    /// Default implementation has no traits.
+    fn traits(&self) -> Vec<Trait> {
+        vec![]
+    }
+
+    /// Rewrites `ipfs://` URLs of onchain NFT metadata to the given HTTP gateway.
+    /// Default implementation has no URLs.
+    fn resolve_ipfs(&mut self, _gateway: &str) {}
+}
 
 impl Cw721State for Empty {}
-impl<T> Cw721State for Option<T> where T: Cw721State {}
+impl<T> Cw721State for Option<T>
+where
+    T: Cw721State,
+{
+    fn traits(&self) -> Vec<Trait> {
+        self.as_ref().map(Cw721State::traits).unwrap_or_default()
+    }
+
+    fn resolve_ipfs(&mut self, gateway: &str) {
+        if let Some(extension) = self {
+            extension.resolve_ipfs(gateway);
+        }
+    }
+}
 
 impl Cw721CustomMsg for Empty {}
 impl<T> Cw721CustomMsg for Option<T> where T: Cw721CustomMsg {}
@@ -162,11 +204,17 @@ pub trait Cw721Execute<
         deps: DepsMut,
         env: &Env,
         info: &MessageInfo,
-        msg: Cw721InstantiateMsg<TCollectionExtensionMsg>,
+        msg: Cw721InstantiateMsg<TNftExtensionMsg, TCollectionExtensionMsg>,
         contract_name: &str,
         contract_version: &str,
     ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
-        instantiate_with_version(deps, env, info, msg, contract_name, contract_version)
+        instantiate_with_version::<
+            TNftExtension,
+            TNftExtensionMsg,
+            TCollectionExtension,
+            TCollectionExtensionMsg,
+            TCustomResponseMsg,
+        >(deps, env, info, msg, contract_name, contract_version)
     }
 
     fn instantiate(
@@ -174,9 +222,15 @@ pub trait Cw721Execute<
         deps: DepsMut,
         env: &Env,
         info: &MessageInfo,
-        msg: Cw721InstantiateMsg<TCollectionExtensionMsg>,
+        msg: Cw721InstantiateMsg<TNftExtensionMsg, TCollectionExtensionMsg>,
     ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
-        instantiate(deps, env, info, msg)
+        instantiate::<
+            TNftExtension,
+            TNftExtensionMsg,
+            TCollectionExtension,
+            TCollectionExtensionMsg,
+            TCustomResponseMsg,
+        >(deps, env, info, msg)
     }
 
     fn execute(
@@ -195,7 +249,56 @@ pub trait Cw721Execute<
                 owner,
                 token_uri,
                 extension,
-            } => self.mint(deps, env, info, token_id, owner, token_uri, extension),
+                ibc_data,
+            } => match ibc_data {
+                Some(ibc_data) => self.mint_with_ibc_data(
+                    deps,
+                    env,
+                    info,
+                    token_id,
+                    owner,
+                    token_uri,
+                    extension,
+                    Some(ibc_data),
+                ),
+                None => self.mint(deps, env, info, token_id, owner, token_uri, extension),
+            },
+            Cw721ExecuteMsg::MintAuto {
+                owner,
+                token_uri,
+                extension,
+            } => self.mint_auto(deps, env, info, owner, token_uri, extension),
+            Cw721ExecuteMsg::MintTimelocked {
+                token_id,
+                owner,
+                token_uri,
+                extension,
+                transferable_after,
+            } => self.mint_timelocked(
+                deps,
+                env,
+                info,
+                token_id,
+                owner,
+                token_uri,
+                extension,
+                transferable_after,
+            ),
+            Cw721ExecuteMsg::MintWithExpiration {
+                token_id,
+                owner,
+                token_uri,
+                extension,
+                expires,
+            } => self.mint_with_expiration(
+                deps, env, info, token_id, owner, token_uri, extension, expires,
+            ),
+            Cw721ExecuteMsg::RedeemVoucher { voucher, signature } => {
+                self.redeem_voucher(deps, env, info, voucher, signature)
+            }
+            Cw721ExecuteMsg::SetVoucherSigner { pubkey } => {
+                self.set_voucher_signer(deps.storage, &info.sender, pubkey)
+            }
             Cw721ExecuteMsg::Approve {
                 spender,
                 token_id,
@@ -211,13 +314,42 @@ pub trait Cw721Execute<
             Cw721ExecuteMsg::TransferNft {
                 recipient,
                 token_id,
-            } => self.transfer_nft(deps, env, info, recipient, token_id),
+                expected_owner,
+            } => self.transfer_nft(deps, env, info, recipient, token_id, expected_owner),
+            Cw721ExecuteMsg::SafeTransferNft {
+                recipient,
+                token_id,
+                expected_owner,
+            } => self.safe_transfer_nft(deps, env, info, recipient, token_id, expected_owner),
             Cw721ExecuteMsg::SendNft {
                 contract,
                 token_id,
                 msg,
-            } => self.send_nft(deps, env, info, contract, token_id, msg),
+                expected_owner,
+            } => self.send_nft(deps, env, info, contract, token_id, msg, expected_owner),
+            Cw721ExecuteMsg::DistributeNfts { transfers } => {
+                self.distribute_nfts(deps, env, info, transfers)
+            }
+            Cw721ExecuteMsg::TransferAndApprove {
+                recipient,
+                token_id,
+                spender,
+                expires,
+            } => self.transfer_and_approve(deps, env, info, recipient, token_id, spender, expires),
+            Cw721ExecuteMsg::ProposeSwap {
+                my_token,
+                want_token,
+                counterparty,
+                expires,
+            } => self.propose_swap(deps, env, info, my_token, want_token, counterparty, expires),
+            Cw721ExecuteMsg::AcceptSwap { offer_id } => self.accept_swap(deps, env, info, offer_id),
+            Cw721ExecuteMsg::CancelSwap { offer_id } => {
+                self.cancel_swap(deps.storage, env, info, offer_id)
+            }
             Cw721ExecuteMsg::Burn { token_id } => self.burn_nft(deps, env, info, token_id),
+            Cw721ExecuteMsg::BurnExpired { token_id } => {
+                self.burn_expired(deps, env, info, token_id)
+            }
             #[allow(deprecated)]
             Cw721ExecuteMsg::UpdateOwnership(action) => {
                 self.update_minter_ownership(deps.api, deps.storage, env, info, action)
@@ -237,13 +369,61 @@ pub trait Cw721Execute<
                 token_uri,
                 extension,
             } => self.update_nft_info(deps, env, info, token_id, token_uri, extension),
+            Cw721ExecuteMsg::BatchUpdateNftInfo { updates } => {
+                self.batch_update_nft_info(deps, env, info, updates)
+            }
             Cw721ExecuteMsg::SetWithdrawAddress { address } => {
                 self.set_withdraw_address(deps, &info.sender, address)
             }
             Cw721ExecuteMsg::RemoveWithdrawAddress {} => {
                 self.remove_withdraw_address(deps.storage, &info.sender)
             }
+            Cw721ExecuteMsg::SetMetadataSchemaHash {
+                metadata_schema_hash,
+            } => self.set_metadata_schema_hash(deps.storage, &info.sender, metadata_schema_hash),
+            Cw721ExecuteMsg::SetBurnFee { burn_fee } => {
+                self.set_burn_fee(deps.storage, &info.sender, burn_fee)
+            }
+            Cw721ExecuteMsg::RevealCommit { seed, assignments } => {
+                self.reveal_commit(deps, info, seed, assignments)
+            }
+            Cw721ExecuteMsg::SetMaxRoyaltyBps { max_royalty_bps } => {
+                self.set_max_royalty_bps(deps.storage, &info.sender, max_royalty_bps)
+            }
+            Cw721ExecuteMsg::UpdateRoyaltyRecipient { address } => {
+                self.update_royalty_recipient(deps, env, &info.sender, address)
+            }
+            Cw721ExecuteMsg::SetTokenRoyalty { token_id, royalty } => {
+                self.set_token_royalty(deps, env, &info.sender, token_id, royalty)
+            }
+            Cw721ExecuteMsg::SetRoyaltySplit { splits } => {
+                self.set_royalty_split(deps, &info.sender, splits)
+            }
+            Cw721ExecuteMsg::SetTransferCooldown { transfer_cooldown } => {
+                self.set_transfer_cooldown(deps.storage, &info.sender, transfer_cooldown)
+            }
+            Cw721ExecuteMsg::SetDefaultApprovalExpiry {
+                default_approval_expiry,
+            } => self.set_default_approval_expiry(
+                deps.storage,
+                &info.sender,
+                default_approval_expiry,
+            ),
+            Cw721ExecuteMsg::SetAcceptedDenoms { accepted_denoms } => {
+                self.set_accepted_denoms(deps.storage, &info.sender, accepted_denoms)
+            }
             Cw721ExecuteMsg::WithdrawFunds { amount } => self.withdraw_funds(deps.storage, &amount),
+            Cw721ExecuteMsg::WithdrawAll {} => self.withdraw_all(deps.as_ref(), env),
+            Cw721ExecuteMsg::PayRoyalty { token_id } => {
+                self.pay_royalty(deps.as_ref(), info, token_id)
+            }
+            Cw721ExecuteMsg::RecoverNft {
+                token_id,
+                recipient,
+            } => self.recover_nft(deps, env, info, token_id, recipient),
+            Cw721ExecuteMsg::ForceTransferAll { from, to, limit } => {
+                self.force_transfer_all(deps, env, info, from, to, limit)
+            }
         }
     }
 
@@ -255,7 +435,7 @@ pub trait Cw721Execute<
         contract_name: &str,
         contract_version: &str,
     ) -> Result<Response, Cw721ContractError> {
-        migrate(deps, env, msg, contract_name, contract_version)
+        migrate::<TNftExtension>(deps, env, msg, contract_name, contract_version)
     }
 
     // ------- ERC721-based functions -------
@@ -266,16 +446,32 @@ pub trait Cw721Execute<
         info: &MessageInfo,
         recipient: String,
         token_id: String,
+        expected_owner: Option<String>,
     ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
-        transfer_nft::<TNftExtension>(deps, env, info, &recipient, &token_id)?;
+        transfer_nft::<TNftExtension, TCustomResponseMsg>(
+            deps,
+            env,
+            info,
+            recipient,
+            token_id,
+            expected_owner,
+        )
+    }
 
-        Ok(Response::new()
-            .add_attribute("action", "transfer_nft")
-            .add_attribute("sender", info.sender.to_string())
-            .add_attribute("recipient", recipient)
-            .add_attribute("token_id", token_id))
+    fn safe_transfer_nft(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        recipient: String,
+        token_id: String,
+        expected_owner: Option<String>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        check_recipient(deps.as_ref(), env, &recipient)?;
+        self.transfer_nft(deps, env, info, recipient, token_id, expected_owner)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn send_nft(
         &self,
         deps: DepsMut,
@@ -284,8 +480,85 @@ pub trait Cw721Execute<
         contract: String,
         token_id: String,
         msg: Binary,
+        expected_owner: Option<String>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        send_nft::<TNftExtension, TCustomResponseMsg>(
+            deps,
+            env,
+            info,
+            contract,
+            token_id,
+            msg,
+            expected_owner,
+        )
+    }
+
+    fn distribute_nfts(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        transfers: Vec<NftTransfer>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        distribute_nfts::<TNftExtension, TCustomResponseMsg>(deps, env, info, transfers)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn transfer_and_approve(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        recipient: String,
+        token_id: String,
+        spender: String,
+        expires: Option<Expiration>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        transfer_and_approve::<TNftExtension, TCustomResponseMsg>(
+            deps, env, info, recipient, token_id, spender, expires,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn propose_swap(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        my_token: String,
+        want_token: String,
+        counterparty: String,
+        expires: Option<Expiration>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        propose_swap::<TNftExtension, TCustomResponseMsg>(
+            deps,
+            env,
+            info,
+            my_token,
+            want_token,
+            counterparty,
+            expires,
+        )
+    }
+
+    fn accept_swap(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        offer_id: u64,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        accept_swap::<TNftExtension, TCustomResponseMsg>(deps, env, info, offer_id)
+    }
+
+    fn cancel_swap(
+        &self,
+        storage: &mut dyn Storage,
+        env: &Env,
+        info: &MessageInfo,
+        offer_id: u64,
     ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
-        send_nft::<TNftExtension, TCustomResponseMsg>(deps, env, info, contract, token_id, msg)
+        cancel_swap::<TCustomResponseMsg>(storage, env, info, offer_id)
     }
 
     fn approve(
@@ -339,7 +612,17 @@ pub trait Cw721Execute<
         info: &MessageInfo,
         token_id: String,
     ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
-        burn_nft::<TCustomResponseMsg>(deps, env, info, token_id)
+        burn_nft::<TNftExtension, TCustomResponseMsg>(deps, env, info, token_id)
+    }
+
+    fn burn_expired(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        token_id: String,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        burn_expired::<TNftExtension, TCustomResponseMsg>(deps, env, info, token_id)
     }
 
     // ------- opionated cw721 functions -------
@@ -389,6 +672,100 @@ pub trait Cw721Execute<
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn mint_timelocked(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        token_id: String,
+        owner: String,
+        token_uri: Option<String>,
+        extension: TNftExtensionMsg,
+        transferable_after: Timestamp,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        mint_timelocked::<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+            deps,
+            env,
+            info,
+            token_id,
+            owner,
+            token_uri,
+            extension,
+            transferable_after,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn mint_with_ibc_data(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        token_id: String,
+        owner: String,
+        token_uri: Option<String>,
+        extension: TNftExtensionMsg,
+        ibc_data: Option<Binary>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        mint_with_ibc_data::<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+            deps, env, info, token_id, owner, token_uri, extension, ibc_data,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn mint_with_expiration(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        token_id: String,
+        owner: String,
+        token_uri: Option<String>,
+        extension: TNftExtensionMsg,
+        expires: Expiration,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        mint_with_expiration::<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+            deps, env, info, token_id, owner, token_uri, extension, expires,
+        )
+    }
+
+    fn mint_auto(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        owner: String,
+        token_uri: Option<String>,
+        extension: TNftExtensionMsg,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        mint_auto::<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+            deps, env, info, owner, token_uri, extension,
+        )
+    }
+
+    fn redeem_voucher(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        voucher: MintVoucher<TNftExtensionMsg>,
+        signature: Binary,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        redeem_voucher::<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+            deps, env, info, voucher, signature,
+        )
+    }
+
+    fn set_voucher_signer(
+        &self,
+        storage: &mut dyn Storage,
+        sender: &Addr,
+        pubkey: Option<Binary>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        set_voucher_signer::<TCustomResponseMsg>(storage, sender, pubkey)
+    }
+
     fn update_minter_ownership(
         &self,
         api: &dyn Api,
@@ -443,6 +820,18 @@ pub trait Cw721Execute<
         )
     }
 
+    fn batch_update_nft_info(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        updates: Vec<NftInfoUpdate<TNftExtensionMsg>>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        batch_update_nft_info::<TNftExtension, TNftExtensionMsg, TCustomResponseMsg>(
+            deps, env, info, updates,
+        )
+    }
+
     fn set_withdraw_address(
         &self,
         deps: DepsMut,
@@ -460,6 +849,100 @@ pub trait Cw721Execute<
         remove_withdraw_address::<TCustomResponseMsg>(storage, sender)
     }
 
+    fn set_metadata_schema_hash(
+        &self,
+        storage: &mut dyn Storage,
+        sender: &Addr,
+        metadata_schema_hash: Option<String>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        set_metadata_schema_hash::<TCustomResponseMsg>(storage, sender, metadata_schema_hash)
+    }
+
+    fn reveal_commit(
+        &self,
+        deps: DepsMut,
+        info: &MessageInfo,
+        seed: Binary,
+        assignments: Vec<RevealAssignment>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        reveal_commit::<TNftExtension, TCustomResponseMsg>(deps, info, seed, assignments)
+    }
+
+    fn set_max_royalty_bps(
+        &self,
+        storage: &mut dyn Storage,
+        sender: &Addr,
+        max_royalty_bps: u16,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        set_max_royalty_bps::<TCustomResponseMsg>(storage, sender, max_royalty_bps)
+    }
+
+    fn update_royalty_recipient(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        sender: &Addr,
+        address: String,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        update_royalty_recipient::<TCustomResponseMsg>(deps, env, sender, address)
+    }
+
+    fn set_token_royalty(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        sender: &Addr,
+        token_id: String,
+        royalty: Option<RoyaltyInfoResponse>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        set_token_royalty::<TCustomResponseMsg>(deps, env, sender, token_id, royalty)
+    }
+
+    fn set_royalty_split(
+        &self,
+        deps: DepsMut,
+        sender: &Addr,
+        splits: Option<Vec<RoyaltySplitMsg>>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        set_royalty_split::<TCustomResponseMsg>(deps, sender, splits)
+    }
+
+    fn set_burn_fee(
+        &self,
+        storage: &mut dyn Storage,
+        sender: &Addr,
+        burn_fee: Option<Coin>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        set_burn_fee::<TCustomResponseMsg>(storage, sender, burn_fee)
+    }
+
+    fn set_transfer_cooldown(
+        &self,
+        storage: &mut dyn Storage,
+        sender: &Addr,
+        transfer_cooldown: Option<u64>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        set_transfer_cooldown::<TCustomResponseMsg>(storage, sender, transfer_cooldown)
+    }
+
+    fn set_default_approval_expiry(
+        &self,
+        storage: &mut dyn Storage,
+        sender: &Addr,
+        default_approval_expiry: Option<Duration>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        set_default_approval_expiry::<TCustomResponseMsg>(storage, sender, default_approval_expiry)
+    }
+
+    fn set_accepted_denoms(
+        &self,
+        storage: &mut dyn Storage,
+        sender: &Addr,
+        accepted_denoms: Vec<String>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        set_accepted_denoms::<TCustomResponseMsg>(storage, sender, accepted_denoms)
+    }
+
     fn withdraw_funds(
         &self,
         storage: &mut dyn Storage,
@@ -467,18 +950,61 @@ pub trait Cw721Execute<
     ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
         withdraw_funds::<TCustomResponseMsg>(storage, amount)
     }
+
+    fn withdraw_all(
+        &self,
+        deps: Deps,
+        env: &Env,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        withdraw_all::<TCustomResponseMsg>(deps, env)
+    }
+
+    fn pay_royalty(
+        &self,
+        deps: Deps,
+        info: &MessageInfo,
+        token_id: String,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        pay_royalty::<TCustomResponseMsg>(deps, info, token_id)
+    }
+
+    fn recover_nft(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        token_id: String,
+        recipient: String,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        recover_nft::<TNftExtension, TCustomResponseMsg>(deps, env, info, token_id, recipient)
+    }
+
+    fn force_transfer_all(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: &MessageInfo,
+        from: String,
+        to: String,
+        limit: Option<u32>,
+    ) -> Result<Response<TCustomResponseMsg>, Cw721ContractError> {
+        force_transfer_all::<TNftExtension, TCustomResponseMsg>(deps, env, info, from, to, limit)
+    }
 }
 
 /// Trait with generic onchain nft and collection extensions used to query the contract state and contains default implementations for all queries.
 pub trait Cw721Query<
     // NftInfo extension (onchain metadata).
     TNftExtension,
+    // NftInfo extension msg for onchain metadata, validated by `ValidateMint`.
+    TNftExtensionMsg,
     // CollectionInfo extension (onchain attributes).
     TCollectionExtension,
     // Custom query msg for custom contract logic. Default implementation returns an empty binary.
     TExtensionQueryMsg,
 > where
     TNftExtension: Cw721State + Contains,
+    TNftExtensionMsg: Cw721CustomMsg + StateFactory<TNftExtension>,
     TCollectionExtension: Cw721State + FromAttributesState,
     TExtensionQueryMsg: Cw721CustomMsg,
 {
@@ -486,7 +1012,12 @@ pub trait Cw721Query<
         &self,
         deps: Deps,
         env: &Env,
-        msg: Cw721QueryMsg<TNftExtension, TCollectionExtension, TExtensionQueryMsg>,
+        msg: Cw721QueryMsg<
+            TNftExtension,
+            TNftExtensionMsg,
+            TCollectionExtension,
+            TExtensionQueryMsg,
+        >,
     ) -> Result<Binary, Cw721ContractError> {
         match msg {
             #[allow(deprecated)]
@@ -502,18 +1033,25 @@ pub trait Cw721Query<
                 &self.query_collection_info_and_extension(deps)?,
             )?),
             Cw721QueryMsg::GetAllInfo {} => Ok(to_json_binary(&self.query_all_info(deps, env)?)?),
+            Cw721QueryMsg::ClassMetadata {} => {
+                Ok(to_json_binary(&self.query_class_metadata(deps, env)?)?)
+            }
             Cw721QueryMsg::GetCollectionExtensionAttributes {} => Ok(to_json_binary(
                 &self.query_collection_extension_attributes(deps)?,
             )?),
-            Cw721QueryMsg::NftInfo { token_id } => Ok(to_json_binary(
-                &self.query_nft_info(deps.storage, token_id)?,
+            Cw721QueryMsg::NftInfo { token_id } => {
+                Ok(to_json_binary(&self.query_nft_info(deps, env, token_id)?)?)
+            }
+            Cw721QueryMsg::ResolvedNftInfo { token_id } => Ok(to_json_binary(
+                &self.query_resolved_nft_info(deps, env, token_id)?,
             )?),
             Cw721QueryMsg::GetNftByExtension {
                 extension,
                 start_after,
                 limit,
             } => Ok(to_json_binary(&self.query_nft_by_extension(
-                deps.storage,
+                deps,
+                env,
                 extension,
                 start_after,
                 limit,
@@ -527,6 +1065,21 @@ pub trait Cw721Query<
                 token_id,
                 include_expired.unwrap_or(false),
             )?)?),
+            Cw721QueryMsg::TokenState { token_id } => Ok(to_json_binary(
+                &self.query_token_state(deps, env, token_id)?,
+            )?),
+            Cw721QueryMsg::OwnerOfAt { token_id, height } => Ok(to_json_binary(
+                &self.query_owner_of_at(deps, token_id, height)?,
+            )?),
+            Cw721QueryMsg::CanTransfer { token_id, sender } => Ok(to_json_binary(
+                &self.query_can_transfer(deps, env, token_id, sender)?,
+            )?),
+            Cw721QueryMsg::ValidateMint { sender, msg, funds } => Ok(to_json_binary(
+                &self.query_validate_mint(deps, env, sender, msg, funds)?,
+            )?),
+            Cw721QueryMsg::SwapOffer { offer_id } => {
+                Ok(to_json_binary(&self.query_swap_offer(deps, offer_id)?)?)
+            }
             Cw721QueryMsg::AllNftInfo {
                 token_id,
                 include_expired,
@@ -563,20 +1116,39 @@ pub trait Cw721Query<
             Cw721QueryMsg::NumTokens {} => {
                 Ok(to_json_binary(&self.query_num_tokens(deps.storage)?)?)
             }
+            Cw721QueryMsg::SupplyStats {} => {
+                Ok(to_json_binary(&self.query_supply_stats(deps.storage)?)?)
+            }
+            Cw721QueryMsg::TraitCounts { trait_type } => Ok(to_json_binary(
+                &self.query_trait_counts(deps.storage, trait_type)?,
+            )?),
             Cw721QueryMsg::Tokens {
                 owner,
                 start_after,
                 limit,
+                cursor,
             } => Ok(to_json_binary(&self.query_tokens(
                 deps,
                 env,
                 owner,
                 start_after,
                 limit,
+                cursor,
             )?)?),
-            Cw721QueryMsg::AllTokens { start_after, limit } => Ok(to_json_binary(
-                &self.query_all_tokens(deps, env, start_after, limit)?,
-            )?),
+            Cw721QueryMsg::AllTokens {
+                start_after,
+                limit,
+                cursor,
+            } => Ok(to_json_binary(&self.query_all_tokens(
+                deps,
+                env,
+                start_after,
+                limit,
+                cursor,
+            )?)?),
+            Cw721QueryMsg::TokenByIndex { index } => {
+                Ok(to_json_binary(&self.query_token_by_index(deps, index)?)?)
+            }
             Cw721QueryMsg::Approval {
                 token_id,
                 spender,
@@ -597,6 +1169,28 @@ pub trait Cw721Query<
                 token_id,
                 include_expired.unwrap_or(false),
             )?)?),
+            Cw721QueryMsg::AllApprovals {
+                start_after,
+                limit,
+                include_expired,
+            } => Ok(to_json_binary(&self.query_all_approvals(
+                deps,
+                env,
+                start_after,
+                limit,
+                include_expired.unwrap_or(false),
+            )?)?),
+            Cw721QueryMsg::OperableTokens {
+                operator,
+                start_after,
+                limit,
+            } => Ok(to_json_binary(&self.query_operable_tokens(
+                deps,
+                env,
+                operator,
+                start_after,
+                limit,
+            )?)?),
             #[allow(deprecated)]
             Cw721QueryMsg::Ownership {} => {
                 Ok(to_json_binary(&self.query_minter_ownership(deps.storage)?)?)
@@ -614,6 +1208,49 @@ pub trait Cw721Query<
             Cw721QueryMsg::GetWithdrawAddress {} => {
                 Ok(to_json_binary(&self.query_withdraw_address(deps)?)?)
             }
+            Cw721QueryMsg::IsUriUsed { token_uri } => Ok(to_json_binary(
+                &self.query_is_uri_used(deps.storage, token_uri)?,
+            )?),
+            Cw721QueryMsg::WithdrawableBalance {} => Ok(to_json_binary(
+                &self.query_withdrawable_balance(deps, env)?,
+            )?),
+            Cw721QueryMsg::RoyaltyRecipient {} => {
+                Ok(to_json_binary(&self.query_royalty_recipient(deps)?)?)
+            }
+            Cw721QueryMsg::GetMetadataSchemaHash {} => {
+                Ok(to_json_binary(&self.query_metadata_schema_hash(deps)?)?)
+            }
+            Cw721QueryMsg::GetBurnFee {} => Ok(to_json_binary(&self.query_burn_fee(deps)?)?),
+            Cw721QueryMsg::GetMaxRoyaltyBps {} => {
+                Ok(to_json_binary(&self.query_max_royalty_bps(deps)?)?)
+            }
+            Cw721QueryMsg::RoyaltyInfo {
+                token_id,
+                sale_price,
+            } => Ok(to_json_binary(
+                &self.query_royalty_info(deps, token_id, sale_price)?,
+            )?),
+            Cw721QueryMsg::RoyaltySummary { start_after, limit } => Ok(to_json_binary(
+                &self.query_royalty_summary(deps, start_after, limit)?,
+            )?),
+            Cw721QueryMsg::TokenMinter { token_id } => {
+                Ok(to_json_binary(&self.query_token_minter(deps, token_id)?)?)
+            }
+            Cw721QueryMsg::GetDefaultApprovalExpiry {} => {
+                Ok(to_json_binary(&self.query_default_approval_expiry(deps)?)?)
+            }
+            Cw721QueryMsg::GetTransferFee {} => {
+                Ok(to_json_binary(&self.query_transfer_fee(deps)?)?)
+            }
+            Cw721QueryMsg::TransferCooldown { token_id } => Ok(to_json_binary(
+                &self.query_transfer_cooldown(deps, env, token_id)?,
+            )?),
+            Cw721QueryMsg::GetAcceptedDenoms {} => {
+                Ok(to_json_binary(&self.query_accepted_denoms(deps)?)?)
+            }
+            Cw721QueryMsg::GetVoucherSigner {} => {
+                Ok(to_json_binary(&self.query_voucher_signer(deps)?)?)
+            }
         }
     }
 
@@ -665,26 +1302,60 @@ pub trait Cw721Query<
         query_all_info(deps, env)
     }
 
+    fn query_class_metadata(
+        &self,
+        deps: Deps,
+        env: &Env,
+    ) -> Result<ClassMetadataResponse, Cw721ContractError>
+    where
+        TCollectionExtension: FromAttributesState,
+    {
+        query_class_metadata::<TCollectionExtension>(deps, env)
+    }
+
     fn query_num_tokens(&self, storage: &dyn Storage) -> StdResult<NumTokensResponse> {
         query_num_tokens(storage)
     }
 
-    fn query_nft_info(
+    fn query_supply_stats(&self, storage: &dyn Storage) -> StdResult<SupplyStatsResponse> {
+        query_supply_stats(storage)
+    }
+
+    fn query_trait_counts(
         &self,
         storage: &dyn Storage,
+        trait_type: String,
+    ) -> StdResult<TraitCountsResponse> {
+        query_trait_counts(storage, trait_type)
+    }
+
+    fn query_nft_info(
+        &self,
+        deps: Deps,
+        env: &Env,
+        token_id: String,
+    ) -> StdResult<NftInfoResponse<TNftExtension>> {
+        query_nft_info::<TNftExtension>(deps, env, token_id)
+    }
+
+    fn query_resolved_nft_info(
+        &self,
+        deps: Deps,
+        env: &Env,
         token_id: String,
     ) -> StdResult<NftInfoResponse<TNftExtension>> {
-        query_nft_info::<TNftExtension>(storage, token_id)
+        query_resolved_nft_info::<TNftExtension>(deps, env, token_id)
     }
 
     fn query_nft_by_extension(
         &self,
-        storage: &dyn Storage,
+        deps: Deps,
+        env: &Env,
         extension: TNftExtension,
         start_after: Option<String>,
         limit: Option<u32>,
     ) -> StdResult<Option<Vec<NftInfoResponse<TNftExtension>>>> {
-        query_nft_by_extension::<TNftExtension>(storage, extension, start_after, limit)
+        query_nft_by_extension::<TNftExtension>(deps, env, extension, start_after, limit)
     }
 
     fn query_owner_of(
@@ -697,6 +1368,52 @@ pub trait Cw721Query<
         query_owner_of(deps, env, token_id, include_expired_approval)
     }
 
+    /// owner, lock and approval state of a token in a single query
+    fn query_token_state(
+        &self,
+        deps: Deps,
+        env: &Env,
+        token_id: String,
+    ) -> StdResult<TokenStateResponse> {
+        query_token_state(deps, env, token_id)
+    }
+
+    /// owner of a token at the beginning of the given block height
+    fn query_owner_of_at(
+        &self,
+        deps: Deps,
+        token_id: String,
+        height: u64,
+    ) -> StdResult<OwnerOfAtResponse> {
+        query_owner_of_at(deps, token_id, height)
+    }
+
+    /// whether sender is allowed to transfer a token
+    fn query_can_transfer(
+        &self,
+        deps: Deps,
+        env: &Env,
+        token_id: String,
+        sender: String,
+    ) -> StdResult<CanTransferResponse> {
+        query_can_transfer(deps, env, token_id, sender)
+    }
+
+    fn query_validate_mint(
+        &self,
+        deps: Deps,
+        env: &Env,
+        sender: String,
+        msg: MintMsg<TNftExtensionMsg>,
+        funds: Vec<Coin>,
+    ) -> StdResult<ValidateMintResponse> {
+        query_validate_mint::<TNftExtension, TNftExtensionMsg>(deps, env, sender, msg, funds)
+    }
+
+    fn query_swap_offer(&self, deps: Deps, offer_id: u64) -> StdResult<SwapOffer> {
+        query_swap_offer(deps, offer_id)
+    }
+
     /// operator returns the approval status of an operator for a given owner if exists
     fn query_operator(
         &self,
@@ -751,6 +1468,28 @@ pub trait Cw721Query<
         query_approvals(deps, env, token_id, include_expired_approval)
     }
 
+    fn query_all_approvals(
+        &self,
+        deps: Deps,
+        env: &Env,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        include_expired_approval: bool,
+    ) -> StdResult<AllApprovalsResponse> {
+        query_all_approvals(deps, env, start_after, limit, include_expired_approval)
+    }
+
+    fn query_operable_tokens(
+        &self,
+        deps: Deps,
+        env: &Env,
+        operator: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<OperableTokensResponse> {
+        query_operable_tokens(deps, env, operator, start_after, limit)
+    }
+
     fn query_tokens(
         &self,
         deps: Deps,
@@ -758,8 +1497,9 @@ pub trait Cw721Query<
         owner: String,
         start_after: Option<String>,
         limit: Option<u32>,
+        cursor: Option<Binary>,
     ) -> StdResult<TokensResponse> {
-        query_tokens(deps, _env, owner, start_after, limit)
+        query_tokens(deps, _env, owner, start_after, limit, cursor)
     }
 
     fn query_all_tokens(
@@ -768,8 +1508,13 @@ pub trait Cw721Query<
         _env: &Env,
         start_after: Option<String>,
         limit: Option<u32>,
+        cursor: Option<Binary>,
     ) -> StdResult<TokensResponse> {
-        query_all_tokens(deps, _env, start_after, limit)
+        query_all_tokens(deps, _env, start_after, limit, cursor)
+    }
+
+    fn query_token_by_index(&self, deps: Deps, index: u64) -> StdResult<TokenByIndexResponse> {
+        query_token_by_index(deps, index)
     }
 
     fn query_all_nft_info(
@@ -808,6 +1553,77 @@ pub trait Cw721Query<
     fn query_withdraw_address(&self, deps: Deps) -> StdResult<Option<String>> {
         query_withdraw_address(deps)
     }
+
+    fn query_is_uri_used(&self, storage: &dyn Storage, token_uri: String) -> StdResult<bool> {
+        query_is_uri_used(storage, token_uri)
+    }
+
+    fn query_withdrawable_balance(&self, deps: Deps, env: &Env) -> StdResult<Vec<Coin>> {
+        query_withdrawable_balance(deps, env)
+    }
+
+    fn query_royalty_recipient(&self, deps: Deps) -> StdResult<Option<String>> {
+        query_royalty_recipient(deps)
+    }
+
+    fn query_metadata_schema_hash(&self, deps: Deps) -> StdResult<Option<String>> {
+        query_metadata_schema_hash(deps)
+    }
+
+    fn query_burn_fee(&self, deps: Deps) -> StdResult<Option<Coin>> {
+        query_burn_fee(deps)
+    }
+
+    fn query_max_royalty_bps(&self, deps: Deps) -> StdResult<Option<u16>> {
+        query_max_royalty_bps(deps)
+    }
+
+    fn query_token_minter(&self, deps: Deps, token_id: String) -> StdResult<Option<String>> {
+        query_token_minter(deps, token_id)
+    }
+
+    fn query_default_approval_expiry(&self, deps: Deps) -> StdResult<Option<Duration>> {
+        query_default_approval_expiry(deps)
+    }
+
+    fn query_transfer_fee(&self, deps: Deps) -> StdResult<Option<Coin>> {
+        query_transfer_fee(deps)
+    }
+
+    fn query_transfer_cooldown(
+        &self,
+        deps: Deps,
+        env: &Env,
+        token_id: String,
+    ) -> StdResult<TransferCooldownResponse> {
+        query_transfer_cooldown(deps, env, token_id)
+    }
+
+    fn query_royalty_info(
+        &self,
+        deps: Deps,
+        token_id: String,
+        sale_price: Uint128,
+    ) -> StdResult<RoyaltyAmountResponse> {
+        query_royalty_info(deps, token_id, sale_price)
+    }
+
+    fn query_royalty_summary(
+        &self,
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<RoyaltySummaryResponse> {
+        query_royalty_summary(deps, start_after, limit)
+    }
+
+    fn query_accepted_denoms(&self, deps: Deps) -> StdResult<Vec<String>> {
+        query_accepted_denoms(deps)
+    }
+
+    fn query_voucher_signer(&self, deps: Deps) -> StdResult<Option<Binary>> {
+        query_voucher_signer(deps)
+    }
 }
 
 /// Generic trait with onchain nft and collection extensions used to call query and execute messages for a given CW721 addr.
@@ -847,7 +1663,12 @@ pub trait Cw721Calls<
     fn query<T: DeserializeOwned>(
         &self,
         querier: &QuerierWrapper,
-        req: Cw721QueryMsg<TNftExtension, TCollectionExtension, TExtensionQueryMsg>,
+        req: Cw721QueryMsg<
+            TNftExtension,
+            TNftExtensionMsg,
+            TCollectionExtension,
+            TExtensionQueryMsg,
+        >,
     ) -> StdResult<T> {
         let query = WasmQuery::Smart {
             contract_addr: self.addr().into(),
@@ -981,6 +1802,7 @@ pub trait Cw721Calls<
             owner: owner.into(),
             start_after,
             limit,
+            cursor: None,
         };
         self.query(querier, req)
     }
@@ -992,7 +1814,11 @@ pub trait Cw721Calls<
         start_after: Option<String>,
         limit: Option<u32>,
     ) -> StdResult<TokensResponse> {
-        let req = Cw721QueryMsg::AllTokens { start_after, limit };
+        let req = Cw721QueryMsg::AllTokens {
+            start_after,
+            limit,
+            cursor: None,
+        };
         self.query(querier, req)
     }
 